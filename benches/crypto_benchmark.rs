@@ -75,17 +75,20 @@ fn bench_compression(c: &mut Criterion) {
     for size_kb in [1, 10, 100, 1000] {
         group.throughput(Throughput::Bytes((size_kb * 1024) as u64));
 
-        for comp_type in [CompressionType::Zstd, CompressionType::Gzip] {
+        for comp_type in [
+            CompressionType::Zstd,
+            CompressionType::Gzip,
+            CompressionType::Lz4,
+            CompressionType::Xz,
+            CompressionType::Brotli,
+            CompressionType::Adaptive,
+        ] {
             group.bench_with_input(
                 BenchmarkId::new(format!("{comp_type:?}"), format!("{size_kb}KB")),
                 &size_kb,
                 |b, &size_kb| {
                     let data = vec![42u8; size_kb * 1024]; // 繰り返しパターンで圧縮効果を確認
-                    let config = match comp_type {
-                        CompressionType::Zstd => CompressionConfig::zstd_default(),
-                        CompressionType::Gzip => CompressionConfig::gzip_default(),
-                        CompressionType::None => CompressionConfig::none(),
-                    };
+                    let config = CompressionConfig::default_for(comp_type);
                     let engine = CompressionEngine::new(comp_type, config);
 
                     b.iter(|| black_box(engine.compress(black_box(&data)).unwrap()));
@@ -104,17 +107,20 @@ fn bench_decompression(c: &mut Criterion) {
     for size_kb in [1, 10, 100, 1000] {
         group.throughput(Throughput::Bytes((size_kb * 1024) as u64));
 
-        for comp_type in [CompressionType::Zstd, CompressionType::Gzip] {
+        for comp_type in [
+            CompressionType::Zstd,
+            CompressionType::Gzip,
+            CompressionType::Lz4,
+            CompressionType::Xz,
+            CompressionType::Brotli,
+            CompressionType::Adaptive,
+        ] {
             group.bench_with_input(
                 BenchmarkId::new(format!("{comp_type:?}"), format!("{size_kb}KB")),
                 &size_kb,
                 |b, &size_kb| {
                     let data = vec![42u8; size_kb * 1024];
-                    let config = match comp_type {
-                        CompressionType::Zstd => CompressionConfig::zstd_default(),
-                        CompressionType::Gzip => CompressionConfig::gzip_default(),
-                        CompressionType::None => CompressionConfig::none(),
-                    };
+                    let config = CompressionConfig::default_for(comp_type);
                     let engine = CompressionEngine::new(comp_type, config);
                     let compressed_data = engine.compress(&data).unwrap();
 
@@ -0,0 +1,471 @@
+//! # 適応圧縮
+//!
+//! ファイルごとに「無圧縮で保存」「高速zstd」「高圧縮zstd」のいずれかを選択します。
+//!
+//! JPEG・MP4・zip・zstd圧縮済みファイルなど、すでに圧縮されたデータを圧縮しても
+//! CPU時間を浪費するだけで、サイズが増えることさえあります。適応圧縮では次の順に判定します。
+//!
+//! 1. 拡張子・マジックバイトで圧縮済み形式と判明したもの → 保存
+//! 2. 先頭サンプルのエントロピーが高いもの → 保存
+//! 3. サンプルの試験圧縮（zstdレベル1）の圧縮率で、保存・高速・高圧縮を選択
+//!
+//! 判定結果は出力の先頭にヘッダーとして記録し、復元時はヘッダーに従って展開します。
+//!
+//! # ヘッダー形式
+//!
+//! | オフセット | サイズ | 内容 |
+//! |-----------|--------|------|
+//! | 0 | 4 | マジック `BSAD` |
+//! | 4 | 1 | 判定（0: 保存, 1: 高速zstd, 2: 高圧縮zstd） |
+//! | 5 | 4 | zstd圧縮レベル（リトルエンディアン） |
+//! | 9 | 8 | 元のサイズ（リトルエンディアン） |
+//! | 17 | - | データ本体 |
+//!
+//! # 使用例
+//!
+//! ```
+//! use backup_suite::compression::adaptive::{self, AdaptiveCompressor, AdaptiveDecision};
+//! use std::path::Path;
+//!
+//! let compressor = AdaptiveCompressor::new(19);
+//! let text = b"log line: everything is fine\n".repeat(1000);
+//! let (encoded, decision) = compressor.compress(&text, Some(Path::new("app.log"))).unwrap();
+//! assert_eq!(decision, AdaptiveDecision::High);
+//!
+//! let (_, decision) = compressor.compress(b"\xFF\xD8\xFF\xE0 jpeg", Some(Path::new("a.jpg"))).unwrap();
+//! assert_eq!(decision, AdaptiveDecision::Store);
+//!
+//! assert_eq!(adaptive::decode(&encoded).unwrap().unwrap(), text);
+//! ```
+
+use crate::error::{BackupError, Result};
+use serde::Serialize;
+use std::path::Path;
+
+/// 適応圧縮ヘッダーのマジックバイト
+pub const ADAPTIVE_MAGIC: &[u8; 4] = b"BSAD";
+
+/// 適応圧縮ヘッダーのサイズ（バイト）
+pub const HEADER_SIZE: usize = 17;
+
+/// 高速zstdの圧縮レベル
+pub const FAST_LEVEL: i32 = 1;
+
+/// 高圧縮zstdのデフォルトレベル
+pub const DEFAULT_HIGH_LEVEL: i32 = 19;
+
+/// 判定に使用するサンプルサイズ（先頭から）
+const SAMPLE_SIZE: usize = 64 * 1024;
+
+/// これより小さいデータはヘッダー分のオーバーヘッドが勝るため保存
+const MIN_COMPRESS_SIZE: usize = 128;
+
+/// これより大きいデータは高圧縮レベルの処理時間が見合わないため高速zstd
+const HIGH_LEVEL_MAX_SIZE: usize = 64 * 1024 * 1024;
+
+/// サンプルのエントロピー（ビット/バイト）がこれ以上なら圧縮済み・暗号化済みとみなして保存
+const ENTROPY_THRESHOLD: f64 = 7.5;
+
+/// 試験圧縮の圧縮率（圧縮後/元）がこれ以上なら保存
+const STORE_RATIO: f64 = 0.9;
+
+/// 試験圧縮の圧縮率がこれ未満なら高圧縮zstd（以上なら高速zstd）
+const HIGH_RATIO: f64 = 0.7;
+
+/// 圧縮済み形式の拡張子（小文字）
+const INCOMPRESSIBLE_EXTENSIONS: &[&str] = &[
+    // 画像
+    "jpg", "jpeg", "png", "gif", "webp", "heic", "heif", "avif", "jxl",
+    // 動画・音声
+    "mp4", "m4v", "mkv", "mov", "avi", "webm", "mp3", "m4a", "aac", "ogg", "opus", "flac",
+    // アーカイブ・圧縮ファイル
+    "zip", "gz", "tgz", "bz2", "xz", "txz", "zst", "lz4", "br", "7z", "rar",
+    // zipベースの形式
+    "jar", "apk", "docx", "xlsx", "pptx", "odt", "ods", "epub",
+];
+
+/// 圧縮済み形式のマジックバイト（オフセット, バイト列）
+const INCOMPRESSIBLE_MAGIC: &[(usize, &[u8])] = &[
+    (0, &[0x28, 0xB5, 0x2F, 0xFD]),             // zstd
+    (0, &[0x1F, 0x8B]),                         // gzip
+    (0, b"PK\x03\x04"),                         // zip
+    (0, &[0xFD, b'7', b'z', b'X', b'Z', 0x00]), // xz
+    (0, b"BZh"),                                // bzip2
+    (0, &[0x04, 0x22, 0x4D, 0x18]),             // lz4
+    (0, b"7z\xBC\xAF\x27\x1C"),                 // 7z
+    (0, b"Rar!"),                               // rar
+    (0, &[0xFF, 0xD8, 0xFF]),                   // jpeg
+    (0, b"\x89PNG"),                            // png
+    (0, b"GIF8"),                               // gif
+    (0, b"OggS"),                               // ogg
+    (0, b"fLaC"),                               // flac
+    (0, b"ID3"),                                // mp3
+    (4, b"ftyp"),                               // mp4 / mov / heic
+    (8, b"WEBP"),                               // webp
+];
+
+/// ファイルごとの圧縮方法の判定結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AdaptiveDecision {
+    /// 圧縮せずに保存
+    Store,
+    /// 高速zstd（レベル1）
+    Fast,
+    /// 高圧縮zstd
+    High,
+}
+
+impl AdaptiveDecision {
+    /// 表示用の名前
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Store => "store",
+            Self::Fast => "fast",
+            Self::High => "high",
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::Store => 0,
+            Self::Fast => 1,
+            Self::High => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Store),
+            1 => Some(Self::Fast),
+            2 => Some(Self::High),
+            _ => None,
+        }
+    }
+}
+
+/// 適応圧縮器
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveCompressor {
+    high_level: i32,
+}
+
+impl AdaptiveCompressor {
+    /// 高圧縮zstdのレベルを指定して作成
+    #[must_use]
+    pub fn new(high_level: i32) -> Self {
+        Self { high_level }
+    }
+
+    /// データの圧縮方法を判定
+    ///
+    /// `path` を指定した場合は拡張子も判定に使用します。
+    #[must_use]
+    pub fn decide(&self, data: &[u8], path: Option<&Path>) -> AdaptiveDecision {
+        if data.len() < MIN_COMPRESS_SIZE || is_incompressible_extension(path) {
+            return AdaptiveDecision::Store;
+        }
+        if has_incompressible_magic(data) {
+            return AdaptiveDecision::Store;
+        }
+
+        let sample = &data[..data.len().min(SAMPLE_SIZE)];
+        if shannon_entropy(sample) >= ENTROPY_THRESHOLD {
+            return AdaptiveDecision::Store;
+        }
+
+        let Ok(trial) = zstd::bulk::compress(sample, FAST_LEVEL) else {
+            return AdaptiveDecision::Store;
+        };
+        let ratio = trial.len() as f64 / sample.len() as f64;
+        if ratio >= STORE_RATIO {
+            AdaptiveDecision::Store
+        } else if ratio < HIGH_RATIO && data.len() <= HIGH_LEVEL_MAX_SIZE {
+            AdaptiveDecision::High
+        } else {
+            AdaptiveDecision::Fast
+        }
+    }
+
+    /// 判定に従ってデータを圧縮し、ヘッダー付きの出力を返す
+    ///
+    /// 圧縮しても小さくならなかった場合は保存に切り替えます。
+    ///
+    /// # Errors
+    ///
+    /// zstd圧縮に失敗した場合にエラーを返します。
+    pub fn compress(
+        &self,
+        data: &[u8],
+        path: Option<&Path>,
+    ) -> Result<(Vec<u8>, AdaptiveDecision)> {
+        let decision = self.decide(data, path);
+        let level = match decision {
+            AdaptiveDecision::Store => 0,
+            AdaptiveDecision::Fast => FAST_LEVEL,
+            AdaptiveDecision::High => self.high_level,
+        };
+
+        if decision != AdaptiveDecision::Store {
+            let compressed = zstd::bulk::compress(data, level)
+                .map_err(|e| BackupError::CompressionError(format!("Zstd圧縮エラー: {e}")))?;
+            if compressed.len() < data.len() {
+                return Ok((encode(decision, level, data.len(), &compressed), decision));
+            }
+        }
+
+        Ok((
+            encode(AdaptiveDecision::Store, 0, data.len(), data),
+            AdaptiveDecision::Store,
+        ))
+    }
+}
+
+impl Default for AdaptiveCompressor {
+    fn default() -> Self {
+        Self::new(DEFAULT_HIGH_LEVEL)
+    }
+}
+
+/// ヘッダー付きの適応圧縮データを展開
+///
+/// 適応圧縮ヘッダーで始まらないデータは `None` を返します。
+///
+/// # Errors
+///
+/// ヘッダーが壊れている場合、展開に失敗した場合、展開後のサイズが
+/// ヘッダーの記録と一致しない場合に `Some(Err)` を返します。
+#[must_use]
+pub fn decode(data: &[u8]) -> Option<Result<Vec<u8>>> {
+    let header = data.get(..HEADER_SIZE)?;
+    if !header.starts_with(ADAPTIVE_MAGIC) {
+        return None;
+    }
+    Some(decode_body(header, &data[HEADER_SIZE..]))
+}
+
+/// ヘッダーから判定結果を取得
+///
+/// 適応圧縮ヘッダーで始まらないデータは `None` を返します。
+#[must_use]
+pub fn decision_of(data: &[u8]) -> Option<AdaptiveDecision> {
+    let header = data.get(..HEADER_SIZE)?;
+    if !header.starts_with(ADAPTIVE_MAGIC) {
+        return None;
+    }
+    AdaptiveDecision::from_byte(header[4])
+}
+
+fn decode_body(header: &[u8], body: &[u8]) -> Result<Vec<u8>> {
+    let decision = AdaptiveDecision::from_byte(header[4])
+        .ok_or_else(|| BackupError::CompressionError("不明な適応圧縮の判定".to_string()))?;
+    let original_size = header
+        .get(9..17)
+        .and_then(|s| s.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or_else(|| BackupError::CompressionError("元のサイズの読み取りに失敗".to_string()))?;
+
+    let decoded = match decision {
+        AdaptiveDecision::Store => body.to_vec(),
+        AdaptiveDecision::Fast | AdaptiveDecision::High => zstd::decode_all(body)
+            .map_err(|e| BackupError::CompressionError(format!("Zstd展開エラー: {e}")))?,
+    };
+
+    if decoded.len() as u64 != original_size {
+        return Err(BackupError::CompressionError(
+            "展開後のサイズが一致しません".to_string(),
+        ));
+    }
+    Ok(decoded)
+}
+
+fn encode(decision: AdaptiveDecision, level: i32, original_size: usize, body: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(HEADER_SIZE + body.len());
+    result.extend_from_slice(ADAPTIVE_MAGIC);
+    result.push(decision.to_byte());
+    result.extend_from_slice(&(level as u32).to_le_bytes());
+    result.extend_from_slice(&(original_size as u64).to_le_bytes());
+    result.extend_from_slice(body);
+    result
+}
+
+fn is_incompressible_extension(path: Option<&Path>) -> bool {
+    path.and_then(Path::extension)
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            INCOMPRESSIBLE_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
+fn has_incompressible_magic(data: &[u8]) -> bool {
+    INCOMPRESSIBLE_MAGIC.iter().any(|(offset, magic)| {
+        data.get(*offset..offset + magic.len())
+            .is_some_and(|bytes| bytes == *magic)
+    })
+}
+
+/// バイト単位のシャノンエントロピー（ビット/バイト、0.0〜8.0）
+fn shannon_entropy(sample: &[u8]) -> f64 {
+    if sample.is_empty() {
+        return 0.0;
+    }
+    let mut counts = [0usize; 256];
+    for &byte in sample {
+        counts[usize::from(byte)] += 1;
+    }
+    let len = sample.len() as f64;
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// 圧縮結果の集計
+///
+/// `stored_files` / `fast_files` / `high_files` は適応圧縮での判定ごとのファイル数です。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CompressionStats {
+    /// 圧縮したファイルの元の合計サイズ
+    pub original_bytes: u64,
+    /// 圧縮後の合計サイズ
+    pub compressed_bytes: u64,
+    /// 適応圧縮で保存（無圧縮）と判定したファイル数
+    pub stored_files: usize,
+    /// 適応圧縮で高速zstdと判定したファイル数
+    pub fast_files: usize,
+    /// 適応圧縮で高圧縮zstdと判定したファイル数
+    pub high_files: usize,
+}
+
+impl CompressionStats {
+    /// 1ファイルの圧縮結果を追加
+    pub fn record(
+        &mut self,
+        original_bytes: u64,
+        compressed_bytes: u64,
+        decision: Option<AdaptiveDecision>,
+    ) {
+        self.original_bytes += original_bytes;
+        self.compressed_bytes += compressed_bytes;
+        match decision {
+            Some(AdaptiveDecision::Store) => self.stored_files += 1,
+            Some(AdaptiveDecision::Fast) => self.fast_files += 1,
+            Some(AdaptiveDecision::High) => self.high_files += 1,
+            None => {}
+        }
+    }
+
+    /// 圧縮で削減したバイト数
+    #[must_use]
+    pub fn saved_bytes(&self) -> u64 {
+        self.original_bytes.saturating_sub(self.compressed_bytes)
+    }
+
+    /// 削減率（%）
+    #[must_use]
+    pub fn saved_percentage(&self) -> f64 {
+        if self.original_bytes == 0 {
+            return 0.0;
+        }
+        self.saved_bytes() as f64 / self.original_bytes as f64 * 100.0
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Tests can use unwrap
+mod tests {
+    use super::*;
+
+    /// 疑似乱数（圧縮できないデータ）
+    fn noise(len: usize) -> Vec<u8> {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state.to_le_bytes()[0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_decide_by_extension_and_magic() {
+        let compressor = AdaptiveCompressor::default();
+        let text = b"plain text that compresses well. ".repeat(100);
+
+        assert_eq!(
+            compressor.decide(&text, Some(Path::new("photo.JPG"))),
+            AdaptiveDecision::Store
+        );
+        let zstd_file = zstd::encode_all(&text[..], 3).unwrap();
+        let mut padded = zstd_file.clone();
+        padded.resize(MIN_COMPRESS_SIZE * 2, 0);
+        assert_eq!(
+            compressor.decide(&padded, Some(Path::new("data.bin"))),
+            AdaptiveDecision::Store
+        );
+        assert_eq!(
+            compressor.decide(&text, Some(Path::new("notes.txt"))),
+            AdaptiveDecision::High
+        );
+    }
+
+    #[test]
+    fn test_decide_by_entropy_and_trial() {
+        let compressor = AdaptiveCompressor::default();
+        assert_eq!(
+            compressor.decide(&noise(8192), None),
+            AdaptiveDecision::Store
+        );
+        assert_eq!(compressor.decide(b"tiny", None), AdaptiveDecision::Store);
+
+        // 半分が乱数のデータは中程度の圧縮率
+        let mut mixed = noise(4096);
+        mixed.extend(std::iter::repeat_n(b'a', 1024));
+        assert!(shannon_entropy(&mixed) < ENTROPY_THRESHOLD);
+        assert_eq!(compressor.decide(&mixed, None), AdaptiveDecision::Fast);
+    }
+
+    #[test]
+    fn test_roundtrip_and_never_grows() {
+        let compressor = AdaptiveCompressor::new(9);
+        for data in [b"compressible ".repeat(500), noise(10_000), Vec::new()] {
+            let (encoded, decision) = compressor.compress(&data, None).unwrap();
+            assert!(encoded.len() <= data.len() + HEADER_SIZE);
+            assert_eq!(decision_of(&encoded), Some(decision));
+            assert_eq!(decode(&encoded).unwrap().unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_decode_rejects_foreign_and_corrupt_data() {
+        assert!(decode(b"not adaptive data at all").is_none());
+
+        let (mut encoded, _) = AdaptiveCompressor::default()
+            .compress(&b"abc".repeat(100), None)
+            .unwrap();
+        encoded.truncate(encoded.len() - 1);
+        assert!(decode(&encoded).unwrap().is_err());
+    }
+
+    #[test]
+    fn test_compression_stats() {
+        let mut stats = CompressionStats::default();
+        stats.record(1000, 250, Some(AdaptiveDecision::High));
+        stats.record(500, 500, Some(AdaptiveDecision::Store));
+        stats.record(500, 250, None);
+        assert_eq!(stats.saved_bytes(), 1000);
+        assert!((stats.saved_percentage() - 50.0).abs() < f64::EPSILON);
+        assert_eq!(
+            (stats.stored_files, stats.fast_files, stats.high_files),
+            (1, 0, 1)
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! # zstd辞書圧縮
+//!
+//! 小さなJSON・設定ファイルが大量にある対象では、ファイルごとのzstd圧縮はほとんど効きません。
+//! 対象のファイルから抽出したサンプルでzstd辞書を学習し、小さなファイルをその辞書で圧縮します。
+//!
+//! 学習した辞書はスナップショットの [`DICTIONARY_DIR`] に `<辞書ID>.zdict` として一度だけ保存します。
+//! 辞書IDはzstdフレームのヘッダーに記録されるため、復元時はフレームから辞書IDを読み取って
+//! 対応する辞書で展開します（[`decode`]）。
+//!
+//! # 使用例
+//!
+//! ```
+//! use backup_suite::compression::dictionary::ZstdDictionary;
+//!
+//! let samples: Vec<Vec<u8>> = (0..200)
+//!     .map(|i| format!(r#"{{"id": {i}, "name": "service-{i}", "enabled": true, "port": {}}}"#, 8000 + i).into_bytes())
+//!     .collect();
+//! let dictionary = ZstdDictionary::train(&samples).unwrap();
+//!
+//! let compressed = dictionary.compress(&samples[0], 3).unwrap();
+//! assert_eq!(backup_suite::compression::dictionary::frame_dictionary_id(&compressed), Some(dictionary.id()));
+//! assert_eq!(dictionary.decompress(&compressed).unwrap(), samples[0]);
+//! ```
+
+use super::engines::CompressionType;
+use super::header;
+use crate::error::{BackupError, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// スナップショット内で辞書を保存するディレクトリ名
+pub const DICTIONARY_DIR: &str = ".dictionaries";
+
+/// 辞書ファイルの拡張子
+const DICTIONARY_EXTENSION: &str = "zdict";
+
+/// 辞書で圧縮するファイルの最大サイズ（これより大きいファイルは通常のzstdで十分に圧縮できる）
+pub const SMALL_FILE_THRESHOLD: u64 = 64 * 1024;
+
+/// 学習に使用するサンプル数の上限
+pub const MAX_SAMPLES: usize = 2000;
+
+/// 学習に必要な最小サンプル数（これ未満の対象では辞書を作成しない）
+pub const MIN_SAMPLES: usize = 16;
+
+/// 辞書の最大サイズ（zstd CLIのデフォルトと同じ約110KiB）
+pub const MAX_DICTIONARY_SIZE: usize = 112_640;
+
+/// 学習済みzstd辞書
+#[derive(Debug, Clone)]
+pub struct ZstdDictionary {
+    id: u32,
+    data: Vec<u8>,
+}
+
+impl ZstdDictionary {
+    /// サンプルから辞書を学習
+    ///
+    /// # Errors
+    ///
+    /// サンプルが不足している場合など、zstdが辞書を学習できない場合にエラーを返します。
+    pub fn train<S: AsRef<[u8]>>(samples: &[S]) -> Result<Self> {
+        let data = zstd::dict::from_samples(samples, MAX_DICTIONARY_SIZE)
+            .map_err(|e| BackupError::CompressionError(format!("zstd辞書の学習エラー: {e}")))?;
+        Self::from_bytes(data)
+    }
+
+    /// 辞書データから作成
+    ///
+    /// # Errors
+    ///
+    /// 辞書IDを持たないデータ（zstd辞書形式でないデータ）の場合にエラーを返します。
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        let id = zstd::zstd_safe::get_dict_id(&data)
+            .ok_or_else(|| BackupError::CompressionError("zstd辞書の形式が不正です".to_string()))?
+            .get();
+        Ok(Self { id, data })
+    }
+
+    /// 辞書ID（zstdフレームのヘッダーに記録される）
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// 辞書データ
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// 辞書を使ってzstd圧縮
+    ///
+    /// # Errors
+    ///
+    /// zstdが圧縮に失敗した場合にエラーを返します。
+    pub fn compress(&self, data: &[u8], level: i32) -> Result<Vec<u8>> {
+        zstd::bulk::Compressor::with_dictionary(level, &self.data)
+            .and_then(|mut compressor| compressor.compress(data))
+            .map_err(|e| BackupError::CompressionError(format!("Zstd辞書圧縮エラー: {e}")))
+    }
+
+    /// 辞書を使ってzstd展開
+    ///
+    /// # Errors
+    ///
+    /// データがこの辞書で圧縮されたzstdフレームでない場合にエラーを返します。
+    pub fn decompress(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut decompressed = Vec::new();
+        zstd::stream::read::Decoder::with_dictionary(data, &self.data)
+            .and_then(|mut decoder| decoder.read_to_end(&mut decompressed))
+            .map_err(|e| BackupError::CompressionError(format!("Zstd辞書展開エラー: {e}")))?;
+        Ok(decompressed)
+    }
+
+    /// スナップショットの辞書ディレクトリに保存（同じIDの辞書が既にあれば何もしない）
+    ///
+    /// # Errors
+    ///
+    /// ディレクトリの作成・ファイルの書き込みに失敗した場合にエラーを返します。
+    pub fn save(&self, backup_dir: &Path) -> Result<PathBuf> {
+        let dir = backup_dir.join(DICTIONARY_DIR);
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{}.{DICTIONARY_EXTENSION}", self.id));
+        if !path.exists() {
+            let mut file = fs::File::create(&path)?;
+            file.write_all(&self.data)?;
+            file.sync_all()?;
+        }
+        Ok(path)
+    }
+
+    /// スナップショットに保存された辞書をすべて読み込み（辞書ID → 辞書）
+    ///
+    /// 辞書ディレクトリがない場合は空のマップを返します。
+    ///
+    /// # Errors
+    ///
+    /// 辞書ファイルの読み込みに失敗した場合、または形式が不正な場合にエラーを返します。
+    pub fn load_all(backup_dir: &Path) -> Result<HashMap<u32, Self>> {
+        let dir = backup_dir.join(DICTIONARY_DIR);
+        let mut dictionaries = HashMap::new();
+        if !dir.is_dir() {
+            return Ok(dictionaries);
+        }
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(DICTIONARY_EXTENSION) {
+                continue;
+            }
+            let dictionary = Self::from_bytes(fs::read(&path)?)?;
+            dictionaries.insert(dictionary.id, dictionary);
+        }
+        Ok(dictionaries)
+    }
+}
+
+/// zstdフレームのヘッダーに記録された辞書IDを取得（辞書なしのフレームは `None`）
+#[must_use]
+pub fn frame_dictionary_id(data: &[u8]) -> Option<u32> {
+    zstd::zstd_safe::get_dict_id_from_frame(data).map(std::num::NonZeroU32::get)
+}
+
+/// 辞書で圧縮されたヘッダー付きzstdデータを展開
+///
+/// コーデックヘッダーがzstdで、フレームに辞書IDが記録されている場合のみ `Some` を返します。
+///
+/// # Errors
+///
+/// 記録された辞書IDの辞書が `dictionaries` にない場合、または展開に失敗した場合に
+/// `Some(Err(..))` を返します。
+pub fn decode(data: &[u8], dictionaries: &HashMap<u32, ZstdDictionary>) -> Option<Result<Vec<u8>>> {
+    if header::codec_of(data) != Some(CompressionType::Zstd) {
+        return None;
+    }
+    let body = data.get(header::HEADER_SIZE..)?;
+    let id = frame_dictionary_id(body)?;
+    Some(match dictionaries.get(&id) {
+        Some(dictionary) => dictionary.decompress(body),
+        None => Err(BackupError::CompressionError(format!(
+            "zstd辞書が見つかりません（辞書ID: {id}）"
+        ))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn samples() -> Vec<Vec<u8>> {
+        (0..300)
+            .map(|i| {
+                format!(
+                    r#"{{"id": {i}, "service": "api-{}", "replicas": {}, "healthcheck": {{"path": "/health", "interval": 30}}}}"#,
+                    i % 7,
+                    i % 5
+                )
+                .into_bytes()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_train_compress_decompress() {
+        let samples = samples();
+        let dictionary = ZstdDictionary::train(&samples).unwrap();
+        let plain = zstd::encode_all(samples[42].as_slice(), 3).unwrap();
+        let compressed = dictionary.compress(&samples[42], 3).unwrap();
+
+        assert!(compressed.len() < plain.len());
+        assert_eq!(frame_dictionary_id(&compressed), Some(dictionary.id()));
+        assert_eq!(frame_dictionary_id(&plain), None);
+        assert_eq!(dictionary.decompress(&compressed).unwrap(), samples[42]);
+    }
+
+    #[test]
+    fn test_save_load_and_decode() {
+        let temp = TempDir::new().unwrap();
+        let samples = samples();
+        let dictionary = ZstdDictionary::train(&samples).unwrap();
+        dictionary.save(temp.path()).unwrap();
+
+        let framed = header::encode(
+            CompressionType::Zstd,
+            &dictionary.compress(&samples[1], 3).unwrap(),
+        );
+        let loaded = ZstdDictionary::load_all(temp.path()).unwrap();
+        assert_eq!(loaded[&dictionary.id()].as_bytes(), dictionary.as_bytes());
+        assert_eq!(decode(&framed, &loaded).unwrap().unwrap(), samples[1]);
+
+        // 辞書がない場合はエラー、辞書なしのフレームは対象外
+        assert!(decode(&framed, &HashMap::new()).unwrap().is_err());
+        let plain = header::encode(
+            CompressionType::Zstd,
+            &zstd::encode_all(&b"x"[..], 3).unwrap(),
+        );
+        assert!(decode(&plain, &loaded).is_none());
+    }
+
+    #[test]
+    fn test_train_with_too_few_samples_fails() {
+        assert!(ZstdDictionary::train(&[b"a".to_vec(), b"b".to_vec()]).is_err());
+    }
+}
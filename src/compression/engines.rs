@@ -1,21 +1,36 @@
 //! # 圧縮エンジン
 //!
-//! zstd と gzip アルゴリズムによる高性能データ圧縮システム
+//! zstd・gzip・LZ4・xz・Brotli アルゴリズムによる高性能データ圧縮システム
 
+use super::adaptive::{self, AdaptiveCompressor, AdaptiveDecision};
+use super::dictionary::{frame_dictionary_id, ZstdDictionary, SMALL_FILE_THRESHOLD};
 use crate::error::{BackupError, Result};
 use clap::ValueEnum;
 use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
 use std::io::{Read, Write};
+use std::ops::RangeInclusive;
+use std::path::Path;
 use std::str::FromStr;
+use std::sync::Arc;
 use zstd::{Decoder as ZstdDecoder, Encoder as ZstdEncoder};
 
 /// 圧縮アルゴリズムタイプ
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum CompressionType {
     /// zstd 圧縮（高速・高圧縮率）
     Zstd,
     /// gzip 圧縮（互換性重視）
     Gzip,
+    /// LZ4 圧縮（最高速・日次バックアップ向け）
+    Lz4,
+    /// xz 圧縮（最高圧縮率・長期アーカイブ向け）
+    Xz,
+    /// Brotli 圧縮（テキストで高圧縮率）
+    Brotli,
+    /// ファイルごとに保存・高速zstd・高圧縮zstdを選択（圧縮済みデータを再圧縮しない）
+    Adaptive,
     /// 圧縮なし
     None,
 }
@@ -27,6 +42,10 @@ impl FromStr for CompressionType {
         match s.to_lowercase().as_str() {
             "zstd" => Ok(Self::Zstd),
             "gzip" => Ok(Self::Gzip),
+            "lz4" => Ok(Self::Lz4),
+            "xz" => Ok(Self::Xz),
+            "brotli" => Ok(Self::Brotli),
+            "adaptive" => Ok(Self::Adaptive),
             "none" => Ok(Self::None),
             _ => Err(BackupError::CompressionError(format!(
                 "不明な圧縮タイプ: {s}"
@@ -42,6 +61,10 @@ impl CompressionType {
         match self {
             Self::Zstd => "zstd",
             Self::Gzip => "gzip",
+            Self::Lz4 => "lz4",
+            Self::Xz => "xz",
+            Self::Brotli => "brotli",
+            Self::Adaptive => "adaptive",
             Self::None => "none",
         }
     }
@@ -52,15 +75,78 @@ impl CompressionType {
         match self {
             Self::Zstd => ".zst",
             Self::Gzip => ".gz",
+            Self::Lz4 => ".lz4",
+            Self::Xz => ".xz",
+            Self::Brotli => ".br",
+            Self::Adaptive => "",
             Self::None => "",
         }
     }
+
+    /// 指定可能な圧縮レベルの範囲（レベルのない形式は `None`）
+    #[must_use]
+    pub fn level_range(&self) -> Option<RangeInclusive<i32>> {
+        match self {
+            Self::Zstd | Self::Adaptive => Some(1..=22),
+            Self::Gzip => Some(1..=9),
+            Self::Xz => Some(0..=9),
+            Self::Brotli => Some(0..=11),
+            Self::Lz4 | Self::None => None,
+        }
+    }
+
+    /// 圧縮レベル未指定時のデフォルト
+    #[must_use]
+    pub fn default_level(&self) -> i32 {
+        match self {
+            Self::Zstd | Self::Gzip => 3,
+            Self::Xz => 6,
+            Self::Brotli => 9,
+            Self::Adaptive => adaptive::DEFAULT_HIGH_LEVEL,
+            Self::Lz4 | Self::None => 0,
+        }
+    }
+
+    /// ファイルヘッダーに記録する識別子
+    #[must_use]
+    pub fn id(&self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Zstd => 1,
+            Self::Gzip => 2,
+            Self::Adaptive => 3,
+            Self::Lz4 => 4,
+            Self::Xz => 5,
+            Self::Brotli => 6,
+        }
+    }
+
+    /// ファイルヘッダーの識別子から圧縮タイプを取得
+    #[must_use]
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::None),
+            1 => Some(Self::Zstd),
+            2 => Some(Self::Gzip),
+            3 => Some(Self::Adaptive),
+            4 => Some(Self::Lz4),
+            5 => Some(Self::Xz),
+            6 => Some(Self::Brotli),
+            _ => None,
+        }
+    }
 }
 
+/// Brotliのウィンドウサイズ（2^22 = 4MB）
+const BROTLI_WINDOW_BITS: u32 = 22;
+
+/// Brotliの内部バッファサイズ
+const BROTLI_BUFFER_SIZE: usize = 64 * 1024;
+
 /// 圧縮設定
 #[derive(Debug, Clone)]
 pub struct CompressionConfig {
-    /// 圧縮レベル（1-22 for zstd, 1-9 for gzip）
+    /// 圧縮レベル（1-22 for zstd, 1-9 for gzip, 0-9 for xz, 0-11 for Brotli, LZ4は未使用）
     pub level: i32,
     /// チャンクサイズ（バイト）
     pub chunk_size: usize,
@@ -109,15 +195,74 @@ impl CompressionConfig {
         }
     }
 
+    /// LZ4用のデフォルト設定（LZ4はレベルを持たない）
+    #[must_use]
+    pub fn lz4_default() -> Self {
+        Self {
+            level: 0,
+            chunk_size: 4 * 1024 * 1024, // 4MB チャンク（速度重視）
+            buffer_size: 256 * 1024,     // 256KB バッファ
+        }
+    }
+
+    /// xz用のデフォルト設定
+    #[must_use]
+    pub fn xz_default() -> Self {
+        Self {
+            level: 6,                // xz コマンドと同じデフォルトレベル
+            chunk_size: 1024 * 1024, // 1MB チャンク
+            buffer_size: 64 * 1024,  // 64KB バッファ
+        }
+    }
+
+    /// Brotli用のデフォルト設定
+    #[must_use]
+    pub fn brotli_default() -> Self {
+        Self {
+            level: 9,                // 最大11は非常に遅いため9
+            chunk_size: 1024 * 1024, // 1MB チャンク
+            buffer_size: 64 * 1024,  // 64KB バッファ
+        }
+    }
+
+    /// 圧縮タイプに応じたデフォルト設定
+    #[must_use]
+    pub fn default_for(compression_type: CompressionType) -> Self {
+        match compression_type {
+            CompressionType::Zstd => Self::zstd_default(),
+            CompressionType::Gzip => Self::gzip_default(),
+            CompressionType::Lz4 => Self::lz4_default(),
+            CompressionType::Xz => Self::xz_default(),
+            CompressionType::Brotli => Self::brotli_default(),
+            CompressionType::Adaptive => Self::adaptive_default(),
+            CompressionType::None => Self::none(),
+        }
+    }
+
+    /// 適応圧縮用のデフォルト設定（レベルは高圧縮zstdに使用）
+    #[must_use]
+    pub fn adaptive_default() -> Self {
+        Self {
+            level: adaptive::DEFAULT_HIGH_LEVEL,
+            chunk_size: 2 * 1024 * 1024,
+            buffer_size: 128 * 1024,
+        }
+    }
+
     /// 高速圧縮設定
     #[must_use]
     pub fn fast(compression_type: CompressionType) -> Self {
         match compression_type {
-            CompressionType::Zstd | CompressionType::Gzip => Self {
+            CompressionType::Zstd
+            | CompressionType::Gzip
+            | CompressionType::Xz
+            | CompressionType::Brotli
+            | CompressionType::Adaptive => Self {
                 level: 1,
                 chunk_size: 2 * 1024 * 1024, // 2MB チャンク（高速化）
                 buffer_size: 128 * 1024,     // 128KB バッファ
             },
+            CompressionType::Lz4 => Self::lz4_default(),
             CompressionType::None => Self::none(),
         }
     }
@@ -136,6 +281,22 @@ impl CompressionConfig {
                 chunk_size: 512 * 1024,
                 buffer_size: 32 * 1024,
             },
+            CompressionType::Xz => Self {
+                level: 9,
+                chunk_size: 512 * 1024,
+                buffer_size: 32 * 1024,
+            },
+            CompressionType::Brotli => Self {
+                level: 11,
+                chunk_size: 512 * 1024,
+                buffer_size: 32 * 1024,
+            },
+            CompressionType::Adaptive => Self {
+                level: 19,
+                chunk_size: 512 * 1024,
+                buffer_size: 32 * 1024,
+            },
+            CompressionType::Lz4 => Self::lz4_default(),
             CompressionType::None => Self::none(),
         }
     }
@@ -189,11 +350,7 @@ impl CompressedData {
         let mut result = Vec::with_capacity(25 + self.data.len());
 
         // ヘッダー情報
-        result.push(match self.compression_type {
-            CompressionType::Zstd => 1,
-            CompressionType::Gzip => 2,
-            CompressionType::None => 0,
-        });
+        result.push(self.compression_type.id());
         #[allow(clippy::cast_sign_loss)]
         result.extend_from_slice(&(self.compression_level as u32).to_le_bytes());
         result.extend_from_slice(&self.original_size.to_le_bytes());
@@ -220,19 +377,13 @@ impl CompressedData {
         }
 
         // SAFETY: Length check above ensures data has at least 25 bytes
-        let compression_type = match *data
+        let compression_type = data
             .first()
-            .ok_or_else(|| BackupError::CompressionError("データが空です".to_string()))?
-        {
-            1 => CompressionType::Zstd,
-            2 => CompressionType::Gzip,
-            0 => CompressionType::None,
-            _ => {
-                return Err(BackupError::CompressionError(
-                    "不明な圧縮タイプ".to_string(),
-                ))
-            }
-        };
+            .ok_or_else(|| BackupError::CompressionError("データが空です".to_string()))
+            .and_then(|&id| {
+                CompressionType::from_id(id)
+                    .ok_or_else(|| BackupError::CompressionError("不明な圧縮タイプ".to_string()))
+            })?;
 
         #[allow(clippy::cast_possible_wrap)]
         let compression_level = u32::from_le_bytes(
@@ -278,6 +429,7 @@ impl CompressedData {
 pub struct CompressionEngine {
     config: CompressionConfig,
     compression_type: CompressionType,
+    dictionary: Option<Arc<ZstdDictionary>>,
 }
 
 impl CompressionEngine {
@@ -287,9 +439,20 @@ impl CompressionEngine {
         Self {
             config,
             compression_type,
+            dictionary: None,
         }
     }
 
+    /// zstd辞書を設定
+    ///
+    /// zstd圧縮で [`dictionary::SMALL_FILE_THRESHOLD`](super::dictionary::SMALL_FILE_THRESHOLD)
+    /// 以下のデータを辞書で圧縮します（他の圧縮タイプでは使用しません）。
+    #[must_use]
+    pub fn with_dictionary(mut self, dictionary: Arc<ZstdDictionary>) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
     /// zstd圧縮エンジンを作成
     #[must_use]
     pub fn zstd(config: Option<CompressionConfig>) -> Self {
@@ -320,23 +483,56 @@ impl CompressionEngine {
     ///
     /// 圧縮エンジンがデータの圧縮に失敗した場合にエラーを返します。
     pub fn compress(&self, data: &[u8]) -> Result<CompressedData> {
+        self.compress_with_hint(data, None)
+            .map(|(compressed, _)| compressed)
+    }
+
+    /// ファイルパスを判定のヒントにしてデータを圧縮
+    ///
+    /// 適応圧縮では拡張子も判定に使用し、判定結果を返します。
+    /// それ以外の圧縮タイプでは `compress` と同じで、判定結果は `None` です。
+    ///
+    /// # Errors
+    ///
+    /// 圧縮エンジンがデータの圧縮に失敗した場合にエラーを返します。
+    pub fn compress_with_hint(
+        &self,
+        data: &[u8],
+        path: Option<&Path>,
+    ) -> Result<(CompressedData, Option<AdaptiveDecision>)> {
         let original_size = data.len() as u64;
 
-        let compressed_data = match self.compression_type {
-            CompressionType::Zstd => self.compress_zstd(data)?,
-            CompressionType::Gzip => self.compress_gzip(data)?,
-            CompressionType::None => data.to_vec(),
+        let (compressed_data, decision) = match self.compression_type {
+            CompressionType::Zstd => match &self.dictionary {
+                Some(dictionary) if original_size <= SMALL_FILE_THRESHOLD => {
+                    (dictionary.compress(data, self.config.level)?, None)
+                }
+                _ => (self.compress_zstd(data)?, None),
+            },
+            CompressionType::Gzip => (self.compress_gzip(data)?, None),
+            CompressionType::Lz4 => (Self::compress_lz4(data)?, None),
+            CompressionType::Xz => (self.compress_xz(data)?, None),
+            CompressionType::Brotli => (self.compress_brotli(data)?, None),
+            CompressionType::Adaptive => {
+                let (encoded, decision) =
+                    AdaptiveCompressor::new(self.config.level).compress(data, path)?;
+                (encoded, Some(decision))
+            }
+            CompressionType::None => (data.to_vec(), None),
         };
 
         let compressed_size = compressed_data.len() as u64;
 
-        Ok(CompressedData {
-            compression_type: self.compression_type,
-            compression_level: self.config.level,
-            original_size,
-            compressed_size,
-            data: compressed_data,
-        })
+        Ok((
+            CompressedData {
+                compression_type: self.compression_type,
+                compression_level: self.config.level,
+                original_size,
+                compressed_size,
+                data: compressed_data,
+            },
+            decision,
+        ))
     }
 
     /// データを展開
@@ -345,10 +541,36 @@ impl CompressionEngine {
     ///
     /// 圧縮エンジンがデータの展開に失敗した場合にエラーを返します。
     pub fn decompress(&self, compressed_data: &CompressedData) -> Result<Vec<u8>> {
-        match compressed_data.compression_type {
-            CompressionType::Zstd => Self::decompress_zstd(&compressed_data.data),
-            CompressionType::Gzip => Self::decompress_gzip(&compressed_data.data),
-            CompressionType::None => Ok(compressed_data.data.clone()),
+        // 設定した辞書で圧縮されたzstdフレームは辞書で展開
+        if let Some(dictionary) = &self.dictionary {
+            if compressed_data.compression_type == CompressionType::Zstd
+                && frame_dictionary_id(&compressed_data.data) == Some(dictionary.id())
+            {
+                return dictionary.decompress(&compressed_data.data);
+            }
+        }
+        Self::decompress_bytes(compressed_data.compression_type, &compressed_data.data)
+    }
+
+    /// 圧縮タイプを指定してデータを展開
+    ///
+    /// # Errors
+    ///
+    /// データが指定した形式で展開できない場合にエラーを返します。
+    pub fn decompress_bytes(compression_type: CompressionType, data: &[u8]) -> Result<Vec<u8>> {
+        match compression_type {
+            CompressionType::Zstd => Self::decompress_zstd(data),
+            CompressionType::Gzip => Self::decompress_gzip(data),
+            CompressionType::Lz4 => {
+                Self::read_decoded(lz4_flex::frame::FrameDecoder::new(data), "LZ4")
+            }
+            CompressionType::Xz => Self::read_decoded(xz2::read::XzDecoder::new(data), "xz"),
+            CompressionType::Brotli => Self::read_decoded(
+                brotli::Decompressor::new(data, BROTLI_BUFFER_SIZE),
+                "Brotli",
+            ),
+            CompressionType::Adaptive => Self::decompress_adaptive(data),
+            CompressionType::None => Ok(data.to_vec()),
         }
     }
 
@@ -411,6 +633,39 @@ impl CompressionEngine {
 
                 encoder.finish()?;
             }
+            CompressionType::Lz4 => {
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(&mut compressed_buffer);
+                original_size = std::io::copy(&mut reader, &mut encoder)?;
+                encoder
+                    .finish()
+                    .map_err(|e| BackupError::CompressionError(format!("LZ4圧縮エラー: {e}")))?;
+            }
+            CompressionType::Xz => {
+                let mut encoder =
+                    xz2::write::XzEncoder::new(&mut compressed_buffer, self.config.level as u32);
+                original_size = std::io::copy(&mut reader, &mut encoder)?;
+                encoder.finish()?;
+            }
+            CompressionType::Brotli => {
+                let mut encoder = brotli::CompressorWriter::new(
+                    &mut compressed_buffer,
+                    self.config.buffer_size,
+                    self.config.level as u32,
+                    BROTLI_WINDOW_BITS,
+                );
+                original_size = std::io::copy(&mut reader, &mut encoder)?;
+                // into_inner で最終ブロックを書き込む
+                let _ = encoder.into_inner();
+            }
+            CompressionType::Adaptive => {
+                // 判定にデータ全体の大きさとサンプルが必要なため、読み込んでから圧縮
+                let mut data = Vec::new();
+                reader.read_to_end(&mut data)?;
+                original_size = data.len() as u64;
+                compressed_buffer = AdaptiveCompressor::new(self.config.level)
+                    .compress(&data, None)?
+                    .0;
+            }
             CompressionType::None => {
                 let mut buffer = vec![0u8; self.config.buffer_size];
                 loop {
@@ -483,6 +738,29 @@ impl CompressionEngine {
                     decompressed_size += bytes_read as u64;
                 }
             }
+            CompressionType::Lz4 => {
+                let mut decoder = lz4_flex::frame::FrameDecoder::new(reader);
+                decompressed_size = std::io::copy(&mut decoder, &mut writer)
+                    .map_err(|e| BackupError::CompressionError(format!("LZ4展開エラー: {e}")))?;
+            }
+            CompressionType::Xz => {
+                let mut decoder = xz2::read::XzDecoder::new(reader);
+                decompressed_size = std::io::copy(&mut decoder, &mut writer)
+                    .map_err(|e| BackupError::CompressionError(format!("xz展開エラー: {e}")))?;
+            }
+            CompressionType::Brotli => {
+                let mut decoder = brotli::Decompressor::new(reader, self.config.buffer_size);
+                decompressed_size = std::io::copy(&mut decoder, &mut writer)
+                    .map_err(|e| BackupError::CompressionError(format!("Brotli展開エラー: {e}")))?;
+            }
+            CompressionType::Adaptive => {
+                let mut reader = reader;
+                let mut data = Vec::new();
+                reader.read_to_end(&mut data)?;
+                let decoded = Self::decompress_adaptive(&data)?;
+                writer.write_all(&decoded)?;
+                decompressed_size = decoded.len() as u64;
+            }
             CompressionType::None => {
                 let mut reader = reader;
                 let mut buffer = vec![0u8; self.config.buffer_size];
@@ -521,6 +799,52 @@ impl CompressionEngine {
             .map_err(|e| BackupError::CompressionError(format!("Gzip圧縮エラー: {e}")))
     }
 
+    fn compress_lz4(data: &[u8]) -> Result<Vec<u8>> {
+        let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+        encoder.write_all(data)?;
+        encoder
+            .finish()
+            .map_err(|e| BackupError::CompressionError(format!("LZ4圧縮エラー: {e}")))
+    }
+
+    fn compress_xz(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut encoder = xz2::write::XzEncoder::new(Vec::new(), self.config.level as u32);
+        encoder.write_all(data)?;
+        encoder
+            .finish()
+            .map_err(|e| BackupError::CompressionError(format!("xz圧縮エラー: {e}")))
+    }
+
+    fn compress_brotli(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut encoder = brotli::CompressorWriter::new(
+            Vec::new(),
+            BROTLI_BUFFER_SIZE,
+            self.config.level as u32,
+            BROTLI_WINDOW_BITS,
+        );
+        encoder
+            .write_all(data)
+            .map_err(|e| BackupError::CompressionError(format!("Brotli圧縮エラー: {e}")))?;
+        Ok(encoder.into_inner())
+    }
+
+    /// デコーダーから全データを読み込む
+    fn read_decoded<R: Read>(mut decoder: R, codec: &str) -> Result<Vec<u8>> {
+        let mut result = Vec::new();
+        decoder
+            .read_to_end(&mut result)
+            .map_err(|e| BackupError::CompressionError(format!("{codec}展開エラー: {e}")))?;
+        Ok(result)
+    }
+
+    fn decompress_adaptive(data: &[u8]) -> Result<Vec<u8>> {
+        adaptive::decode(data).unwrap_or_else(|| {
+            Err(BackupError::CompressionError(
+                "適応圧縮ヘッダーがありません".to_string(),
+            ))
+        })
+    }
+
     fn decompress_gzip(data: &[u8]) -> Result<Vec<u8>> {
         use std::io::Cursor;
         let mut decoder = GzDecoder::new(Cursor::new(data));
@@ -550,6 +874,10 @@ mod tests {
             "none".parse::<CompressionType>().unwrap(),
             CompressionType::None
         );
+        assert_eq!(
+            "adaptive".parse::<CompressionType>().unwrap(),
+            CompressionType::Adaptive
+        );
 
         assert_eq!(CompressionType::Zstd.to_str(), "zstd");
         assert_eq!(CompressionType::Gzip.file_extension(), ".gz");
@@ -593,6 +921,36 @@ mod tests {
         assert_eq!(compressed.compression_percentage(), 0.0);
     }
 
+    #[test]
+    fn test_adaptive_compression() {
+        let engine = CompressionEngine::new(
+            CompressionType::Adaptive,
+            CompressionConfig::adaptive_default(),
+        );
+        let text = b"Adaptive compression picks a level per file. ".repeat(100);
+
+        let (compressed, decision) = engine
+            .compress_with_hint(&text, Some(Path::new("notes.txt")))
+            .unwrap();
+        assert_eq!(decision, Some(AdaptiveDecision::High));
+        assert!(compressed.compressed_size < compressed.original_size);
+        assert_eq!(engine.decompress(&compressed).unwrap(), text);
+
+        // 拡張子から圧縮済みと判定したファイルは保存（ヘッダー分のみ増加）
+        let (stored, decision) = engine
+            .compress_with_hint(&text, Some(Path::new("movie.mp4")))
+            .unwrap();
+        assert_eq!(decision, Some(AdaptiveDecision::Store));
+        assert_eq!(
+            stored.compressed_size,
+            stored.original_size + adaptive::HEADER_SIZE as u64
+        );
+
+        let serialized = CompressedData::from_bytes(&compressed.to_bytes()).unwrap();
+        assert_eq!(serialized.compression_type, CompressionType::Adaptive);
+        assert_eq!(engine.decompress(&serialized).unwrap(), text);
+    }
+
     #[test]
     fn test_compressed_data_serialization() {
         let engine = CompressionEngine::zstd(None);
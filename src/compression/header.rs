@@ -0,0 +1,110 @@
+//! # 圧縮ファイルヘッダー
+//!
+//! バックアップ内の圧縮ファイルの先頭に圧縮形式を記録し、復元時に試行展開せずに
+//! 形式を判定できるようにします（Brotliのようにマジックバイトを持たない形式も判定可能）。
+//!
+//! # ヘッダー形式
+//!
+//! | オフセット | サイズ | 内容 |
+//! |-----------|--------|------|
+//! | 0 | 4 | マジック `BSCZ` |
+//! | 4 | 1 | 圧縮形式の識別子（[`CompressionType::id`]） |
+//! | 5 | - | 圧縮データ |
+//!
+//! ヘッダーのない旧形式のファイルは、復元時に従来どおり zstd → gzip → 無圧縮の順で判定します。
+//!
+//! # 使用例
+//!
+//! ```
+//! use backup_suite::compression::{header, CompressionType};
+//!
+//! let framed = header::encode(CompressionType::Brotli, b"payload");
+//! assert_eq!(header::codec_of(&framed), Some(CompressionType::Brotli));
+//! assert!(header::decode(b"no header").is_none());
+//! ```
+
+use super::engines::{CompressionEngine, CompressionType};
+use crate::error::{BackupError, Result};
+
+/// 圧縮ファイルヘッダーのマジックバイト
+pub const CODEC_MAGIC: &[u8; 4] = b"BSCZ";
+
+/// 圧縮ファイルヘッダーのサイズ（バイト）
+pub const HEADER_SIZE: usize = 5;
+
+/// 圧縮データの先頭にヘッダーを付加
+#[must_use]
+pub fn encode(compression_type: CompressionType, body: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(HEADER_SIZE + body.len());
+    result.extend_from_slice(CODEC_MAGIC);
+    result.push(compression_type.id());
+    result.extend_from_slice(body);
+    result
+}
+
+/// ヘッダーに記録された圧縮形式を取得
+///
+/// ヘッダーがない場合、または識別子が不明な場合は `None` を返します。
+#[must_use]
+pub fn codec_of(data: &[u8]) -> Option<CompressionType> {
+    data.starts_with(CODEC_MAGIC)
+        .then(|| data.get(4).copied())
+        .flatten()
+        .and_then(CompressionType::from_id)
+}
+
+/// ヘッダー付きのデータを記録された形式で展開
+///
+/// ヘッダーで始まらないデータは `None` を返します。
+///
+/// # Errors
+///
+/// 識別子が不明な場合、または展開に失敗した場合に `Some(Err)` を返します。
+#[must_use]
+pub fn decode(data: &[u8]) -> Option<Result<Vec<u8>>> {
+    if data.len() < HEADER_SIZE || !data.starts_with(CODEC_MAGIC) {
+        return None;
+    }
+    Some(
+        codec_of(data)
+            .ok_or_else(|| BackupError::CompressionError("不明な圧縮形式".to_string()))
+            .and_then(|codec| CompressionEngine::decompress_bytes(codec, &data[HEADER_SIZE..])),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)] // Tests can use unwrap
+mod tests {
+    use super::*;
+    use crate::compression::CompressionConfig;
+
+    #[test]
+    fn test_header_roundtrip_for_every_codec() {
+        let data = b"header roundtrip test data ".repeat(64);
+        for codec in [
+            CompressionType::None,
+            CompressionType::Zstd,
+            CompressionType::Gzip,
+            CompressionType::Lz4,
+            CompressionType::Xz,
+            CompressionType::Brotli,
+            CompressionType::Adaptive,
+        ] {
+            let engine = CompressionEngine::new(codec, CompressionConfig::default_for(codec));
+            let framed = encode(codec, &engine.compress(&data).unwrap().data);
+            assert_eq!(codec_of(&framed), Some(codec));
+            assert_eq!(decode(&framed).unwrap().unwrap(), data, "{codec:?}");
+        }
+    }
+
+    #[test]
+    fn test_decode_without_header_or_unknown_codec() {
+        assert!(decode(b"BSC").is_none());
+        assert!(decode(&zstd::encode_all(&b"raw zstd"[..], 3).unwrap()).is_none());
+
+        let mut unknown = CODEC_MAGIC.to_vec();
+        unknown.push(0xFF);
+        assert!(codec_of(&unknown).is_none());
+        assert!(decode(&unknown).unwrap().is_err());
+    }
+}
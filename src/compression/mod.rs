@@ -1,9 +1,16 @@
 //! # 圧縮モジュール
 //!
 //! バックアップファイルの圧縮・展開機能を提供します。
-//! zstd と gzip アルゴリズムをサポートします。
+//! zstd・gzip・LZ4・xz・Brotli アルゴリズム、およびファイルごとに圧縮方法を選択する適応圧縮をサポートします。
+//! 圧縮ファイルの先頭には形式を記録したヘッダーを付加します（[`header`]）。
+//! 小さなファイルが多い対象では、学習したzstd辞書で圧縮できます（[`dictionary`]）。
 
+pub mod adaptive;
+pub mod dictionary;
 pub mod engines;
+pub mod header;
 
 // 主要な型と関数を再エクスポート
+pub use adaptive::{AdaptiveDecision, CompressionStats};
+pub use dictionary::ZstdDictionary;
 pub use engines::{CompressedData, CompressionConfig, CompressionEngine, CompressionType};
@@ -1,21 +1,441 @@
 use anyhow::{Context, Result};
 use rayon::prelude::*;
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use serde::Serialize;
+use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use walkdir::WalkDir;
 
-use super::copy_engine::CopyEngine;
+use super::cancel::CancellationToken;
+use super::checkpoint::{self, Checkpoint, CheckpointEntry, CheckpointHeader};
+use super::copy_engine::{CopyEngine, SparseMap};
+use super::events::{Event, EventSink, Operation, Phase};
 use super::filter::FileFilter;
+use super::history::TargetRecord;
+use super::hooks::{self, HookConfig, HookContext, HookOutcome, HookStage};
 use super::incremental::{BackupType, IncrementalBackupEngine};
-use super::integrity::IntegrityChecker;
+use super::integrity::{BackupMetadata, FileStamp, HashAlgorithm, IntegrityChecker};
+use super::lock::{LockKind, RepositoryLock};
+use super::pack;
 use super::pipeline::{PipelineConfig, ProcessingPipeline};
+use super::report::{ErrorKind, ReportError, RunCounts, RunReport, TargetReport};
+use super::special::{EntryKind, HardlinkTracker, SpecialEntry};
+use super::sqlite;
+use super::staging;
 use super::{Config, Priority, Target, TargetType};
-use crate::compression::CompressionType;
-use crate::crypto::{EncryptionConfig, KeyManager};
+use crate::compression::dictionary::{MAX_SAMPLES, MIN_SAMPLES, SMALL_FILE_THRESHOLD};
+use crate::compression::{
+    AdaptiveDecision, CompressionConfig, CompressionStats, CompressionType, ZstdDictionary,
+};
+use crate::crypto::{EncryptionConfig, KeyManager, MasterKey};
 use crate::i18n::{get_message, MessageKey};
 use crate::security::{safe_join, AuditEvent, AuditLog};
-use crate::ui::progress::BackupProgress;
+use crate::ui::ConsoleSink;
+
+/// コマンド出力・標準入力・データベースの複製を一時的に保存するステージング内のディレクトリ名
+const VIRTUAL_DIR: &str = ".virtual";
+
+/// ProcessingPipelineの単位（圧縮形式、レベル、zstd辞書ID）
+type CodecKey = (CompressionType, i32, Option<u32>);
+
+/// 1ファイルの書き込み結果
+struct WrittenFile {
+    /// 集計用のバイト数
+    bytes: u64,
+    /// 元ファイルから読み込んだサイズ
+    read_len: u64,
+    /// 書き込んだサイズ
+    written_len: u64,
+    /// 書き込んだ内容のハッシュ（チェックポイント用、無圧縮コピーでハッシュ不要の場合は `None`）
+    written_hash: Option<String>,
+    /// 書き込みと同じ読み込みで計算した元ファイルのハッシュ（整合性検証有効時）
+    source_hash: Option<String>,
+    /// 圧縮前後のサイズと適応圧縮の判定（圧縮有効時）
+    compression: Option<(u64, u64, Option<AdaptiveDecision>)>,
+    /// リフリンク・`copy_file_range` でカーネル内コピーしたか
+    offloaded: bool,
+    /// データ領域のみを保存したスパースファイルの配置
+    sparse: Option<SparseMap>,
+}
+
+/// 並列処理した1ファイルの結果
+///
+/// ハッシュはワーカー間で共有するロックを介さず、結果として返して並列処理後に登録します。
+#[derive(Default)]
+struct FileOutcome<'a> {
+    /// バックアップ内の相対パスと元ファイルのハッシュ
+    hash: Option<(PathBuf, String)>,
+    /// 失敗した元ファイルとエラーの種類・内容
+    failure: Option<(&'a PathBuf, ErrorKind, String)>,
+    /// 圧縮前後のサイズと適応圧縮の判定
+    compression: Option<(u64, u64, Option<AdaptiveDecision>)>,
+    /// 読み込んだサイズと書き込んだサイズ
+    bytes: (u64, u64),
+    /// 前回スナップショットからハードリンクしたか
+    linked: bool,
+    /// カーネル内コピーしたか
+    offloaded: bool,
+    /// 元ファイルと保存したファイルのサイズ・更新日時（スナップショットモード）
+    stamp: Option<FileStamp>,
+    /// キャンセルにより処理しなかったか
+    cancelled: bool,
+    /// データ領域のみを保存したスパースファイルの配置（復元時に元の配置へ書き込む）
+    sparse: Option<SparseMap>,
+}
+
+impl<'a> FileOutcome<'a> {
+    fn hashed(relative_path: &Path, hash: Option<String>) -> Self {
+        Self {
+            hash: hash.map(|hash| (relative_path.to_path_buf(), hash)),
+            ..Self::default()
+        }
+    }
+
+    fn failed(source: &'a PathBuf, kind: ErrorKind, error: String) -> Self {
+        Self {
+            failure: Some((source, kind, error)),
+            ..Self::default()
+        }
+    }
+
+    fn cancelled() -> Self {
+        Self {
+            cancelled: true,
+            ..Self::default()
+        }
+    }
+
+    fn with_bytes(mut self, read: u64, written: u64) -> Self {
+        self.bytes = (read, written);
+        self
+    }
+
+    fn with_compression(
+        mut self,
+        compression: Option<(u64, u64, Option<AdaptiveDecision>)>,
+    ) -> Self {
+        self.compression = compression;
+        self
+    }
+}
+
+/// 並列処理の各ワーカーで1ファイルをバックアップするための共有状態と集計
+struct FileProcessor<'a> {
+    events: &'a dyn EventSink,
+    backup_base: &'a Path,
+    algorithm: HashAlgorithm,
+    /// 整合性マニフェスト用にソースのハッシュを計算する場合のアルゴリズム
+    hash_algorithm: Option<HashAlgorithm>,
+    resume_state: Option<&'a checkpoint::CheckpointState>,
+    checkpoint: Option<&'a Checkpoint>,
+    /// SQLiteデータベースの書き込み先 → 事前に作成した一貫した複製
+    db_snapshots: &'a HashMap<PathBuf, PathBuf>,
+    /// 未変更ファイルをハードリンクする前回スナップショット
+    link_source: Option<&'a (PathBuf, BackupMetadata)>,
+    /// 元ファイル・保存したファイルのサイズと更新日時を記録するか（スナップショットモード）
+    record_stamps: bool,
+    /// 未変更ファイルの判定でサイズ・更新日時を信用せず、常にハッシュを比較するか
+    checksum: bool,
+    target_codecs: &'a HashMap<PathBuf, CodecKey>,
+    default_codec: CodecKey,
+    pipelines: &'a HashMap<CodecKey, Option<ProcessingPipeline>>,
+    master_key: Option<&'a MasterKey>,
+    encryption_salt: Option<[u8; 16]>,
+    copy_engine: &'a CopyEngine,
+    change_retries: u32,
+    success_count: AtomicUsize,
+    failed_count: AtomicUsize,
+    total_bytes: AtomicUsize,
+    linked_count: AtomicUsize,
+    resumed_count: AtomicUsize,
+    /// 再試行してもバックアップ中に変更され続けたファイル（元ファイル、書き込み先）
+    inconsistent: Mutex<Vec<(PathBuf, PathBuf)>>,
+}
+
+impl FileProcessor<'_> {
+    /// 1ファイルを書き込み先にバックアップ
+    ///
+    /// 再開時の検証済みファイルのスキップ、前回スナップショットへのハードリンク、
+    /// 圧縮・暗号化またはコピー、書き込み中の変更の再試行、チェックポイントへの記録を行います。
+    fn process_file<'f>(&self, source: &'f PathBuf, dest: &PathBuf) -> FileOutcome<'f> {
+        // バックアップディレクトリからの相対パスを計算（整合性検証用）
+        let relative_path = dest.strip_prefix(self.backup_base).ok();
+
+        // バックアップ先のディレクトリを作成
+        if let Some(parent) = dest.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                self.failed_count.fetch_add(1, Ordering::Relaxed);
+                return FileOutcome::failed(
+                    source,
+                    ErrorKind::from_io(&e),
+                    format!("ディレクトリ作成失敗 parent.display(): {e}"),
+                );
+            }
+        }
+
+        // 再開時: 中断前に書き込み済みで内容を検証できたファイルはスキップ
+        if let (Some(state), Some(rel_path)) = (&self.resume_state, relative_path) {
+            if let Some(entry) = state.entries.get(rel_path) {
+                if entry.is_valid(source, dest, self.algorithm) {
+                    self.success_count.fetch_add(1, Ordering::Relaxed);
+                    self.resumed_count.fetch_add(1, Ordering::Relaxed);
+                    self.total_bytes
+                        .fetch_add(entry.written_len as usize, Ordering::Relaxed);
+                    let hash = self.hash_algorithm.and_then(|_| match &entry.source_hash {
+                        Some(hash) => Some(hash.clone()),
+                        None => self.algorithm.hash_file(source).ok(),
+                    });
+                    return FileOutcome {
+                        sparse: entry.sparse.clone(),
+                        ..FileOutcome::hashed(rel_path, hash)
+                    }
+                    .with_bytes(entry.source_len, entry.written_len);
+                }
+            }
+            // 書きかけのファイルは削除してから書き直す
+            // （ハードリンク共有先への上書きを防ぐため）
+            let _ = std::fs::remove_file(dest);
+        }
+
+        // 読み込み前のソースのサイズと更新日時（再開時の変更検出用）
+        let stamp = checkpoint::source_stamp(source);
+
+        // SQLiteデータベースは事前に作成した一貫した複製から処理
+        let original_source = source;
+        let db_snapshot = self.db_snapshots.get(dest);
+        let source = db_snapshot.unwrap_or(source);
+
+        // データベースは WAL への書き込みで本体の更新日時が変わらないことがあるため、
+        // サイズ・更新日時による未変更の判定には使わない
+        let link_stamp = stamp.filter(|_| db_snapshot.is_none());
+
+        // スナップショットモード: 未変更ファイルは前回スナップショットからハードリンク
+        let mut source_hash = None;
+        if let (Some(link_source), Some(rel_path)) = (self.link_source, relative_path) {
+            let attempt = link_unchanged(
+                source,
+                link_stamp,
+                dest,
+                rel_path,
+                link_source,
+                self.checksum,
+            );
+            if let (Some(stored), Some(hash)) = (attempt.linked, &attempt.hash) {
+                self.success_count.fetch_add(1, Ordering::Relaxed);
+                self.linked_count.fetch_add(1, Ordering::Relaxed);
+                let sparse = link_source.1.sparse_files.get(rel_path).cloned();
+                if let (Some(cp), Some((len, mtime))) = (&self.checkpoint, stamp) {
+                    record_checkpoint(
+                        self.events,
+                        cp,
+                        &CheckpointEntry {
+                            path: rel_path.to_path_buf(),
+                            source_len: len,
+                            source_mtime_ns: mtime,
+                            source_hash: Some(hash.clone()),
+                            written_len: len,
+                            written_hash: Some(hash.clone()),
+                            sparse: sparse.clone(),
+                        },
+                    );
+                }
+                return FileOutcome {
+                    linked: true,
+                    sparse,
+                    stamp: link_stamp.map(|source| FileStamp { source, stored }),
+                    ..FileOutcome::hashed(rel_path, attempt.hash)
+                };
+            }
+            source_hash = attempt.hash;
+        }
+
+        // ProcessingPipelineまたはCopyEngineでファイル処理
+        // 元ファイルのハッシュは書き込みと同じ読み込みで計算し、読み直さない
+        let codec = self
+            .target_codecs
+            .get(dest)
+            .copied()
+            .unwrap_or(self.default_codec);
+        let pipeline = self.pipelines.get(&codec).and_then(Option::as_ref);
+        // リンクの判定で計算済みのソースのハッシュはコピー時に読み直さない
+        // （最初の試行のみ使用し、再試行時は変更後の内容で計算し直す）
+        let known_hash = Cell::new(source_hash.clone());
+        let write_backup =
+            |source: &Path| -> std::result::Result<WrittenFile, (ErrorKind, String)> {
+                if let Some(pipeline) = pipeline {
+                    // 暗号化・圧縮パイプライン使用
+                    let processed = pipeline
+                        .process_file(source, self.master_key, self.encryption_salt)
+                        .map_err(|e| {
+                            (
+                                ErrorKind::classify(&e),
+                                format!("処理失敗 source.display(): {e}"),
+                            )
+                        })?;
+                    // 処理後のデータをファイルに書き込み
+                    std::fs::write(dest, &processed.data)
+                        .and_then(|()| staging::sync_file(dest))
+                        .map_err(|e| {
+                            (
+                                ErrorKind::from_io(&e),
+                                format!("書き込み失敗 dest.display(): {e}"),
+                            )
+                        })?;
+                    Ok(WrittenFile {
+                        bytes: processed.metadata.final_size,
+                        read_len: processed.metadata.original_size,
+                        written_len: processed.data.len() as u64,
+                        written_hash: Some(self.algorithm.hash_bytes(&processed.data)),
+                        source_hash: processed.source_hash,
+                        compression: processed.compression_info.is_some().then_some((
+                            processed.metadata.original_size,
+                            processed.metadata.compressed_size,
+                            processed.compression_decision,
+                        )),
+                        offloaded: false,
+                        sparse: processed.sparse,
+                    })
+                } else {
+                    // 従来のCopyEngine使用（暗号化・圧縮なし）
+                    // リフリンク・copy_file_range を優先し、ハッシュは整合性検証が有効で未計算の場合のみ計算
+                    let known_hash = known_hash.take();
+                    let algorithm = self.hash_algorithm.filter(|_| known_hash.is_none());
+                    let copied = self
+                        .copy_engine
+                        .copy_file_hashed(source, dest, algorithm)
+                        .and_then(|copied| {
+                            staging::sync_file(dest)?;
+                            Ok(copied)
+                        })
+                        .map_err(|e| {
+                            (
+                                ErrorKind::classify(&e),
+                                format!("コピー失敗 source.display(): {e}"),
+                            )
+                        })?;
+                    // 無圧縮コピーの内容ハッシュはソースのハッシュと同一
+                    let hash = copied.hash.or_else(|| self.hash_algorithm.and(known_hash));
+                    Ok(WrittenFile {
+                        bytes: copied.bytes,
+                        read_len: copied.bytes,
+                        written_len: copied.bytes,
+                        written_hash: hash.clone(),
+                        source_hash: hash,
+                        compression: None,
+                        offloaded: copied.offloaded,
+                        sparse: None,
+                    })
+                }
+            };
+
+        // 読み込みの前後でサイズ・更新日時が変わった場合は、書き込み中のファイルとして再試行
+        // （データベースの複製は一貫しているため対象外）
+        let mut stamp = stamp;
+        let mut attempts = 0;
+        let (copy_result, stable) = loop {
+            let result = write_backup(source);
+            let after = checkpoint::source_stamp(source);
+            if result.is_err() || db_snapshot.is_some() || after == stamp {
+                break (result, true);
+            }
+            if attempts >= self.change_retries {
+                break (result, false);
+            }
+            attempts += 1;
+            stamp = after;
+            let _ = std::fs::remove_file(dest);
+        };
+
+        match &copy_result {
+            Ok(written) => {
+                self.success_count.fetch_add(1, Ordering::Relaxed);
+                self.total_bytes
+                    .fetch_add(written.bytes as usize, Ordering::Relaxed);
+            }
+            Err(_) => {
+                self.failed_count.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        let (read_len, written_len) = copy_result
+            .as_ref()
+            .map_or((0, 0), |written| (written.read_len, written.written_len));
+
+        if !stable {
+            self.events.warning(format!(
+                "警告: バックアップ中に変更され続けたため内容が不整合の可能性があります: {}",
+                original_source.display()
+            ));
+            if let Ok(mut list) = self.inconsistent.lock() {
+                list.push((original_source.clone(), dest.clone()));
+            }
+            // 保存した内容と一致するハッシュを確認できないため、整合性情報は記録しない
+            if let Some(copy) = &db_snapshot {
+                let _ = std::fs::remove_file(copy);
+            }
+            // 保存したスパースファイルの配置は復元に必要なため記録する
+            return FileOutcome {
+                sparse: copy_result.ok().and_then(|written| written.sparse),
+                ..FileOutcome::default()
+            }
+            .with_bytes(read_len, written_len);
+        }
+
+        if let Some(copy) = &db_snapshot {
+            let _ = std::fs::remove_file(copy);
+        }
+
+        match copy_result {
+            Ok(written) => {
+                // 整合性検証：書き込み時に計算した元ファイルのハッシュ
+                let source_hash = written.source_hash.or(source_hash);
+
+                // チェックポイントに記録（再開時の検証用）
+                if let (Some(cp), Some(rel_path), Some((len, mtime))) =
+                    (&self.checkpoint, relative_path, stamp)
+                {
+                    record_checkpoint(
+                        self.events,
+                        cp,
+                        &CheckpointEntry {
+                            path: rel_path.to_path_buf(),
+                            source_len: len,
+                            source_mtime_ns: mtime,
+                            source_hash: source_hash.clone(),
+                            written_len: written.written_len,
+                            written_hash: written.written_hash,
+                            sparse: written.sparse.clone(),
+                        },
+                    );
+                }
+
+                // スナップショットモード: 次回に内容を読まずに未変更を判定するためのサイズ・更新日時
+                let stamp = stamp
+                    .filter(|_| self.record_stamps && db_snapshot.is_none())
+                    .zip(
+                        std::fs::symlink_metadata(dest)
+                            .ok()
+                            .map(|meta| checkpoint::stamp_of(&meta)),
+                    )
+                    .map(|(source, stored)| FileStamp { source, stored });
+                FileOutcome {
+                    offloaded: written.offloaded,
+                    stamp,
+                    sparse: written.sparse,
+                    ..relative_path
+                        .map(|rel_path| FileOutcome::hashed(rel_path, source_hash))
+                        .unwrap_or_default()
+                }
+                .with_compression(written.compression)
+                .with_bytes(read_len, written_len)
+            }
+            Err((kind, e)) => FileOutcome::failed(original_source, kind, e),
+        }
+    }
+}
 
 /// バックアップ実行結果
 ///
@@ -26,9 +446,17 @@ use crate::ui::progress::BackupProgress;
 /// * `total_files` - 処理対象の総ファイル数
 /// * `successful` - 成功したファイル数
 /// * `failed` - 失敗したファイル数
-/// * `total_bytes` - コピーした総バイト数
+/// * `total_bytes` - コピーした総バイト数（ハードリンクしたファイルは含まない）
+/// * `linked_files` - 前回スナップショットからハードリンクしたファイル数
+/// * `resumed_files` - 中断前に書き込み済みで再利用したファイル数
+/// * `inconsistent_files` - 再試行してもバックアップ中に変更され続けたファイル（内容が不整合の可能性）
+/// * `merkle_root` - 整合性マニフェストのMerkleルート（整合性検証有効時）
+/// * `compression_stats` - 圧縮前後の合計サイズと適応圧縮の判定ごとのファイル数
+/// * `packed_files` - パックファイルにまとめたファイル数
 /// * `errors` - エラーメッセージのリスト
 /// * `backup_name` - 作成されたバックアップディレクトリ名
+/// * `report` - 対象ごとの集計と種類付きのエラーを含む実行レポート（ドライラン時は `None`）
+/// * `cancelled` - キャンセルされたか（処理済みのファイルのみを保存し、マニフェストに部分的なバックアップとして記録）
 ///
 /// # 使用例
 ///
@@ -47,14 +475,22 @@ use crate::ui::progress::BackupProgress;
 /// }
 /// println!("✓ 成功: {}件 ({}バイト)", result.successful, result.total_bytes);
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct BackupResult {
     pub total_files: usize,
     pub successful: usize,
     pub failed: usize,
     pub total_bytes: u64,
+    pub linked_files: usize,
+    pub resumed_files: usize,
+    pub inconsistent_files: Vec<PathBuf>,
+    pub merkle_root: Option<String>,
+    pub compression_stats: CompressionStats,
+    pub packed_files: usize,
     pub errors: Vec<String>,
     pub backup_name: String,
+    pub report: Option<RunReport>,
+    pub cancelled: bool,
 }
 
 impl BackupResult {
@@ -64,8 +500,16 @@ impl BackupResult {
             successful: 0,
             failed: 0,
             total_bytes: 0,
+            linked_files: 0,
+            resumed_files: 0,
+            inconsistent_files: Vec::new(),
+            merkle_root: None,
+            compression_stats: CompressionStats::default(),
+            packed_files: 0,
             errors: Vec::new(),
             backup_name: String::new(),
+            report: None,
+            cancelled: false,
         }
     }
 }
@@ -102,13 +546,26 @@ pub struct BackupRunner {
     config: Config,
     dry_run: bool,
     show_progress: bool,
+    quiet: bool,
+    event_sink: Option<Arc<dyn EventSink>>,
     enable_encryption: bool,
     password: Option<String>,
     compression_type: CompressionType,
     compression_level: i32,
+    train_dictionaries: bool,
+    pack_small_files: bool,
     verify_integrity: bool,
     audit_log: Option<AuditLog>,
+    /// 作成時の監査ログ初期化エラー（最初の `events()` 呼び出しで警告として送る）
+    audit_log_warning: Mutex<Option<String>>,
     incremental: bool,
+    snapshot: bool,
+    checksum: bool,
+    resume: bool,
+    job: Option<String>,
+    changed_paths: Option<BTreeSet<PathBuf>>,
+    stdin: Option<(String, Box<dyn Read>)>,
+    cancel: CancellationToken,
     lang: crate::i18n::Language,
 }
 
@@ -134,22 +591,38 @@ impl BackupRunner {
     /// ```
     #[must_use]
     pub fn new(config: Config, dry_run: bool) -> Self {
-        // 監査ログの初期化（失敗してもバックアップ処理は継続）
-        let audit_log = AuditLog::new()
-            .map_err(|e| eprintln!("警告: 監査ログの初期化に失敗しました: {e}"))
-            .ok();
+        // 監査ログの初期化（失敗してもバックアップ処理は継続、警告は実行時にイベントとして送る）
+        let (audit_log, audit_log_warning) = match AuditLog::new() {
+            Ok(log) => (Some(log), None),
+            Err(e) => (
+                None,
+                Some(format!("警告: 監査ログの初期化に失敗しました: {e}")),
+            ),
+        };
 
         Self {
             config,
             dry_run,
             show_progress: true, // デフォルトで進捗表示を有効化
+            quiet: false,
+            event_sink: None,
             enable_encryption: false,
             password: None,
             compression_type: CompressionType::Zstd,
             compression_level: 3,
+            train_dictionaries: false,
+            pack_small_files: false,
             verify_integrity: true, // デフォルトで整合性検証を有効化
             audit_log,
+            audit_log_warning: Mutex::new(audit_log_warning),
             incremental: false,
+            snapshot: false,
+            checksum: false,
+            resume: false,
+            job: None,
+            changed_paths: None,
+            stdin: None,
+            cancel: CancellationToken::new(),
             lang: crate::i18n::Language::detect(),
         }
     }
@@ -179,6 +652,58 @@ impl BackupRunner {
         self
     }
 
+    /// 処理モードやドライランの対象一覧など、標準出力への情報表示を抑制
+    ///
+    /// `--output json` など標準出力を機械可読な結果のみにする場合に使用します
+    /// （警告は引き続き標準エラー出力に表示されます）。
+    #[must_use]
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// 進行状況のイベントの送り先を設定
+    ///
+    /// 設定した場合はコンソールに表示しません（`with_progress`・`with_quiet` は無視されます）。
+    #[must_use]
+    pub fn with_event_sink(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(sink);
+        self
+    }
+
+    /// イベントの送り先（未設定の場合は進捗表示・情報表示の設定に従ってコンソールに表示）
+    fn events(&self) -> Arc<dyn EventSink> {
+        let sink: Arc<dyn EventSink> = self.event_sink.clone().unwrap_or_else(|| {
+            Arc::new(
+                ConsoleSink::new(self.lang)
+                    .with_progress(self.show_progress)
+                    .with_quiet(self.quiet),
+            )
+        });
+        if let Some(warning) = self
+            .audit_log_warning
+            .lock()
+            .ok()
+            .and_then(|mut w| w.take())
+        {
+            sink.warning(warning);
+        }
+        sink
+    }
+
+    /// キャンセル・一時停止のトークンを設定
+    ///
+    /// キャンセルされると新しいファイルの処理を停止し、処理中のファイルの書き込みを完了させてから
+    /// 処理済みのファイルだけを記録したマニフェストでスナップショットを確定します。
+    /// マニフェストには部分的なバックアップ（[`BackupMetadata::partial`]）として記録され、
+    /// クリーンアップの保持数に数えられず、復元時は最新の完全なバックアップが優先されます。
+    /// 結果の `cancelled` が `true` になり、履歴・監査ログにはキャンセルとして記録されます。
+    #[must_use]
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
     /// 暗号化を有効化
     #[must_use]
     pub fn with_encryption(mut self, password: String) -> Self {
@@ -195,6 +720,214 @@ impl BackupRunner {
         self
     }
 
+    /// 対象ごとのzstd辞書学習の有効/無効を設定
+    ///
+    /// 有効な場合、zstd圧縮する対象ごとに小さなファイルのサンプルから辞書を学習し、
+    /// スナップショットに保存した辞書で小さなファイルを圧縮します（暗号化時は使用しません）。
+    #[must_use]
+    pub fn with_dictionary_training(mut self, enabled: bool) -> Self {
+        self.train_dictionaries = enabled;
+        self
+    }
+
+    /// 小さなファイルをパックファイルにまとめるかを設定
+    ///
+    /// 有効な場合、確定前に小さなファイルを `.packs` 内のパックに連結し、
+    /// 位置をマニフェストに記録します（スナップショットモードでは使用しません）。
+    #[must_use]
+    pub fn with_pack_files(mut self, enabled: bool) -> Self {
+        self.pack_small_files = enabled;
+        self
+    }
+
+    /// 対象ごとの圧縮形式とレベルを解決
+    ///
+    /// 対象で圧縮形式のみ指定した場合、レベルはその形式のデフォルトを使用します。
+    fn target_codec(&self, target: &Target) -> (CompressionType, i32) {
+        let compression_type = target.compression.unwrap_or(self.compression_type);
+        let level = target.compression_level.unwrap_or_else(|| {
+            if compression_type == self.compression_type {
+                self.compression_level
+            } else {
+                compression_type.default_level()
+            }
+        });
+        (compression_type, level)
+    }
+
+    /// 対象の小さなファイルからzstd辞書を学習し、スナップショットに保存
+    ///
+    /// 辞書学習が無効な場合、zstd以外の圧縮形式・暗号化・ドライランの場合、
+    /// 小さなファイルが少なすぎる場合は `None` を返します。
+    fn train_target_dictionary(
+        &self,
+        events: &dyn EventSink,
+        target: &Target,
+        compression_type: CompressionType,
+        files: &[(PathBuf, PathBuf)],
+        backup_base: &Path,
+    ) -> Option<ZstdDictionary> {
+        if !self.train_dictionaries
+            || self.dry_run
+            || self.enable_encryption
+            || compression_type != CompressionType::Zstd
+        {
+            return None;
+        }
+
+        let small_files: Vec<&PathBuf> = files
+            .iter()
+            .map(|(source, _)| source)
+            .filter(|source| {
+                std::fs::metadata(source)
+                    .is_ok_and(|m| m.is_file() && m.len() > 0 && m.len() <= SMALL_FILE_THRESHOLD)
+            })
+            .collect();
+        if small_files.len() < MIN_SAMPLES {
+            return None;
+        }
+
+        // 対象全体から均等にサンプルを抽出
+        let step = small_files.len().div_ceil(MAX_SAMPLES);
+        let samples: Vec<Vec<u8>> = small_files
+            .into_iter()
+            .step_by(step)
+            .filter_map(|source| std::fs::read(source).ok())
+            .collect();
+
+        match ZstdDictionary::train(&samples)
+            .and_then(|dictionary| dictionary.save(backup_base).map(|_| dictionary))
+        {
+            Ok(dictionary) => Some(dictionary),
+            Err(e) => {
+                events.warning(format!(
+                    "警告: zstd辞書を作成できません（通常のzstdで圧縮します）: {}: {e}",
+                    target.path.display()
+                ));
+                None
+            }
+        }
+    }
+
+    /// 圧縮形式・レベル・辞書に応じたProcessingPipelineを作成（暗号化も圧縮もない場合は `None`）
+    /// 処理対象のSQLiteデータベースの一貫した複製を作成
+    ///
+    /// 複製に成功したデータベースの付随ファイル（`-wal` / `-shm` / `-journal`）は内容が
+    /// 複製に取り込まれるため対象から外し、失敗したデータベースは付随ファイルとともに
+    /// 通常コピーします。付随ファイルだけが変更された場合（増分・監視モード）は、
+    /// データベース本体も対象に加えます。ドライランでは複製せず、成功したものとして扱います。
+    ///
+    /// 処理対象、書き込み先から複製へのマップ、対象から外した付随ファイルを返します。
+    fn snapshot_databases(
+        &self,
+        events: &dyn EventSink,
+        mut files_to_backup: Vec<(PathBuf, PathBuf)>,
+        all_files: &[(PathBuf, PathBuf)],
+        backup_base: &Path,
+    ) -> (
+        Vec<(PathBuf, PathBuf)>,
+        HashMap<PathBuf, PathBuf>,
+        HashSet<PathBuf>,
+    ) {
+        let sidecars: HashMap<&PathBuf, PathBuf> = all_files
+            .iter()
+            .filter_map(|(source, _)| sqlite::sidecar_database(source).map(|db| (source, db)))
+            .collect();
+        if sidecars.is_empty()
+            && !files_to_backup
+                .iter()
+                .any(|(s, _)| sqlite::is_sqlite_database(s))
+        {
+            return (files_to_backup, HashMap::new(), HashSet::new());
+        }
+
+        // 付随ファイルが変更されていればデータベース本体も変更されている
+        let scheduled: HashSet<PathBuf> = files_to_backup
+            .iter()
+            .map(|(source, _)| source.clone())
+            .collect();
+        let missing: HashSet<&PathBuf> = files_to_backup
+            .iter()
+            .filter_map(|(source, _)| sidecars.get(source))
+            .filter(|db| !scheduled.contains(*db))
+            .collect();
+        files_to_backup.extend(
+            all_files
+                .iter()
+                .filter(|(source, _)| missing.contains(source))
+                .cloned(),
+        );
+
+        let databases: Vec<&(PathBuf, PathBuf)> = files_to_backup
+            .iter()
+            .filter(|(source, _)| sqlite::is_sqlite_database(source))
+            .collect();
+        let (snapshotted, db_snapshots): (HashSet<PathBuf>, HashMap<PathBuf, PathBuf>) = if self
+            .dry_run
+        {
+            let sources = databases.iter().map(|(source, _)| source.clone()).collect();
+            (sources, HashMap::new())
+        } else {
+            databases
+                    .par_iter()
+                    .filter_map(|(source, dest)| {
+                        let copy = backup_base
+                            .join(VIRTUAL_DIR)
+                            .join("sqlite")
+                            .join(dest.strip_prefix(backup_base).ok()?);
+                        match sqlite::snapshot_database(source, &copy) {
+                            Ok(()) => Some((source.clone(), (dest.clone(), copy))),
+                            Err(e) => {
+                                events.warning(format!(
+                                    "警告: SQLiteのオンラインバックアップに失敗したため通常コピーします: {}: {e:#}",
+                                    source.display()
+                                ));
+                                None
+                            }
+                        }
+                    })
+                    .unzip()
+        };
+
+        let absorbed: HashSet<PathBuf> = sidecars
+            .into_iter()
+            .filter(|(_, db)| snapshotted.contains(db))
+            .map(|(sidecar, _)| sidecar.clone())
+            .collect();
+        files_to_backup.retain(|(source, _)| !absorbed.contains(source));
+        (files_to_backup, db_snapshots, absorbed)
+    }
+
+    fn build_pipeline(
+        &self,
+        (compression_type, level, dictionary_id): CodecKey,
+        hash_algorithm: Option<HashAlgorithm>,
+        dictionaries: &HashMap<u32, Arc<ZstdDictionary>>,
+    ) -> Option<ProcessingPipeline> {
+        if !self.enable_encryption && compression_type == CompressionType::None {
+            return None;
+        }
+
+        // CompressionConfigを作成（compression_typeに応じたデフォルトからlevelを変更）
+        let mut compression_config = CompressionConfig::default_for(compression_type);
+        compression_config.level = level;
+
+        let mut config =
+            PipelineConfig::default().with_compression(compression_type, compression_config);
+        if self.enable_encryption {
+            config = config.with_encryption(EncryptionConfig::default());
+        }
+        // 元ファイルのハッシュは圧縮のために読み込んだデータから計算
+        if let Some(algorithm) = hash_algorithm {
+            config = config.with_source_hash(algorithm);
+        }
+        if let Some(dictionary) = dictionary_id.and_then(|id| dictionaries.get(&id)) {
+            config = config.with_dictionary(Arc::clone(dictionary));
+        }
+
+        Some(ProcessingPipeline::new(config))
+    }
+
     /// 整合性検証の有効/無効を設定
     #[must_use]
     pub fn with_verification(mut self, verify: bool) -> Self {
@@ -209,6 +942,70 @@ impl BackupRunner {
         self
     }
 
+    /// スナップショットモードを有効化
+    ///
+    /// 前回のスナップショットから変更のないファイルをハードリンクで共有し、
+    /// 各バックアップを完全なツリーとして保持します（`rsync --link-dest` 相当）。
+    /// 圧縮・暗号化が有効な場合は無視されます。増分バックアップより優先されます。
+    #[must_use]
+    pub fn with_snapshot(mut self, snapshot: bool) -> Self {
+        self.snapshot = snapshot;
+        self
+    }
+
+    /// スナップショットモードで未変更ファイルを内容のハッシュで判定
+    ///
+    /// 既定ではサイズと更新日時が前回と一致するファイルを読み込まずに未変更と判定し、
+    /// リンク元のファイルもサイズと更新日時で改変の有無を確認します。
+    /// 有効にすると、両方のファイルのハッシュを比較してからリンクします（`rsync --checksum` 相当）。
+    #[must_use]
+    pub fn with_checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// 中断されたバックアップの再開を有効化
+    ///
+    /// 保存先に残っている最新のステージングディレクトリをチェックポイントとともに再利用し、
+    /// 書き込み済みで内容を検証できたファイルをスキップします。
+    /// 再開可能なバックアップがない場合は新規にバックアップします。
+    #[must_use]
+    pub fn with_resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// 実行するジョブ名を設定
+    ///
+    /// 履歴に記録され、ジョブの保持期間（`keep_days`）による削除の対象判定に使われます。
+    #[must_use]
+    pub fn with_job(mut self, name: impl Into<String>) -> Self {
+        self.job = Some(name.into());
+        self
+    }
+
+    /// 増分バックアップで変更を比較するファイルを限定（監視モード用）
+    ///
+    /// 指定したパス（ディレクトリの場合は配下すべて）のファイルのみハッシュを比較し、
+    /// それ以外のファイルは前回のマニフェストのハッシュを引き継ぎます。
+    /// 変更されたファイルがない場合はバックアップを作成しません。
+    #[must_use]
+    pub fn with_changed_paths(mut self, paths: Vec<PathBuf>) -> Self {
+        self.changed_paths = Some(paths.into_iter().collect());
+        self
+    }
+
+    /// ストリームを仮想ファイルとしてバックアップ
+    ///
+    /// 設定済みのバックアップ対象の代わりに `reader` の内容を `name` という名前の
+    /// ファイルとして保存します（`backup-suite run --stdin --name db.sql`）。
+    /// カテゴリはカテゴリフィルタ、未指定の場合は `stdin` になります。
+    #[must_use]
+    pub fn with_stdin(mut self, name: String, reader: impl Read + 'static) -> Self {
+        self.stdin = Some((name, Box::new(reader)));
+        self
+    }
+
     /// 言語を設定
     #[must_use]
     pub fn with_language(mut self, lang: crate::i18n::Language) -> Self {
@@ -238,6 +1035,14 @@ impl BackupRunner {
     /// * 前回のメタデータ読み込みに失敗した場合（増分バックアップ時）
     /// * 整合性メタデータの保存に失敗した場合
     /// * バックアップ履歴の保存に失敗した場合
+    /// * グローバルの `pre_backup` フックが失敗した場合（`abort_on_pre_failure` 有効時）
+    ///
+    /// # フック
+    ///
+    /// ドライラン以外では設定されたフックを実行します（詳細は [`hooks`] を参照）。
+    /// ターゲットの `pre_backup` が失敗した場合、`abort_on_pre_failure` が有効であれば
+    /// その対象をスキップしてエラーに記録します。エラー終了時もグローバルの
+    /// `on_failure` フックが実行されます。
     ///
     /// # 使用例
     ///
@@ -260,6 +1065,46 @@ impl BackupRunner {
         priority_filter: Option<&Priority>,
         category_filter: Option<&str>,
     ) -> Result<BackupResult> {
+        let events = self.events();
+        let result = self.execute(&*events, priority_filter, category_filter);
+        if let Ok(ref result) = result {
+            events.phase(
+                Operation::Backup,
+                Phase::Finished {
+                    failed: result.failed,
+                },
+            );
+        }
+
+        if let Err(ref e) = result {
+            if !self.dry_run {
+                let hooks = self.config.hooks.clone();
+                let ctx =
+                    HookContext::new(&self.config.backup.destination).with_error(&format!("{e:#}"));
+                let user = AuditLog::current_user();
+                run_logged_hook(
+                    &*events,
+                    &mut self.audit_log,
+                    &user,
+                    None,
+                    HookStage::OnFailure,
+                    &hooks,
+                    &ctx,
+                );
+            }
+        }
+
+        result
+    }
+
+    fn execute(
+        &mut self,
+        events: &dyn EventSink,
+        priority_filter: Option<&Priority>,
+        category_filter: Option<&str>,
+    ) -> Result<BackupResult> {
+        let started = std::time::Instant::now();
+        let started_at = chrono::Utc::now();
         let user = AuditLog::current_user();
         let target_desc = format!("priority={priority_filter:?}, category={category_filter:?}");
 
@@ -267,11 +1112,25 @@ impl BackupRunner {
         if let Some(ref mut audit_log) = self.audit_log {
             let _ = audit_log
                 .log(AuditEvent::backup_started(&target_desc, &user))
-                .map_err(|e| eprintln!("警告: 監査ログの記録に失敗しました: {e}"));
+                .map_err(|e| events.warning(format!("警告: 監査ログの記録に失敗しました: {e}")));
         }
 
+        // 標準入力モードでは設定済みの対象の代わりにストリームのみをバックアップ
+        let stdin = self.stdin.take();
+        let stdin_target = stdin.as_ref().map(|(name, _)| {
+            Target::from_command(
+                PathBuf::from(name),
+                None,
+                Priority::High,
+                category_filter.unwrap_or("stdin").to_string(),
+            )
+        });
+        let mut stdin_reader = stdin.map(|(_, reader)| reader);
+
         // バックアップ対象をフィルタ（優先度 → カテゴリの順）
-        let mut targets: Vec<&Target> = if let Some(priority) = priority_filter {
+        let mut targets: Vec<&Target> = if let Some(ref target) = stdin_target {
+            vec![target]
+        } else if let Some(priority) = priority_filter {
             self.config.filter_by_priority(priority)
         } else {
             self.config.targets.iter().collect()
@@ -288,10 +1147,132 @@ impl BackupRunner {
 
         // バックアップ先ディレクトリの準備（バックアップ名/カテゴリ階層構造）
         let dest_base = &self.config.backup.destination;
+
+        // 同時実行中のバックアップ・クリーンアップとの競合を防止（ドライランは読み取りのみ）
+        let lock_kind = if self.dry_run {
+            LockKind::Shared
+        } else {
+            LockKind::Exclusive
+        };
+        let repo_lock = RepositoryLock::acquire(dest_base, lock_kind, "backup")?;
+        for stale in repo_lock.stale_removed() {
+            events.warning(format!("警告: 古いロックを削除しました: {stale}"));
+        }
+
         let now = chrono::Local::now();
         let timestamp = now.format("%Y%m%d_%H%M%S");
-        let backup_name = format!("backup_{timestamp}");
-        let backup_base = dest_base.join(&backup_name);
+        let algorithm = self.config.backup.hash_algorithm;
+        let checkpoint_header = CheckpointHeader {
+            compression: format!("{:?}", self.compression_type).to_lowercase(),
+            target_compression: targets
+                .iter()
+                .map(|target| (target, self.target_codec(target)))
+                .filter(|(_, codec)| *codec != (self.compression_type, self.compression_level))
+                .map(|(target, (compression_type, level))| {
+                    (
+                        target.path.display().to_string(),
+                        format!("{compression_type:?}:{level}").to_lowercase(),
+                    )
+                })
+                .collect(),
+            encrypted: self.enable_encryption,
+            snapshot: self.snapshot,
+            hash_algorithm: algorithm,
+        };
+
+        // --resume: 最新の中断されたバックアップのステージングディレクトリを再利用
+        let resumed = if self.resume {
+            staging::find_stale_staging(dest_base)
+                .into_iter()
+                .rev()
+                .find_map(|dir| Checkpoint::load(&dir).map(|state| (dir, state)))
+        } else {
+            None
+        };
+        if let Some((dir, state)) = &resumed {
+            if state.header != checkpoint_header {
+                anyhow::bail!(
+                    "中断時と圧縮・暗号化・スナップショットの設定が異なるため再開できません: {} ({:?})",
+                    dir.display(),
+                    state.header
+                );
+            }
+            events.info(format!(
+                "{}: {}",
+                get_message(MessageKey::ResumingBackup, self.lang),
+                dir.display()
+            ));
+            events.info(format!(
+                "  {}: {}",
+                get_message(MessageKey::ResumedFiles, self.lang),
+                state.entries.len()
+            ));
+        } else if self.resume {
+            events.info(get_message(MessageKey::NoResumableBackup, self.lang));
+        }
+
+        let backup_name = if let Some((dir, _)) = &resumed {
+            dir.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .and_then(|name| {
+                    name.strip_prefix(staging::STAGING_PREFIX)
+                        .map(str::to_string)
+                })
+                .ok_or_else(|| anyhow::anyhow!("ステージングディレクトリ名が不正です"))?
+        } else {
+            let mut backup_name = format!("backup_{timestamp}");
+            // 同一秒内の連続実行では既存バックアップと衝突しないよう連番を付与
+            let mut suffix = 1;
+            while dest_base.join(&backup_name).exists()
+                || staging::staging_path(dest_base, &backup_name).exists()
+            {
+                backup_name = format!("backup_{timestamp}_{suffix}");
+                suffix += 1;
+            }
+            backup_name
+        };
+        let final_base = dest_base.join(&backup_name);
+        let resume_state = resumed.map(|(_, state)| state);
+
+        // グローバル pre_backup フック
+        let global_hooks = self.config.hooks.clone();
+        let hook_ctx = HookContext::new(dest_base).with_snapshot(&backup_name);
+        if !self.dry_run {
+            if let Some(outcome) = run_logged_hook(
+                events,
+                &mut self.audit_log,
+                &user,
+                None,
+                HookStage::PreBackup,
+                &global_hooks,
+                &hook_ctx,
+            ) {
+                if !outcome.success && global_hooks.abort_on_pre_failure {
+                    anyhow::bail!(
+                        "{}のためバックアップを中止しました",
+                        outcome.describe_failure()
+                    );
+                }
+            }
+        }
+
+        // 中断されたバックアップのステージングディレクトリを報告
+        let stale: Vec<PathBuf> = staging::find_stale_staging(dest_base)
+            .into_iter()
+            .filter(|dir| !dir.ends_with(format!("{}{backup_name}", staging::STAGING_PREFIX)))
+            .collect();
+        if !stale.is_empty() {
+            events.warning(format!(
+                "警告: 未完了のバックアップが{}件残っています（`backup-suite run --resume` で再開、`backup-suite cleanup` で削除できます）",
+                stale.len()
+            ));
+            for dir in &stale {
+                events.warning(format!("  {}", dir.display()));
+            }
+        }
+
+        // ステージングディレクトリに書き込み、完了後にアトミックに確定する
+        let backup_base = staging::staging_path(dest_base, &backup_name);
 
         // 暗号化が有効な場合、KeyManagerとmaster keyを準備
         let (_key_manager, master_key, encryption_salt) =
@@ -310,17 +1291,67 @@ impl BackupRunner {
 
         // 各ターゲットからファイルリストを収集
         let mut all_files: Vec<(PathBuf, PathBuf)> = Vec::new();
+        // 通常ファイル以外のエントリ（バックアップ内相対パス → エントリ）
+        let mut special_entries: BTreeMap<PathBuf, SpecialEntry> = BTreeMap::new();
+        let mut hardlinks = HardlinkTracker::new();
+
+        // ファイル収集開始
+        events.phase(Operation::Backup, Phase::Scanning);
+
+        // 対象ごとの圧縮設定（書き込み先 → 圧縮形式・レベル・辞書、実行時の設定と異なる場合のみ）
+        let default_codec: CodecKey = (self.compression_type, self.compression_level, None);
+        let mut target_codecs: HashMap<PathBuf, CodecKey> = HashMap::new();
+        // 学習したzstd辞書（辞書ID → 辞書）
+        let mut dictionaries: HashMap<u32, Arc<ZstdDictionary>> = HashMap::new();
+        // 収集できなかった対象（pre_backup フックやコマンドの失敗）
+        let mut failed_targets: Vec<&Target> = Vec::new();
+        let mut target_errors: Vec<ReportError> = Vec::new();
+        // 書き込み先 → 対象のインデックス（レポートの対象ごとの集計用）
+        let mut target_of: HashMap<PathBuf, usize> = HashMap::new();
+        // 対象ごとの除外パターンに一致したファイル数
+        let mut excluded = vec![0usize; targets.len()];
+        // ドライランで実行しないコマンド出力（表示名、書き込み先）
+        let mut virtual_previews: Vec<(String, PathBuf)> = Vec::new();
+
+        for (index, target) in targets.iter().enumerate() {
+            // ターゲットの pre_backup フック
+            if !self.dry_run {
+                let target_ctx = hook_ctx.clone().with_target(&target.path, &target.category);
+                if let Some(outcome) = run_logged_hook(
+                    events,
+                    &mut self.audit_log,
+                    &user,
+                    Some(&target.path),
+                    HookStage::PreBackup,
+                    &target.hooks,
+                    &target_ctx,
+                ) {
+                    if !outcome.success && target.hooks.abort_on_pre_failure {
+                        let message = format!(
+                            "{}のためスキップしました: {}",
+                            outcome.describe_failure(),
+                            target.path.display()
+                        );
+                        run_logged_hook(
+                            events,
+                            &mut self.audit_log,
+                            &user,
+                            Some(&target.path),
+                            HookStage::OnFailure,
+                            &target.hooks,
+                            &target_ctx.with_error(&message),
+                        );
+                        target_errors.push(ReportError {
+                            path: target.path.clone(),
+                            kind: ErrorKind::Hook,
+                            message,
+                        });
+                        failed_targets.push(target);
+                        continue;
+                    }
+                }
+            }
 
-        // スピナー表示（ファイル収集中）
-        let collection_spinner = if self.show_progress {
-            let spinner = BackupProgress::new_spinner();
-            spinner.set_message("Collecting backup target files...");
-            Some(spinner)
-        } else {
-            None
-        };
-
-        for target in &targets {
             // 各ターゲットのカテゴリをディレクトリ名に使用
             // （カテゴリフィルタは221-223行で既に適用済み）
             let category = target.category.clone();
@@ -335,7 +1366,7 @@ impl BackupRunner {
                 match FileFilter::new(&target.exclude_patterns) {
                     Ok(f) => Some(f),
                     Err(e) => {
-                        eprintln!("警告: 除外パターンの処理に失敗: {e}");
+                        events.warning(format!("警告: 除外パターンの処理に失敗: {e}"));
                         None
                     }
                 }
@@ -343,12 +1374,14 @@ impl BackupRunner {
                 None
             };
 
+            let first_file = all_files.len();
             match target.target_type {
                 TargetType::File => {
                     if target.path.exists() {
                         // 除外フィルタチェック
                         if let Some(ref f) = filter {
                             if f.should_exclude(&target.path) {
+                                excluded[index] += 1;
                                 continue;
                             }
                         }
@@ -359,12 +1392,12 @@ impl BackupRunner {
                             const LARGE_FILE_THRESHOLD: u64 = 100 * 1024 * 1024 * 1024; // 100GB
 
                             if file_size > LARGE_FILE_THRESHOLD {
-                                eprintln!(
+                                events.warning(format!(
                                     "⚠️  警告: 大容量ファイル検出 ({}GB): {:?}",
                                     file_size / (1024 * 1024 * 1024),
                                     target.path
-                                );
-                                eprintln!("    メモリ不足のリスクがあります。処理を続行しますが、システム監視を推奨します。");
+                                ));
+                                events.warning("    メモリ不足のリスクがあります。処理を続行しますが、システム監視を推奨します。");
                             }
                         }
 
@@ -373,7 +1406,9 @@ impl BackupRunner {
                             // safe_joinを使用してディレクトリトラバーサル対策
                             match safe_join(&backup_dir, std::path::Path::new(file_name)) {
                                 Ok(dest) => all_files.push((target.path.clone(), dest)),
-                                Err(e) => eprintln!("警告: ファイルパス処理エラー: {e}"),
+                                Err(e) => {
+                                    events.warning(format!("警告: ファイルパス処理エラー: {e}"))
+                                }
                             }
                         }
                     }
@@ -387,82 +1422,241 @@ impl BackupRunner {
                         .into_iter()
                         .filter_map(std::result::Result::ok)
                     {
-                        if entry.file_type().is_file() {
-                            let source = entry.path().to_path_buf();
+                        let source = entry.path().to_path_buf();
 
-                            // ファイルサイズチェック（100GB超の警告）
-                            if let Ok(metadata) = entry.metadata() {
-                                let file_size = metadata.len();
-                                const LARGE_FILE_THRESHOLD: u64 = 100 * 1024 * 1024 * 1024; // 100GB
-
-                                if file_size > LARGE_FILE_THRESHOLD {
-                                    eprintln!(
-                                        "⚠️  警告: 大容量ファイル検出 ({}GB): {:?}",
-                                        file_size / (1024 * 1024 * 1024),
-                                        source
-                                    );
-                                    eprintln!("    メモリ不足のリスクがあります。処理を続行しますが、システム監視を推奨します。");
-                                }
+                        // ディレクトリ名を含めた相対パスを保持してバックアップ先を決定
+                        let relative = match source.strip_prefix(base_path) {
+                            Ok(relative) => relative,
+                            Err(e) => {
+                                events.warning(format!("警告: パスのstrip_prefixに失敗: {e}"));
+                                continue;
                             }
+                        };
 
-                            // ディレクトリ名を含めた相対パスを保持してバックアップ先を決定
-                            match source.strip_prefix(base_path) {
-                                Ok(relative) => {
-                                    // 除外フィルタチェック（相対パスに対して）
-                                    if let Some(ref f) = filter {
-                                        if f.should_exclude(relative) {
-                                            continue;
-                                        }
-                                    }
-
-                                    // safe_joinを使用してディレクトリトラバーサル対策
-                                    match safe_join(&backup_dir, relative) {
-                                        Ok(dest) => all_files.push((source, dest)),
-                                        Err(e) => {
-                                            eprintln!("警告: パストラバーサル検出、スキップ: {e}")
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    eprintln!("警告: パスのstrip_prefixに失敗: {e}");
+                        // 除外フィルタチェック（相対パスに対して）
+                        if let Some(ref f) = filter {
+                            if f.should_exclude(relative) {
+                                if entry.file_type().is_file() {
+                                    excluded[index] += 1;
                                 }
+                                continue;
+                            }
+                        }
+
+                        // safe_joinを使用してディレクトリトラバーサル対策
+                        let dest = match safe_join(&backup_dir, relative) {
+                            Ok(dest) => dest,
+                            Err(e) => {
+                                events
+                                    .warning(format!("警告: パストラバーサル検出、スキップ: {e}"));
+                                continue;
+                            }
+                        };
+                        let Ok(manifest_path) = dest.strip_prefix(&backup_base) else {
+                            continue;
+                        };
+
+                        if !entry.file_type().is_file() {
+                            // シンボリックリンク・ディレクトリ・特殊ファイルはマニフェストに記録
+                            if let Some(special) = SpecialEntry::from_path(&source) {
+                                special_entries.insert(manifest_path.to_path_buf(), special);
+                            }
+                            continue;
+                        }
+
+                        if let Ok(metadata) = entry.metadata() {
+                            // ファイルサイズチェック（100GB超の警告）
+                            let file_size = metadata.len();
+                            const LARGE_FILE_THRESHOLD: u64 = 100 * 1024 * 1024 * 1024; // 100GB
+
+                            if file_size > LARGE_FILE_THRESHOLD {
+                                events.warning(format!(
+                                    "⚠️  警告: 大容量ファイル検出 ({}GB): {:?}",
+                                    file_size / (1024 * 1024 * 1024),
+                                    source
+                                ));
+                                events.warning("    メモリ不足のリスクがあります。処理を続行しますが、システム監視を推奨します。");
+                            }
+
+                            // 2つ目以降のハードリンクは最初のパスへの参照として記録
+                            if let Some(first) = hardlinks.register(&metadata, manifest_path) {
+                                special_entries.insert(
+                                    manifest_path.to_path_buf(),
+                                    SpecialEntry::new(EntryKind::Hardlink { target: first }, None),
+                                );
+                                continue;
                             }
                         }
+
+                        all_files.push((source, dest));
+                    }
+                }
+                TargetType::Command => {
+                    let dest = match safe_join(&backup_dir, &target.path) {
+                        Ok(dest) => dest,
+                        Err(e) => {
+                            events
+                                .warning(format!("警告: 仮想ファイル名が不正です、スキップ: {e}"));
+                            continue;
+                        }
+                    };
+                    let label = target
+                        .command
+                        .clone()
+                        .unwrap_or_else(|| "<stdin>".to_string());
+                    if self.dry_run {
+                        virtual_previews.push((label, dest));
+                        continue;
+                    }
+
+                    // 出力を一時ファイルに保存し、通常のファイルと同様に処理する
+                    let capture = backup_base
+                        .join(VIRTUAL_DIR)
+                        .join(all_files.len().to_string());
+                    let reader = if target.command.is_none() {
+                        stdin_reader.take()
+                    } else {
+                        None
+                    };
+                    match capture_output(target.command.as_deref(), reader, &capture) {
+                        Ok(()) => all_files.push((capture, dest)),
+                        Err(e) => {
+                            let message = format!("{label} の出力を取得できません: {e:#}");
+                            events.warning(format!("警告: {message}"));
+                            run_logged_hook(
+                                events,
+                                &mut self.audit_log,
+                                &user,
+                                Some(&target.path),
+                                HookStage::OnFailure,
+                                &target.hooks,
+                                &hook_ctx
+                                    .clone()
+                                    .with_target(&target.path, &target.category)
+                                    .with_error(&message),
+                            );
+                            target_errors.push(ReportError {
+                                path: target.path.clone(),
+                                kind: ErrorKind::Command,
+                                message,
+                            });
+                            failed_targets.push(target);
+                        }
                     }
                 }
             }
-        }
 
-        // スピナー完了
-        if let Some(spinner) = collection_spinner {
-            spinner.finish(&format!(
-                "{} {}",
-                all_files.len(),
-                get_message(MessageKey::FilesDetected, self.lang)
-            ));
+            for (_, dest) in &all_files[first_file..] {
+                target_of.insert(dest.clone(), index);
+            }
+
+            let (compression_type, level) = self.target_codec(target);
+            let dictionary_id = self
+                .train_target_dictionary(
+                    events,
+                    target,
+                    compression_type,
+                    &all_files[first_file..],
+                    &backup_base,
+                )
+                .map(|dictionary| {
+                    let id = dictionary.id();
+                    dictionaries
+                        .entry(id)
+                        .or_insert_with(|| Arc::new(dictionary));
+                    id
+                });
+            let codec = (compression_type, level, dictionary_id);
+            if codec != default_codec {
+                for (_, dest) in &all_files[first_file..] {
+                    target_codecs.insert(dest.clone(), codec);
+                }
+            }
         }
 
+        // ファイル収集完了
+        events.phase(
+            Operation::Backup,
+            Phase::Planning {
+                found_files: all_files.len(),
+            },
+        );
+
         // 増分バックアップ処理
         let inc_engine = IncrementalBackupEngine::new(dest_base.clone());
-        let backup_type = if self.incremental {
+
+        // スナップショットモード（ハードリンク共有は無圧縮・非暗号化のファイルでのみ可能）
+        let snapshot_mode = self.snapshot
+            && !self.enable_encryption
+            && self.compression_type == CompressionType::None;
+        if self.snapshot && !snapshot_mode {
+            events.warning("警告: スナップショットモードは圧縮・暗号化なしの場合のみ有効です。通常のバックアップを実行します");
+        }
+        let link_source = if snapshot_mode {
+            events.info(get_message(MessageKey::SnapshotBackupMode, self.lang));
+            // ハッシュアルゴリズムが異なるスナップショットとは未変更ファイルを比較できない
+            let previous = inc_engine
+                .find_latest_snapshot()?
+                .filter(|(path, metadata)| {
+                    let same = metadata.hash_algorithm == algorithm;
+                    if !same {
+                        events.warning(format!(
+                            "警告: 前回のスナップショット {:?} はハッシュアルゴリズム（{}）が異なるため、ハードリンクせずにコピーします",
+                            path.file_name().unwrap_or_default(),
+                            metadata.hash_algorithm.as_str()
+                        ));
+                    }
+                    same
+                });
+            match previous {
+                Some((ref path, _)) => events.info(format!(
+                    "  {}: {:?}",
+                    get_message(MessageKey::PreviousBackupLabel, self.lang),
+                    path.file_name().unwrap_or_default()
+                )),
+                None => events.info(get_message(MessageKey::NoBackupsFound, self.lang)),
+            }
+            previous
+        } else {
+            None
+        };
+
+        let backup_type = if self.incremental && !snapshot_mode {
             inc_engine.determine_backup_type()?
         } else {
             BackupType::Full
         };
 
         // 増分バックアップの場合、前回のメタデータを読み込み（失敗した場合はフルバックアップにフォールバック）
-        let (actual_backup_type, parent_backup_name, files_to_backup) =
+        // （未変更ファイルのハッシュは前回のマニフェストと同一のため、読み直さずに引き継ぐ）
+        let (actual_backup_type, parent_backup_name, files_to_backup, previous_hashes) =
             if backup_type == BackupType::Incremental {
-                match inc_engine.load_previous_metadata() {
+                // ハッシュアルゴリズムが異なる場合は変更を比較できないためフルバックアップ
+                let previous = inc_engine.load_previous_metadata().and_then(|metadata| {
+                    if metadata.hash_algorithm == algorithm {
+                        Ok(metadata)
+                    } else {
+                        Err(anyhow::anyhow!(
+                            "前回のバックアップのハッシュアルゴリズム（{}）が設定（{}）と異なります",
+                            metadata.hash_algorithm.as_str(),
+                            algorithm.as_str()
+                        ))
+                    }
+                });
+                match previous {
                     Ok(previous_metadata) => {
-                        println!(
-                            "{}",
-                            get_message(MessageKey::IncrementalBackupMode, self.lang)
-                        );
+                        events.info(get_message(MessageKey::IncrementalBackupMode, self.lang));
 
                         // バックアップディレクトリからの相対パスを計算
+                        // 監視モードでは変更通知のあったパスのみ比較
                         let files_with_relative: Vec<(PathBuf, PathBuf)> = all_files
                             .iter()
+                            .filter(|(source, _)| {
+                                // 変更されたパス自身またはその配下のファイル（祖先を集合で照合）
+                                self.changed_paths.as_ref().is_none_or(|paths| {
+                                    source.ancestors().any(|path| paths.contains(path))
+                                })
+                            })
                             .filter_map(|(source, dest)| {
                                 dest.strip_prefix(&backup_base)
                                     .ok()
@@ -474,29 +1668,37 @@ impl BackupRunner {
                             .detect_changed_files(&files_with_relative, &previous_metadata)?;
 
                         // 元のall_files形式に戻す（source, dest）
-                        let changed_files: Vec<(PathBuf, PathBuf)> = changed_files_relative
+                        let changed: HashSet<&Path> = changed_files_relative
                             .iter()
-                            .filter_map(|(_relative_path, source_path)| {
-                                all_files
-                                    .iter()
-                                    .find(|(src, _)| src == source_path)
-                                    .cloned()
+                            .map(|(relative_path, _)| relative_path.as_path())
+                            .collect();
+                        let changed_files: Vec<(PathBuf, PathBuf)> = all_files
+                            .iter()
+                            .filter(|(_, dest)| {
+                                dest.strip_prefix(&backup_base)
+                                    .is_ok_and(|rel| changed.contains(rel))
                             })
+                            .cloned()
                             .collect();
 
                         let parent_name = inc_engine.get_previous_backup_name()?;
-                        println!(
+                        events.info(format!(
                             "  {}: {parent_name:?}",
                             get_message(MessageKey::PreviousBackupLabel, self.lang)
-                        );
-                        println!(
+                        ));
+                        events.info(format!(
                             "  {}: {}/{}",
                             get_message(MessageKey::ChangedFilesLabel, self.lang),
                             changed_files.len(),
                             all_files.len()
-                        );
-
-                        (BackupType::Incremental, parent_name, changed_files)
+                        ));
+
+                        (
+                            BackupType::Incremental,
+                            parent_name,
+                            changed_files,
+                            previous_metadata.file_hashes,
+                        )
                     }
                     Err(e) => {
                         // エラーメッセージの内容で初回実行時か実際のエラーかを判別
@@ -505,272 +1707,554 @@ impl BackupRunner {
                             || error_msg.contains("前回のバックアップメタデータ読み込み失敗")
                         {
                             // 初回実行時: 情報レベルのメッセージ
-                            println!("{}", get_message(MessageKey::NoBackupsFound, self.lang));
+                            events.info(get_message(MessageKey::NoBackupsFound, self.lang));
                         } else {
                             // 実際のエラー時（メタデータ破損など）: 警告レベルのメッセージ
-                            eprintln!("{}", get_message(MessageKey::FullBackupFallback, self.lang));
-                            eprintln!(
+                            events.warning(get_message(MessageKey::FullBackupFallback, self.lang));
+                            events.warning(format!(
                                 "{}: {e}",
                                 get_message(MessageKey::MetadataLoadFailed, self.lang)
-                            );
+                            ));
                         }
-                        println!("{}", get_message(MessageKey::FullBackupMode, self.lang));
-                        (BackupType::Full, None, all_files.clone())
+                        events.info(get_message(MessageKey::FullBackupMode, self.lang));
+                        (BackupType::Full, None, all_files.clone(), HashMap::new())
                     }
                 }
             } else {
                 // --incremental フラグが指定されているが、前回のバックアップがない場合
-                if self.incremental {
-                    println!("{}", get_message(MessageKey::NoBackupsFound, self.lang));
+                if self.incremental && !snapshot_mode {
+                    events.info(get_message(MessageKey::NoBackupsFound, self.lang));
                 }
-                println!("{}", get_message(MessageKey::FullBackupMode, self.lang));
-                (BackupType::Full, None, all_files.clone())
+                if !snapshot_mode {
+                    events.info(get_message(MessageKey::FullBackupMode, self.lang));
+                }
+                (BackupType::Full, None, all_files.clone(), HashMap::new())
             };
 
+        // 監視モードで変更されたファイルがない場合はバックアップを作成しない
+        if self.changed_paths.is_some()
+            && actual_backup_type == BackupType::Incremental
+            && files_to_backup.is_empty()
+        {
+            if resume_state.is_none() {
+                let _ = std::fs::remove_dir_all(&backup_base);
+            }
+            return Ok(BackupResult::new());
+        }
+
+        // SQLiteデータベースはオンラインバックアップAPIで一貫した複製を作成してから処理
+        let (files_to_backup, db_snapshots, absorbed_sidecars) =
+            self.snapshot_databases(events, files_to_backup, &all_files, &backup_base);
+        all_files.retain(|(source, _)| !absorbed_sidecars.contains(source));
+
         let total_files = files_to_backup.len();
 
         if self.dry_run {
-            println!(
-                "{}",
+            let total_files = total_files + virtual_previews.len();
+            events.info(
                 get_message(MessageKey::DryRunMode, self.lang)
                     .replace("{}", &total_files.to_string())
+                    .to_string(),
             );
+            let shown = |dest: &PathBuf| {
+                dest.strip_prefix(&backup_base)
+                    .map_or_else(|_| dest.clone(), |rel| final_base.join(rel))
+            };
             for (source, dest) in &files_to_backup {
-                println!("  {} → {}", source.display(), dest.display());
+                events.info(format!(
+                    "  {} → {}",
+                    source.display(),
+                    shown(dest).display()
+                ));
+            }
+            for (label, dest) in &virtual_previews {
+                events.info(format!("  $ {label} → {}", shown(dest).display()));
+            }
+            // ドライランで作成したステージングディレクトリは残さない（再開対象は保持）
+            if resume_state.is_none() {
+                let _ = std::fs::remove_dir_all(&backup_base);
             }
             return Ok(BackupResult {
                 total_files,
                 successful: 0,
                 failed: 0,
                 total_bytes: 0,
+                linked_files: 0,
+                resumed_files: 0,
+                inconsistent_files: Vec::new(),
+                merkle_root: None,
+                compression_stats: CompressionStats::default(),
+                packed_files: 0,
                 errors: Vec::new(),
                 backup_name,
+                report: None,
+                cancelled: false,
             });
         }
 
-        // ProcessingPipelineの作成（暗号化または圧縮が有効な場合）
-        let pipeline = if self.enable_encryption || self.compression_type != CompressionType::None {
-            // CompressionConfigを作成（compression_typeに応じたデフォルトからlevelを変更）
-            let mut compression_config = match self.compression_type {
-                CompressionType::Zstd => crate::compression::CompressionConfig::zstd_default(),
-                CompressionType::Gzip => crate::compression::CompressionConfig::gzip_default(),
-                CompressionType::None => crate::compression::CompressionConfig::none(),
-            };
-            compression_config.level = self.compression_level;
-
-            let mut config = PipelineConfig::default()
-                .with_compression(self.compression_type, compression_config);
-
-            if self.enable_encryption {
-                config = config.with_encryption(EncryptionConfig::default());
-            }
-
-            Some(Arc::new(ProcessingPipeline::new(config)))
-        } else {
-            None
-        };
-
-        // プログレスバーの初期化
-        let progress = if self.show_progress {
-            Some(Arc::new(BackupProgress::with_language(
-                total_files as u64,
-                self.lang,
-            )))
-        } else {
-            None
-        };
+        // 整合性検証用ハッシュのアルゴリズム
+        // （スナップショットモードでは次回の変更検出にハッシュが必要なため常に計算）
+        let hash_algorithm = (self.verify_integrity || snapshot_mode).then_some(algorithm);
+
+        // ProcessingPipelineの作成（圧縮形式・レベル・辞書の組み合わせごと、暗号化または圧縮が有効な場合）
+        let pipelines: HashMap<CodecKey, Option<ProcessingPipeline>> =
+            std::iter::once(default_codec)
+                .chain(target_codecs.values().copied())
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .map(|codec| {
+                    let pipeline = self.build_pipeline(codec, hash_algorithm, &dictionaries);
+                    (codec, pipeline)
+                })
+                .collect();
+
+        // ファイルの転送開始
+        events.phase(Operation::Backup, Phase::Processing { total_files });
 
         // CopyEngineの初期化（I/O最適化）
         let copy_engine = Arc::new(CopyEngine::new());
 
         // 整合性検証チェッカーの初期化
-        let integrity_checker = if self.verify_integrity {
-            Some(Arc::new(std::sync::Mutex::new(IntegrityChecker::new())))
+        // （ハッシュは各ワーカーで計算して結果とともに返し、並列処理後にまとめて登録）
+        let mut integrity_checker =
+            hash_algorithm.map(|algorithm| IntegrityChecker::new().with_hash_algorithm(algorithm));
+
+        // チェックポイント（書き込み済みファイルの記録）を準備
+        std::fs::create_dir_all(&backup_base).context("ステージングディレクトリ作成失敗")?;
+        let checkpoint = if resume_state.is_some() {
+            Checkpoint::append(&backup_base)
         } else {
-            None
-        };
+            Checkpoint::create(&backup_base, &checkpoint_header)
+        }
+        .map_err(|e| {
+            events.warning(format!(
+                "警告: チェックポイントを作成できません（再開不可）: {e}"
+            ))
+        })
+        .ok();
 
         // 並列バックアップ処理
-        let success_count = AtomicUsize::new(0);
-        let failed_count = AtomicUsize::new(0);
-        let total_bytes = AtomicUsize::new(0);
+        let processor = FileProcessor {
+            events,
+            backup_base: &backup_base,
+            algorithm,
+            hash_algorithm,
+            resume_state: resume_state.as_ref(),
+            checkpoint: checkpoint.as_ref(),
+            db_snapshots: &db_snapshots,
+            link_source: link_source.as_ref(),
+            record_stamps: snapshot_mode,
+            checksum: self.checksum,
+            target_codecs: &target_codecs,
+            default_codec,
+            pipelines: &pipelines,
+            master_key: master_key.as_deref(),
+            encryption_salt,
+            copy_engine: &copy_engine,
+            change_retries: self.config.backup.change_retries,
+            success_count: AtomicUsize::new(0),
+            failed_count: AtomicUsize::new(0),
+            total_bytes: AtomicUsize::new(0),
+            linked_count: AtomicUsize::new(0),
+            resumed_count: AtomicUsize::new(0),
+            inconsistent: Mutex::new(Vec::new()),
+        };
 
-        let errors: Vec<String> = files_to_backup
+        // 進捗イベント用の累計（処理済みファイル数・読み込みバイト数）
+        let files_done = AtomicUsize::new(0);
+        let bytes_done = AtomicU64::new(0);
+
+        let cancel = &self.cancel;
+        let outcomes: Vec<FileOutcome> = files_to_backup
             .par_iter()
-            .filter_map(|(source, dest)| {
-                // 進捗表示更新
-                if let Some(ref pb) = progress {
-                    if let Some(file_name) = source.file_name() {
-                        pb.set_message(&format!("処理中: {file_name:?}"));
-                    }
+            .map(|(source, dest)| {
+                // キャンセル後は新しいファイルの処理を開始しない（一時停止中は再開まで待機）
+                if cancel.should_stop() {
+                    return FileOutcome::cancelled();
                 }
+                events.on_event(&Event::FileStarted {
+                    path: source.clone(),
+                });
+                let outcome = processor.process_file(source, dest);
+
+                events.on_event(&match &outcome.failure {
+                    Some((path, kind, message)) => Event::FileFailed {
+                        path: (*path).clone(),
+                        kind: *kind,
+                        message: message.clone(),
+                    },
+                    None => Event::FileFinished {
+                        path: source.clone(),
+                        bytes_read: outcome.bytes.0,
+                        bytes_written: outcome.bytes.1,
+                    },
+                });
+                events.on_event(&Event::Progress {
+                    files_done: files_done.fetch_add(1, Ordering::Relaxed) + 1,
+                    total_files,
+                    bytes_done: bytes_done.fetch_add(outcome.bytes.0, Ordering::Relaxed)
+                        + outcome.bytes.0,
+                });
+                outcome
+            })
+            .collect();
 
-                // バックアップディレクトリからの相対パスを計算（整合性検証用）
-                let relative_path = dest.strip_prefix(&backup_base).ok();
+        // 対象ごとの集計（増分バックアップで変更のなかったファイルはスキップとして数える）
+        let mut target_counts: Vec<RunCounts> = excluded
+            .iter()
+            .map(|&excluded| RunCounts {
+                excluded,
+                ..RunCounts::default()
+            })
+            .collect();
+        for (_, dest) in &all_files {
+            if let Some(&index) = target_of.get(dest) {
+                target_counts[index].skipped += 1;
+            }
+        }
 
-                // バックアップ先のディレクトリを作成
-                if let Some(parent) = dest.parent() {
-                    if let Err(e) = std::fs::create_dir_all(parent) {
-                        failed_count.fetch_add(1, Ordering::Relaxed);
-                        if let Some(ref pb) = progress {
-                            pb.inc(1);
-                        }
-                        return Some(format!("ディレクトリ作成失敗 parent.display(): {e}"));
-                    }
-                }
+        let cancelled = outcomes.iter().any(|outcome| outcome.cancelled);
+        if cancelled {
+            events.phase(Operation::Backup, Phase::Cancelled);
+        }
 
-                // ProcessingPipelineまたはCopyEngineでファイル処理
-                let copy_result = if let Some(ref pipeline) = pipeline {
-                    // 暗号化・圧縮パイプライン使用
-                    match pipeline.process_file(
-                        source,
-                        master_key.as_ref().map(std::convert::AsRef::as_ref),
-                        encryption_salt,
-                    ) {
-                        Ok(processed) => {
-                            // 処理後のデータをファイルに書き込み
-                            match std::fs::write(dest, &processed.data) {
-                                Ok(_) => {
-                                    success_count.fetch_add(1, Ordering::Relaxed);
-                                    total_bytes.fetch_add(
-                                        processed.metadata.final_size as usize,
-                                        Ordering::Relaxed,
-                                    );
-                                    if let Some(ref pb) = progress {
-                                        pb.inc(1);
-                                    }
-                                    Ok(())
-                                }
-                                Err(e) => {
-                                    failed_count.fetch_add(1, Ordering::Relaxed);
-                                    if let Some(ref pb) = progress {
-                                        pb.inc(1);
-                                    }
-                                    Err(format!("書き込み失敗 dest.display(): {e}"))
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            failed_count.fetch_add(1, Ordering::Relaxed);
-                            if let Some(ref pb) = progress {
-                                pb.inc(1);
-                            }
-                            Err(format!("処理失敗 source.display(): {e}"))
-                        }
-                    }
+        let mut failures: Vec<(&PathBuf, ErrorKind, String)> = Vec::new();
+        let mut compression_stats = CompressionStats::default();
+        // 書き込みを試みたファイル（キャンセルで処理しなかったファイルはマニフェスト・パックに含めない）
+        let mut processed: Vec<&PathBuf> = Vec::with_capacity(files_to_backup.len());
+        // 圧縮して保存したファイルの圧縮形式（復元時に試行展開せずに判定するためマニフェストに記録）
+        let mut file_codecs: BTreeMap<PathBuf, CompressionType> = BTreeMap::new();
+        // スナップショットで記録するサイズ・更新日時（次回の未変更判定用）
+        let mut file_stamps: BTreeMap<PathBuf, FileStamp> = BTreeMap::new();
+        // データ領域のみを保存したスパースファイルの配置（復元時に元の配置へ書き込むため記録）
+        let mut sparse_files: BTreeMap<PathBuf, SparseMap> = BTreeMap::new();
+        for ((_, dest), outcome) in files_to_backup.iter().zip(outcomes) {
+            let counts = target_of.get(dest).map(|&index| &mut target_counts[index]);
+            if outcome.cancelled {
+                if let Some(counts) = counts {
+                    counts.cancelled += 1;
+                    counts.skipped = counts.skipped.saturating_sub(1);
+                }
+                continue;
+            }
+            processed.push(dest);
+            if let Some(counts) = counts {
+                counts.files += 1;
+                counts.skipped = counts.skipped.saturating_sub(1);
+                if outcome.failure.is_some() {
+                    counts.failed += 1;
                 } else {
-                    // 従来のCopyEngine使用（暗号化・圧縮なし）
-                    match copy_engine.copy_file(source, dest) {
-                        Ok(bytes) => {
-                            success_count.fetch_add(1, Ordering::Relaxed);
-                            total_bytes.fetch_add(bytes as usize, Ordering::Relaxed);
-                            if let Some(ref pb) = progress {
-                                pb.inc(1);
-                            }
-                            Ok(())
+                    counts.successful += 1;
+                }
+                if outcome.linked {
+                    counts.linked += 1;
+                }
+                if outcome.offloaded {
+                    counts.offloaded += 1;
+                }
+                counts.bytes_read += outcome.bytes.0;
+                counts.bytes_written += outcome.bytes.1;
+            }
+            if let Some((original, compressed, decision)) = outcome.compression {
+                compression_stats.record(original, compressed, decision);
+            }
+            let (codec, _, _) = target_codecs.get(dest).copied().unwrap_or(default_codec);
+            if codec != CompressionType::None && outcome.failure.is_none() {
+                if let Ok(rel_path) = dest.strip_prefix(&backup_base) {
+                    file_codecs.insert(rel_path.to_path_buf(), codec);
+                }
+            }
+            if let (Some(stamp), Ok(rel_path)) = (outcome.stamp, dest.strip_prefix(&backup_base)) {
+                file_stamps.insert(rel_path.to_path_buf(), stamp);
+            }
+            if let (Some(map), Ok(rel_path)) = (outcome.sparse, dest.strip_prefix(&backup_base)) {
+                sparse_files.insert(rel_path.to_path_buf(), map);
+            }
+            if let (Some(checker), Some((rel_path, hash))) = (&mut integrity_checker, outcome.hash)
+            {
+                checker.add_file_hash(rel_path, hash);
+            }
+            failures.extend(outcome.failure);
+        }
+
+        let FileProcessor {
+            success_count,
+            failed_count,
+            total_bytes,
+            linked_count,
+            resumed_count,
+            inconsistent,
+            ..
+        } = processor;
+        let mut inconsistent_files = inconsistent.into_inner().unwrap_or_default();
+        inconsistent_files.sort();
+
+        // ファイルの転送完了、メタデータの保存と確定
+        events.phase(Operation::Backup, Phase::Finalizing);
+
+        // 小さなファイルをパックにまとめる（スナップショットはハードリンク共有のため対象外）
+        let packed_files = if self.pack_small_files && !snapshot_mode {
+            let written: Vec<PathBuf> = processed
+                .iter()
+                .filter_map(|dest| dest.strip_prefix(&backup_base).ok())
+                .map(Path::to_path_buf)
+                .collect();
+            pack::pack_files(&backup_base, &written).context("パックファイルの作成失敗")?
+        } else {
+            BTreeMap::new()
+        };
+        let packed_count = packed_files.len();
+
+        // 整合性メタデータを保存（増分情報を含む）
+        let mut merkle_root = None;
+        if let Some(mut checker) = integrity_checker {
+            // 増分バックアップ情報を追加
+            checker.metadata.backup_type = actual_backup_type;
+            checker.metadata.parent_backup = parent_backup_name;
+            checker.metadata.inconsistent_files = inconsistent_files
+                .iter()
+                .filter_map(|(_, dest)| dest.strip_prefix(&backup_base).ok())
+                .map(Path::to_path_buf)
+                .collect();
+            checker.metadata.changed_files = processed
+                .iter()
+                .filter_map(|dest| {
+                    dest.strip_prefix(&backup_base)
+                        .ok()
+                        .map(std::path::Path::to_path_buf)
+                })
+                .collect();
+
+            // 増分バックアップの場合、変更されなかったファイルのハッシュも保存
+            // （次回の増分バックアップで正しく比較できるようにするため）
+            // 変更検出で前回と一致したハッシュをそのまま引き継ぐ
+            if actual_backup_type == BackupType::Incremental {
+                for (_, dest) in &all_files {
+                    if let Ok(rel_path) = dest.strip_prefix(&backup_base) {
+                        // 既にハッシュが保存されているファイル・不整合なファイルはスキップ
+                        if checker.metadata.file_hashes.contains_key(rel_path)
+                            || checker
+                                .metadata
+                                .inconsistent_files
+                                .iter()
+                                .any(|p| p == rel_path)
+                        {
+                            continue;
                         }
-                        Err(e) => {
-                            failed_count.fetch_add(1, Ordering::Relaxed);
-                            if let Some(ref pb) = progress {
-                                pb.inc(1);
-                            }
-                            Err(format!("コピー失敗 source.display(): {e}"))
+                        if let Some(hash) = previous_hashes.get(rel_path) {
+                            checker.add_file_hash(rel_path.to_path_buf(), hash.clone());
                         }
                     }
-                };
+                }
+            }
 
-                // 整合性検証：元ファイルのハッシュを計算して保存
-                if copy_result.is_ok() {
-                    if let Some(ref checker) = integrity_checker {
-                        if let Some(rel_path) = relative_path {
-                            if let Ok(mut guard) = checker.lock() {
-                                if let Ok(hash) = guard.compute_hash(source) {
-                                    guard.add_file_hash(rel_path.to_path_buf(), hash);
-                                }
-                            }
-                        }
-                    }
+            checker.metadata.special_entries = special_entries;
+            checker.metadata.packed_files = packed_files;
+            checker.metadata.file_codecs = file_codecs;
+            checker.metadata.file_stamps = file_stamps;
+            checker.metadata.sparse_files = sparse_files;
+            checker.metadata.snapshot = snapshot_mode;
+            checker.metadata.partial = cancelled;
+            checker.metadata.link_dest = link_source.as_ref().and_then(|(path, _)| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+            });
+
+            match checker.save_metadata(&backup_base) {
+                Ok(()) => merkle_root = checker.metadata.merkle_root,
+                Err(e) => {
+                    events.warning(format!("警告: 整合性メタデータの保存に失敗しました: {e}"))
                 }
+            }
+        } else {
+            // 整合性検証が無効でも、特殊エントリ・パックの索引・圧縮形式は復元に必要なためマニフェストに記録
+            let mut metadata = BackupMetadata::new();
+            metadata.backup_type = actual_backup_type;
+            metadata.parent_backup = parent_backup_name;
+            metadata.special_entries = special_entries;
+            metadata.packed_files = packed_files;
+            metadata.file_codecs = file_codecs;
+            metadata.sparse_files = sparse_files;
+            metadata.partial = cancelled;
+            if let Err(e) = metadata.save(&backup_base) {
+                events.warning(format!("警告: 整合性メタデータの保存に失敗しました: {e}"));
+            }
+        }
 
-                copy_result.err()
-            })
-            .collect();
+        // コマンド出力の一時ファイルを削除
+        let _ = std::fs::remove_dir_all(backup_base.join(VIRTUAL_DIR));
 
-        // プログレスバー完了
-        if let Some(pb) = progress {
-            let failed = failed_count.load(Ordering::Relaxed);
-            if failed == 0 {
-                pb.finish(get_message(MessageKey::BackupComplete, self.lang));
-            } else {
-                pb.finish(&format!(
-                    "{} ({} {})",
-                    get_message(MessageKey::BackupCompleteWithFailures, self.lang)
-                        .replace("（失敗あり）", "")
-                        .replace("（有失败）", "")
-                        .replace("（有失敗）", "")
-                        .replace("(with failures)", ""),
-                    failed,
-                    get_message(MessageKey::FailedLabel, self.lang)
-                ));
+        // チェックポイントは確定後のバックアップに不要なため削除
+        if let Some(cp) = checkpoint {
+            if let Err(e) = cp.finish() {
+                events.warning(format!("警告: {e}"));
             }
         }
 
-        // 整合性メタデータを保存（増分情報を含む）
-        if let Some(ref checker) = integrity_checker {
-            if let Ok(mut guard) = checker.lock() {
-                // 増分バックアップ情報を追加
-                guard.metadata.backup_type = actual_backup_type;
-                guard.metadata.parent_backup = parent_backup_name;
-                guard.metadata.changed_files = files_to_backup
+        // 実行レポートをスナップショット内に保存
+        let target_reports: Vec<TargetReport> = targets
+            .iter()
+            .zip(target_counts)
+            .map(|(target, counts)| TargetReport {
+                path: target.path.clone(),
+                category: target.category.clone(),
+                priority: target.priority,
+                counts,
+                aborted: failed_targets
                     .iter()
-                    .filter_map(|(_, dest)| {
-                        dest.strip_prefix(&backup_base)
-                            .ok()
-                            .map(std::path::Path::to_path_buf)
-                    })
-                    .collect();
-
-                // 増分バックアップの場合、変更されなかったファイルのハッシュも保存
-                // （次回の増分バックアップで正しく比較できるようにするため）
-                if actual_backup_type == BackupType::Incremental {
-                    for (source, dest) in &all_files {
-                        if let Ok(rel_path) = dest.strip_prefix(&backup_base) {
-                            // 既にハッシュが保存されているファイルはスキップ
-                            if !guard.metadata.file_hashes.contains_key(rel_path) {
-                                if let Ok(hash) = guard.compute_hash(source) {
-                                    guard.add_file_hash(rel_path.to_path_buf(), hash);
-                                }
-                            }
-                        }
-                    }
-                }
+                    .any(|aborted| std::ptr::eq(*aborted, *target)),
+            })
+            .collect();
+        let mut report_errors = target_errors;
+        report_errors.extend(
+            failures
+                .into_iter()
+                .map(|(path, kind, message)| ReportError {
+                    path: path.clone(),
+                    kind,
+                    message,
+                }),
+        );
+        let report = RunReport::new(
+            backup_name.clone(),
+            actual_backup_type,
+            started_at,
+            u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            target_reports,
+            report_errors,
+        );
+        if let Err(e) = report.save(&backup_base) {
+            events.warning(format!("警告: 実行レポートの保存に失敗しました: {e:#}"));
+        }
 
-                if let Err(e) = guard.save_metadata(&backup_base) {
-                    eprintln!("警告: 整合性メタデータの保存に失敗しました: {e}");
-                }
+        // ステージングディレクトリを確定（アトミックなリネーム）
+        let backup_base = staging::commit(&backup_base, &final_base)?;
+
+        // ターゲットごとの結果と post_backup / on_failure フック
+        let mut target_records = Vec::with_capacity(targets.len());
+        for (target, target_report) in targets.iter().zip(&report.targets) {
+            let total = target_report.counts.files;
+            let failed = target_report.counts.failed;
+            target_records.push(TargetRecord {
+                path: target.path.clone(),
+                category: target.category.clone(),
+                priority: target.priority,
+                files: total,
+                failed,
+                success: !target_report.aborted
+                    && failed == 0
+                    && target_report.counts.cancelled == 0,
+            });
+
+            if target_report.aborted || target.hooks.is_empty() {
+                continue;
+            }
+            let target_ctx = hook_ctx
+                .clone()
+                .with_target(&target.path, &target.category)
+                .with_counts(total, total - failed, failed);
+            run_logged_hook(
+                events,
+                &mut self.audit_log,
+                &user,
+                Some(&target.path),
+                HookStage::PostBackup,
+                &target.hooks,
+                &target_ctx,
+            );
+            if failed > 0 {
+                run_logged_hook(
+                    events,
+                    &mut self.audit_log,
+                    &user,
+                    Some(&target.path),
+                    HookStage::OnFailure,
+                    &target.hooks,
+                    &target_ctx,
+                );
             }
         }
 
+        let errors = report
+            .errors
+            .iter()
+            .map(|error| error.message.clone())
+            .collect();
+
         let result = BackupResult {
             total_files,
             successful: success_count.load(Ordering::Relaxed),
             failed: failed_count.load(Ordering::Relaxed),
             total_bytes: total_bytes.load(Ordering::Relaxed) as u64,
+            linked_files: linked_count.load(Ordering::Relaxed),
+            resumed_files: resumed_count.load(Ordering::Relaxed),
+            inconsistent_files: inconsistent_files
+                .into_iter()
+                .map(|(source, _)| source)
+                .collect(),
+            merkle_root,
+            compression_stats,
+            packed_files: packed_count,
             errors,
             backup_name,
+            report: Some(report),
+            cancelled,
         };
 
+        // グローバル post_backup / on_failure フック
+        let success = result.failed == 0 && failed_targets.is_empty() && !cancelled;
+        let mut global_ctx =
+            hook_ctx.with_counts(result.total_files, result.successful, result.failed);
+        if cancelled {
+            global_ctx = global_ctx.with_error("キャンセルされました");
+        } else if !success {
+            global_ctx = global_ctx.with_error(&result.errors.join("; "));
+        }
+        run_logged_hook(
+            events,
+            &mut self.audit_log,
+            &user,
+            None,
+            HookStage::PostBackup,
+            &global_hooks,
+            &global_ctx,
+        );
+        if !success {
+            run_logged_hook(
+                events,
+                &mut self.audit_log,
+                &user,
+                None,
+                HookStage::OnFailure,
+                &global_hooks,
+                &global_ctx,
+            );
+        }
+
         // 履歴保存（バックアップ全体のベースディレクトリを使用）
-        let success = result.failed == 0;
-        if let Err(e) = super::BackupHistory::save(&super::BackupHistory::new(
+        let history = super::BackupHistory::new(
             backup_base.clone(),
             result.total_files,
             result.total_bytes,
             success,
-            self.compression_type != CompressionType::None,
+            all_files.iter().any(|(_, dest)| {
+                target_codecs
+                    .get(dest)
+                    .map_or(default_codec.0, |codec| codec.0)
+                    != CompressionType::None
+            }),
             self.enable_encryption,
-        )) {
-            eprintln!("履歴保存失敗: {e}");
+        )
+        .with_inconsistent_files(result.inconsistent_files.clone())
+        .with_cancelled(cancelled)
+        .with_job(self.job.clone())
+        .with_targets(target_records);
+        let history = match &result.report {
+            Some(report) => history.with_report(report),
+            None => history,
+        };
+        if let Err(e) = super::BackupHistory::save(&history) {
+            events.warning(format!("履歴保存失敗: {e}"));
         }
 
         // 監査ログ: バックアップ完了 or 失敗
@@ -780,28 +2264,191 @@ impl BackupRunner {
                 "successful": result.successful,
                 "failed": result.failed,
                 "total_bytes": result.total_bytes,
+                "linked_files": result.linked_files,
+                "resumed_files": result.resumed_files,
+                "inconsistent_files": result.inconsistent_files.len(),
+                "merkle_root": result.merkle_root,
+                "compressed_bytes_saved": result.compression_stats.saved_bytes(),
                 "backup_name": result.backup_name,
+                "duration_ms": result.report.as_ref().map(|report| report.duration_ms),
             });
 
-            let event = if success {
+            let event = if cancelled {
+                AuditEvent::backup_cancelled(&target_desc, &user, metadata)
+            } else if success {
                 AuditEvent::backup_completed(&target_desc, &user, metadata)
             } else {
                 AuditEvent::backup_failed(
                     &target_desc,
                     &user,
-                    format!("{}件のファイルでエラーが発生しました", result.failed),
+                    if failed_targets.is_empty() {
+                        format!("{}件のファイルでエラーが発生しました", result.failed)
+                    } else {
+                        format!(
+                            "{}件のファイルでエラーが発生し、{}件の対象を収集できませんでした",
+                            result.failed,
+                            failed_targets.len()
+                        )
+                    },
                 )
             };
 
             let _ = audit_log
                 .log(event)
-                .map_err(|e| eprintln!("警告: 監査ログの記録に失敗しました: {e}"));
+                .map_err(|e| events.warning(format!("警告: 監査ログの記録に失敗しました: {e}")));
         }
 
         Ok(result)
     }
 }
 
+/// コマンドの標準出力またはストリームの内容をファイルに保存
+///
+/// `command` が指定されていればシェル経由で実行し、未指定の場合は `reader` を読み込みます。
+fn capture_output(command: Option<&str>, reader: Option<Box<dyn Read>>, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("一時ディレクトリ作成失敗")?;
+    }
+    let mut file = std::fs::File::create(path).context("一時ファイル作成失敗")?;
+
+    match (command, reader) {
+        (Some(command), _) => {
+            let status = hooks::shell_command(command)
+                .stdin(Stdio::null())
+                .stdout(file.try_clone()?)
+                .status()
+                .context("コマンドを起動できません")?;
+            if !status.success() {
+                anyhow::bail!(
+                    "コマンドが失敗しました（終了コード: {}）",
+                    status
+                        .code()
+                        .map_or_else(|| "シグナル".to_string(), |c| c.to_string())
+                );
+            }
+        }
+        (None, Some(mut reader)) => {
+            std::io::copy(&mut reader, &mut file).context("入力の読み込み失敗")?;
+        }
+        (None, None) => anyhow::bail!("コマンドが設定されていません"),
+    }
+
+    file.sync_all().context("一時ファイルの同期失敗")?;
+    Ok(())
+}
+
+/// フックを実行し、結果を監査ログに記録
+///
+/// 失敗したフックは警告として通知します。フックが設定されていない場合は `None` を返します。
+fn run_logged_hook(
+    events: &dyn EventSink,
+    audit_log: &mut Option<AuditLog>,
+    user: &str,
+    target: Option<&Path>,
+    stage: HookStage,
+    hooks: &HookConfig,
+    ctx: &HookContext,
+) -> Option<HookOutcome> {
+    let outcome = hooks::run_hook(stage, hooks, ctx)?;
+    if !outcome.success {
+        events.warning(format!("警告: {}", outcome.describe_failure()));
+    }
+    if let Some(ref mut audit_log) = audit_log {
+        let event = AuditEvent::hook_executed(
+            target.map(|path| path.display().to_string()),
+            user,
+            outcome.to_json(),
+        );
+        let _ = audit_log
+            .log(event)
+            .map_err(|e| events.warning(format!("警告: 監査ログの記録に失敗しました: {e}")));
+    }
+    Some(outcome)
+}
+
+/// 書き込み済みファイルをチェックポイントに記録（失敗しても再開時に再コピーされるだけのため警告のみ）
+fn record_checkpoint(events: &dyn EventSink, checkpoint: &Checkpoint, entry: &CheckpointEntry) {
+    if let Err(e) = checkpoint.record(entry) {
+        events.warning(format!("警告: チェックポイントの記録に失敗しました: {e}"));
+    }
+}
+
+/// 前回スナップショットへのハードリンクの試行結果
+struct LinkAttempt {
+    /// リンクした場合はリンク元ファイルのサイズと更新日時
+    linked: Option<(u64, u64)>,
+    /// ソースのハッシュ（リンクした場合は前回の記録、リンクしなかった場合は比較のために計算した値）
+    hash: Option<String>,
+}
+
+/// 前回スナップショットの同一ファイルへのハードリンクを試行
+///
+/// `rsync --link-dest` と同様に、ソースのサイズと更新日時が前回の記録と一致すれば内容を
+/// 読み込まずに未変更と判定します。一致しない場合や記録がない場合、`checksum` 指定時は
+/// ソースのハッシュを前回のマニフェストと比較します。
+/// リンク元のファイルは、記録時からサイズと更新日時が変わっていないこと（記録がない場合・
+/// `checksum` 指定時は内容のハッシュが一致すること）を確認し、改変・破損したファイルを
+/// 以降のスナップショットに引き継がないようにします。
+/// リンクできなかった場合（別ファイルシステム、リンク数上限など）は、呼び出し側で
+/// 通常のコピーにフォールバックします。
+fn link_unchanged(
+    source: &Path,
+    stamp: Option<(u64, u64)>,
+    dest: &Path,
+    relative_path: &Path,
+    (prev_dir, prev_metadata): &(PathBuf, BackupMetadata),
+    checksum: bool,
+) -> LinkAttempt {
+    let Some(prev_hash) = prev_metadata.file_hashes.get(relative_path) else {
+        return LinkAttempt {
+            linked: None,
+            hash: None,
+        };
+    };
+    let prev_stamp = prev_metadata
+        .file_stamps
+        .get(relative_path)
+        .filter(|_| !checksum);
+
+    let (hash, computed) = if stamp.is_some() && prev_stamp.map(|prev| prev.source) == stamp {
+        (prev_hash.clone(), false)
+    } else {
+        match prev_metadata.hash_algorithm.hash_file(source) {
+            Ok(hash) if &hash == prev_hash => (hash, true),
+            Ok(hash) => {
+                return LinkAttempt {
+                    linked: None,
+                    hash: Some(hash),
+                }
+            }
+            Err(_) => {
+                return LinkAttempt {
+                    linked: None,
+                    hash: None,
+                }
+            }
+        }
+    };
+
+    let prev_file = prev_dir.join(relative_path);
+    let stored = std::fs::symlink_metadata(&prev_file)
+        .ok()
+        .filter(|meta| meta.file_type().is_file())
+        .map(|meta| checkpoint::stamp_of(&meta));
+    let intact = stored.is_some_and(|stored| match prev_stamp {
+        Some(prev) => prev.stored == stored,
+        None => prev_metadata
+            .hash_algorithm
+            .hash_file(&prev_file)
+            .is_ok_and(|hash| &hash == prev_hash),
+    });
+    let linked = intact && std::fs::hard_link(&prev_file, dest).is_ok();
+    LinkAttempt {
+        linked: stored.filter(|_| linked),
+        hash: (linked || computed).then_some(hash),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -830,6 +2477,47 @@ mod tests {
         assert!(result.total_bytes > 0);
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_uncompressed_backup_uses_kernel_copy() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("large.bin");
+        // copy_file_range の閾値（10MB）以上のファイル
+        let data: Vec<u8> = (0..12 * 1024 * 1024u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&source, &data).unwrap();
+
+        let destination = temp.path().join("backups");
+
+        for verify in [true, false] {
+            let mut config = Config::default();
+            config.add_target(Target::new(
+                source.clone(),
+                Priority::High,
+                "test".to_string(),
+            ));
+            config.backup.destination = destination.clone();
+
+            let mut runner = BackupRunner::new(config, false)
+                .with_compression(CompressionType::None, 0)
+                .with_verification(verify);
+            let result = runner.run(None, None).unwrap();
+            assert_eq!(result.successful, 1);
+
+            // リフリンクまたは copy_file_range でコピーされ、ユーザー空間コピーは行わない
+            let report = result.report.unwrap();
+            assert_eq!(report.summary.totals.offloaded, 1);
+
+            let backup_dir = destination.join(&result.backup_name);
+            let copied = std::fs::read(backup_dir.join("test").join("large.bin")).unwrap();
+            assert_eq!(copied, data);
+
+            // 整合性検証が有効な場合のみハッシュを記録
+            let metadata = BackupMetadata::load(&backup_dir).unwrap();
+            assert_eq!(metadata.file_hashes.len(), usize::from(verify));
+            std::thread::sleep(std::time::Duration::from_millis(1100));
+        }
+    }
+
     #[test]
     fn test_backup_dry_run() {
         let temp = TempDir::new().unwrap();
@@ -0,0 +1,506 @@
+//! # カレンダースケジュール
+//!
+//! スケジュールの実行時刻を表し、systemd timer の `OnCalendar` と launchd の
+//! `StartCalendarInterval` の両方に変換します。
+//!
+//! # 書式
+//!
+//! - **キーワード**: `hourly` / `daily` / `weekly` / `monthly`（午前2時・日曜・1日）
+//! - **cron式**: `分 時 日 月 曜日`（`*`、`,` のリスト、`-` の範囲、`/` の間隔、曜日名に対応）
+//!   - 例: `0 9,18 * * 1-5`（平日の9時と18時）、`*/30 * * * *`（30分ごと）
+//! - **systemd `OnCalendar` 形式**: `[曜日] [*-月-日] 時:分[:00]`（範囲は `..`）
+//!   - 例: `Mon..Fri *-*-* 09,18:00`、`*-*-01,15 03:30`
+//!
+//! launchd は分単位のため、秒は `0` のみ指定できます。cron式で日と曜日を同時に
+//! 指定した場合、cronは「いずれか」に一致したときに実行しますが、systemd と launchd は
+//! 「両方」に一致したときのみ実行するため、エラーとして扱います。
+//!
+//! # 使用例
+//!
+//! ```
+//! use backup_suite::core::calendar::CalendarSpec;
+//!
+//! let spec = CalendarSpec::parse("0 9,18 * * mon-fri").unwrap();
+//! assert_eq!(spec.to_on_calendar(), "Mon,Tue,Wed,Thu,Fri *-*-* 09,18:00:00");
+//! assert_eq!(spec.launchd_intervals().unwrap().len(), 10);
+//! ```
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike};
+
+use super::scheduler::Frequency;
+
+/// launchd の `StartCalendarInterval` に展開できる組み合わせの上限
+pub const MAX_LAUNCHD_INTERVALS: usize = 512;
+
+/// 次回実行時刻を探索する最大日数（2月29日のみの指定でも見つかる範囲）
+const MAX_SEARCH_DAYS: u32 = 366 * 8;
+
+const WEEKDAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+const WEEKDAY_FULL_NAMES: [&str; 7] = [
+    "sunday",
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+];
+
+/// 実行時刻の指定（各フィールドの `None` はすべての値）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarSpec {
+    minutes: Option<Vec<u8>>,
+    hours: Option<Vec<u8>>,
+    days: Option<Vec<u8>>,
+    months: Option<Vec<u8>>,
+    weekdays: Option<Vec<u8>>,
+}
+
+impl From<Frequency> for CalendarSpec {
+    fn from(frequency: Frequency) -> Self {
+        let mut spec = Self {
+            minutes: Some(vec![0]),
+            hours: Some(vec![2]),
+            days: None,
+            months: None,
+            weekdays: None,
+        };
+        match frequency {
+            Frequency::Daily => {}
+            Frequency::Weekly => spec.weekdays = Some(vec![0]),
+            Frequency::Monthly => spec.days = Some(vec![1]),
+            Frequency::Hourly => spec.hours = None,
+        }
+        spec
+    }
+}
+
+impl CalendarSpec {
+    /// キーワード・cron式・`OnCalendar` 形式のいずれかを解析
+    ///
+    /// # Errors
+    ///
+    /// 書式が不正な場合、値が範囲外の場合、または cron式で日と曜日を同時に指定した場合に
+    /// エラーを返します。
+    pub fn parse(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Ok(frequency) = Frequency::parse(s) {
+            return Ok(frequency.into());
+        }
+        if s.contains(':') {
+            Self::parse_on_calendar(s)
+        } else {
+            Self::parse_cron(s)
+        }
+        .with_context(|| format!("不正なスケジュール: {s}"))
+    }
+
+    /// cron式（`分 時 日 月 曜日`）を解析
+    fn parse_cron(s: &str) -> Result<Self> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            bail!("cron式は「分 時 日 月 曜日」の5フィールドで指定してください");
+        };
+        let spec = Self {
+            minutes: parse_field(minute, 0, 59, "-", false)?,
+            hours: parse_field(hour, 0, 23, "-", false)?,
+            days: parse_field(day, 1, 31, "-", false)?,
+            months: parse_field(month, 1, 12, "-", false)?,
+            weekdays: parse_field(weekday, 0, 7, "-", true)?.map(normalize_weekdays),
+        };
+        if spec.days.is_some() && spec.weekdays.is_some() {
+            bail!("cron式では日と曜日を同時に指定できません（systemd・launchdでは両方に一致した場合のみ実行されるため）");
+        }
+        Ok(spec)
+    }
+
+    /// systemd の `OnCalendar` 形式（`[曜日] [*-月-日] 時:分[:秒]`）を解析
+    fn parse_on_calendar(s: &str) -> Result<Self> {
+        let mut tokens = s.split_whitespace().peekable();
+
+        let weekdays = match tokens.peek() {
+            Some(token) if token.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                let token = tokens.next().unwrap_or_default();
+                parse_field(token, 0, 7, "..", true)?.map(normalize_weekdays)
+            }
+            _ => None,
+        };
+
+        let (mut days, mut months) = (None, None);
+        if let Some(token) = tokens.next_if(|t| !t.contains(':')) {
+            let parts: Vec<&str> = token.split('-').collect();
+            let (month, day) = match parts[..] {
+                [year, month, day] => {
+                    if year != "*" {
+                        bail!("年は指定できません（* のみ）: {token}");
+                    }
+                    (month, day)
+                }
+                [month, day] => (month, day),
+                _ => bail!("日付は 年-月-日 の形式で指定してください: {token}"),
+            };
+            months = parse_field(month, 1, 12, "..", false)?;
+            days = parse_field(day, 1, 31, "..", false)?;
+        }
+
+        let time = tokens
+            .next()
+            .ok_or_else(|| anyhow!("時刻（時:分）を指定してください"))?;
+        if let Some(extra) = tokens.next() {
+            bail!("余分な指定があります: {extra}");
+        }
+        let parts: Vec<&str> = time.split(':').collect();
+        let (hour, minute) = match parts[..] {
+            [hour, minute] => (hour, minute),
+            [hour, minute, second] => {
+                if second.parse::<u8>().ok() != Some(0) {
+                    bail!("秒は 0 のみ指定できます（launchd は分単位のため）: {time}");
+                }
+                (hour, minute)
+            }
+            _ => bail!("時刻は 時:分[:秒] の形式で指定してください: {time}"),
+        };
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, "..", false)?,
+            hours: parse_field(hour, 0, 23, "..", false)?,
+            days,
+            months,
+            weekdays,
+        })
+    }
+
+    /// systemd timer の `OnCalendar` の値に変換
+    #[must_use]
+    pub fn to_on_calendar(&self) -> String {
+        let weekdays = self.weekdays.as_ref().map_or(String::new(), |days| {
+            let names: Vec<&str> = days.iter().map(|d| WEEKDAY_NAMES[*d as usize]).collect();
+            format!("{} ", names.join(","))
+        });
+        format!(
+            "{weekdays}*-{}-{} {}:{}:00",
+            format_field(self.months.as_ref()),
+            format_field(self.days.as_ref()),
+            format_field(self.hours.as_ref()),
+            format_field(self.minutes.as_ref())
+        )
+    }
+
+    /// `after` より後で最初に一致する時刻（分単位）
+    ///
+    /// 夏時間の切り替えで存在しない時刻はスキップします。一致する時刻がない場合
+    /// （例: 2月30日）は `None` を返します。
+    #[must_use]
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let local = after.naive_local();
+        let start = local.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut date = start.date();
+
+        for _ in 0..MAX_SEARCH_DAYS {
+            if self.matches_date(date) {
+                for hour in values_or_all(self.hours.as_ref(), 0, 23) {
+                    for minute in values_or_all(self.minutes.as_ref(), 0, 59) {
+                        let time = date.and_hms_opt(u32::from(hour), u32::from(minute), 0)?;
+                        if time < start {
+                            continue;
+                        }
+                        if let Some(next) = after.timezone().from_local_datetime(&time).earliest() {
+                            return Some(next);
+                        }
+                    }
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+
+    /// 日付（月・日・曜日）が一致するか
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        let contains = |values: Option<&Vec<u8>>, value: u32| {
+            values.is_none_or(|values| values.iter().any(|v| u32::from(*v) == value))
+        };
+        contains(self.months.as_ref(), date.month())
+            && contains(self.days.as_ref(), date.day())
+            && contains(
+                self.weekdays.as_ref(),
+                date.weekday().num_days_from_sunday(),
+            )
+    }
+
+    /// launchd の `StartCalendarInterval` のエントリ（キーと値の組）に展開
+    ///
+    /// # Errors
+    ///
+    /// 組み合わせが [`MAX_LAUNCHD_INTERVALS`] を超える場合にエラーを返します。
+    pub fn launchd_intervals(&self) -> Result<Vec<Vec<(&'static str, u8)>>> {
+        let fields = [
+            ("Hour", &self.hours),
+            ("Minute", &self.minutes),
+            ("Day", &self.days),
+            ("Weekday", &self.weekdays),
+            ("Month", &self.months),
+        ];
+        let count = fields
+            .iter()
+            .filter_map(|(_, values)| values.as_ref().map(Vec::len))
+            .product::<usize>();
+        if count > MAX_LAUNCHD_INTERVALS {
+            bail!(
+                "launchd で表現するには組み合わせが多すぎます（{count}件、上限{MAX_LAUNCHD_INTERVALS}件）"
+            );
+        }
+
+        let mut intervals = vec![Vec::new()];
+        for (key, values) in fields {
+            let Some(values) = values else { continue };
+            intervals = intervals
+                .into_iter()
+                .flat_map(|entry| {
+                    values.iter().map(move |value| {
+                        let mut entry = entry.clone();
+                        entry.push((key, *value));
+                        entry
+                    })
+                })
+                .collect();
+        }
+        Ok(intervals)
+    }
+}
+
+/// 1フィールドを解析（全範囲を含む場合は `None`）
+fn parse_field(
+    spec: &str,
+    min: u8,
+    max: u8,
+    range_separator: &str,
+    weekday: bool,
+) -> Result<Option<Vec<u8>>> {
+    let mut values = Vec::new();
+    for item in spec.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step: u8 = step
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| anyhow!("間隔は1以上の数値で指定してください: {item}"))?;
+                (range, Some(step))
+            }
+            None => (item, None),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once(range_separator) {
+            (
+                parse_value(start, min, max, weekday)?,
+                parse_value(end, min, max, weekday)?,
+            )
+        } else {
+            let start = parse_value(range, min, max, weekday)?;
+            // `a/n` は a から最大値まで n 間隔
+            (start, if step.is_some() { max } else { start })
+        };
+        if start > end {
+            bail!("範囲の開始が終了より大きくなっています: {item}");
+        }
+        values.extend((start..=end).step_by(usize::from(step.unwrap_or(1))));
+    }
+    values.sort_unstable();
+    values.dedup();
+
+    let full = if weekday {
+        // 曜日の 7 は日曜（0）と同じ
+        (0..=6).all(|d| values.contains(&d) || (d == 0 && values.contains(&7)))
+    } else {
+        values.len() == usize::from(max - min) + 1
+    };
+    Ok(if full { None } else { Some(values) })
+}
+
+/// 1つの値（数値、または曜日名）を解析
+fn parse_value(s: &str, min: u8, max: u8, weekday: bool) -> Result<u8> {
+    if weekday && s.len() >= 3 {
+        let lower = s.to_ascii_lowercase();
+        if let Some(index) = (0u8..)
+            .zip(WEEKDAY_FULL_NAMES)
+            .find_map(|(index, name)| name.starts_with(&lower).then_some(index))
+        {
+            return Ok(index);
+        }
+    }
+    let value: u8 = s
+        .parse()
+        .map_err(|_| anyhow!("数値として解釈できません: {s}"))?;
+    if !(min..=max).contains(&value) {
+        bail!("値は {min}-{max} の範囲で指定してください: {value}");
+    }
+    Ok(value)
+}
+
+/// フィールドの値（`None` の場合は範囲内のすべての値）
+fn values_or_all(values: Option<&Vec<u8>>, min: u8, max: u8) -> Vec<u8> {
+    values.cloned().unwrap_or_else(|| (min..=max).collect())
+}
+
+/// 曜日の 7（日曜）を 0 にまとめる
+fn normalize_weekdays(mut days: Vec<u8>) -> Vec<u8> {
+    for day in &mut days {
+        if *day == 7 {
+            *day = 0;
+        }
+    }
+    days.sort_unstable();
+    days.dedup();
+    days
+}
+
+/// `OnCalendar` 用にフィールドを書式化（2桁、`,` 区切り）
+fn format_field(values: Option<&Vec<u8>>) -> String {
+    values.map_or_else(
+        || "*".to_string(),
+        |values| {
+            values
+                .iter()
+                .map(|v| format!("{v:02}"))
+                .collect::<Vec<_>>()
+                .join(",")
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keywords_match_previous_timers() {
+        let cases = [
+            ("daily", "*-*-* 02:00:00"),
+            ("weekly", "Sun *-*-* 02:00:00"),
+            ("monthly", "*-*-01 02:00:00"),
+            ("hourly", "*-*-* *:00:00"),
+        ];
+        for (keyword, expected) in cases {
+            assert_eq!(
+                CalendarSpec::parse(keyword).unwrap().to_on_calendar(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_cron() {
+        let spec = CalendarSpec::parse("*/15 9-17 * * Mon-Fri").unwrap();
+        assert_eq!(
+            spec.to_on_calendar(),
+            "Mon,Tue,Wed,Thu,Fri *-*-* 09,10,11,12,13,14,15,16,17:00,15,30,45:00"
+        );
+        assert_eq!(spec.launchd_intervals().unwrap().len(), 4 * 9 * 5);
+
+        let sunday = CalendarSpec::parse("30 3 * * 7").unwrap();
+        assert_eq!(sunday.to_on_calendar(), "Sun *-*-* 03:30:00");
+        assert_eq!(
+            CalendarSpec::parse("0 0 * * *").unwrap(),
+            CalendarSpec::parse("0 0 * * 0-7").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_on_calendar() {
+        let spec = CalendarSpec::parse("Mon..Fri *-*-* 09,18:00").unwrap();
+        assert_eq!(spec, CalendarSpec::parse("0 9,18 * * 1-5").unwrap());
+
+        let monthly = CalendarSpec::parse("*-*-01,15 03:30:00").unwrap();
+        assert_eq!(monthly.to_on_calendar(), "*-*-01,15 03:30:00");
+        assert_eq!(
+            monthly.launchd_intervals().unwrap(),
+            vec![
+                vec![("Hour", 3), ("Minute", 30), ("Day", 1)],
+                vec![("Hour", 3), ("Minute", 30), ("Day", 15)],
+            ]
+        );
+
+        assert_eq!(
+            CalendarSpec::parse("Sat,Sun 10:00")
+                .unwrap()
+                .to_on_calendar(),
+            "Sun,Sat *-*-* 10:00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_schedules() {
+        for invalid in [
+            "sometimes",
+            "0 9 * *",
+            "60 * * * *",
+            "0 24 * * *",
+            "0 9 1 * 1",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "2025-*-* 09:00",
+            "*-*-* 09:00:30",
+            "Mon..Fri",
+        ] {
+            assert!(CalendarSpec::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_next_after() {
+        use chrono::Utc;
+
+        let at = |s: &str| {
+            chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+                .unwrap()
+                .and_utc()
+        };
+        let weekdays = CalendarSpec::parse("0 9,18 * * mon-fri").unwrap();
+        // 2025-01-03 は金曜日
+        assert_eq!(
+            weekdays.next_after(&at("2025-01-03 09:00:00")),
+            Some(at("2025-01-03 18:00:00"))
+        );
+        assert_eq!(
+            weekdays.next_after(&at("2025-01-03 18:30:15")),
+            Some(at("2025-01-06 09:00:00"))
+        );
+
+        let daily = CalendarSpec::from(Frequency::Daily);
+        assert_eq!(
+            daily.next_after(&at("2025-01-31 01:59:59")),
+            Some(at("2025-01-31 02:00:00"))
+        );
+        let monthly = CalendarSpec::from(Frequency::Monthly);
+        assert_eq!(
+            monthly.next_after(&at("2025-01-31 03:00:00")),
+            Some(at("2025-02-01 02:00:00"))
+        );
+
+        let leap_day = CalendarSpec::parse("0 0 29 2 *").unwrap();
+        assert_eq!(
+            leap_day.next_after(&at("2025-03-01 00:00:00")),
+            Some(at("2028-02-29 00:00:00"))
+        );
+        assert_eq!(
+            CalendarSpec::parse("0 0 30 2 *")
+                .unwrap()
+                .next_after(&Utc::now()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_launchd_interval_limit() {
+        let spec = CalendarSpec::parse("* * * * *").unwrap();
+        assert_eq!(spec.launchd_intervals().unwrap(), vec![Vec::new()]);
+        assert!(CalendarSpec::parse("0-58 0-22 * * *")
+            .unwrap()
+            .launchd_intervals()
+            .is_err());
+    }
+}
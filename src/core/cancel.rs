@@ -0,0 +1,142 @@
+//! # キャンセルと一時停止
+//!
+//! 実行中のバックアップ・復元・クリーンアップを外部から安全に停止・一時停止するためのトークンを提供します。
+//!
+//! 各エンジンはファイルを処理する前にトークンを確認し、キャンセルされていれば新しいファイルの処理を開始しません。
+//! 処理中のファイルは書き込みを完了させ、処理済みのファイルだけを記録したマニフェストで結果を確定します。
+//! 整合性検証はバックアップ・復元の各ファイルの処理に含まれるため、同じトークンで停止します。
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! use backup_suite::core::CancellationToken;
+//! use backup_suite::{BackupRunner, Config};
+//!
+//! let token = CancellationToken::new();
+//! let handle = token.clone();
+//! std::thread::spawn(move || {
+//!     std::thread::sleep(std::time::Duration::from_secs(60));
+//!     handle.cancel();
+//! });
+//!
+//! let config = Config::load().unwrap();
+//! let mut runner = BackupRunner::new(config, false).with_cancellation(token);
+//! let result = runner.run(None, None).unwrap();
+//! if result.cancelled {
+//!     println!("キャンセル: {}/{} ファイルを保存", result.successful, result.total_files);
+//! }
+//! ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// キャンセル・一時停止のトークン
+///
+/// 複製したトークンは同じ状態を共有するため、シグナルハンドラや別スレッドから操作できます。
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<TokenState>,
+}
+
+#[derive(Debug, Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    paused: Mutex<bool>,
+    resumed: Condvar,
+}
+
+impl CancellationToken {
+    /// キャンセルも一時停止もされていないトークンを作成
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// キャンセルを要求（一時停止中の処理も再開させて停止させる）
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::SeqCst);
+        let _guard = self.state.paused.lock();
+        self.state.resumed.notify_all();
+    }
+
+    /// キャンセルが要求されたか
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// 一時停止を要求（処理中のファイルは完了させ、次のファイルの前で待機）
+    pub fn pause(&self) {
+        if let Ok(mut paused) = self.state.paused.lock() {
+            *paused = true;
+        }
+    }
+
+    /// 一時停止を解除
+    pub fn resume(&self) {
+        if let Ok(mut paused) = self.state.paused.lock() {
+            *paused = false;
+            self.state.resumed.notify_all();
+        }
+    }
+
+    /// 一時停止中か
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.state.paused.lock().is_ok_and(|paused| *paused)
+    }
+
+    /// 次のファイルの処理前に呼び出し、停止すべきかを返す
+    ///
+    /// 一時停止中は再開またはキャンセルされるまで待機します。
+    #[must_use]
+    pub fn should_stop(&self) -> bool {
+        if let Ok(mut paused) = self.state.paused.lock() {
+            while *paused && !self.is_cancelled() {
+                match self.state.resumed.wait(paused) {
+                    Ok(guard) => paused = guard,
+                    Err(_) => break,
+                }
+            }
+        }
+        self.is_cancelled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_cancel_is_shared_between_clones() {
+        let token = CancellationToken::new();
+        let handle = token.clone();
+        assert!(!token.should_stop());
+
+        handle.cancel();
+        assert!(token.is_cancelled());
+        assert!(token.should_stop());
+    }
+
+    #[test]
+    fn test_pause_blocks_until_resumed_or_cancelled() {
+        let token = CancellationToken::new();
+        token.pause();
+        assert!(token.is_paused());
+
+        let handle = token.clone();
+        let waiter = std::thread::spawn(move || handle.should_stop());
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!waiter.is_finished());
+        token.resume();
+        assert!(!waiter.join().unwrap());
+
+        token.pause();
+        let handle = token.clone();
+        let waiter = std::thread::spawn(move || handle.should_stop());
+        std::thread::sleep(Duration::from_millis(50));
+        token.cancel();
+        assert!(waiter.join().unwrap());
+    }
+}
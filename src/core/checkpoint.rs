@@ -0,0 +1,329 @@
+//! # チェックポイントモジュール
+//!
+//! 中断されたバックアップを再開するため、ステージングディレクトリ内に
+//! 書き込み済みファイルの記録（`.checkpoint`）を保持します。
+//!
+//! # 形式
+//!
+//! JSON Lines 形式で、1行目に実行設定のヘッダー、2行目以降に書き込み済みファイルを
+//! 1件ずつ追記します。ファイル本体の fsync 後に記録するため、記録済みのファイルは
+//! ディスク上に存在することが保証されます。クラッシュで途中まで書かれた最終行は
+//! 読み込み時に無視され、再開時の追記前に切り詰められます。
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! use backup_suite::core::checkpoint::{Checkpoint, CheckpointHeader};
+//! use backup_suite::core::integrity::HashAlgorithm;
+//! use std::path::Path;
+//!
+//! let staging = Path::new("/backups/.staging_backup_20250107_120000");
+//! let header = CheckpointHeader {
+//!     compression: "zstd".to_string(),
+//!     target_compression: Default::default(),
+//!     encrypted: false,
+//!     snapshot: false,
+//!     hash_algorithm: HashAlgorithm::Sha256,
+//! };
+//! let checkpoint = Checkpoint::create(staging, &header).unwrap();
+//!
+//! // 再開時
+//! if let Some(state) = Checkpoint::load(staging) {
+//!     println!("書き込み済み: {}件", state.entries.len());
+//! }
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::copy_engine::SparseMap;
+use super::integrity::HashAlgorithm;
+
+/// チェックポイントファイル名
+pub const CHECKPOINT_FILE: &str = ".checkpoint";
+
+/// チェックポイントのヘッダー（中断時の実行設定）
+///
+/// 再開時に設定が一致しない場合、書き込み済みファイルの形式が異なるため再開できません。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointHeader {
+    /// 圧縮アルゴリズム名
+    pub compression: String,
+    /// 実行時の設定と異なる圧縮設定の対象（対象パス → `圧縮形式:レベル`）
+    #[serde(default)]
+    pub target_compression: BTreeMap<String, String>,
+    /// 暗号化の有無
+    pub encrypted: bool,
+    /// スナップショットモードの有無
+    pub snapshot: bool,
+    /// 記録するハッシュのアルゴリズム
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+}
+
+/// 書き込み済みファイルの記録
+///
+/// # フィールド
+///
+/// * `path` - バックアップ内の相対パス
+/// * `source_len` / `source_mtime_ns` - 書き込み時点のソースのサイズと更新日時
+/// * `source_hash` - ソースのハッシュ（整合性マニフェスト用）
+/// * `written_len` / `written_hash` - 書き込んだファイルのサイズとハッシュ
+///   （整合性検証が無効な無圧縮コピーではハッシュを計算しないため `None`）
+/// * `sparse` - データ領域のみを保存したスパースファイルの配置
+///
+/// ハッシュはヘッダーの `hash_algorithm` で計算されます。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointEntry {
+    pub path: PathBuf,
+    pub source_len: u64,
+    pub source_mtime_ns: u64,
+    #[serde(default)]
+    pub source_hash: Option<String>,
+    pub written_len: u64,
+    #[serde(default)]
+    pub written_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sparse: Option<SparseMap>,
+}
+
+impl CheckpointEntry {
+    /// 書き込み済みファイルがまだ有効かを検証
+    ///
+    /// ソースのサイズと更新日時が記録時と同じで、書き込み先のサイズとハッシュ
+    /// （`algorithm` で計算）が記録と一致する場合のみ `true` を返します。
+    /// ハッシュを記録していない場合はサイズのみを比較します。
+    #[must_use]
+    pub fn is_valid(&self, source: &Path, dest: &Path, algorithm: HashAlgorithm) -> bool {
+        let Ok(source_meta) = fs::metadata(source) else {
+            return false;
+        };
+        if source_meta.len() != self.source_len || mtime_ns(&source_meta) != self.source_mtime_ns {
+            return false;
+        }
+
+        let dest_len = fs::symlink_metadata(dest)
+            .ok()
+            .filter(fs::Metadata::is_file)
+            .map(|m| m.len());
+        dest_len == Some(self.written_len)
+            && self.written_hash.as_ref().is_none_or(|written_hash| {
+                algorithm
+                    .hash_file(dest)
+                    .is_ok_and(|hash| &hash == written_hash)
+            })
+    }
+}
+
+/// 再開用に読み込んだチェックポイントの内容
+#[derive(Debug)]
+pub struct CheckpointState {
+    pub header: CheckpointHeader,
+    pub entries: HashMap<PathBuf, CheckpointEntry>,
+}
+
+/// チェックポイントの書き込みハンドル
+///
+/// 並列処理中の複数スレッドから [`Checkpoint::record`] を呼び出せます。
+pub struct Checkpoint {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl Checkpoint {
+    /// 新しいチェックポイントを作成し、ヘッダーを書き込む
+    ///
+    /// # Errors
+    ///
+    /// ファイルの作成または書き込みに失敗した場合にエラーを返します。
+    pub fn create(staging_dir: &Path, header: &CheckpointHeader) -> Result<Self> {
+        let path = staging_dir.join(CHECKPOINT_FILE);
+        let mut file = File::create(&path).context("チェックポイント作成失敗")?;
+        writeln!(file, "{}", serde_json::to_string(header)?)
+            .context("チェックポイント書き込み失敗")?;
+        file.sync_all().context("チェックポイント同期失敗")?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// 既存のチェックポイントに追記するために開く
+    ///
+    /// クラッシュで途中まで書かれた最終行は、次の記録と連結されないよう切り詰めます。
+    ///
+    /// # Errors
+    ///
+    /// ファイルのオープンまたは切り詰めに失敗した場合にエラーを返します。
+    pub fn append(staging_dir: &Path) -> Result<Self> {
+        let path = staging_dir.join(CHECKPOINT_FILE);
+        let content = fs::read(&path).context("チェックポイントの読み込み失敗")?;
+        let complete_len = content
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |pos| pos + 1);
+        let file = OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .context("チェックポイントのオープン失敗")?;
+        if complete_len < content.len() {
+            file.set_len(complete_len as u64)
+                .context("チェックポイントの切り詰め失敗")?;
+        }
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// ステージングディレクトリからチェックポイントを読み込む
+    ///
+    /// チェックポイントが存在しない、またはヘッダーが読めない場合は `None` を返します。
+    #[must_use]
+    pub fn load(staging_dir: &Path) -> Option<CheckpointState> {
+        let content = fs::read_to_string(staging_dir.join(CHECKPOINT_FILE)).ok()?;
+        let mut lines = content.lines();
+        let header: CheckpointHeader = serde_json::from_str(lines.next()?).ok()?;
+        let entries = lines
+            .filter_map(|line| serde_json::from_str::<CheckpointEntry>(line).ok())
+            .map(|entry| (entry.path.clone(), entry))
+            .collect();
+        Some(CheckpointState { header, entries })
+    }
+
+    /// 書き込み済みファイルを記録
+    ///
+    /// 記録に失敗しても再開時に再コピーされるだけのため、呼び出し側では警告にとどめてください。
+    ///
+    /// # Errors
+    ///
+    /// シリアライズまたは書き込みに失敗した場合にエラーを返します。
+    pub fn record(&self, entry: &CheckpointEntry) -> Result<()> {
+        let line = serde_json::to_string(entry)?;
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|_| anyhow::anyhow!("チェックポイントのロック取得失敗"))?;
+        writeln!(file, "{line}").context("チェックポイント書き込み失敗")
+    }
+
+    /// バックアップ完了時にチェックポイントを削除
+    ///
+    /// # Errors
+    ///
+    /// ファイルの削除に失敗した場合にエラーを返します。
+    pub fn finish(self) -> Result<()> {
+        drop(self.file);
+        fs::remove_file(&self.path).context("チェックポイント削除失敗")
+    }
+}
+
+/// ソースファイルの記録用メタデータ（サイズ、更新日時ナノ秒）を取得
+#[must_use]
+pub fn source_stamp(source: &Path) -> Option<(u64, u64)> {
+    fs::metadata(source).ok().map(|meta| stamp_of(&meta))
+}
+
+/// メタデータのサイズと更新日時（ナノ秒）
+#[must_use]
+pub fn stamp_of(metadata: &fs::Metadata) -> (u64, u64) {
+    (metadata.len(), mtime_ns(metadata))
+}
+
+fn mtime_ns(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn header() -> CheckpointHeader {
+        CheckpointHeader {
+            compression: "none".to_string(),
+            target_compression: BTreeMap::new(),
+            encrypted: false,
+            snapshot: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip_and_validation() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source.txt");
+        let dest = temp.path().join("dest.txt");
+        fs::write(&source, b"payload").unwrap();
+        fs::copy(&source, &dest).unwrap();
+
+        let (source_len, source_mtime_ns) = source_stamp(&source).unwrap();
+        let hash = HashAlgorithm::Sha256.hash_bytes(b"payload");
+        let checkpoint = Checkpoint::create(temp.path(), &header()).unwrap();
+        checkpoint
+            .record(&CheckpointEntry {
+                path: PathBuf::from("dest.txt"),
+                source_len,
+                source_mtime_ns,
+                source_hash: Some(hash.clone()),
+                written_len: 7,
+                written_hash: Some(hash),
+                sparse: None,
+            })
+            .unwrap();
+        drop(checkpoint);
+
+        // クラッシュで途中まで書かれた行は無視される
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(temp.path().join(CHECKPOINT_FILE))
+            .unwrap();
+        write!(file, "{{\"path\":\"torn").unwrap();
+        drop(file);
+
+        let state = Checkpoint::load(temp.path()).unwrap();
+        assert_eq!(state.header, header());
+        assert_eq!(state.entries.len(), 1);
+
+        // 再開後の追記は途中まで書かれた行と連結されない
+        let resumed = Checkpoint::append(temp.path()).unwrap();
+        resumed
+            .record(&CheckpointEntry {
+                path: PathBuf::from("next.txt"),
+                source_len: 0,
+                source_mtime_ns: 0,
+                source_hash: None,
+                written_len: 0,
+                written_hash: None,
+                sparse: None,
+            })
+            .unwrap();
+        drop(resumed);
+        let state = Checkpoint::load(temp.path()).unwrap();
+        assert_eq!(state.entries.len(), 2);
+        assert!(state.entries.contains_key(Path::new("next.txt")));
+        let entry = &state.entries[Path::new("dest.txt")];
+        assert!(entry.is_valid(&source, &dest, HashAlgorithm::Sha256));
+
+        // 書き込み先が壊れていれば無効
+        fs::write(&dest, b"paylo").unwrap();
+        assert!(!entry.is_valid(&source, &dest, HashAlgorithm::Sha256));
+    }
+
+    #[test]
+    fn test_finish_removes_checkpoint() {
+        let temp = TempDir::new().unwrap();
+        let checkpoint = Checkpoint::create(temp.path(), &header()).unwrap();
+        checkpoint.finish().unwrap();
+        assert!(Checkpoint::load(temp.path()).is_none());
+    }
+}
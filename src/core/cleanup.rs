@@ -1,10 +1,21 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use walkdir::WalkDir;
 
-use super::{BackupHistory, Config, Priority};
+use super::cancel::CancellationToken;
+use super::events::{Event, EventSink, Operation, Phase};
+use super::integrity::BackupMetadata;
+use super::lock::{LockKind, RepositoryLock, LOCK_DIR};
+use super::report::ErrorKind;
+use super::{staging, BackupHistory, Config, Priority};
+use crate::i18n::Language;
 use crate::security::{AuditEvent, AuditLog};
+use crate::ui::ConsoleSink;
 
 /// クリーンアップポリシー
 ///
@@ -19,6 +30,8 @@ pub struct CleanupPolicy {
     pub max_total_size: Option<u64>,
     /// 優先度別保持（高優先度は長く保持）
     pub priority_based: bool,
+    /// 対象とするジョブ名（指定した場合はそのジョブが作成したバックアップのみ削除）
+    pub job: Option<String>,
 }
 
 impl Default for CleanupPolicy {
@@ -28,6 +41,7 @@ impl Default for CleanupPolicy {
             keep_count: None,
             max_total_size: None,
             priority_based: false,
+            job: None,
         }
     }
 }
@@ -68,15 +82,29 @@ impl CleanupPolicy {
         self.priority_based = true;
         self
     }
+
+    /// 削除対象を指定したジョブが作成したバックアップに限定
+    ///
+    /// 同じ保存先を共有する他のジョブや手動実行のバックアップ、
+    /// 未完了のステージングディレクトリは削除しません。
+    #[must_use]
+    pub fn with_job(mut self, name: impl Into<String>) -> Self {
+        self.job = Some(name.into());
+        self
+    }
 }
 
 /// クリーンアップ結果
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct CleanupResult {
     pub total_checked: usize,
     pub deleted: usize,
     pub freed_bytes: u64,
+    /// 削除した未完了バックアップ（ステージングディレクトリ）の数
+    pub stale_removed: usize,
     pub errors: Vec<String>,
+    /// キャンセルされたか（残りのバックアップは削除していない）
+    pub cancelled: bool,
 }
 
 impl CleanupResult {
@@ -85,7 +113,9 @@ impl CleanupResult {
             total_checked: 0,
             deleted: 0,
             freed_bytes: 0,
+            stale_removed: 0,
             errors: Vec::new(),
+            cancelled: false,
         }
     }
 }
@@ -97,6 +127,69 @@ struct BackupInfo {
     modified_time: DateTime<Utc>,
     size: u64,
     priority: Option<Priority>,
+    /// キャンセルされた部分的なバックアップか
+    partial: bool,
+}
+
+/// ハードリンク共有を考慮した解放容量トラッカー
+///
+/// スナップショット間でハードリンクを共有するinodeは、最後のリンクが削除されたときに
+/// 初めて容量が解放されます。削除対象をまたいで残りリンク数を追跡し、
+/// ドライランでも同じ計算で解放予定バイト数を算出します。
+#[derive(Debug, Default)]
+struct InodeTracker {
+    /// (デバイス, inode) → (残りリンク数, ファイルサイズ)
+    remaining: HashMap<(u64, u64), (u64, u64)>,
+}
+
+impl InodeTracker {
+    /// ディレクトリ内の全ファイルのリンクを解放し、解放されるバイト数を返す
+    fn release_dir(&mut self, dir: &Path) -> u64 {
+        WalkDir::new(dir)
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| entry.metadata().ok())
+            .map(|metadata| self.release(&metadata))
+            .sum()
+    }
+
+    #[cfg(unix)]
+    fn release(&mut self, metadata: &std::fs::Metadata) -> u64 {
+        use std::os::unix::fs::MetadataExt;
+
+        if metadata.nlink() <= 1 {
+            return metadata.len();
+        }
+        let (links, size) = self
+            .remaining
+            .entry((metadata.dev(), metadata.ino()))
+            .or_insert((metadata.nlink(), metadata.len()));
+        *links = links.saturating_sub(1);
+        if *links == 0 {
+            *size
+        } else {
+            0
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn release(&mut self, metadata: &std::fs::Metadata) -> u64 {
+        metadata.len()
+    }
+}
+
+/// 同一inodeの2つ目以降のリンクでなければtrue（ディレクトリサイズの重複計上防止）
+#[cfg(unix)]
+fn is_first_link(seen: &mut HashSet<(u64, u64)>, metadata: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    metadata.nlink() <= 1 || seen.insert((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn is_first_link(_seen: &mut HashSet<(u64, u64)>, _metadata: &std::fs::Metadata) -> bool {
+    true
 }
 
 /// クリーンアップエンジン
@@ -106,25 +199,80 @@ pub struct CleanupEngine {
     policy: CleanupPolicy,
     dry_run: bool,
     interactive: bool,
+    quiet: bool,
+    event_sink: Option<Arc<dyn EventSink>>,
+    cancel: CancellationToken,
     audit_log: Option<AuditLog>,
+    /// 作成時の監査ログ初期化エラー（最初の `events()` 呼び出しで警告として送る）
+    audit_log_warning: Mutex<Option<String>>,
 }
 
 impl CleanupEngine {
     /// 新しいCleanupEngineを作成
     #[must_use]
     pub fn new(policy: CleanupPolicy, dry_run: bool) -> Self {
-        let audit_log = AuditLog::new()
-            .map_err(|e| eprintln!("警告: 監査ログの初期化に失敗しました: {e}"))
-            .ok();
+        let (audit_log, audit_log_warning) = match AuditLog::new() {
+            Ok(log) => (Some(log), None),
+            Err(e) => (
+                None,
+                Some(format!("警告: 監査ログの初期化に失敗しました: {e}")),
+            ),
+        };
 
         Self {
             policy,
             dry_run,
             interactive: false,
+            quiet: false,
+            event_sink: None,
+            cancel: CancellationToken::new(),
             audit_log,
+            audit_log_warning: Mutex::new(audit_log_warning),
         }
     }
 
+    /// 標準出力への情報表示（削除したバックアップの一覧など）を抑制
+    #[must_use]
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// 進行状況のイベントの送り先を設定
+    ///
+    /// 設定した場合はコンソールに表示しません（`with_quiet` は無視されます）。
+    /// 対話的確認（`with_interactive`）のプロンプトは引き続き端末に表示されます。
+    #[must_use]
+    pub fn with_event_sink(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(sink);
+        self
+    }
+
+    /// イベントの送り先（未設定の場合はコンソールに表示）
+    fn events(&self) -> Arc<dyn EventSink> {
+        let sink: Arc<dyn EventSink> = self.event_sink.clone().unwrap_or_else(|| {
+            Arc::new(ConsoleSink::new(Language::detect()).with_quiet(self.quiet))
+        });
+        if let Some(warning) = self
+            .audit_log_warning
+            .lock()
+            .ok()
+            .and_then(|mut w| w.take())
+        {
+            sink.warning(warning);
+        }
+        sink
+    }
+
+    /// キャンセル・一時停止のトークンを設定
+    ///
+    /// キャンセルされると次のバックアップから削除を停止します（削除中のディレクトリは削除を完了）。
+    #[must_use]
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
     /// 対話的削除を有効化
     #[must_use]
     pub fn with_interactive(mut self, interactive: bool) -> Self {
@@ -143,6 +291,8 @@ impl CleanupEngine {
     /// * 削除対象の決定に失敗した場合
     /// * 対話的確認の入力処理に失敗した場合
     pub fn cleanup(&mut self) -> Result<CleanupResult> {
+        let events = self.events();
+        let events = &*events;
         let user = AuditLog::current_user();
         let days = self.policy.retention_days.unwrap_or(0);
 
@@ -150,7 +300,7 @@ impl CleanupEngine {
         if let Some(ref mut audit_log) = self.audit_log {
             let _ = audit_log
                 .log(AuditEvent::cleanup_started(&user, days))
-                .map_err(|e| eprintln!("警告: 監査ログの記録に失敗しました: {e}"));
+                .map_err(|e| events.warning(format!("警告: 監査ログの記録に失敗しました: {e}")));
         }
 
         let config = Config::load()?;
@@ -160,41 +310,123 @@ impl CleanupEngine {
             return Ok(CleanupResult::new());
         }
 
+        // 実行中のバックアップ・復元が参照しているバックアップを削除しないよう排他ロック
+        let repo_lock = RepositoryLock::acquire(dest, LockKind::Exclusive, "cleanup")?;
+        for stale in repo_lock.stale_removed() {
+            events.warning(format!("警告: 古いロックを削除しました: {stale}"));
+        }
+
         // バックアップディレクトリ一覧を取得
-        let mut backups = self.get_backup_list(dest)?;
+        events.phase(Operation::Cleanup, Phase::Scanning);
+        let history = BackupHistory::load_all().unwrap_or_default();
+        let mut backups = self.get_backup_list(dest, &history)?;
 
         // ソート（新しい順）
-        backups.sort_by(|a, b| b.modified_time.cmp(&a.modified_time));
+        backups.sort_by_key(|b| std::cmp::Reverse(b.modified_time));
 
         let mut result = CleanupResult::new();
         result.total_checked = backups.len();
+        events.phase(
+            Operation::Cleanup,
+            Phase::Planning {
+                found_files: backups.len(),
+            },
+        );
+
+        // 中断されたバックアップのステージングディレクトリを削除
+        // （保持期間内のチェックポイントを持つものは `run --resume` 用に残す）
+        let resume_window = self
+            .policy
+            .retention_days
+            .map(|days| Duration::from_secs(u64::from(days) * 24 * 60 * 60));
+        let mut inodes = InodeTracker::default();
+        let stale_dirs = if self.policy.job.is_some() {
+            Vec::new()
+        } else {
+            staging::find_stale_staging(dest)
+        };
+        for stale in stale_dirs {
+            if self.cancel.should_stop() {
+                result.cancelled = true;
+                break;
+            }
+            if staging::is_resumable(&stale, resume_window) {
+                events.info(format!("⏸️  再開可能な未完了バックアップを保持: {stale:?}"));
+                continue;
+            }
+            let freed = inodes.release_dir(&stale);
+            if self.dry_run {
+                events.info(format!(
+                    "🗑️  [ドライラン] 未完了バックアップ削除予定: {stale:?}"
+                ));
+                result.stale_removed += 1;
+                result.freed_bytes += freed;
+            } else {
+                match std::fs::remove_dir_all(&stale) {
+                    Ok(()) => {
+                        events.info(format!("🗑️  未完了バックアップ削除完了: {stale:?}"));
+                        result.stale_removed += 1;
+                        result.freed_bytes += freed;
+                    }
+                    Err(e) => {
+                        result.errors.push(format!("削除失敗 {stale:?}: {e}"));
+                    }
+                }
+            }
+        }
 
         // 削除対象を決定
         let to_delete = self.determine_deletions(&backups)?;
+        events.phase(
+            Operation::Cleanup,
+            Phase::Processing {
+                total_files: to_delete.len(),
+            },
+        );
 
         for backup in to_delete {
+            // キャンセル後は次のバックアップを削除しない（一時停止中は再開まで待機）
+            if result.cancelled || self.cancel.should_stop() {
+                result.cancelled = true;
+                break;
+            }
             if self.interactive {
                 // 対話的確認
-                if !self.confirm_deletion(&backup)? {
+                if !self.confirm_deletion(events, &backup)? {
                     continue;
                 }
             }
 
+            // 他のスナップショットと共有するinodeは最後のリンク削除時のみ計上
+            let freed = inodes.release_dir(&backup.path);
+
             if self.dry_run {
-                println!("🗑️  [ドライラン] 削除予定: {:?}", backup.path);
+                events.info(format!("🗑️  [ドライラン] 削除予定: {:?}", backup.path));
                 result.deleted += 1;
-                result.freed_bytes += backup.size;
+                result.freed_bytes += freed;
             } else {
+                events.on_event(&Event::FileStarted {
+                    path: backup.path.clone(),
+                });
                 match std::fs::remove_dir_all(&backup.path) {
                     Ok(_) => {
-                        println!("🗑️  削除完了: {:?}", backup.path);
+                        events.info(format!("🗑️  削除完了: {:?}", backup.path));
+                        events.on_event(&Event::FileFinished {
+                            path: backup.path.clone(),
+                            bytes_read: freed,
+                            bytes_written: 0,
+                        });
                         result.deleted += 1;
-                        result.freed_bytes += backup.size;
+                        result.freed_bytes += freed;
                     }
                     Err(e) => {
-                        result
-                            .errors
-                            .push(format!("削除失敗 {:?}: {}", backup.path, e));
+                        let message = format!("削除失敗 {:?}: {}", backup.path, e);
+                        events.on_event(&Event::FileFailed {
+                            path: backup.path.clone(),
+                            kind: ErrorKind::from_io(&e),
+                            message: message.clone(),
+                        });
+                        result.errors.push(message);
                     }
                 }
             }
@@ -206,10 +438,13 @@ impl CleanupEngine {
                 "total_checked": result.total_checked,
                 "deleted": result.deleted,
                 "freed_bytes": result.freed_bytes,
+                "stale_removed": result.stale_removed,
                 "policy": format!("{:?}", self.policy),
             });
 
-            let event = if result.errors.is_empty() {
+            let event = if result.cancelled {
+                AuditEvent::cleanup_cancelled(&user, metadata)
+            } else if result.errors.is_empty() {
                 AuditEvent::cleanup_completed(&user, metadata)
             } else {
                 AuditEvent::cleanup_failed(
@@ -220,14 +455,25 @@ impl CleanupEngine {
 
             let _ = audit_log
                 .log(event)
-                .map_err(|e| eprintln!("警告: 監査ログの記録に失敗しました: {e}"));
+                .map_err(|e| events.warning(format!("警告: 監査ログの記録に失敗しました: {e}")));
         }
 
+        if result.cancelled {
+            events.phase(Operation::Cleanup, Phase::Cancelled);
+        }
+        events.phase(
+            Operation::Cleanup,
+            Phase::Finished {
+                failed: result.errors.len(),
+            },
+        );
         Ok(result)
     }
 
     /// バックアップ一覧を取得
-    fn get_backup_list(&self, dest: &Path) -> Result<Vec<BackupInfo>> {
+    ///
+    /// 優先度とジョブ名は `history` から取得します。
+    fn get_backup_list(&self, dest: &Path, history: &[BackupHistory]) -> Result<Vec<BackupInfo>> {
         let mut backups = Vec::new();
 
         for entry in WalkDir::new(dest)
@@ -235,58 +481,71 @@ impl CleanupEngine {
             .into_iter()
             .filter_map(std::result::Result::ok)
         {
-            if !entry.file_type().is_dir() || entry.path() == dest {
+            // ステージングディレクトリは未完了のためバックアップとして扱わない
+            if !entry.file_type().is_dir()
+                || entry.path() == dest
+                || staging::is_staging_dir(entry.path())
+                || entry.file_name() == LOCK_DIR
+            {
                 continue;
             }
 
             let path = entry.path().to_path_buf();
+            let record = history.iter().find(|h| h.backup_dir == path);
+
+            // ジョブ指定時は、そのジョブの履歴があるバックアップのみ対象
+            if let Some(job) = &self.policy.job {
+                if record.and_then(|h| h.job.as_ref()) != Some(job) {
+                    continue;
+                }
+            }
+
             let metadata = std::fs::metadata(&path)?;
             let modified_time: DateTime<Utc> = metadata.modified()?.into();
             let size = self.calculate_size(&path)?;
 
             // 優先度を履歴から取得（可能な場合）
-            let priority = self.get_priority_from_history(&path);
+            let priority = record.and_then(|h| h.priority);
 
+            let partial = BackupMetadata::is_partial(&path);
             backups.push(BackupInfo {
                 path,
                 modified_time,
                 size,
                 priority,
+                partial,
             });
         }
 
         Ok(backups)
     }
 
-    /// ディレクトリサイズを計算
+    /// ディレクトリサイズを計算（ディレクトリ内のハードリンクは1回だけ計上）
     fn calculate_size(&self, dir: &Path) -> Result<u64> {
         let mut total = 0;
+        let mut seen = HashSet::new();
         for entry in WalkDir::new(dir)
             .into_iter()
             .filter_map(std::result::Result::ok)
         {
             if entry.file_type().is_file() {
-                total += entry.metadata()?.len();
+                let metadata = entry.metadata()?;
+                if is_first_link(&mut seen, &metadata) {
+                    total += metadata.len();
+                }
             }
         }
         Ok(total)
     }
 
-    /// 履歴から優先度を取得
-    fn get_priority_from_history(&self, backup_dir: &Path) -> Option<Priority> {
-        if let Ok(history) = BackupHistory::load_all() {
-            history
-                .iter()
-                .find(|h| h.backup_dir == backup_dir)
-                .and_then(|h| h.priority)
-        } else {
-            None
-        }
-    }
-
-    /// 削除対象を決定
+    /// 削除対象を決定（`backups` は新しい順）
+    ///
+    /// キャンセルされた部分的なバックアップは完全な復元ポイントではないため、保持数に数えず、
+    /// より新しい完全なバックアップがあれば削除します。より新しいバックアップが部分的なものだけの場合、
+    /// 最新の完全なバックアップは保持期間・保持数では削除しません。
     fn determine_deletions(&self, backups: &[BackupInfo]) -> Result<Vec<BackupInfo>> {
         let mut to_delete = Vec::new();
+        let latest_complete = backups.iter().position(|backup| !backup.partial);
 
         // 1. 保持期間による削除
         if let Some(days) = self.policy.retention_days {
@@ -312,13 +571,27 @@ impl CleanupEngine {
             }
         }
 
-        // 2. 保持数による削除
+        // 2. 保持数による削除（完全なバックアップのみを数える）
         if let Some(keep) = self.policy.keep_count {
-            if backups.len() > keep {
-                to_delete.extend_from_slice(&backups[keep..]);
+            let mut complete = 0;
+            for (index, backup) in backups.iter().enumerate() {
+                let superseded = if backup.partial {
+                    latest_complete.is_some_and(|latest| latest < index)
+                } else {
+                    complete += 1;
+                    complete > keep
+                };
+                if superseded {
+                    to_delete.push(backup.clone());
+                }
             }
         }
 
+        // 部分的なバックアップしか残らないよう削除しない
+        if let Some(latest) = latest_complete.filter(|&index| index > 0) {
+            to_delete.retain(|backup| backup.path != backups[latest].path);
+        }
+
         // 3. 最大サイズによる削除
         if let Some(max_size) = self.policy.max_total_size {
             let mut current_size = 0u64;
@@ -338,18 +611,20 @@ impl CleanupEngine {
     }
 
     /// 削除確認（対話的）
-    fn confirm_deletion(&self, backup: &BackupInfo) -> Result<bool> {
+    ///
+    /// 削除候補の情報はイベントとして送り、確認のプロンプトのみ端末に表示します。
+    fn confirm_deletion(&self, events: &dyn EventSink, backup: &BackupInfo) -> Result<bool> {
         use dialoguer::Confirm;
 
-        println!("\n削除候補:");
-        println!("  パス: {:?}", backup.path);
-        println!(
+        events.info("削除候補:");
+        events.info(format!("  パス: {:?}", backup.path));
+        events.info(format!(
             "  作成日時: {}",
             backup.modified_time.format("%Y-%m-%d %H:%M:%S")
-        );
-        println!("  サイズ: {}", format_bytes(backup.size));
+        ));
+        events.info(format!("  サイズ: {}", format_bytes(backup.size)));
         if let Some(ref priority) = backup.priority {
-            println!("  優先度: {priority:?}");
+            events.info(format!("  優先度: {priority:?}"));
         }
 
         let confirm = Confirm::new()
@@ -417,4 +692,102 @@ mod tests {
 
         assert_eq!(size, 10); // "hello" + "world" = 10 bytes
     }
+
+    #[test]
+    fn test_job_policy_only_lists_own_backups() {
+        let temp = TempDir::new().unwrap();
+        for name in ["backup_nightly", "backup_other", "backup_manual"] {
+            fs::create_dir_all(temp.path().join(name)).unwrap();
+        }
+        let record = |name: &str, job: Option<&str>| {
+            BackupHistory::new(temp.path().join(name), 1, 1, true, false, false)
+                .with_job(job.map(str::to_string))
+        };
+        let history = vec![
+            record("backup_nightly", Some("nightly")),
+            record("backup_other", Some("weekly")),
+            record("backup_manual", None),
+        ];
+
+        let engine = CleanupEngine::new(CleanupPolicy::retention_days(0).with_job("nightly"), true);
+        let backups = engine.get_backup_list(temp.path(), &history).unwrap();
+        let paths: Vec<_> = backups.iter().map(|b| b.path.clone()).collect();
+        assert_eq!(paths, vec![temp.path().join("backup_nightly")]);
+
+        // ジョブ指定なしでは全バックアップが対象
+        let engine = CleanupEngine::new(CleanupPolicy::retention_days(0), true);
+        assert_eq!(
+            engine.get_backup_list(temp.path(), &history).unwrap().len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_partial_backups_are_not_recovery_points() {
+        let now = Utc::now();
+        // 新しい順: 部分的, 完全, 部分的, 完全, 完全
+        let backups: Vec<BackupInfo> = [true, false, true, false, false]
+            .iter()
+            .enumerate()
+            .map(|(i, &partial)| BackupInfo {
+                path: PathBuf::from(format!("backup_{i}")),
+                modified_time: now - chrono::Duration::days(i as i64 * 10),
+                size: 1,
+                priority: None,
+                partial,
+            })
+            .collect();
+        let deleted = |policy: CleanupPolicy| -> Vec<String> {
+            CleanupEngine::new(policy, true)
+                .determine_deletions(&backups)
+                .unwrap()
+                .into_iter()
+                .map(|b| b.path.display().to_string())
+                .collect()
+        };
+
+        // 部分的なバックアップは保持数に数えず、より新しい完全なバックアップがあれば削除
+        assert_eq!(
+            deleted(CleanupPolicy::keep_count(2)),
+            ["backup_2", "backup_4"]
+        );
+
+        // より新しいものが部分的なバックアップだけの場合、最新の完全なバックアップは残す
+        assert_eq!(
+            deleted(CleanupPolicy::retention_days(5)),
+            ["backup_2", "backup_3", "backup_4"]
+        );
+        assert_eq!(
+            deleted(CleanupPolicy::keep_count(0)),
+            ["backup_2", "backup_3", "backup_4"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_freed_bytes_with_shared_inodes() {
+        let temp = TempDir::new().unwrap();
+        let older = temp.path().join("backup_1");
+        let newer = temp.path().join("backup_2");
+        fs::create_dir_all(&older).unwrap();
+        fs::create_dir_all(&newer).unwrap();
+
+        // 共有ファイル（10バイト）と各スナップショット固有のファイル
+        fs::write(older.join("shared.txt"), b"0123456789").unwrap();
+        fs::hard_link(older.join("shared.txt"), newer.join("shared.txt")).unwrap();
+        fs::write(older.join("old.txt"), b"old").unwrap();
+        fs::write(newer.join("new.txt"), b"newer").unwrap();
+
+        // ディレクトリ内で同じinodeを2回数えない
+        fs::hard_link(newer.join("new.txt"), newer.join("new_link.txt")).unwrap();
+        let engine = CleanupEngine::new(CleanupPolicy::default(), true);
+        assert_eq!(engine.calculate_size(&newer).unwrap(), 15);
+
+        // 古いスナップショットのみ削除: 共有ファイルは解放されない
+        let mut inodes = InodeTracker::default();
+        assert_eq!(inodes.release_dir(&older), 3);
+
+        // 続けて新しいスナップショットも削除: 共有ファイルの最後のリンクで解放
+        assert_eq!(inodes.release_dir(&newer), 15);
+    }
 }
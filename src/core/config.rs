@@ -1,8 +1,13 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-use super::Target;
+use super::calendar::CalendarSpec;
+use super::hooks::HookConfig;
+use super::integrity::HashAlgorithm;
+use super::job::BackupJob;
+use super::{Priority, Target, TargetType};
 use crate::error::{BackupError, Result as BackupResult};
 use crate::security::{check_read_permission, check_write_permission};
 
@@ -14,9 +19,12 @@ use crate::security::{check_read_permission, check_write_permission};
 /// # フィールド
 ///
 /// * `enabled` - スケジュール機能の有効/無効
-/// * `high_frequency` - 高優先度のバックアップ頻度（"daily", "weekly", "monthly"）
+/// * `high_frequency` - 高優先度のバックアップ頻度（"daily" などのキーワード、cron式、`OnCalendar` 形式）
 /// * `medium_frequency` - 中優先度のバックアップ頻度
 /// * `low_frequency` - 低優先度のバックアップ頻度
+/// * `randomized_delay_secs` - 実行開始をランダムに遅らせる最大秒数（0 で遅延なし）
+///
+/// 頻度の書式は [`CalendarSpec`](super::calendar::CalendarSpec) を参照してください。
 ///
 /// # 使用例
 ///
@@ -27,7 +35,8 @@ use crate::security::{check_read_permission, check_write_permission};
 ///     enabled: true,
 ///     high_frequency: "daily".to_string(),
 ///     medium_frequency: "weekly".to_string(),
-///     low_frequency: "monthly".to_string(),
+///     low_frequency: "0 3 * * sat,sun".to_string(),
+///     randomized_delay_secs: 600,
 /// };
 /// ```
 #[derive(Debug, Serialize, Deserialize)]
@@ -36,6 +45,8 @@ pub struct ScheduleConfig {
     pub high_frequency: String, // "daily", "weekly", "monthly"
     pub medium_frequency: String,
     pub low_frequency: String,
+    #[serde(default)]
+    pub randomized_delay_secs: u64,
 }
 
 impl Default for ScheduleConfig {
@@ -45,10 +56,95 @@ impl Default for ScheduleConfig {
             high_frequency: "daily".to_string(),
             medium_frequency: "weekly".to_string(),
             low_frequency: "monthly".to_string(),
+            randomized_delay_secs: 0,
         }
     }
 }
 
+/// 監視モード設定
+///
+/// `backup-suite watch` が変更通知をまとめて増分バックアップを作成する間隔を定義します。
+///
+/// # フィールド
+///
+/// * `debounce_secs` - 最後の変更からこの秒数だけ変更がなければバックアップを作成
+/// * `min_interval_secs` - バックアップを作成する最小間隔（`git checkout` やビルドによる連続作成を防止）
+/// * `max_delay_secs` - 変更が続いていても、最初の変更からこの秒数が経過したらバックアップを作成
+///
+/// # 使用例
+///
+/// ```no_run
+/// use backup_suite::core::config::WatchConfig;
+///
+/// let watch = WatchConfig {
+///     debounce_secs: 10,
+///     min_interval_secs: 300,
+///     max_delay_secs: 1800,
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchConfig {
+    pub debounce_secs: u64,
+    pub min_interval_secs: u64,
+    pub max_delay_secs: u64,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            debounce_secs: 10,
+            min_interval_secs: 300,
+            max_delay_secs: 1800,
+        }
+    }
+}
+
+/// 目標復旧時点（RPO）設定
+///
+/// 優先度ごとに、最後に成功したバックアップの許容経過時間（時間）を定義します。
+/// 対象の `max_age_hours` が設定されている場合はそちらを優先します。
+/// `backup-suite check` とダッシュボードが超過した対象を表示します。
+///
+/// # 使用例
+///
+/// ```no_run
+/// use backup_suite::core::config::RpoConfig;
+///
+/// let rpo = RpoConfig {
+///     high_hours: Some(24),
+///     medium_hours: Some(24 * 7),
+///     low_hours: None,
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpoConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub high_hours: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub medium_hours: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_hours: Option<u64>,
+}
+
+impl RpoConfig {
+    /// 優先度の許容経過時間（時間）
+    #[must_use]
+    pub fn max_age_hours(&self, priority: &Priority) -> Option<u64> {
+        match priority {
+            Priority::High => self.high_hours,
+            Priority::Medium => self.medium_hours,
+            Priority::Low => self.low_hours,
+        }
+    }
+
+    /// 未設定か
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// バックアップ設定
 ///
 /// バックアップ先ディレクトリと保存期間を定義します。
@@ -58,17 +154,22 @@ impl Default for ScheduleConfig {
 /// * `destination` - バックアップファイルの保存先ディレクトリ
 /// * `auto_cleanup` - 古いバックアップの自動削除を有効にするか
 /// * `keep_days` - バックアップを保持する日数（1-3650日）
+/// * `change_retries` - バックアップ中に変更されたファイルを読み直す回数
+/// * `hash_algorithm` - 整合性マニフェストのハッシュアルゴリズム（`sha256` / `blake3`）
 ///
 /// # 使用例
 ///
 /// ```no_run
 /// use backup_suite::core::config::BackupConfig;
+/// use backup_suite::core::integrity::HashAlgorithm;
 /// use std::path::PathBuf;
 ///
 /// let config = BackupConfig {
 ///     destination: PathBuf::from("/backup/storage"),
 ///     auto_cleanup: true,
 ///     keep_days: 30,
+///     change_retries: 2,
+///     hash_algorithm: HashAlgorithm::Blake3,
 /// };
 /// ```
 #[derive(Debug, Serialize, Deserialize)]
@@ -76,6 +177,17 @@ pub struct BackupConfig {
     pub destination: PathBuf,
     pub auto_cleanup: bool,
     pub keep_days: u32,
+    #[serde(default = "default_change_retries")]
+    pub change_retries: u32,
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+}
+
+/// バックアップ中に変更されたファイルを読み直すデフォルト回数
+pub const DEFAULT_CHANGE_RETRIES: u32 = 2;
+
+fn default_change_retries() -> u32 {
+    DEFAULT_CHANGE_RETRIES
 }
 
 impl Default for BackupConfig {
@@ -85,6 +197,8 @@ impl Default for BackupConfig {
             destination: home.join("backup-suite/backups"),
             auto_cleanup: false,
             keep_days: 30,
+            change_retries: DEFAULT_CHANGE_RETRIES,
+            hash_algorithm: HashAlgorithm::default(),
         }
     }
 }
@@ -99,6 +213,10 @@ impl Default for BackupConfig {
 /// * `version` - 設定ファイルのバージョン
 /// * `backup` - バックアップ関連の設定
 /// * `schedule` - スケジュール関連の設定
+/// * `watch` - 監視モード（`backup-suite watch`）の設定
+/// * `rpo` - 優先度ごとの目標復旧時点（RPO）
+/// * `hooks` - 全対象のバックアップ前後に実行するグローバルフック
+/// * `jobs` - 名前付きバックアップジョブ（`run --job <名前>` とスケジューラで使用）
 /// * `targets` - バックアップ対象のリスト
 ///
 /// # 使用例
@@ -127,6 +245,14 @@ pub struct Config {
     pub backup: BackupConfig,
     #[serde(default)]
     pub schedule: ScheduleConfig,
+    #[serde(default)]
+    pub watch: WatchConfig,
+    #[serde(default, skip_serializing_if = "RpoConfig::is_empty")]
+    pub rpo: RpoConfig,
+    #[serde(default, skip_serializing_if = "HookConfig::is_empty")]
+    pub hooks: HookConfig,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub jobs: BTreeMap<String, BackupJob>,
     pub targets: Vec<Target>,
 }
 
@@ -136,6 +262,10 @@ impl Default for Config {
             version: "1.0.0".to_string(),
             backup: BackupConfig::default(),
             schedule: ScheduleConfig::default(),
+            watch: WatchConfig::default(),
+            rpo: RpoConfig::default(),
+            hooks: HookConfig::default(),
+            jobs: BTreeMap::new(),
             targets: vec![],
         }
     }
@@ -431,6 +561,32 @@ impl Config {
             .collect()
     }
 
+    /// 名前付きジョブを取得
+    ///
+    /// # Errors
+    ///
+    /// 指定した名前のジョブが定義されていない場合、またはジョブ定義が不正な場合にエラーを返します。
+    ///
+    /// # 使用例
+    ///
+    /// ```no_run
+    /// use backup_suite::Config;
+    ///
+    /// let config = Config::load().unwrap();
+    /// let job = config.job("nightly").unwrap();
+    /// println!("圧縮: {}", job.compression_type().to_str());
+    /// ```
+    pub fn job(&self, name: &str) -> BackupResult<&BackupJob> {
+        let job = self
+            .jobs
+            .get(name)
+            .ok_or_else(|| BackupError::ConfigValidationError {
+                message: format!("ジョブが定義されていません: {name}"),
+            })?;
+        job.validate(name)?;
+        Ok(job)
+    }
+
     /// 設定の妥当性を検証
     ///
     /// すべての設定項目が正しく、実行可能であることを確認します。
@@ -441,6 +597,7 @@ impl Config {
     /// - 保存期間（keep_days）の妥当性（1-3650日）
     /// - 各ターゲットの存在確認と読み取り権限
     /// - 除外パターンの正規表現の妥当性
+    /// - スケジュールの頻度（キーワード・cron式・`OnCalendar` 形式）と名前付きジョブの定義
     ///
     /// # 戻り値
     ///
@@ -479,11 +636,12 @@ impl Config {
 
         // 4. 各ターゲットの検証
         for target in &self.targets {
-            // 4.1 ターゲットの存在確認
-            if !target.path.exists() {
+            // 4.1 ターゲットの存在確認（コマンド出力は実行時に生成されるため対象外）
+            let is_command = target.target_type == TargetType::Command;
+            if !is_command && !target.path.exists() {
                 eprintln!("警告: バックアップ対象が存在しません: {:?}", target.path);
                 // 警告のみで処理は継続（後で追加される可能性があるため）
-            } else {
+            } else if !is_command {
                 // 4.2 読み取り権限チェック
                 check_read_permission(&target.path)?;
             }
@@ -495,9 +653,42 @@ impl Config {
                     source: e,
                 })?;
             }
+
+            // 4.4 対象ごとの圧縮レベルの範囲チェック（圧縮形式を指定した場合のみ）
+            if let (Some(compression_type), Some(level)) =
+                (target.compression, target.compression_level)
+            {
+                if let Some(range) = compression_type.level_range() {
+                    if !range.contains(&level) {
+                        return Err(BackupError::ConfigValidationError {
+                            message: format!(
+                                "{:?} の compression_level は {}-{} の範囲で指定してください（現在: {}）",
+                                target.path,
+                                range.start(),
+                                range.end(),
+                                level
+                            ),
+                        });
+                    }
+                }
+            }
         }
 
-        // 5. ターゲットが1つもない場合は警告
+        // 5. スケジュール・名前付きジョブの定義チェック
+        for (label, frequency) in [
+            ("high_frequency", &self.schedule.high_frequency),
+            ("medium_frequency", &self.schedule.medium_frequency),
+            ("low_frequency", &self.schedule.low_frequency),
+        ] {
+            CalendarSpec::parse(frequency).map_err(|e| BackupError::ConfigValidationError {
+                message: format!("schedule.{label}: {e:#}"),
+            })?;
+        }
+        for (name, job) in &self.jobs {
+            job.validate(name)?;
+        }
+
+        // 6. ターゲットが1つもない場合は警告
         if self.targets.is_empty() {
             eprintln!("警告: バックアップ対象が設定されていません");
         }
@@ -509,7 +700,23 @@ impl Config {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::target::{Priority, TargetType};
+    use crate::core::target::Priority;
+
+    #[test]
+    fn test_validate_schedule_frequencies() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.backup.destination = temp.path().to_path_buf();
+        config.schedule.high_frequency = "0 9,18 * * 1-5".to_string();
+        config.schedule.low_frequency = "Sat,Sun 03:00".to_string();
+        assert!(config.validate().is_ok());
+
+        config.schedule.medium_frequency = "0 25 * * *".to_string();
+        assert!(matches!(
+            config.validate(),
+            Err(BackupError::ConfigValidationError { .. })
+        ));
+    }
 
     #[test]
     fn test_default_config() {
@@ -529,6 +736,11 @@ mod tests {
             category: "test".to_string(),
             added_date: chrono::Utc::now(),
             exclude_patterns: vec![],
+            hooks: HookConfig::default(),
+            command: None,
+            compression: None,
+            compression_level: None,
+            max_age_hours: None,
         };
 
         config.add_target(target);
@@ -546,6 +758,11 @@ mod tests {
             category: "test".to_string(),
             added_date: chrono::Utc::now(),
             exclude_patterns: vec![],
+            hooks: HookConfig::default(),
+            command: None,
+            compression: None,
+            compression_level: None,
+            max_age_hours: None,
         };
 
         config.add_target(target);
@@ -1,8 +1,16 @@
+use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::Path;
 
-use crate::error::Result;
+use super::integrity::{HashAlgorithm, StreamHasher};
+use crate::error::{BackupError, Result};
+
+/// スパースファイルの復元時にホールとして扱うブロックサイズ
+const SPARSE_BLOCK_SIZE: usize = 4096;
+
+/// スパース書き込みを行う最小データサイズ（これ未満は通常書き込み）
+const SPARSE_WRITE_THRESHOLD: usize = 64 * 1024;
 
 /// ファイルコピー最適化エンジン
 ///
@@ -10,8 +18,12 @@ use crate::error::Result;
 ///
 /// # 機能
 ///
+/// - リフリンク: 対応ファイルシステム（Btrfs/XFS等）ではデータを共有してほぼ瞬時にコピー（Linux）
+/// - スパースファイル: `SEEK_DATA`/`SEEK_HOLE` でデータ領域のみをコピーしホールを維持（Linux）
 /// - 小ファイル: 標準の`fs::copy`を使用（高速）
 /// - 大ファイル: バッファリングコピー（メモリ効率）
+/// - ハッシュ付きコピー: カーネル内コピー後に1回だけ読み込むか、コピーと同じ読み込みでハッシュを計算
+///   （[`CopyEngine::copy_file_hashed`]）
 /// - 並列処理対応: 複数ファイルの同時コピー
 ///
 /// # 使用例
@@ -89,6 +101,7 @@ impl CopyEngine {
     /// ファイルをコピー
     ///
     /// ファイルサイズに応じて最適なコピー手法を自動選択します。
+    /// Linuxではリフリンク、スパースファイルのホール維持コピーを優先して試行します。
     ///
     /// # 引数
     ///
@@ -126,6 +139,22 @@ impl CopyEngine {
         let metadata = std::fs::metadata(source)?;
         let size = metadata.len();
 
+        // リフリンク・スパースコピー・copy_file_range（Linuxのみ、非対応時は通常コピーへフォールバック）
+        #[cfg(target_os = "linux")]
+        if let Some(bytes) = linux::fast_copy(source, dest, &metadata, self.parallel_threshold)? {
+            return Ok(bytes);
+        }
+
+        self.plain_copy_sized(source, dest, size)
+    }
+
+    /// ユーザー空間での通常コピー（サイズに応じて `fs::copy` またはバッファリングコピー）
+    fn plain_copy(&self, source: &Path, dest: &Path) -> Result<u64> {
+        let size = std::fs::metadata(source)?.len();
+        self.plain_copy_sized(source, dest, size)
+    }
+
+    fn plain_copy_sized(&self, source: &Path, dest: &Path, size: u64) -> Result<u64> {
         // 小さいファイルは標準のコピーを使用（最速）
         if size < self.parallel_threshold {
             return std::fs::copy(source, dest).map_err(Into::into);
@@ -135,6 +164,116 @@ impl CopyEngine {
         self.buffered_copy(source, dest)
     }
 
+    /// ファイルをコピーし、必要に応じて内容のハッシュを計算
+    ///
+    /// [`CopyEngine::copy_file`] と同じくリフリンク・スパースコピー・`copy_file_range`
+    /// （Linux）を優先し、カーネル内でコピーできた場合は、ハッシュが必要なときだけ
+    /// ソースを1回読み込んで計算します（スパースファイルはデータ領域のみ読み込み）。
+    /// ユーザー空間でコピーする場合は、コピーと同じ読み込みでハッシュを計算します。
+    ///
+    /// # 引数
+    ///
+    /// * `source` - コピー元ファイルパス
+    /// * `dest` - コピー先ファイルパス
+    /// * `algorithm` - ハッシュアルゴリズム（`None` の場合はハッシュを計算しない）
+    ///
+    /// # 戻り値
+    ///
+    /// コピーしたバイト数、ハッシュ値、カーネル内でコピーしたか（[`CopiedFile`]）
+    ///
+    /// # Errors
+    ///
+    /// [`CopyEngine::copy_file`] と同じ場合、またはハッシュ計算のための読み込みに
+    /// 失敗した場合にエラーを返します。
+    ///
+    /// # 使用例
+    ///
+    /// ```no_run
+    /// use backup_suite::core::copy_engine::CopyEngine;
+    /// use backup_suite::core::integrity::HashAlgorithm;
+    /// use std::path::Path;
+    ///
+    /// let engine = CopyEngine::new();
+    /// let copied = engine.copy_file_hashed(
+    ///     Path::new("/source/file.txt"),
+    ///     Path::new("/dest/file.txt"),
+    ///     Some(HashAlgorithm::Sha256),
+    /// ).unwrap();
+    /// println!("{}バイト, ハッシュ: {:?}", copied.bytes, copied.hash);
+    /// ```
+    pub fn copy_file_hashed(
+        &self,
+        source: &Path,
+        dest: &Path,
+        algorithm: Option<HashAlgorithm>,
+    ) -> Result<CopiedFile> {
+        #[cfg(target_os = "linux")]
+        {
+            let metadata = std::fs::metadata(source)?;
+            if let Some(bytes) = linux::fast_copy(source, dest, &metadata, self.parallel_threshold)?
+            {
+                let hash = algorithm
+                    .map(|algorithm| linux::hash_file(source, &metadata, algorithm))
+                    .transpose()?;
+                return Ok(CopiedFile {
+                    bytes,
+                    hash,
+                    offloaded: true,
+                });
+            }
+        }
+
+        let (bytes, hash) = match algorithm {
+            Some(algorithm) => {
+                let (bytes, hash) = self.hashing_copy(source, dest, algorithm)?;
+                (bytes, Some(hash))
+            }
+            None => (self.plain_copy(source, dest)?, None),
+        };
+        Ok(CopiedFile {
+            bytes,
+            hash,
+            offloaded: false,
+        })
+    }
+
+    /// ハッシュを計算しながらバッファリングコピーを実行
+    #[allow(clippy::indexing_slicing)] // read() guarantees bytes_read <= buffer.len()
+    fn hashing_copy(
+        &self,
+        source: &Path,
+        dest: &Path,
+        algorithm: HashAlgorithm,
+    ) -> Result<(u64, String)> {
+        let mut reader = File::open(source)?;
+        let mut writer = BufWriter::with_capacity(self.buffer_size, File::create(dest)?);
+
+        let mut hasher = StreamHasher::new(algorithm);
+        let mut buffer = vec![0u8; self.buffer_size];
+        let mut total_bytes = 0u64;
+
+        loop {
+            let bytes_read = reader.read(&mut buffer)?;
+            if bytes_read == 0 {
+                break;
+            }
+
+            hasher.update(&buffer[..bytes_read]);
+            writer.write_all(&buffer[..bytes_read])?;
+            total_bytes += bytes_read as u64;
+        }
+
+        writer.flush()?;
+
+        #[cfg(unix)]
+        {
+            let perms = reader.metadata()?.permissions();
+            std::fs::set_permissions(dest, perms)?;
+        }
+
+        Ok((total_bytes, hasher.finalize()))
+    }
+
     /// バッファリングコピーを実行
     ///
     /// 大きいファイルをメモリ効率的にコピーします。
@@ -198,12 +337,442 @@ impl CopyEngine {
     }
 }
 
+/// [`CopyEngine::copy_file_hashed`] の結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopiedFile {
+    /// コピーしたバイト数
+    pub bytes: u64,
+    /// 内容のハッシュ（16進数文字列、ハッシュを要求しなかった場合は `None`）
+    pub hash: Option<String>,
+    /// リフリンク・スパースコピー・`copy_file_range` でカーネル内コピーしたか
+    pub offloaded: bool,
+}
+
 impl Default for CopyEngine {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// データをスパースファイルとして書き込み
+///
+/// ゼロで埋められたブロックは書き込まずにシークで飛ばし、ファイルシステム上のホールとして
+/// 再作成します。スパースなVMディスクイメージ等を復元する際のディスク使用量を抑えます。
+/// 小さいデータは通常の書き込みを行います。
+///
+/// # 引数
+///
+/// * `dest` - 書き込み先ファイルパス
+/// * `data` - 書き込むデータ
+///
+/// # Errors
+///
+/// 以下の場合にエラーを返します:
+/// * ファイルの作成に失敗した場合
+/// * 書き込み・シーク・サイズ設定に失敗した場合
+///
+/// # 使用例
+///
+/// ```no_run
+/// use backup_suite::core::copy_engine::write_sparse;
+/// use std::path::Path;
+///
+/// let data = vec![0u8; 1024 * 1024];
+/// write_sparse(Path::new("/restore/disk.img"), &data).unwrap();
+/// ```
+pub fn write_sparse(dest: &Path, data: &[u8]) -> Result<()> {
+    use std::io::{Seek, SeekFrom};
+
+    if data.len() < SPARSE_WRITE_THRESHOLD {
+        return std::fs::write(dest, data).map_err(Into::into);
+    }
+
+    let mut file = File::create(dest)?;
+    let mut offset = 0usize;
+
+    for block in data.chunks(SPARSE_BLOCK_SIZE) {
+        if block.iter().all(|&b| b == 0) {
+            offset += block.len();
+            continue;
+        }
+        file.seek(SeekFrom::Start(offset as u64))?;
+        file.write_all(block)?;
+        offset += block.len();
+    }
+
+    // 末尾がホールの場合もファイルサイズを確定
+    file.set_len(data.len() as u64)?;
+    Ok(())
+}
+
+/// スパースファイルのデータ領域の配置
+///
+/// 圧縮・暗号化するスパースファイルはデータ領域のみを連結して処理し、ホールを含む
+/// 元の配置をマニフェストに記録します。これにより、処理・復元時のメモリ使用量は
+/// 論理サイズではなく割り当て済みのデータ量に比例します。
+///
+/// # フィールド
+///
+/// * `len` - 元ファイルの論理サイズ
+/// * `extents` - データ領域（開始位置・長さ）の昇順のリスト（それ以外はホール）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseMap {
+    pub len: u64,
+    pub extents: Vec<(u64, u64)>,
+}
+
+impl SparseMap {
+    /// 連結したデータ領域の合計サイズ
+    #[must_use]
+    pub fn data_len(&self) -> u64 {
+        self.extents.iter().map(|&(_, len)| len).sum()
+    }
+
+    /// 連結したデータ領域を元の配置で書き込み（ホールを再作成）
+    ///
+    /// # Errors
+    ///
+    /// 以下の場合にエラーを返します:
+    /// * `data` の長さ・配置が記録と一致しない場合
+    /// * ファイルの作成・書き込み・サイズ設定に失敗した場合
+    pub fn write(&self, dest: &Path, data: &[u8]) -> Result<()> {
+        use std::io::{Seek, SeekFrom};
+
+        self.check(data)?;
+        let mut file = File::create(dest)?;
+        let mut consumed = 0usize;
+        for &(offset, len) in &self.extents {
+            let end = consumed + len as usize;
+            file.seek(SeekFrom::Start(offset))?;
+            file.write_all(data.get(consumed..end).unwrap_or_default())?;
+            consumed = end;
+        }
+        // 末尾がホールの場合もファイルサイズを確定
+        file.set_len(self.len)?;
+        Ok(())
+    }
+
+    /// 元ファイルの内容（ホールはゼロ）のハッシュを計算
+    ///
+    /// # Errors
+    ///
+    /// `data` の長さ・配置が記録と一致しない場合にエラーを返します。
+    pub fn hash(&self, algorithm: HashAlgorithm, data: &[u8]) -> Result<String> {
+        self.check(data)?;
+        let mut hasher = StreamHasher::new(algorithm);
+        let mut position = 0u64;
+        let mut consumed = 0usize;
+        for &(offset, len) in &self.extents {
+            hash_zeros(&mut hasher, offset - position);
+            let end = consumed + len as usize;
+            hasher.update(data.get(consumed..end).unwrap_or_default());
+            consumed = end;
+            position = offset + len;
+        }
+        hash_zeros(&mut hasher, self.len - position);
+        Ok(hasher.finalize())
+    }
+
+    /// データ領域が昇順で重ならず論理サイズ内にあり、合計が `data` の長さと一致するか確認
+    fn check(&self, data: &[u8]) -> Result<()> {
+        let mut position = 0u64;
+        for &(offset, len) in &self.extents {
+            if offset < position || offset.saturating_add(len) > self.len {
+                return Err(BackupError::Other(anyhow::anyhow!(
+                    "スパースファイルの配置が不正です"
+                )));
+            }
+            position = offset + len;
+        }
+        if self.data_len() != data.len() as u64 {
+            return Err(BackupError::Other(anyhow::anyhow!(
+                "スパースファイルのデータサイズが記録と一致しません（記録: {}、実際: {}）",
+                self.data_len(),
+                data.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// スパースファイルのデータ領域のみを読み込み
+///
+/// 割り当て済みのブロックが論理サイズより少ないファイルは、`SEEK_DATA`/`SEEK_HOLE` で
+/// データ領域のみを読み込んで連結し、配置とともに返します。スパースファイルでない場合
+/// （およびLinux以外）は `None` を返すため、呼び出し元で通常どおり読み込みます。
+///
+/// # Errors
+///
+/// ファイルのオープン・読み込み・シークに失敗した場合にエラーを返します。
+///
+/// # 使用例
+///
+/// ```no_run
+/// use backup_suite::core::copy_engine::read_sparse;
+/// use std::path::Path;
+///
+/// if let Some((map, data)) = read_sparse(Path::new("/var/lib/images/disk.img")).unwrap() {
+///     assert_eq!(map.data_len(), data.len() as u64);
+/// }
+/// ```
+pub fn read_sparse(source: &Path) -> Result<Option<(SparseMap, Vec<u8>)>> {
+    #[cfg(target_os = "linux")]
+    {
+        let metadata = std::fs::metadata(source)?;
+        if metadata.len() < SPARSE_WRITE_THRESHOLD as u64 || !linux::is_sparse(&metadata) {
+            return Ok(None);
+        }
+        Ok(Some(linux::read_extents(source, metadata.len())?))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = source;
+        Ok(None)
+    }
+}
+
+/// ホールの長さ分のゼロをハッシュに加える
+fn hash_zeros(hasher: &mut StreamHasher, len: u64) {
+    static ZEROS: [u8; 64 * 1024] = [0u8; 64 * 1024];
+    let mut remaining = len;
+    while remaining > 0 {
+        let n = remaining.min(ZEROS.len() as u64) as usize;
+        hasher.update(&ZEROS[..n]);
+        remaining -= n as u64;
+    }
+}
+
+/// Linux固有の高速コピー（リフリンク・スパースコピー）
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::fs::{File, Metadata};
+    use std::io;
+    use std::os::unix::fs::{FileExt, MetadataExt};
+    use std::os::unix::io::AsRawFd;
+    use std::path::Path;
+
+    use super::{hash_zeros, HashAlgorithm, SparseMap, StreamHasher};
+    use crate::error::Result;
+
+    /// リフリンク → スパースコピー → copy_file_range の順に試行
+    ///
+    /// いずれもカーネル内で完結するため、ユーザー空間へのデータコピーが発生しません。
+    /// 閾値未満の通常ファイル（`fs::copy` で十分高速）や、カーネルコピー非対応の場合は
+    /// `None` を返し、呼び出し元で通常コピーを行います。
+    pub(super) fn fast_copy(
+        source: &Path,
+        dest: &Path,
+        metadata: &Metadata,
+        large_threshold: u64,
+    ) -> Result<Option<u64>> {
+        let size = metadata.len();
+        let src = File::open(source)?;
+        let dst = File::create(dest)?;
+
+        let copied = if try_reflink(&src, &dst) {
+            true
+        } else if is_sparse(metadata) {
+            sparse_copy(&src, &dst, size)?;
+            true
+        } else if size >= large_threshold {
+            copy_range(&src, &dst, 0, size)?
+        } else {
+            false
+        };
+
+        if !copied {
+            return Ok(None);
+        }
+
+        std::fs::set_permissions(dest, metadata.permissions())?;
+        Ok(Some(size))
+    }
+
+    /// 割り当てブロック数が論理サイズより少なければスパースファイル
+    pub(super) fn is_sparse(metadata: &Metadata) -> bool {
+        metadata.blocks().saturating_mul(512) < metadata.len()
+    }
+
+    /// FICLONE ioctlでデータブロックを共有（Btrfs/XFS/OCFS2等）
+    fn try_reflink(src: &File, dst: &File) -> bool {
+        // SAFETY: 両ファイルディスクリプタは呼び出し中有効
+        unsafe { libc::ioctl(dst.as_raw_fd(), libc::FICLONE, src.as_raw_fd()) == 0 }
+    }
+
+    /// データ領域のみをコピーし、ホールを維持
+    fn sparse_copy(src: &File, dst: &File, size: u64) -> io::Result<()> {
+        let mut offset = 0u64;
+        while offset < size {
+            let Some(data_start) = seek(src, offset, libc::SEEK_DATA)? else {
+                break; // 以降はすべてホール
+            };
+            let data_end = seek(src, data_start, libc::SEEK_HOLE)?.unwrap_or(size);
+            let len = data_end - data_start;
+            if !copy_range(src, dst, data_start, len)? {
+                copy_range_userspace(src, dst, data_start, len)?;
+            }
+            offset = data_end;
+        }
+        dst.set_len(size)
+    }
+
+    /// ファイル内容のハッシュを計算
+    ///
+    /// スパースファイルはデータ領域のみを読み込み、ホールはゼロとしてハッシュに加えます。
+    #[allow(clippy::indexing_slicing)] // read_at() guarantees n <= buffer.len()
+    pub(super) fn hash_file(
+        source: &Path,
+        metadata: &Metadata,
+        algorithm: HashAlgorithm,
+    ) -> Result<String> {
+        if !is_sparse(metadata) {
+            return Ok(algorithm.hash_file(source)?);
+        }
+
+        let size = metadata.len();
+        let src = File::open(source)?;
+        let mut hasher = StreamHasher::new(algorithm);
+        let mut buffer = vec![0u8; 64 * 1024];
+
+        // offset: ハッシュ済みの論理位置
+        let mut offset = 0u64;
+        'extents: while offset < size {
+            let data_start = seek(&src, offset, libc::SEEK_DATA)?
+                .unwrap_or(size)
+                .min(size);
+            hash_zeros(&mut hasher, data_start - offset);
+            offset = data_start;
+            let data_end = seek(&src, data_start, libc::SEEK_HOLE)?
+                .unwrap_or(size)
+                .min(size);
+            while offset < data_end {
+                let want = ((data_end - offset) as usize).min(buffer.len());
+                let n = src.read_at(&mut buffer[..want], offset)?;
+                if n == 0 {
+                    break 'extents; // 読み込み中にファイルが縮小した
+                }
+                hasher.update(&buffer[..n]);
+                offset += n as u64;
+            }
+        }
+
+        Ok(hasher.finalize())
+    }
+
+    /// データ領域のみを読み込んで連結
+    ///
+    /// 読み込み中にファイルが縮小した場合は、読み込めた範囲までを記録します。
+    #[allow(clippy::indexing_slicing)] // read_at() guarantees n <= remaining buffer
+    pub(super) fn read_extents(source: &Path, size: u64) -> Result<(SparseMap, Vec<u8>)> {
+        let src = File::open(source)?;
+        let mut extents = Vec::new();
+        let mut data = Vec::new();
+
+        let mut offset = 0u64;
+        'extents: while offset < size {
+            let Some(data_start) = seek(&src, offset, libc::SEEK_DATA)? else {
+                break; // 以降はすべてホール
+            };
+            if data_start >= size {
+                break;
+            }
+            let data_end = seek(&src, data_start, libc::SEEK_HOLE)?
+                .unwrap_or(size)
+                .min(size);
+            let start = data.len();
+            data.resize(start + (data_end - data_start) as usize, 0);
+            let mut filled = start;
+            while filled < data.len() {
+                let n = src.read_at(&mut data[filled..], data_start + (filled - start) as u64)?;
+                if n == 0 {
+                    data.truncate(filled);
+                    extents.push((data_start, (filled - start) as u64));
+                    break 'extents; // 読み込み中にファイルが縮小した
+                }
+                filled += n;
+            }
+            extents.push((data_start, data_end - data_start));
+            offset = data_end;
+        }
+
+        extents.retain(|&(_, len)| len > 0);
+        Ok((SparseMap { len: size, extents }, data))
+    }
+
+    /// lseek（データが存在しない場合は `None`）
+    fn seek(file: &File, offset: u64, whence: libc::c_int) -> io::Result<Option<u64>> {
+        // SAFETY: ファイルディスクリプタは呼び出し中有効
+        let pos = unsafe { libc::lseek(file.as_raw_fd(), offset as libc::off_t, whence) };
+        if pos >= 0 {
+            return Ok(Some(pos as u64));
+        }
+        let err = io::Error::last_os_error();
+        if err.raw_os_error() == Some(libc::ENXIO) {
+            Ok(None)
+        } else {
+            Err(err)
+        }
+    }
+
+    /// copy_file_range で指定範囲をコピー
+    ///
+    /// ファイルシステムが非対応の場合は何もコピーせず `false` を返します。
+    fn copy_range(src: &File, dst: &File, start: u64, len: u64) -> io::Result<bool> {
+        let mut off_in = start as libc::loff_t;
+        let mut off_out = start as libc::loff_t;
+        let mut remaining = len;
+
+        while remaining > 0 {
+            // SAFETY: 両ファイルディスクリプタとオフセットポインタは呼び出し中有効
+            let n = unsafe {
+                libc::copy_file_range(
+                    src.as_raw_fd(),
+                    &mut off_in,
+                    dst.as_raw_fd(),
+                    &mut off_out,
+                    remaining as usize,
+                    0,
+                )
+            };
+            if n > 0 {
+                remaining -= n as u64;
+                continue;
+            }
+            if n == 0 {
+                break; // コピー中にファイルが縮小した
+            }
+            let err = io::Error::last_os_error();
+            return match err.raw_os_error() {
+                Some(libc::EXDEV | libc::ENOSYS | libc::EINVAL | libc::EOPNOTSUPP)
+                    if remaining == len =>
+                {
+                    Ok(false)
+                }
+                _ => Err(err),
+            };
+        }
+        Ok(true)
+    }
+
+    /// pread/pwrite で指定範囲をコピー
+    #[allow(clippy::indexing_slicing)] // read_at() guarantees n <= buffer.len()
+    fn copy_range_userspace(src: &File, dst: &File, start: u64, len: u64) -> io::Result<()> {
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut pos = start;
+        let end = start + len;
+        while pos < end {
+            let want = ((end - pos) as usize).min(buffer.len());
+            let n = src.read_at(&mut buffer[..want], pos)?;
+            if n == 0 {
+                break;
+            }
+            dst.write_all_at(&buffer[..n], pos)?;
+            pos += n as u64;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -284,6 +853,193 @@ mod tests {
         assert_eq!(copied_content, content);
     }
 
+    #[test]
+    fn test_copy_file_hashed() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source.bin");
+        let dest = temp.path().join("dest.bin");
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&source, &data).unwrap();
+
+        // 閾値以上のファイルはカーネル内コピー、未満はユーザー空間コピーを試行
+        for engine in [
+            CopyEngine::with_config(4096, 1024),
+            CopyEngine::with_config(4096, u64::MAX),
+        ] {
+            for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Blake3] {
+                let copied = engine
+                    .copy_file_hashed(&source, &dest, Some(algorithm))
+                    .unwrap();
+                assert_eq!(copied.bytes, data.len() as u64);
+                assert_eq!(std::fs::read(&dest).unwrap(), data);
+                assert_eq!(copied.hash, Some(algorithm.hash_bytes(&data)));
+            }
+
+            let copied = engine.copy_file_hashed(&source, &dest, None).unwrap();
+            assert_eq!(copied.bytes, data.len() as u64);
+            assert_eq!(copied.hash, None);
+            assert_eq!(std::fs::read(&dest).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_write_sparse_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let dest = temp_dir.path().join("sparse.img");
+
+        // 先頭・中央にデータ、その他はゼロ（末尾もホール）
+        let mut data = vec![0u8; 1024 * 1024];
+        data[..5].copy_from_slice(b"start");
+        data[512 * 1024..512 * 1024 + 6].copy_from_slice(b"middle");
+
+        write_sparse(&dest, &data).unwrap();
+
+        assert_eq!(std::fs::read(&dest).unwrap(), data);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_copy_sparse_file_preserves_holes() {
+        use std::io::{Seek, SeekFrom};
+        use std::os::unix::fs::MetadataExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("sparse_source.img");
+        let dest = temp_dir.path().join("sparse_dest.img");
+
+        // 64MBの論理サイズに対し、データは先頭と末尾付近のみ
+        let mut file = File::create(&source).unwrap();
+        file.write_all(b"header").unwrap();
+        file.seek(SeekFrom::Start(48 * 1024 * 1024)).unwrap();
+        file.write_all(b"payload").unwrap();
+        file.set_len(64 * 1024 * 1024).unwrap();
+        drop(file);
+
+        let engine = CopyEngine::new();
+        let bytes = engine.copy_file(&source, &dest).unwrap();
+        assert_eq!(bytes, 64 * 1024 * 1024);
+
+        let src_meta = std::fs::metadata(&source).unwrap();
+        let dst_meta = std::fs::metadata(&dest).unwrap();
+        assert_eq!(dst_meta.len(), src_meta.len());
+        assert_eq!(
+            std::fs::read(&source).unwrap(),
+            std::fs::read(&dest).unwrap()
+        );
+
+        // ソースがスパースとして作成できた環境では、コピー先もホールを維持
+        if src_meta.blocks() * 512 < src_meta.len() {
+            assert!(dst_meta.blocks() * 512 < dst_meta.len());
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_copy_file_hashed_sparse_file() {
+        use std::io::{Seek, SeekFrom};
+        use std::os::unix::fs::MetadataExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("sparse_source.img");
+        let dest = temp_dir.path().join("sparse_dest.img");
+
+        let mut file = File::create(&source).unwrap();
+        file.seek(SeekFrom::Start(8 * 1024 * 1024)).unwrap();
+        file.write_all(b"payload").unwrap();
+        file.set_len(32 * 1024 * 1024).unwrap();
+        drop(file);
+
+        let data = std::fs::read(&source).unwrap();
+        let engine = CopyEngine::new();
+        for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Blake3] {
+            let copied = engine
+                .copy_file_hashed(&source, &dest, Some(algorithm))
+                .unwrap();
+            assert_eq!(copied.bytes, data.len() as u64);
+            assert_eq!(copied.hash, Some(algorithm.hash_bytes(&data)));
+            assert_eq!(std::fs::read(&dest).unwrap(), data);
+        }
+
+        let src_meta = std::fs::metadata(&source).unwrap();
+        let dst_meta = std::fs::metadata(&dest).unwrap();
+        if src_meta.blocks() * 512 < src_meta.len() {
+            assert!(dst_meta.blocks() * 512 < dst_meta.len());
+        }
+    }
+
+    #[test]
+    fn test_sparse_map_write_and_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        let dest = temp_dir.path().join("sparse.img");
+
+        let map = SparseMap {
+            len: 1024 * 1024,
+            extents: vec![(0, 5), (512 * 1024, 6)],
+        };
+        let mut expected = vec![0u8; 1024 * 1024];
+        expected[..5].copy_from_slice(b"start");
+        expected[512 * 1024..512 * 1024 + 6].copy_from_slice(b"middle");
+
+        map.write(&dest, b"startmiddle").unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), expected);
+        assert_eq!(
+            map.hash(HashAlgorithm::Blake3, b"startmiddle").unwrap(),
+            HashAlgorithm::Blake3.hash_bytes(&expected)
+        );
+
+        // 記録と一致しないデータ・配置は書き込まない
+        assert!(map.write(&dest, b"short").is_err());
+        let overlapping = SparseMap {
+            len: 16,
+            extents: vec![(0, 8), (4, 8)],
+        };
+        assert!(overlapping.hash(HashAlgorithm::Sha256, &[1u8; 16]).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_sparse_reads_only_data_extents() {
+        use std::io::{Seek, SeekFrom};
+        use std::os::unix::fs::MetadataExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("sparse_source.img");
+
+        // 64MBの論理サイズに対し、データは先頭と中央付近のみ
+        let mut file = File::create(&source).unwrap();
+        file.write_all(b"header").unwrap();
+        file.seek(SeekFrom::Start(32 * 1024 * 1024)).unwrap();
+        file.write_all(b"payload").unwrap();
+        file.set_len(64 * 1024 * 1024).unwrap();
+        drop(file);
+
+        let meta = std::fs::metadata(&source).unwrap();
+        if meta.blocks() * 512 >= meta.len() {
+            return; // スパースファイルを作成できないファイルシステム
+        }
+
+        let (map, data) = read_sparse(&source).unwrap().unwrap();
+        assert_eq!(map.len, 64 * 1024 * 1024);
+        assert_eq!(map.data_len(), data.len() as u64);
+        // 読み込むのは割り当て済みのブロックのみ
+        assert!(data.len() as u64 <= meta.blocks() * 512);
+
+        let dest = temp_dir.path().join("sparse_dest.img");
+        map.write(&dest, &data).unwrap();
+        let dst_meta = std::fs::metadata(&dest).unwrap();
+        assert_eq!(dst_meta.len(), meta.len());
+        assert!(dst_meta.blocks() * 512 < dst_meta.len());
+        assert_eq!(
+            map.hash(HashAlgorithm::Blake3, &data).unwrap(),
+            HashAlgorithm::Blake3.hash_file(&source).unwrap()
+        );
+
+        // 通常のファイルは対象外
+        let plain = temp_dir.path().join("plain.txt");
+        std::fs::write(&plain, vec![1u8; 128 * 1024]).unwrap();
+        assert!(read_sparse(&plain).unwrap().is_none());
+    }
+
     #[test]
     fn test_default_engine() {
         let engine = CopyEngine::default();
@@ -0,0 +1,623 @@
+//! # スケジューラデーモン
+//!
+//! systemd（ユーザーインスタンス）や launchd を使用できないサーバー・コンテナ向けに、
+//! `backup-suite daemon` として常駐し、スケジュールされたバックアップを自身で実行します。
+//!
+//! # 仕組み
+//!
+//! - 定期的（[`TICK_INTERVAL`]）に設定を読み込み、[`Scheduler`](super::Scheduler) と同じ
+//!   ユニット（全優先度と `schedule` が設定されたジョブ）の実行時刻を確認する
+//! - 実行時刻を過ぎたユニットは、生成されるユニットファイルと同じく
+//!   `backup-suite run --priority <優先度>` / `run --job <名前>` を子プロセスとして実行する
+//! - ユニットは1つずつ順番に実行し、保存先のリポジトリロックが保持されている間
+//!   （手動のバックアップ・クリーンアップの実行中）は次の確認まで待つ
+//! - 確認から状態ファイルの保存までは設定ディレクトリの排他ロックを保持し、
+//!   cron から重複して起動された `daemon --once` が同じユニットを実行しないようにする
+//! - 次回実行時刻と実行結果は状態ファイル（`~/.config/backup-suite/daemon-state.json`）に
+//!   保存するため、停止中に過ぎた実行時刻は再起動後に1回だけまとめて実行される
+//!   （systemd timer の `Persistent=true` と同じ）
+//!
+//! 状態ファイルは `backup-suite schedule status` で表示されます。
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! use backup_suite::core::daemon::Daemon;
+//!
+//! let mut daemon = Daemon::from_current_exe().unwrap();
+//! daemon
+//!     .run_forever(|run| println!("{}: {}", run.unit, run.success))
+//!     .unwrap();
+//! ```
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::calendar::CalendarSpec;
+use super::config::Config;
+use super::events::EventSink;
+use super::lock::{self, LockKind, RepositoryLock};
+use super::scheduler::ScheduleUnit;
+use crate::error::BackupError;
+use crate::i18n::Language;
+use crate::ui::ConsoleSink;
+
+/// 状態ファイル名（設定ディレクトリ内）
+pub const DAEMON_STATE_FILE: &str = "daemon-state.json";
+
+/// 実行時刻を確認する間隔
+pub const TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// ユニットごとの実行状態
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnitState {
+    /// 次回実行時刻の計算に使用したスケジュール（`OnCalendar` 形式、変更されたら再計算）
+    pub schedule: String,
+    /// 次回実行時刻
+    pub next_run: Option<DateTime<Utc>>,
+    /// 最終実行時刻
+    pub last_run: Option<DateTime<Utc>>,
+    /// 最終実行が成功したか
+    pub last_success: Option<bool>,
+    /// 最終実行の終了コード
+    pub last_exit_code: Option<i32>,
+}
+
+/// デーモンの状態（状態ファイルの内容）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonState {
+    /// 実行中のデーモンのPID
+    pub pid: Option<u32>,
+    /// 実行中のデーモンのホスト名
+    pub host: Option<String>,
+    /// デーモンの開始日時
+    pub started_at: Option<DateTime<Utc>>,
+    /// 最後に実行時刻を確認した日時
+    pub heartbeat: Option<DateTime<Utc>>,
+    /// ユニットID（`high`、`job-nightly` など）ごとの状態
+    #[serde(default)]
+    pub units: BTreeMap<String, UnitState>,
+}
+
+impl DaemonState {
+    /// 状態ファイルのパス（`~/.config/backup-suite/daemon-state.json`）
+    ///
+    /// # Errors
+    ///
+    /// ホームディレクトリが取得できない場合にエラーを返します。
+    pub fn path() -> Result<PathBuf> {
+        let config_dir = Config::config_path()?
+            .parent()
+            .context("設定ディレクトリが見つかりません")?
+            .to_path_buf();
+        Ok(config_dir.join(DAEMON_STATE_FILE))
+    }
+
+    /// 状態ファイルを読み込み（存在しない場合はデフォルト）
+    ///
+    /// # Errors
+    ///
+    /// ファイルの読み込み、または解析に失敗した場合にエラーを返します。
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("状態ファイル読み込み失敗: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("状態ファイル解析失敗: {}", path.display()))
+    }
+
+    /// 状態ファイルを保存（一時ファイルに書き込んでからリネーム）
+    ///
+    /// # Errors
+    ///
+    /// ファイルの書き込みに失敗した場合にエラーを返します。
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("状態ファイル書き込み失敗: {}", temp_path.display()))?;
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("状態ファイル書き込み失敗: {}", path.display()))?;
+        Ok(())
+    }
+
+    /// デーモンが実行中か（同一ホスト上でPIDのプロセスが存在する）
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        match (self.pid, &self.host) {
+            (Some(pid), Some(host)) => *host == lock::hostname() && lock::process_alive(pid),
+            _ => false,
+        }
+    }
+}
+
+/// ユニットの実行結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitRun {
+    /// ユニットID
+    pub unit: String,
+    /// 成功したか
+    pub success: bool,
+    /// 終了コード（シグナルで終了した場合は `None`）
+    pub exit_code: Option<i32>,
+}
+
+/// スケジューラデーモン
+pub struct Daemon {
+    executable: PathBuf,
+    state_path: PathBuf,
+    state: DaemonState,
+    event_sink: Option<Arc<dyn EventSink>>,
+}
+
+impl Daemon {
+    /// 実行ファイルと状態ファイルを指定して作成
+    ///
+    /// # Errors
+    ///
+    /// 状態ファイルの読み込みに失敗した場合にエラーを返します。
+    pub fn new(executable: PathBuf, state_path: PathBuf) -> Result<Self> {
+        let state = DaemonState::load(&state_path)?;
+        Ok(Self {
+            executable,
+            state_path,
+            state,
+            event_sink: None,
+        })
+    }
+
+    /// イベント（警告）の送り先を設定
+    ///
+    /// 設定しない場合はコンソールに表示します。
+    #[must_use]
+    pub fn with_event_sink(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(sink);
+        self
+    }
+
+    /// イベントの送り先（未設定の場合はコンソールに表示）
+    fn events(&self) -> Arc<dyn EventSink> {
+        self.event_sink
+            .clone()
+            .unwrap_or_else(|| Arc::new(ConsoleSink::new(Language::detect())))
+    }
+
+    /// 現在の実行ファイルとデフォルトの状態ファイルで作成
+    ///
+    /// # Errors
+    ///
+    /// 実行ファイルのパス・状態ファイルのパスが取得できない場合、
+    /// または状態ファイルの読み込みに失敗した場合にエラーを返します。
+    pub fn from_current_exe() -> Result<Self> {
+        Self::new(std::env::current_exe()?, DaemonState::path()?)
+    }
+
+    /// 現在の状態
+    #[must_use]
+    pub fn state(&self) -> &DaemonState {
+        &self.state
+    }
+
+    /// 常駐して実行時刻になったユニットを実行し続ける
+    ///
+    /// 各ユニットの実行後に `on_run` を呼び出します。
+    ///
+    /// # Errors
+    ///
+    /// 別のデーモンが実行中の場合、または状態ファイルの保存に失敗した場合にエラーを返します。
+    /// 設定ファイルの読み込みエラーは警告としてイベントの送り先に送り、次の確認まで待ちます。
+    pub fn run_forever(&mut self, mut on_run: impl FnMut(&UnitRun)) -> Result<()> {
+        self.ensure_not_running()?;
+        self.state.pid = Some(std::process::id());
+        self.state.host = Some(lock::hostname());
+        self.state.started_at = Some(Utc::now());
+        self.state.save(&self.state_path)?;
+
+        loop {
+            match Config::load() {
+                Ok(config) => match self.run_pending(&config, Local::now()) {
+                    Ok(runs) => runs.iter().for_each(&mut on_run),
+                    // `daemon --once` が確認中の場合は次の確認まで待つ
+                    Err(e) if is_locked(&e) => self.events().warning(format!("{e:#}")),
+                    Err(e) => return Err(e),
+                },
+                Err(e) => self
+                    .events()
+                    .warning(format!("設定ファイルの読み込みに失敗しました: {e:#}")),
+            }
+            std::thread::sleep(TICK_INTERVAL);
+        }
+    }
+
+    /// 実行時刻になったユニットを1回だけ確認・実行（cron などから定期的に呼び出す場合）
+    ///
+    /// # Errors
+    ///
+    /// 別のデーモンが実行中の場合、別の `daemon --once` が確認中の場合、
+    /// または状態ファイルの保存に失敗した場合にエラーを返します。
+    pub fn run_once(&mut self, config: &Config) -> Result<Vec<UnitRun>> {
+        self.ensure_not_running()?;
+        self.run_pending(config, Local::now())
+    }
+
+    /// 別のデーモンが実行中でないことを確認
+    fn ensure_not_running(&mut self) -> Result<()> {
+        self.state = DaemonState::load(&self.state_path)?;
+        if self.state.is_running() && self.state.pid != Some(std::process::id()) {
+            anyhow::bail!(
+                "スケジューラデーモンは既に実行中です（PID: {}）",
+                self.state.pid.unwrap_or_default()
+            );
+        }
+        Ok(())
+    }
+
+    /// `now` 時点で実行時刻を過ぎたユニットを順番に実行
+    ///
+    /// 初めて確認するユニットは次回実行時刻を記録するだけで、実行しません。
+    /// 停止中に複数回の実行時刻が過ぎていても、実行は1回にまとめます。
+    /// 最終実行時刻と次回実行時刻は各ユニットの実行終了時刻（`now` からの経過時間を加算）を
+    /// 基準にするため、実行間隔より長くかかったユニットが続けて実行されることはありません。
+    ///
+    /// 確認から状態ファイルの保存まで状態ファイルのディレクトリの排他ロックを保持し、
+    /// ロック取得後に状態ファイルを読み込み直します。
+    ///
+    /// # Errors
+    ///
+    /// 別のプロセスが確認中（ロックを保持している）の場合、または状態ファイルの
+    /// 読み込み・保存に失敗した場合にエラーを返します。
+    pub fn run_pending(&mut self, config: &Config, now: DateTime<Local>) -> Result<Vec<UnitRun>> {
+        let state_dir = self
+            .state_path
+            .parent()
+            .context("状態ファイルのディレクトリが見つかりません")?;
+        fs::create_dir_all(state_dir)?;
+        let _lock = RepositoryLock::acquire(state_dir, LockKind::Exclusive, "daemon")?;
+        self.state = DaemonState::load(&self.state_path)?;
+        let started = Instant::now();
+        let events = self.events();
+
+        let units = if config.schedule.enabled {
+            ScheduleUnit::all(config)
+        } else {
+            Vec::new()
+        };
+        let ids: BTreeSet<String> = units.iter().map(ScheduleUnit::id).collect();
+        self.state.units.retain(|id, _| ids.contains(id));
+        self.state.heartbeat = Some(now.with_timezone(&Utc));
+
+        let mut runs = Vec::new();
+        for unit in units {
+            let id = unit.id();
+            let calendar = match unit.calendar(config) {
+                Ok(calendar) => calendar,
+                Err(e) => {
+                    events.warning(format!("{id} のスケジュールが不正です: {e:#}"));
+                    continue;
+                }
+            };
+            let schedule = calendar.to_on_calendar();
+            let delay = unit.randomized_delay_secs(config);
+
+            let entry = self.state.units.entry(id.clone()).or_insert(UnitState {
+                schedule: schedule.clone(),
+                next_run: None,
+                last_run: None,
+                last_success: None,
+                last_exit_code: None,
+            });
+            if entry.schedule != schedule || entry.next_run.is_none() {
+                entry.schedule = schedule;
+                entry.next_run = next_run(&calendar, &now, delay);
+            }
+            if entry.next_run.is_none_or(|due| due > now) {
+                continue;
+            }
+
+            // 同じ保存先で他のバックアップ・クリーンアップが実行中の場合は次の確認まで待つ
+            if repository_busy(&config.backup.destination) {
+                continue;
+            }
+
+            let status = Command::new(&self.executable)
+                .args(unit.run_args())
+                .status()
+                .with_context(|| format!("{} の実行に失敗しました", self.executable.display()));
+            let (success, exit_code) = match &status {
+                Ok(status) => (status.success(), status.code()),
+                Err(e) => {
+                    events.warning(format!("{e:#}"));
+                    (false, None)
+                }
+            };
+
+            // 実行終了時刻を基準に記録（実行中に過ぎた実行時刻は次回にまとめない）
+            let finished = now + chrono::Duration::from_std(started.elapsed()).unwrap_or_default();
+            entry.last_run = Some(finished.with_timezone(&Utc));
+            entry.last_success = Some(success);
+            entry.last_exit_code = exit_code;
+            entry.next_run = next_run(&calendar, &finished, delay);
+            runs.push(UnitRun {
+                unit: id,
+                success,
+                exit_code,
+            });
+            self.state.save(&self.state_path)?;
+        }
+
+        self.state.save(&self.state_path)?;
+        Ok(runs)
+    }
+}
+
+/// 次回実行時刻（ランダム遅延を加算）
+fn next_run(
+    calendar: &CalendarSpec,
+    after: &DateTime<Local>,
+    delay_secs: u64,
+) -> Option<DateTime<Utc>> {
+    let next = calendar.next_after(after)?;
+    let delay = if delay_secs > 0 {
+        rand::rng().random_range(0..=delay_secs)
+    } else {
+        0
+    };
+    let delay = chrono::Duration::seconds(i64::try_from(delay).unwrap_or(i64::MAX));
+    Some((next + delay).with_timezone(&Utc))
+}
+
+/// ロックの競合によるエラーか
+fn is_locked(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<BackupError>(),
+        Some(BackupError::RepositoryLocked { .. })
+    )
+}
+
+/// 保存先に有効なリポジトリロックがあるか
+fn repository_busy(destination: &Path) -> bool {
+    lock::list_locks(destination)
+        .iter()
+        .any(|(_, info)| !info.is_stale())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::core::events::Event;
+    use crate::core::job::BackupJob;
+    use chrono::TimeZone;
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// 引数を記録するだけの実行ファイル
+    fn fake_executable(dir: &Path) -> (PathBuf, PathBuf) {
+        let log = dir.join("runs.log");
+        let script = dir.join("fake-backup-suite");
+        fs::write(
+            &script,
+            format!("#!/bin/sh\necho \"$@\" >> {}\n", log.display()),
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        (script, log)
+    }
+
+    fn test_config(dir: &Path) -> Config {
+        let mut config = Config::default();
+        config.backup.destination = dir.join("backups");
+        config.schedule.enabled = true;
+        config.schedule.high_frequency = "hourly".to_string();
+        config.jobs.insert(
+            "nightly".to_string(),
+            BackupJob {
+                schedule: Some("30 1 * * *".to_string()),
+                ..Default::default()
+            },
+        );
+        config
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2025, 1, day, hour, minute, 0)
+            .single()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_run_pending_runs_due_units_once() {
+        let temp = TempDir::new().unwrap();
+        let (executable, log) = fake_executable(temp.path());
+        let state_path = temp.path().join(DAEMON_STATE_FILE);
+        let config = test_config(temp.path());
+
+        // 2025-01-01 は水曜日: 初回は次回実行時刻を記録するだけ
+        let mut daemon = Daemon::new(executable.clone(), state_path.clone()).unwrap();
+        assert!(daemon
+            .run_pending(&config, at(1, 10, 30))
+            .unwrap()
+            .is_empty());
+        assert_eq!(daemon.state().units.len(), 4);
+
+        let runs = daemon.run_pending(&config, at(1, 12, 5)).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].unit, "high");
+        assert!(runs[0].success);
+        assert_eq!(fs::read_to_string(&log).unwrap(), "run --priority high\n");
+
+        // 停止中に過ぎた実行時刻は再起動後に1回だけ実行される
+        let mut restarted = Daemon::new(executable, state_path).unwrap();
+        let runs = restarted.run_pending(&config, at(3, 9, 0)).unwrap();
+        let units: Vec<&str> = runs.iter().map(|r| r.unit.as_str()).collect();
+        assert_eq!(units, ["high", "job-nightly"]);
+        let state = &restarted.state().units["job-nightly"];
+        assert_eq!(state.last_success, Some(true));
+        assert!(state.next_run.unwrap() > at(3, 9, 0));
+    }
+
+    #[test]
+    fn test_long_run_schedules_from_its_end() {
+        let temp = TempDir::new().unwrap();
+        let script = temp.path().join("slow-backup-suite");
+        fs::write(&script, "#!/bin/sh\nsleep 2\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let config = test_config(temp.path());
+
+        let mut daemon = Daemon::new(script, temp.path().join(DAEMON_STATE_FILE)).unwrap();
+        daemon.run_pending(&config, at(1, 10, 30)).unwrap();
+
+        // 12:59:59 に開始して 13:00 を過ぎて終了した hourly のユニットは、
+        // 13:00 に続けて実行せず 14:00 に実行する
+        let tick = Local
+            .with_ymd_and_hms(2025, 1, 1, 12, 59, 59)
+            .single()
+            .unwrap();
+        assert_eq!(daemon.run_pending(&config, tick).unwrap().len(), 1);
+        let state = &daemon.state().units["high"];
+        assert!(state.last_run.unwrap() >= at(1, 13, 0).with_timezone(&Utc));
+        assert_eq!(state.next_run, Some(at(1, 14, 0).with_timezone(&Utc)));
+        assert!(daemon
+            .run_pending(&config, at(1, 13, 1))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_run_pending_waits_for_repository_lock() {
+        let temp = TempDir::new().unwrap();
+        let (executable, log) = fake_executable(temp.path());
+        let config = test_config(temp.path());
+        fs::create_dir_all(&config.backup.destination).unwrap();
+
+        let mut daemon = Daemon::new(executable, temp.path().join(DAEMON_STATE_FILE)).unwrap();
+        daemon.run_pending(&config, at(1, 10, 30)).unwrap();
+
+        let lock =
+            RepositoryLock::acquire(&config.backup.destination, LockKind::Exclusive, "backup")
+                .unwrap();
+        assert!(daemon
+            .run_pending(&config, at(1, 11, 5))
+            .unwrap()
+            .is_empty());
+        assert!(!log.exists());
+
+        drop(lock);
+        assert_eq!(daemon.run_pending(&config, at(1, 11, 6)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_run_pending_is_exclusive() {
+        let temp = TempDir::new().unwrap();
+        let (executable, log) = fake_executable(temp.path());
+        let state_path = temp.path().join(DAEMON_STATE_FILE);
+        let config = test_config(temp.path());
+
+        let mut first = Daemon::new(executable.clone(), state_path.clone()).unwrap();
+        first.run_pending(&config, at(1, 10, 30)).unwrap();
+
+        // 別のプロセスが確認中の間は実行しない
+        let mut second = Daemon::new(executable, state_path).unwrap();
+        let lock = RepositoryLock::acquire(temp.path(), LockKind::Exclusive, "daemon").unwrap();
+        let err = second.run_pending(&config, at(1, 11, 5)).unwrap_err();
+        assert!(is_locked(&err));
+        assert!(!log.exists());
+        drop(lock);
+
+        // ロック取得後に状態ファイルを読み込み直すため、実行済みのユニットは再実行しない
+        assert_eq!(first.run_pending(&config, at(1, 11, 5)).unwrap().len(), 1);
+        assert!(second
+            .run_pending(&config, at(1, 11, 5))
+            .unwrap()
+            .is_empty());
+        assert_eq!(fs::read_to_string(&log).unwrap(), "run --priority high\n");
+    }
+
+    #[test]
+    fn test_disabled_schedule_and_running_daemon() {
+        let temp = TempDir::new().unwrap();
+        let (executable, _) = fake_executable(temp.path());
+        let state_path = temp.path().join(DAEMON_STATE_FILE);
+        let mut config = test_config(temp.path());
+        config.schedule.enabled = false;
+
+        let mut daemon = Daemon::new(executable.clone(), state_path.clone()).unwrap();
+        assert!(daemon
+            .run_pending(&config, at(1, 10, 30))
+            .unwrap()
+            .is_empty());
+        assert!(daemon.state().units.is_empty());
+
+        // 同一ホストで別のデーモン（存在するプロセス）が実行中の場合はエラー
+        let running = DaemonState {
+            pid: Some(1),
+            host: Some(lock::hostname()),
+            ..Default::default()
+        };
+        running.save(&state_path).unwrap();
+        let mut other = Daemon::new(executable, state_path).unwrap();
+        assert!(other.run_once(&config).is_err());
+    }
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<Event>>);
+
+    impl EventSink for Recorder {
+        fn on_event(&self, event: &Event) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn test_warnings_go_to_event_sink() {
+        let temp = TempDir::new().unwrap();
+        let mut config = test_config(temp.path());
+        config.jobs.get_mut("nightly").unwrap().schedule = Some("not a schedule".to_string());
+
+        let recorder = Arc::new(Recorder::default());
+        let mut daemon = Daemon::new(
+            temp.path().join("missing-executable"),
+            temp.path().join(DAEMON_STATE_FILE),
+        )
+        .unwrap()
+        .with_event_sink(recorder.clone());
+        daemon.run_pending(&config, at(1, 10, 30)).unwrap();
+        let runs = daemon.run_pending(&config, at(1, 12, 5)).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert!(!runs[0].success);
+
+        // 不正なスケジュール（2回の確認）と実行ファイルの起動失敗
+        let warnings: Vec<String> = recorder
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                Event::Warning(message) => Some(message.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(
+            warnings
+                .iter()
+                .filter(|w| w.contains("job-nightly"))
+                .count(),
+            2
+        );
+        assert!(warnings.iter().any(|w| w.contains("missing-executable")));
+    }
+}
@@ -0,0 +1,168 @@
+//! # イベント通知
+//!
+//! バックアップ・復元・クリーンアップの進行状況を型付きのイベントとして通知します。
+//!
+//! 各エンジンは標準出力・標準エラー出力に直接書き込まず、[`EventSink`] にイベントを送ります。
+//! 既定のシンクはコンソールに表示する [`ConsoleSink`](crate::ui::ConsoleSink) で、
+//! `with_event_sink` で独自のシンクに置き換えるとログやUIに転送できます。
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! use backup_suite::core::events::{Event, EventSink};
+//! use backup_suite::{BackupRunner, Config};
+//! use std::sync::Arc;
+//!
+//! struct LogSink;
+//!
+//! impl EventSink for LogSink {
+//!     fn on_event(&self, event: &Event) {
+//!         match event {
+//!             Event::FileFailed { path, message, .. } => {
+//!                 eprintln!("failed: {}: {message}", path.display());
+//!             }
+//!             Event::Warning(message) => eprintln!("warning: {message}"),
+//!             _ => {}
+//!         }
+//!     }
+//! }
+//!
+//! let config = Config::load().unwrap();
+//! let mut runner = BackupRunner::new(config, false).with_event_sink(Arc::new(LogSink));
+//! let result = runner.run(None, None).unwrap();
+//! ```
+
+use std::path::PathBuf;
+
+use super::report::ErrorKind;
+
+/// イベントを送る処理
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Backup,
+    Restore,
+    /// 復元せずにバックアップを検証
+    Verify,
+    Cleanup,
+}
+
+/// 処理の段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// 対象ファイル・バックアップの収集
+    Scanning,
+    /// 収集完了（見つかったファイル数）、増分比較などで処理するファイルを決定中
+    Planning { found_files: usize },
+    /// ファイルの転送（クリーンアップでは削除）
+    Processing { total_files: usize },
+    /// キャンセルされ、新しいファイルの処理を停止した
+    Cancelled,
+    /// メタデータ・履歴の保存と確定
+    Finalizing,
+    /// 完了（失敗したファイル数）
+    Finished { failed: usize },
+}
+
+/// 進行状況のイベント
+///
+/// ファイルのイベントはバックアップでは並列に送られるため、順序は保証されません。
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// 処理の段階が変わった
+    PhaseChanged { operation: Operation, phase: Phase },
+    /// ファイルの処理を開始した
+    FileStarted { path: PathBuf },
+    /// ファイルの処理が完了した（圧縮・暗号化後の書き込みバイト数を含む）
+    ///
+    /// クリーンアップでは削除したバックアップのディレクトリと解放したバイト数（`bytes_read`）を送ります。
+    FileFinished {
+        path: PathBuf,
+        bytes_read: u64,
+        bytes_written: u64,
+    },
+    /// ファイルの処理に失敗した
+    FileFailed {
+        path: PathBuf,
+        kind: ErrorKind,
+        message: String,
+    },
+    /// 処理済みのファイル数と読み込んだバイト数の累計
+    Progress {
+        files_done: usize,
+        total_files: usize,
+        bytes_done: u64,
+    },
+    /// 処理は継続する警告
+    Warning(String),
+    /// 処理モード・対象一覧などの情報
+    Info(String),
+}
+
+/// イベントの受け取り先
+///
+/// バックアップのワーカースレッドから呼ばれるため `Send + Sync` が必要です。
+/// 呼び出しは処理をブロックするため、重い処理はチャネルなどで別スレッドに渡してください。
+pub trait EventSink: Send + Sync {
+    fn on_event(&self, event: &Event);
+}
+
+impl dyn EventSink + '_ {
+    /// 警告を送る
+    pub fn warning(&self, message: impl Into<String>) {
+        self.on_event(&Event::Warning(message.into()));
+    }
+
+    /// 情報を送る
+    pub fn info(&self, message: impl Into<String>) {
+        self.on_event(&Event::Info(message.into()));
+    }
+
+    /// 段階の変更を送る
+    pub fn phase(&self, operation: Operation, phase: Phase) {
+        self.on_event(&Event::PhaseChanged { operation, phase });
+    }
+}
+
+/// すべてのイベントを破棄するシンク
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullSink;
+
+impl EventSink for NullSink {
+    fn on_event(&self, _event: &Event) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<Event>>);
+
+    impl EventSink for Recorder {
+        fn on_event(&self, event: &Event) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn test_helpers_send_events() {
+        let recorder = Recorder::default();
+        let sink: &dyn EventSink = &recorder;
+        sink.phase(Operation::Backup, Phase::Scanning);
+        sink.warning("disk almost full");
+        sink.info(String::from("full backup"));
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                Event::PhaseChanged {
+                    operation: Operation::Backup,
+                    phase: Phase::Scanning
+                },
+                Event::Warning("disk almost full".to_string()),
+                Event::Info("full backup".to_string()),
+            ]
+        );
+    }
+}
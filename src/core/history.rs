@@ -5,6 +5,7 @@ use std::fs;
 use std::path::PathBuf;
 use walkdir::WalkDir;
 
+use super::report::{RunReport, RunSummary};
 use super::{Config, Priority};
 
 /// バックアップステータス
@@ -14,8 +15,34 @@ pub enum BackupStatus {
     Success,
     Failed,
     Partial,
+    /// キャンセルされ、処理済みのファイルのみを保存
+    Cancelled,
 }
 
+/// 対象ごとのバックアップ結果
+///
+/// # フィールド
+///
+/// * `path` - バックアップ対象のパス
+/// * `category` / `priority` - 対象のカテゴリと優先度
+/// * `files` - バックアップしたファイル数（増分バックアップでは変更されたファイル数）
+/// * `failed` - 失敗したファイル数
+/// * `success` - 対象全体が成功したか（pre_backup フックやコマンドの失敗でスキップされた場合は `false`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TargetRecord {
+    pub path: PathBuf,
+    pub category: String,
+    pub priority: Priority,
+    pub files: usize,
+    pub failed: usize,
+    pub success: bool,
+}
+
+/// 履歴に保持するエントリ数
+///
+/// 各対象の最後に成功したバックアップは、RPOの確認のためこれより古くても保持します。
+pub const MAX_HISTORY_ENTRIES: usize = 100;
+
 /// バックアップ履歴エントリ（Phase 2拡張版）
 ///
 /// 1回のバックアップ実行の詳細な記録を保持します。
@@ -33,6 +60,9 @@ pub enum BackupStatus {
 /// * `encrypted` - 暗号化されているか
 /// * `duration_ms` - 処理時間（ミリ秒）
 /// * `error_message` - エラーメッセージ（失敗時）
+/// * `inconsistent_files` - バックアップ中に変更され続けたファイル（内容が不整合の可能性）
+/// * `targets` - 対象ごとの結果
+/// * `summary` - 実行レポートの集計（詳細はスナップショット内の [`REPORT_FILE`](super::report::REPORT_FILE)）
 /// * `success` - 後方互換性のための成功フラグ
 ///
 /// # 使用例
@@ -71,6 +101,15 @@ pub struct BackupHistory {
     pub duration_ms: u64,
     #[serde(default)]
     pub error_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inconsistent_files: Vec<PathBuf>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<TargetRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<RunSummary>,
+    /// バックアップを作成したジョブ名（`run --job` で実行した場合）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job: Option<String>,
     // 後方互換性のため残す
     pub success: bool,
 }
@@ -135,10 +174,81 @@ impl BackupHistory {
             encrypted,
             duration_ms: 0,
             error_message: None,
+            inconsistent_files: Vec::new(),
+            targets: Vec::new(),
+            summary: None,
+            job: None,
             success,
         }
     }
 
+    /// バックアップ中に変更され続けたファイルを設定
+    ///
+    /// 不整合なファイルがある場合、成功したバックアップは `Partial` として記録されます。
+    #[must_use]
+    pub fn with_inconsistent_files(mut self, files: Vec<PathBuf>) -> Self {
+        if !files.is_empty() && self.status == BackupStatus::Success {
+            self.status = BackupStatus::Partial;
+        }
+        self.inconsistent_files = files;
+        self
+    }
+
+    /// キャンセルされたバックアップとして記録
+    #[must_use]
+    pub fn with_cancelled(mut self, cancelled: bool) -> Self {
+        if cancelled {
+            self.status = BackupStatus::Cancelled;
+            self.success = false;
+            self.error_message = Some("キャンセルされました".to_string());
+        }
+        self
+    }
+
+    /// 実行レポートの処理時間と集計を設定
+    #[must_use]
+    pub fn with_report(mut self, report: &RunReport) -> Self {
+        self.duration_ms = report.duration_ms;
+        self.summary = Some(report.summary.clone());
+        self
+    }
+
+    /// バックアップを作成したジョブ名を設定
+    #[must_use]
+    pub fn with_job(mut self, job: Option<String>) -> Self {
+        self.job = job;
+        self
+    }
+
+    /// 対象ごとの結果を設定
+    ///
+    /// 全対象の優先度・カテゴリが同じ場合は、エントリの `priority` / `category` にも設定します。
+    #[must_use]
+    pub fn with_targets(mut self, targets: Vec<TargetRecord>) -> Self {
+        if let Some(first) = targets.first() {
+            if targets.iter().all(|t| t.priority == first.priority) {
+                self.priority = Some(first.priority);
+            }
+            if targets.iter().all(|t| t.category == first.category) {
+                self.category = Some(first.category.clone());
+            }
+        }
+        self.targets = targets;
+        self
+    }
+
+    /// 対象の最後に成功したバックアップのエントリ
+    #[must_use]
+    pub fn last_success_for<'a>(
+        entries: &'a [BackupHistory],
+        path: &std::path::Path,
+    ) -> Option<&'a BackupHistory> {
+        entries
+            .iter()
+            .filter(|h| h.targets.iter().any(|t| t.path == path && t.success))
+            .max_by_key(|h| h.timestamp)
+    }
+
     /// 履歴ファイルのパスを取得
     ///
     /// # 戻り値
@@ -161,7 +271,8 @@ impl BackupHistory {
     /// 履歴エントリを保存
     ///
     /// 新しいバックアップ履歴を履歴ファイルに追加します。
-    /// 履歴は最新100件のみ保持され、古いエントリは自動削除されます。
+    /// 履歴は最新100件（[`MAX_HISTORY_ENTRIES`]）と各対象の最後に成功したエントリのみ保持され、
+    /// 古いエントリは自動削除されます。
     ///
     /// # 引数
     ///
@@ -200,10 +311,7 @@ impl BackupHistory {
         let mut history = Self::load_all()?;
         history.push(entry.clone());
 
-        // 最新100件のみ保持
-        if history.len() > 100 {
-            history.drain(0..history.len() - 100);
-        }
+        trim_history(&mut history);
 
         // Windows での PathBuf シリアライゼーション問題を回避するため、
         // pretty フォーマットを無効化
@@ -408,7 +516,7 @@ impl BackupHistory {
     /// ```
     pub fn get_recent_entries(count: usize) -> Result<Vec<BackupHistory>> {
         let mut all = Self::load_all()?;
-        all.sort_by(|a, b| b.timestamp.cmp(&a.timestamp)); // 新しい順
+        all.sort_by_key(|b| std::cmp::Reverse(b.timestamp)); // 新しい順
         Ok(all.into_iter().take(count).collect())
     }
 
@@ -452,7 +560,11 @@ impl BackupHistory {
             .filter_map(std::result::Result::ok)
             .filter(|e| e.file_type().is_dir())
             .map(|e| e.path().to_path_buf())
-            .filter(|p| p != dest)
+            .filter(|p| {
+                p != dest
+                    && !super::staging::is_staging_dir(p)
+                    && !p.ends_with(super::lock::LOCK_DIR)
+            })
             .collect();
 
         dirs.sort_by(|a, b| {
@@ -469,3 +581,98 @@ impl BackupHistory {
 struct HistoryFile {
     history: Vec<BackupHistory>,
 }
+
+/// 最新 [`MAX_HISTORY_ENTRIES`] 件と、各対象の最後に成功したエントリを残して削除
+fn trim_history(history: &mut Vec<BackupHistory>) {
+    if history.len() <= MAX_HISTORY_ENTRIES {
+        return;
+    }
+    let cutoff = history.len() - MAX_HISTORY_ENTRIES;
+
+    // 新しい順に走査し、各対象で最初に見つかった成功エントリを保持
+    let mut seen = std::collections::HashSet::new();
+    let mut keep = vec![false; history.len()];
+    for (index, entry) in history.iter().enumerate().rev() {
+        for target in entry.targets.iter().filter(|t| t.success) {
+            if seen.insert(target.path.clone()) {
+                keep[index] = true;
+            }
+        }
+    }
+
+    let mut index = 0;
+    history.retain(|_| {
+        let retained = index >= cutoff || keep[index];
+        index += 1;
+        retained
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, success: bool) -> BackupHistory {
+        BackupHistory::new(PathBuf::from("/backup"), 1, 1, success, false, false).with_targets(
+            vec![TargetRecord {
+                path: PathBuf::from(path),
+                category: "docs".to_string(),
+                priority: Priority::High,
+                files: 1,
+                failed: usize::from(!success),
+                success,
+            }],
+        )
+    }
+
+    #[test]
+    fn test_with_targets_sets_common_priority_and_category() {
+        let history = entry("/data/docs", true);
+        assert_eq!(history.priority, Some(Priority::High));
+        assert_eq!(history.category.as_deref(), Some("docs"));
+
+        let mut mixed = entry("/data/docs", true).targets;
+        mixed.push(TargetRecord {
+            priority: Priority::Low,
+            ..mixed[0].clone()
+        });
+        let history = BackupHistory::new(PathBuf::from("/backup"), 2, 2, true, false, false)
+            .with_targets(mixed);
+        assert_eq!(history.priority, None);
+        assert_eq!(history.category.as_deref(), Some("docs"));
+    }
+
+    #[test]
+    fn test_trim_history_keeps_last_success_per_target() {
+        let mut history = vec![entry("/data/old", true), entry("/data/old", false)];
+        history.extend((0..MAX_HISTORY_ENTRIES).map(|_| entry("/data/busy", true)));
+
+        trim_history(&mut history);
+        assert_eq!(history.len(), MAX_HISTORY_ENTRIES + 1);
+        assert_eq!(history[0].targets[0].path, PathBuf::from("/data/old"));
+        assert!(history[0].success);
+        assert!(
+            BackupHistory::last_success_for(&history, std::path::Path::new("/data/old")).is_some()
+        );
+    }
+
+    #[test]
+    fn test_history_with_targets_round_trips_toml() {
+        let file = HistoryFile {
+            history: vec![entry("/data/docs", true), entry("/data/docs", false)],
+        };
+        let parsed: HistoryFile = toml::from_str(&toml::to_string(&file).unwrap()).unwrap();
+        assert_eq!(parsed.history[1].targets, file.history[1].targets);
+    }
+
+    #[test]
+    fn test_with_cancelled_marks_entry_cancelled() {
+        let history = entry("/data/docs", true).with_cancelled(true);
+        assert_eq!(history.status, BackupStatus::Cancelled);
+        assert!(!history.success);
+        assert!(history.error_message.is_some());
+
+        let history = entry("/data/docs", true).with_cancelled(false);
+        assert_eq!(history.status, BackupStatus::Success);
+    }
+}
@@ -0,0 +1,417 @@
+//! # フックモジュール
+//!
+//! バックアップの前後に任意のコマンドを実行するフック機能を提供します。
+//!
+//! # フックの種類
+//!
+//! - **`pre_backup`**: バックアップ前（DBダンプ、サービス停止、キャッシュのフラッシュなど）
+//! - **`post_backup`**: バックアップ後に常に実行（通知、アンマウント、サービス再開など）
+//! - **`on_failure`**: バックアップが失敗した場合に追加で実行
+//!
+//! フックはグローバル設定（`[hooks]`）とターゲットごと（`[targets.hooks]`）に設定できます。
+//! コマンドはシェル（Unix: `sh -c`、Windows: `cmd /C`）経由で実行され、
+//! 実行内容は `BACKUP_SUITE_*` 環境変数で渡されます。
+//! フックの標準出力・標準エラー出力はどちらも本体の標準エラー出力に表示されます。
+//!
+//! # 設定例
+//!
+//! ```toml
+//! [hooks]
+//! post_backup = "notify-send \"backup $BACKUP_SUITE_STATUS\""
+//!
+//! [[targets]]
+//! path = "/var/lib/postgresql/dumps"
+//! # ...
+//! [targets.hooks]
+//! pre_backup = "pg_dump mydb > /var/lib/postgresql/dumps/mydb.sql"
+//! timeout_secs = 600
+//! abort_on_pre_failure = true
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// フックのデフォルトタイムアウト（秒）
+pub const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 300;
+
+fn default_timeout_secs() -> u64 {
+    DEFAULT_HOOK_TIMEOUT_SECS
+}
+
+fn default_abort_on_pre_failure() -> bool {
+    true
+}
+
+/// フック設定
+///
+/// # フィールド
+///
+/// * `pre_backup` - バックアップ前に実行するコマンド
+/// * `post_backup` - バックアップ後に実行するコマンド
+/// * `on_failure` - 失敗時に実行するコマンド
+/// * `timeout_secs` - 各フックのタイムアウト（秒、超過時は強制終了して失敗扱い）
+/// * `abort_on_pre_failure` - `pre_backup` 失敗時に対象のバックアップを中止するか
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_backup: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_backup: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_failure: Option<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_abort_on_pre_failure")]
+    pub abort_on_pre_failure: bool,
+}
+
+impl Default for HookConfig {
+    fn default() -> Self {
+        Self {
+            pre_backup: None,
+            post_backup: None,
+            on_failure: None,
+            timeout_secs: DEFAULT_HOOK_TIMEOUT_SECS,
+            abort_on_pre_failure: true,
+        }
+    }
+}
+
+impl HookConfig {
+    /// フックが1つも設定されていないか
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pre_backup.is_none() && self.post_backup.is_none() && self.on_failure.is_none()
+    }
+
+    /// 指定段階のコマンドを取得
+    #[must_use]
+    pub fn command(&self, stage: HookStage) -> Option<&str> {
+        match stage {
+            HookStage::PreBackup => self.pre_backup.as_deref(),
+            HookStage::PostBackup => self.post_backup.as_deref(),
+            HookStage::OnFailure => self.on_failure.as_deref(),
+        }
+    }
+}
+
+/// フックの実行段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookStage {
+    PreBackup,
+    PostBackup,
+    OnFailure,
+}
+
+impl HookStage {
+    /// 設定キーと同じ名前を返す
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HookStage::PreBackup => "pre_backup",
+            HookStage::PostBackup => "post_backup",
+            HookStage::OnFailure => "on_failure",
+        }
+    }
+}
+
+/// フックに渡す実行情報（`BACKUP_SUITE_*` 環境変数）
+///
+/// # 環境変数
+///
+/// * `BACKUP_SUITE_HOOK` - 実行段階（pre_backup / post_backup / on_failure）
+/// * `BACKUP_SUITE_SNAPSHOT` - バックアップ名
+/// * `BACKUP_SUITE_DESTINATION` - 保存先ディレクトリ
+/// * `BACKUP_SUITE_TARGET_PATH` / `BACKUP_SUITE_TARGET_CATEGORY` - 対象（ターゲットフックのみ）
+/// * `BACKUP_SUITE_TOTAL_FILES` / `BACKUP_SUITE_SUCCESSFUL` / `BACKUP_SUITE_FAILED` - 結果件数（事後フックのみ）
+/// * `BACKUP_SUITE_STATUS` - success / failure（事後フックのみ）
+/// * `BACKUP_SUITE_ERROR` - エラー内容（失敗時のみ）
+#[derive(Debug, Clone, Default)]
+pub struct HookContext {
+    vars: Vec<(String, String)>,
+}
+
+impl HookContext {
+    /// 保存先から作成
+    #[must_use]
+    pub fn new(destination: &Path) -> Self {
+        Self::default().with_var("BACKUP_SUITE_DESTINATION", destination.display())
+    }
+
+    /// バックアップ名を追加
+    #[must_use]
+    pub fn with_snapshot(self, snapshot: &str) -> Self {
+        self.with_var("BACKUP_SUITE_SNAPSHOT", snapshot)
+    }
+
+    /// 対象ターゲットの情報を追加
+    #[must_use]
+    pub fn with_target(self, path: &Path, category: &str) -> Self {
+        self.with_var("BACKUP_SUITE_TARGET_PATH", path.display())
+            .with_var("BACKUP_SUITE_TARGET_CATEGORY", category)
+    }
+
+    /// 結果件数を追加
+    #[must_use]
+    pub fn with_counts(self, total: usize, successful: usize, failed: usize) -> Self {
+        let status = if failed == 0 { "success" } else { "failure" };
+        self.with_var("BACKUP_SUITE_TOTAL_FILES", total)
+            .with_var("BACKUP_SUITE_SUCCESSFUL", successful)
+            .with_var("BACKUP_SUITE_FAILED", failed)
+            .with_var("BACKUP_SUITE_STATUS", status)
+    }
+
+    /// エラー内容を追加
+    #[must_use]
+    pub fn with_error(self, error: &str) -> Self {
+        self.with_var("BACKUP_SUITE_STATUS", "failure")
+            .with_var("BACKUP_SUITE_ERROR", error)
+    }
+
+    fn with_var(mut self, key: &str, value: impl std::fmt::Display) -> Self {
+        self.vars.retain(|(k, _)| k != key);
+        self.vars.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// 環境変数の一覧
+    #[must_use]
+    pub fn vars(&self) -> &[(String, String)] {
+        &self.vars
+    }
+}
+
+/// フックの実行結果
+#[derive(Debug, Clone)]
+pub struct HookOutcome {
+    pub stage: HookStage,
+    pub command: String,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub duration: Duration,
+    /// 起動失敗時のエラー
+    pub error: Option<String>,
+}
+
+impl HookOutcome {
+    /// 監査ログ用のメタデータ
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "stage": self.stage.as_str(),
+            "command": self.command,
+            "success": self.success,
+            "exit_code": self.exit_code,
+            "timed_out": self.timed_out,
+            "duration_ms": u64::try_from(self.duration.as_millis()).unwrap_or(u64::MAX),
+            "error": self.error,
+        })
+    }
+
+    /// 失敗内容の説明
+    #[must_use]
+    pub fn describe_failure(&self) -> String {
+        if self.timed_out {
+            format!(
+                "{}フックがタイムアウトしました: {}",
+                self.stage.as_str(),
+                self.command
+            )
+        } else if let Some(ref e) = self.error {
+            format!(
+                "{}フックを起動できません: {} ({e})",
+                self.stage.as_str(),
+                self.command
+            )
+        } else {
+            format!(
+                "{}フックが失敗しました（終了コード: {}）: {}",
+                self.stage.as_str(),
+                self.exit_code
+                    .map_or_else(|| "シグナル".to_string(), |c| c.to_string()),
+                self.command
+            )
+        }
+    }
+}
+
+/// 設定されたフックを実行
+///
+/// 指定段階のコマンドが設定されていない場合は `None` を返します。
+/// タイムアウトを超えたプロセスは強制終了され、失敗として扱われます。
+#[must_use]
+pub fn run_hook(stage: HookStage, hooks: &HookConfig, ctx: &HookContext) -> Option<HookOutcome> {
+    let command = hooks.command(stage)?.to_string();
+    let timeout = Duration::from_secs(hooks.timeout_secs);
+    let started = Instant::now();
+
+    let mut cmd = shell_command(&command);
+    cmd.env("BACKUP_SUITE_HOOK", stage.as_str())
+        .envs(ctx.vars().iter().map(|(k, v)| (k.as_str(), v.as_str())))
+        .stdin(Stdio::null())
+        // JSON出力（`--output json`）を壊さないよう、フックの標準出力は標準エラー出力へ
+        .stdout(Stdio::from(std::io::stderr()));
+    // タイムアウト時にフックが起動した子プロセスもまとめて終了できるよう、
+    // 独立したプロセスグループで実行する
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+
+    let mut outcome = HookOutcome {
+        stage,
+        command,
+        success: false,
+        exit_code: None,
+        timed_out: false,
+        duration: Duration::ZERO,
+        error: None,
+    };
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            outcome.error = Some(e.to_string());
+            return Some(outcome);
+        }
+    };
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                outcome.success = status.success();
+                outcome.exit_code = status.code();
+                break;
+            }
+            Ok(None) if started.elapsed() >= timeout => {
+                kill_process_tree(&mut child);
+                let _ = child.wait();
+                outcome.timed_out = true;
+                break;
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => {
+                outcome.error = Some(e.to_string());
+                break;
+            }
+        }
+    }
+
+    outcome.duration = started.elapsed();
+    Some(outcome)
+}
+
+/// フックのプロセスグループ全体を強制終了
+///
+/// `sh -c` だけを終了すると、フックが起動したコマンドが標準出力などのパイプを
+/// 保持したまま残るため、グループ全体に `SIGKILL` を送ります。
+fn kill_process_tree(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        if let Ok(pgid) = libc::pid_t::try_from(child.id()) {
+            // SAFETY: process_group(0) で作成した、フック専用のプロセスグループにのみ送信する
+            if unsafe { libc::kill(-pgid, libc::SIGKILL) } == 0 {
+                return;
+            }
+        }
+    }
+    let _ = child.kill();
+}
+
+/// シェル経由でコマンドを実行する `Command` を作成
+pub(crate) fn shell_command(command: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    }
+    #[cfg(not(windows))]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_hook_receives_environment() {
+        let temp = TempDir::new().unwrap();
+        let out = temp.path().join("env.txt");
+        let hooks = HookConfig {
+            post_backup: Some(format!(
+                "echo \"$BACKUP_SUITE_HOOK $BACKUP_SUITE_SNAPSHOT $BACKUP_SUITE_FAILED $BACKUP_SUITE_STATUS\" > {}",
+                out.display()
+            )),
+            ..Default::default()
+        };
+        let ctx = HookContext::new(temp.path())
+            .with_snapshot("backup_1")
+            .with_counts(3, 2, 1);
+
+        let outcome = run_hook(HookStage::PostBackup, &hooks, &ctx).unwrap();
+        assert!(outcome.success);
+        assert_eq!(
+            std::fs::read_to_string(out).unwrap().trim(),
+            "post_backup backup_1 1 failure"
+        );
+        assert!(run_hook(HookStage::PreBackup, &hooks, &ctx).is_none());
+    }
+
+    #[test]
+    fn test_hook_failure_and_timeout() {
+        let ctx = HookContext::default();
+
+        let failing = HookConfig {
+            pre_backup: Some("exit 3".to_string()),
+            ..Default::default()
+        };
+        let failed = run_hook(HookStage::PreBackup, &failing, &ctx).unwrap();
+        assert!(!failed.success);
+        assert_eq!(failed.exit_code, Some(3));
+
+        let slow = HookConfig {
+            post_backup: Some("sleep 5".to_string()),
+            timeout_secs: 0,
+            ..Default::default()
+        };
+        let timed_out = run_hook(HookStage::PostBackup, &slow, &ctx).unwrap();
+        assert!(timed_out.timed_out);
+        assert!(!timed_out.success);
+        assert!(timed_out.duration < Duration::from_secs(5));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_hook_timeout_kills_child_processes() {
+        let temp = TempDir::new().unwrap();
+        let pid_file = temp.path().join("pid");
+        let hooks = HookConfig {
+            pre_backup: Some(format!("sleep 30 & echo $! > {}; wait", pid_file.display())),
+            timeout_secs: 1,
+            ..Default::default()
+        };
+
+        let outcome = run_hook(HookStage::PreBackup, &hooks, &HookContext::default()).unwrap();
+        assert!(outcome.timed_out);
+
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        let stat_path = format!("/proc/{}/stat", pid.trim());
+        let deadline = Instant::now() + Duration::from_secs(5);
+        // 終了済み（ゾンビを含む）であれば孫プロセスも停止している
+        let stopped = || {
+            std::fs::read_to_string(&stat_path)
+                .map_or(true, |stat| stat.split_whitespace().nth(2) == Some("Z"))
+        };
+        while !stopped() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        assert!(stopped(), "sleep started by the hook is still running");
+    }
+}
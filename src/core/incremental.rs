@@ -152,6 +152,42 @@ impl IncrementalBackupEngine {
         Ok(Some(backups[0].clone()))
     }
 
+    /// 最新のスナップショットを検索
+    ///
+    /// スナップショットモードで作成されたバックアップ（無圧縮・非暗号化の完全ツリー）のみを
+    /// 新しい順に探し、最初に見つかったもののパスとメタデータを返します。
+    ///
+    /// # 戻り値
+    ///
+    /// 成功時は最新スナップショットのパスとメタデータ（存在しない場合はNone）、失敗時はエラー
+    ///
+    /// # Errors
+    ///
+    /// 以下の場合にエラーを返します:
+    /// * バックアップディレクトリの読み込みに失敗した場合
+    pub fn find_latest_snapshot(&self) -> Result<Option<(PathBuf, BackupMetadata)>> {
+        if !self.backup_base.exists() {
+            return Ok(None);
+        }
+
+        let mut backups: Vec<PathBuf> = std::fs::read_dir(&self.backup_base)
+            .context("バックアップディレクトリの読み込み失敗")?
+            .filter_map(std::result::Result::ok)
+            .filter(|entry| entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false))
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("backup_"))
+            .map(|entry| entry.path())
+            .collect();
+
+        // タイムスタンプでソート（降順）
+        backups.sort_by(|a, b| b.cmp(a));
+        Ok(backups.into_iter().find_map(|path| {
+            BackupMetadata::load(&path)
+                .ok()
+                .filter(|metadata| metadata.snapshot)
+                .map(|metadata| (path, metadata))
+        }))
+    }
+
     /// 変更ファイルを検出
     ///
     /// 前回のバックアップメタデータと現在のファイルハッシュを比較し、
@@ -195,7 +231,9 @@ impl IncrementalBackupEngine {
             let previous_hash = previous_metadata.file_hashes.get(relative_path);
 
             // 現在のハッシュを計算
-            let current_hash = BackupMetadata::compute_file_hash(absolute_path)
+            let current_hash = previous_metadata
+                .hash_algorithm
+                .hash_file(absolute_path)
                 .context("ハッシュ計算失敗: absolute_path.display()".to_string())?;
 
             // ハッシュが異なる場合、または新規ファイルの場合は変更とみなす
@@ -4,7 +4,9 @@
 //!
 //! # 機能
 //!
-//! - **ハッシュ計算**: ファイルのSHA-256ハッシュ計算
+//! - **ハッシュ計算**: ファイルのSHA-256ハッシュ計算（BLAKE3も選択可能）
+//! - **ストリーミングハッシュ**: バックアップのコピーと同じ読み込みでハッシュを計算（[`StreamHasher`]）
+//! - **Merkleルート**: 全ファイルのハッシュから1つのダイジェストを計算し、スナップショットを識別
 //! - **メタデータ管理**: `.integrity` ファイルによるハッシュ保存
 //! - **検証**: 復元時のファイル整合性検証
 //!
@@ -29,16 +31,163 @@
 //! let is_valid = metadata.verify_file(&file_path, &file_path).unwrap();
 //! assert!(is_valid);
 //! ```
+//!
+//! # Merkleルート
+//!
+//! マニフェストの全ファイルハッシュを相対パス順（区切りは `/`）に並べた葉から、
+//! マニフェストと同じアルゴリズムで二分木を構成します。
+//!
+//! - 葉: `H("leaf\0" || パス || "\0" || ファイルハッシュ)`
+//! - 節: `H("node\0" || 左の子 || 右の子)`（ハッシュはいずれも16進数文字列）
+//! - 奇数個の段では、最後の要素をそのまま上の段に繰り上げます
+//!
+//! 同じ内容のスナップショットは同じルートになるため、署名・重複排除・複製先間の比較に利用できます。
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::io::Read;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 
+use super::copy_engine::SparseMap;
 use super::incremental::BackupType;
+use super::pack::PackEntry;
+use super::special::SpecialEntry;
+use crate::compression::CompressionType;
+
+/// ハッシュアルゴリズム
+///
+/// ハッシュ値はいずれも16進数文字列で表現されます。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    /// SHA-256（デフォルト）
+    #[default]
+    Sha256,
+    /// BLAKE3（大量のファイルで高速）
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// 設定・マニフェストで使用する名前
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+
+    /// ファイルのハッシュを計算
+    ///
+    /// # Errors
+    ///
+    /// ファイルのオープンまたは読み込みに失敗した場合にエラーを返します。
+    pub fn hash_file(self, path: &Path) -> Result<String> {
+        let file = fs::File::open(path)
+            .with_context(|| format!("ファイル読み込み失敗: {}", path.display()))?;
+        self.hash_reader(file).context("ファイル読み込みエラー")
+    }
+
+    /// データ全体のハッシュを計算
+    #[must_use]
+    pub fn hash_bytes(self, data: &[u8]) -> String {
+        let mut hasher = StreamHasher::new(self);
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// 読み込み元の終端までのハッシュを計算
+    ///
+    /// # Errors
+    ///
+    /// 読み込みに失敗した場合にエラーを返します。
+    #[allow(clippy::indexing_slicing)] // read() guarantees bytes_read <= buffer.len()
+    pub fn hash_reader(self, mut reader: impl Read) -> io::Result<String> {
+        let mut hasher = StreamHasher::new(self);
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let bytes_read = reader.read(&mut buffer)?;
+            if bytes_read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..bytes_read]);
+        }
+        Ok(hasher.finalize())
+    }
+}
+
+/// ストリーミングハッシュ計算
+///
+/// コピーや圧縮のために読み込んだデータをそのまま渡すことで、
+/// ハッシュ計算のためにファイルを読み直す必要がなくなります。
+///
+/// # 使用例
+///
+/// ```
+/// use backup_suite::core::integrity::{HashAlgorithm, StreamHasher};
+///
+/// let mut hasher = StreamHasher::new(HashAlgorithm::Blake3);
+/// hasher.update(b"hello ");
+/// hasher.update(b"world");
+/// assert_eq!(hasher.finalize(), HashAlgorithm::Blake3.hash_bytes(b"hello world"));
+/// ```
+#[derive(Clone)]
+pub enum StreamHasher {
+    Sha256(Sha256),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl StreamHasher {
+    /// 指定アルゴリズムで作成
+    #[must_use]
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => StreamHasher::Sha256(Sha256::new()),
+            HashAlgorithm::Blake3 => StreamHasher::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    /// データを追加
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            StreamHasher::Sha256(hasher) => hasher.update(data),
+            StreamHasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    /// ハッシュ値（16進数文字列）を取得
+    #[must_use]
+    pub fn finalize(self) -> String {
+        match self {
+            StreamHasher::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            StreamHasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+        }
+    }
+}
+
+/// メタデータ形式のバージョン（1.1: ハッシュアルゴリズムとMerkleルートを追加、1.2: パックファイルの索引を追加、
+/// 1.3: ファイルごとの圧縮形式を追加）
+pub const METADATA_VERSION: &str = "1.3";
+
+/// スナップショットで記録したファイルのサイズと更新日時
+///
+/// 次回のスナップショットで、内容を読み込まずに未変更のファイルを判定するために使用します
+/// （rsync の `--link-dest` と同様）。更新日時はUNIXエポックからのナノ秒です。
+///
+/// # フィールド
+///
+/// * `source` - バックアップ時点の元ファイルのサイズと更新日時
+/// * `stored` - スナップショット内のファイルのサイズと更新日時（リンク前に改変の有無を確認するため）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    pub source: (u64, u64),
+    pub stored: (u64, u64),
+}
 
 /// バックアップメタデータ
 ///
@@ -47,8 +196,18 @@ use super::incremental::BackupType;
 /// # フィールド
 ///
 /// * `version` - メタデータ形式のバージョン
-/// * `file_hashes` - ファイルパスとSHA-256ハッシュのマップ
+/// * `hash_algorithm` - ファイルハッシュのアルゴリズム
+/// * `file_hashes` - ファイルパスとハッシュのマップ
+/// * `merkle_root` - 全ファイルハッシュのMerkleルート
 /// * `timestamp` - バックアップ作成日時
+/// * `special_entries` - 通常ファイル以外のエントリ
+/// * `snapshot` - スナップショットモードで作成されたか
+/// * `link_dest` - ハードリンク元のスナップショット名
+/// * `inconsistent_files` - バックアップ中に変更され続けたファイル（ハッシュは記録しない）
+/// * `packed_files` - パックファイルにまとめたファイルの位置
+/// * `file_codecs` - 圧縮して保存したファイルの圧縮形式
+/// * `file_stamps` - スナップショットで記録したファイルのサイズと更新日時
+/// * `sparse_files` - データ領域のみを保存したスパースファイルの配置
 ///
 /// # 使用例
 ///
@@ -68,8 +227,14 @@ use super::incremental::BackupType;
 pub struct BackupMetadata {
     /// メタデータ形式のバージョン
     pub version: String,
-    /// ファイルパス（相対パス）とSHA-256ハッシュのマップ
+    /// ファイルハッシュのアルゴリズム（記録のない旧形式はSHA-256）
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+    /// ファイルパス（相対パス）とハッシュのマップ
     pub file_hashes: HashMap<PathBuf, String>,
+    /// 全ファイルハッシュのMerkleルート（保存時に計算）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merkle_root: Option<String>,
     /// バックアップ作成日時（ISO 8601形式）
     pub timestamp: String,
     /// バックアップタイプ（Full/Incremental）
@@ -81,6 +246,34 @@ pub struct BackupMetadata {
     /// 変更ファイルリスト（増分バックアップ時の変更ファイル）
     #[serde(default)]
     pub changed_files: Vec<PathBuf>,
+    /// 通常ファイル以外のエントリ（ディレクトリ・シンボリックリンク・ハードリンク・特殊ファイル）
+    #[serde(default)]
+    pub special_entries: BTreeMap<PathBuf, SpecialEntry>,
+    /// スナップショット（無圧縮・非暗号化の完全ツリー）として作成されたか
+    #[serde(default)]
+    pub snapshot: bool,
+    /// 未変更ファイルのハードリンク元スナップショット名（スナップショットモードのみ）
+    #[serde(default)]
+    pub link_dest: Option<String>,
+    /// バックアップ中に変更され続けたファイル（相対パス、内容が不整合の可能性）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inconsistent_files: Vec<PathBuf>,
+    /// パックファイルにまとめたファイル（相対パス → パック内の位置）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub packed_files: BTreeMap<PathBuf, PackEntry>,
+    /// 圧縮して保存したファイル（相対パス → 圧縮形式、記録のないファイルは無圧縮）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub file_codecs: BTreeMap<PathBuf, CompressionType>,
+    /// スナップショットで記録したファイルのサイズと更新日時（相対パス → 元ファイル・保存したファイル）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub file_stamps: BTreeMap<PathBuf, FileStamp>,
+    /// データ領域のみを連結して保存したスパースファイル（相対パス → 元の配置）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sparse_files: BTreeMap<PathBuf, SparseMap>,
+    /// キャンセルされ、処理済みのファイルのみを保存したバックアップか
+    /// （完全な復元ポイントではないため、保持数に数えず、復元時は警告する）
+    #[serde(default)]
+    pub partial: bool,
 }
 
 impl BackupMetadata {
@@ -96,20 +289,50 @@ impl BackupMetadata {
     /// use backup_suite::core::integrity::BackupMetadata;
     ///
     /// let metadata = BackupMetadata::new();
-    /// assert_eq!(metadata.version, "1.0");
+    /// assert_eq!(metadata.version, "1.3");
     /// ```
     #[must_use]
     pub fn new() -> Self {
         Self {
-            version: "1.0".to_string(),
+            version: METADATA_VERSION.to_string(),
+            hash_algorithm: HashAlgorithm::default(),
             file_hashes: HashMap::new(),
+            merkle_root: None,
             timestamp: chrono::Utc::now().to_rfc3339(),
             backup_type: BackupType::Full,
             parent_backup: None,
             changed_files: Vec::new(),
+            special_entries: BTreeMap::new(),
+            snapshot: false,
+            link_dest: None,
+            inconsistent_files: Vec::new(),
+            packed_files: BTreeMap::new(),
+            file_codecs: BTreeMap::new(),
+            file_stamps: BTreeMap::new(),
+            sparse_files: BTreeMap::new(),
+            partial: false,
         }
     }
 
+    /// キャンセルされた部分的なバックアップかどうか（マニフェストを読み込めない場合は `false`）
+    #[must_use]
+    pub fn is_partial(backup_dir: &Path) -> bool {
+        Self::load(backup_dir).is_ok_and(|metadata| metadata.partial)
+    }
+
+    /// ファイルごとの圧縮形式を記録した形式（1.3以降）かどうか
+    ///
+    /// 記録した形式では `file_codecs` にないファイルは無圧縮で保存されているため、
+    /// 復元時に展開を試行しません。
+    #[must_use]
+    pub fn records_codecs(&self) -> bool {
+        let mut parts = self
+            .version
+            .split('.')
+            .map(|part| part.parse::<u32>().unwrap_or(0));
+        (parts.next().unwrap_or(0), parts.next().unwrap_or(0)) >= (1, 3)
+    }
+
     /// バックアップディレクトリからメタデータを読み込み
     ///
     /// `.integrity` ファイルから JSON 形式のメタデータを読み込みます。
@@ -241,10 +464,88 @@ impl BackupMetadata {
             }
         };
 
-        let actual_hash = Self::compute_file_hash(actual_file_path)?;
+        let actual_hash = self.hash_algorithm.hash_file(actual_file_path)?;
+        Ok(&actual_hash == expected_hash)
+    }
+
+    /// 復元したデータ（メモリ上）の整合性を検証
+    ///
+    /// [`verify_file`](Self::verify_file) と同じく記録されたハッシュと比較します。
+    /// [`sparse_files`](Self::sparse_files) に記録されたファイルは、`data` をデータ領域の連結として
+    /// ホールをゼロで補ったハッシュと比較します。
+    ///
+    /// # Errors
+    ///
+    /// ファイルに対応するハッシュ情報が見つからない場合、またはデータ領域の配置が
+    /// `data` と一致しない場合にエラーを返します。
+    pub fn verify_data(&self, relative_path: &Path, data: &[u8]) -> Result<bool> {
+        let expected_hash = self.file_hashes.get(relative_path).ok_or_else(|| {
+            anyhow::anyhow!(
+                "ファイルのハッシュ情報が見つかりません: {}",
+                relative_path.display()
+            )
+        })?;
+        let actual_hash = match self.sparse_files.get(relative_path) {
+            Some(map) => map.hash(self.hash_algorithm, data)?,
+            None => self.hash_algorithm.hash_bytes(data),
+        };
         Ok(&actual_hash == expected_hash)
     }
 
+    /// 全ファイルハッシュのMerkleルートを計算
+    ///
+    /// ファイルハッシュが1件もない場合は `None` を返します。
+    /// 計算方法はモジュールドキュメントを参照してください。
+    #[must_use]
+    pub fn compute_merkle_root(&self) -> Option<String> {
+        let algorithm = self.hash_algorithm;
+        let mut leaves: Vec<(String, &String)> = self
+            .file_hashes
+            .iter()
+            .map(|(path, hash)| (manifest_path(path), hash))
+            .collect();
+        leaves.sort();
+
+        let mut level: Vec<String> = leaves
+            .into_iter()
+            .map(|(path, hash)| {
+                let mut hasher = StreamHasher::new(algorithm);
+                hasher.update(b"leaf\0");
+                hasher.update(path.as_bytes());
+                hasher.update(b"\0");
+                hasher.update(hash.as_bytes());
+                hasher.finalize()
+            })
+            .collect();
+
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => {
+                        let mut hasher = StreamHasher::new(algorithm);
+                        hasher.update(b"node\0");
+                        hasher.update(left.as_bytes());
+                        hasher.update(right.as_bytes());
+                        hasher.finalize()
+                    }
+                    // 奇数個の最後の要素は繰り上げ
+                    single => single.concat(),
+                })
+                .collect();
+        }
+        level.pop()
+    }
+
+    /// 記録されたMerkleルートがファイルハッシュと一致するかを検証
+    ///
+    /// ルートが記録されていない旧形式のマニフェストは `None` を返します。
+    #[must_use]
+    pub fn verify_merkle_root(&self) -> Option<bool> {
+        let recorded = self.merkle_root.as_ref()?;
+        Some(self.compute_merkle_root().as_ref() == Some(recorded))
+    }
+
     /// ファイルのSHA-256ハッシュを計算（公開静的メソッド）
     ///
     /// # 引数
@@ -261,25 +562,18 @@ impl BackupMetadata {
     /// * ファイルのオープンに失敗した場合
     /// * ファイルの読み込みに失敗した場合
     pub fn compute_file_hash(file_path: &Path) -> Result<String> {
-        let mut file = fs::File::open(file_path)
-            .context("ファイル読み込み失敗: file_path.display()".to_string())?;
-
-        let mut hasher = Sha256::new();
-        let mut buffer = vec![0u8; 8192]; // 8KB バッファ
-
-        loop {
-            let bytes_read = file.read(&mut buffer).context("ファイル読み込みエラー")?;
-            if bytes_read == 0 {
-                break;
-            }
-            hasher.update(&buffer[..bytes_read]);
-        }
-
-        let result = hasher.finalize();
-        Ok(format!("{result:x}"))
+        HashAlgorithm::Sha256.hash_file(file_path)
     }
 }
 
+/// Merkleルート計算用のパス表現（プラットフォームによらず `/` 区切り）
+fn manifest_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 impl Default for BackupMetadata {
     fn default() -> Self {
         Self::new()
@@ -332,7 +626,23 @@ impl IntegrityChecker {
         }
     }
 
-    /// ファイルのSHA-256ハッシュを計算
+    /// ハッシュアルゴリズムを設定
+    ///
+    /// # 使用例
+    ///
+    /// ```
+    /// use backup_suite::core::integrity::{HashAlgorithm, IntegrityChecker};
+    ///
+    /// let checker = IntegrityChecker::new().with_hash_algorithm(HashAlgorithm::Blake3);
+    /// assert_eq!(checker.metadata.hash_algorithm, HashAlgorithm::Blake3);
+    /// ```
+    #[must_use]
+    pub fn with_hash_algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.metadata.hash_algorithm = algorithm;
+        self
+    }
+
+    /// ファイルのハッシュを計算（設定されたアルゴリズム、デフォルトはSHA-256）
     ///
     /// # 引数
     ///
@@ -340,7 +650,7 @@ impl IntegrityChecker {
     ///
     /// # 戻り値
     ///
-    /// 成功時は16進数文字列形式のSHA-256ハッシュ、失敗時はエラー
+    /// 成功時は16進数文字列形式のハッシュ、失敗時はエラー
     ///
     /// # Errors
     ///
@@ -359,7 +669,7 @@ impl IntegrityChecker {
     /// println!("SHA-256: {}", hash);
     /// ```
     pub fn compute_hash(&self, file_path: &Path) -> Result<String> {
-        BackupMetadata::compute_file_hash(file_path)
+        self.metadata.hash_algorithm.hash_file(file_path)
     }
 
     /// ファイルハッシュをメタデータに追加
@@ -367,7 +677,7 @@ impl IntegrityChecker {
     /// # 引数
     ///
     /// * `relative_path` - バックアップ内の相対パス
-    /// * `hash` - SHA-256ハッシュ（16進数文字列）
+    /// * `hash` - ファイルハッシュ（16進数文字列）
     ///
     /// # 使用例
     ///
@@ -384,6 +694,8 @@ impl IntegrityChecker {
 
     /// メタデータをバックアップディレクトリに保存
     ///
+    /// 保存前に全ファイルハッシュのMerkleルートを計算して記録します。
+    ///
     /// # 引数
     ///
     /// * `backup_dir` - バックアップディレクトリのパス
@@ -407,7 +719,8 @@ impl IntegrityChecker {
     /// checker.add_file_hash(PathBuf::from("test.txt"), "abc123...".to_string());
     /// checker.save_metadata(&PathBuf::from("/backup/backup_20250107_120000")).unwrap();
     /// ```
-    pub fn save_metadata(&self, backup_dir: &Path) -> Result<()> {
+    pub fn save_metadata(&mut self, backup_dir: &Path) -> Result<()> {
+        self.metadata.merkle_root = self.metadata.compute_merkle_root();
         self.metadata.save(backup_dir)
     }
 
@@ -552,6 +865,57 @@ mod tests {
         assert!(!is_valid);
     }
 
+    #[test]
+    fn test_blake3_manifest_and_merkle_root() {
+        let temp = TempDir::new().unwrap();
+        let backup_dir = temp.path().join("backup");
+        fs::create_dir(&backup_dir).unwrap();
+        let test_file = temp.path().join("test.txt");
+        fs::write(&test_file, b"test content").unwrap();
+
+        let mut checker = IntegrityChecker::new().with_hash_algorithm(HashAlgorithm::Blake3);
+        let hash = checker.compute_hash(&test_file).unwrap();
+        assert_eq!(hash, blake3::hash(b"test content").to_hex().to_string());
+        checker.add_file_hash(PathBuf::from("test.txt"), hash);
+        checker.add_file_hash(PathBuf::from("data/a.dat"), "a".to_string());
+        checker.add_file_hash(PathBuf::from("data/b.dat"), "b".to_string());
+        checker.save_metadata(&backup_dir).unwrap();
+
+        let metadata = BackupMetadata::load(&backup_dir).unwrap();
+        assert_eq!(metadata.hash_algorithm, HashAlgorithm::Blake3);
+        assert!(metadata
+            .verify_file(Path::new("test.txt"), &test_file)
+            .unwrap());
+        assert_eq!(metadata.verify_merkle_root(), Some(true));
+
+        // ルートはハッシュの登録順に依存せず、内容が変われば変化する
+        let mut reordered = BackupMetadata::new();
+        reordered.hash_algorithm = HashAlgorithm::Blake3;
+        for path in ["data/b.dat", "test.txt", "data/a.dat"] {
+            let path = PathBuf::from(path);
+            let hash = metadata.file_hashes[&path].clone();
+            reordered.file_hashes.insert(path, hash);
+        }
+        assert_eq!(reordered.compute_merkle_root(), metadata.merkle_root);
+
+        let mut tampered = metadata.clone();
+        tampered
+            .file_hashes
+            .insert(PathBuf::from("data/a.dat"), "changed".to_string());
+        assert_eq!(tampered.verify_merkle_root(), Some(false));
+        assert!(BackupMetadata::new().compute_merkle_root().is_none());
+    }
+
+    #[test]
+    fn test_legacy_manifest_defaults_to_sha256() {
+        let json = r#"{"version":"1.0","file_hashes":{},"timestamp":"2025-01-07T12:00:00Z"}"#;
+        let metadata: BackupMetadata = serde_json::from_str(json).unwrap();
+        assert_eq!(metadata.hash_algorithm, HashAlgorithm::Sha256);
+        assert!(metadata.verify_merkle_root().is_none());
+        assert!(!metadata.records_codecs());
+        assert!(BackupMetadata::new().records_codecs());
+    }
+
     #[test]
     fn test_metadata_json_format() {
         let temp = TempDir::new().unwrap();
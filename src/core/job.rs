@@ -0,0 +1,316 @@
+//! # バックアップジョブ
+//!
+//! `config.toml` の `[jobs.<名前>]` に、対象の選択と実行オプション一式を名前付きで定義します。
+//! `backup-suite run --job <名前>` とスケジューラが生成する launchd/systemd ユニットは
+//! ジョブの設定をそのまま使用するため、定期実行でも暗号化・圧縮レベル・増分モードが
+//! 手動実行と同じになります。
+//!
+//! # 設定例
+//!
+//! ```toml
+//! [jobs.nightly]
+//! priority = "high"
+//! compress = "zstd"
+//! compress_level = 9
+//! incremental = true
+//! keep_days = 14
+//! schedule = "0 1 * * mon-fri"
+//! randomized_delay_secs = 900
+//! encryption = { password_file = "/etc/backup-suite/nightly.key" }
+//!
+//! [jobs.nightly.hooks]
+//! post_backup = "notify-send \"nightly $BACKUP_SUITE_STATUS\""
+//! ```
+//!
+//! 暗号化パスワードは設定ファイルに直接書かず、環境変数（`password_env`）または
+//! ファイル（`password_file`）から実行時に読み込みます。
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::calendar::CalendarSpec;
+use super::hooks::HookConfig;
+use super::target::Priority;
+use crate::compression::CompressionType;
+use crate::error::{BackupError, Result};
+
+/// ジョブ名の最大長
+pub const MAX_JOB_NAME_LEN: usize = 64;
+
+/// 暗号化パスワードの取得元
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// 環境変数から読み込む
+    PasswordEnv(String),
+    /// ファイルの内容（末尾の改行を除く）を読み込む
+    PasswordFile(PathBuf),
+}
+
+impl KeySource {
+    /// パスワードを読み込む
+    ///
+    /// # Errors
+    ///
+    /// 環境変数が未設定の場合、ファイルを読み込めない場合、またはパスワードが空の場合に
+    /// エラーを返します。
+    pub fn resolve(&self) -> Result<String> {
+        let password = match self {
+            KeySource::PasswordEnv(name) => std::env::var(name).map_err(|_| {
+                BackupError::EncryptionError(format!("環境変数 {name} が設定されていません"))
+            })?,
+            KeySource::PasswordFile(path) => std::fs::read_to_string(path)
+                .map_err(|e| {
+                    BackupError::EncryptionError(format!(
+                        "パスワードファイルを読み込めません: {}: {e}",
+                        path.display()
+                    ))
+                })?
+                .trim_end_matches(['\r', '\n'])
+                .to_string(),
+        };
+        if password.is_empty() {
+            return Err(BackupError::EncryptionError(
+                "暗号化パスワードが空です".to_string(),
+            ));
+        }
+        Ok(password)
+    }
+}
+
+/// 名前付きバックアップジョブ
+///
+/// # フィールド
+///
+/// * `priority` / `category` - 対象の選択（未指定の場合は全対象）
+/// * `compress` / `compress_level` - 圧縮形式とレベル（未指定の場合は zstd と形式ごとのデフォルト）
+/// * `zstd_dict` / `pack` - zstd辞書圧縮・パックファイル
+/// * `encryption` - 暗号化パスワードの取得元（指定した場合のみ暗号化）
+/// * `incremental` / `snapshot` - 増分バックアップ・スナップショットモード
+/// * `checksum` - スナップショットモードで、サイズと更新日時ではなく内容のハッシュで未変更を判定
+/// * `keep_days` - バックアップ後に、このジョブが作成したバックアップのうちこの日数より古いものを削除
+/// * `hooks` - ジョブ実行時にグローバルフックの代わりに実行するフック
+/// * `schedule` - スケジューラで定期実行する頻度（キーワード・cron式・`OnCalendar` 形式、
+///   書式は [`CalendarSpec`] を参照）
+/// * `randomized_delay_secs` - 実行開始をランダムに遅らせる最大秒数（未指定の場合は `[schedule]` の設定）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupJob {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compress: Option<CompressionType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compress_level: Option<i32>,
+    #[serde(default)]
+    pub zstd_dict: bool,
+    #[serde(default)]
+    pub pack: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<KeySource>,
+    #[serde(default)]
+    pub incremental: bool,
+    #[serde(default)]
+    pub snapshot: bool,
+    #[serde(default)]
+    pub checksum: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_days: Option<u32>,
+    #[serde(default, skip_serializing_if = "HookConfig::is_empty")]
+    pub hooks: HookConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub randomized_delay_secs: Option<u64>,
+}
+
+impl BackupJob {
+    /// 圧縮形式（未指定の場合は zstd）
+    #[must_use]
+    pub fn compression_type(&self) -> CompressionType {
+        self.compress.unwrap_or(CompressionType::Zstd)
+    }
+
+    /// 圧縮レベル（未指定の場合は圧縮形式ごとのデフォルト）
+    #[must_use]
+    pub fn compression_level(&self) -> i32 {
+        self.compress_level
+            .unwrap_or_else(|| self.compression_type().default_level())
+    }
+
+    /// 定期実行の時刻（`schedule` 未指定の場合は `None`）
+    ///
+    /// # Errors
+    ///
+    /// スケジュールの書式が不正な場合にエラーを返します。
+    pub fn calendar(&self) -> anyhow::Result<Option<CalendarSpec>> {
+        self.schedule
+            .as_deref()
+            .map(CalendarSpec::parse)
+            .transpose()
+    }
+
+    /// ジョブ定義の妥当性を検証
+    ///
+    /// # Errors
+    ///
+    /// 以下の場合に `BackupError::ConfigValidationError` を返します:
+    /// * ジョブ名が空、長すぎる、または英数字・`-`・`_` 以外を含む場合
+    /// * 圧縮レベルが圧縮形式の範囲外の場合
+    /// * `keep_days` が範囲外（1-3650日）の場合
+    /// * `schedule` の書式が不正な場合
+    /// * 同時に指定できないオプション（スナップショットと圧縮・暗号化、zstd辞書と暗号化）を指定した場合
+    pub fn validate(&self, name: &str) -> Result<()> {
+        let invalid = |message: String| {
+            Err(BackupError::ConfigValidationError {
+                message: format!("ジョブ {name}: {message}"),
+            })
+        };
+
+        if !is_valid_job_name(name) {
+            return invalid(format!(
+                "ジョブ名は{MAX_JOB_NAME_LEN}文字以内の英数字・'-'・'_'で指定してください"
+            ));
+        }
+
+        let compression_type = self.compression_type();
+        if let (Some(range), Some(level)) = (compression_type.level_range(), self.compress_level) {
+            if !range.contains(&level) {
+                return invalid(format!(
+                    "{} の compress_level は {}-{} の範囲で指定してください（現在: {level}）",
+                    compression_type.to_str(),
+                    range.start(),
+                    range.end()
+                ));
+            }
+        }
+
+        if let Some(days) = self.keep_days {
+            if days == 0 || days > 3650 {
+                return invalid(format!(
+                    "keep_days は 1-3650 の範囲で指定してください（現在: {days}）"
+                ));
+            }
+        }
+
+        if let Err(e) = self.calendar() {
+            return invalid(format!("{e:#}"));
+        }
+
+        if self.snapshot && (compression_type != CompressionType::None || self.encryption.is_some())
+        {
+            return invalid(
+                "snapshot は compress = \"none\" かつ暗号化なしの場合のみ指定できます".to_string(),
+            );
+        }
+
+        if self.zstd_dict && self.encryption.is_some() {
+            return invalid("zstd_dict と encryption は同時に指定できません".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// ジョブ名として使用できるか（ユニット名・ファイル名に埋め込むため英数字・`-`・`_` のみ）
+#[must_use]
+pub fn is_valid_job_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_JOB_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::scheduler::Frequency;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_job_from_toml() {
+        let job: BackupJob = toml::from_str(
+            r#"
+            priority = "high"
+            compress = "xz"
+            compress_level = 6
+            incremental = true
+            keep_days = 14
+            schedule = "daily"
+            encryption = { password_env = "NIGHTLY_PASSWORD" }
+
+            [hooks]
+            post_backup = "echo done"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(job.priority, Some(Priority::High));
+        assert_eq!(job.compression_type(), CompressionType::Xz);
+        assert_eq!(job.compression_level(), 6);
+        assert!(job.incremental);
+        assert_eq!(
+            job.encryption,
+            Some(KeySource::PasswordEnv("NIGHTLY_PASSWORD".to_string()))
+        );
+        assert_eq!(job.hooks.post_backup.as_deref(), Some("echo done"));
+        assert_eq!(
+            job.calendar().unwrap(),
+            Some(CalendarSpec::from(Frequency::Daily))
+        );
+        assert!(job.validate("nightly").is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_jobs() {
+        assert!(BackupJob::default().validate("bad name").is_err());
+        assert!(BackupJob::default().validate("").is_err());
+
+        let level = BackupJob {
+            compress: Some(CompressionType::Gzip),
+            compress_level: Some(15),
+            ..Default::default()
+        };
+        assert!(level.validate("job").is_err());
+
+        let schedule = BackupJob {
+            schedule: Some("sometimes".to_string()),
+            ..Default::default()
+        };
+        assert!(schedule.validate("job").is_err());
+
+        let snapshot = BackupJob {
+            snapshot: true,
+            ..Default::default()
+        };
+        assert!(snapshot.validate("job").is_err());
+
+        let keep_days = BackupJob {
+            keep_days: Some(0),
+            ..Default::default()
+        };
+        assert!(keep_days.validate("job").is_err());
+    }
+
+    #[test]
+    fn test_key_source_from_file() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("job.key");
+        std::fs::write(&path, "s3cret-Passw0rd!\n").unwrap();
+        assert_eq!(
+            KeySource::PasswordFile(path).resolve().unwrap(),
+            "s3cret-Passw0rd!"
+        );
+
+        let empty = temp.path().join("empty.key");
+        std::fs::write(&empty, "\n").unwrap();
+        assert!(KeySource::PasswordFile(empty).resolve().is_err());
+        assert!(
+            KeySource::PasswordEnv("BACKUP_SUITE_TEST_UNSET_JOB_PASSWORD".to_string())
+                .resolve()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! # リポジトリロックモジュール
+//!
+//! バックアップ保存先（リポジトリ）への同時アクセスを制御します。
+//!
+//! # 仕組み
+//!
+//! `<保存先>/.locks/` に保持者ごとのロックファイル（PID・ホスト名・開始日時）を作成します。
+//!
+//! - **共有ロック**: 復元・検証など読み取りのみの処理。共有ロック同士は共存可能
+//! - **排他ロック**: バックアップ・クリーンアップなど保存先を変更する処理
+//!
+//! 同一ホスト上で保持プロセスが既に存在しないロックは古いロックとして自動的に削除されます。
+//! 別ホストのロックは判定できないため、`backup-suite unlock --force` で解除します。
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! use backup_suite::core::lock::{LockKind, RepositoryLock};
+//! use std::path::Path;
+//!
+//! let _lock = RepositoryLock::acquire(Path::new("/backups"), LockKind::Exclusive, "backup").unwrap();
+//! // ... バックアップ処理（ロックはドロップ時に解放） ...
+//! ```
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::BackupError;
+
+/// ロックファイルを格納するディレクトリ名
+pub const LOCK_DIR: &str = ".locks";
+
+/// ロックの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockKind {
+    /// 共有ロック（読み取り処理）
+    Shared,
+    /// 排他ロック（保存先を変更する処理）
+    Exclusive,
+}
+
+impl LockKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            LockKind::Shared => "shared",
+            LockKind::Exclusive => "exclusive",
+        }
+    }
+}
+
+/// ロック保持者の情報
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockInfo {
+    /// ロックの種類
+    pub kind: LockKind,
+    /// ロックを取得した処理（backup / restore / verify / cleanup など）
+    pub operation: String,
+    /// 保持プロセスのPID
+    pub pid: u32,
+    /// 保持プロセスのホスト名
+    pub host: String,
+    /// ロック取得日時
+    pub started_at: DateTime<Utc>,
+}
+
+impl LockInfo {
+    /// 保持プロセスが既に存在しない（同一ホストでのみ判定可能）
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        self.host == hostname() && !process_alive(self.pid)
+    }
+}
+
+impl std::fmt::Display for LockInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}) pid={} host={} since {}",
+            self.operation,
+            self.kind.as_str(),
+            self.pid,
+            self.host,
+            self.started_at.to_rfc3339()
+        )
+    }
+}
+
+/// 取得済みのリポジトリロック
+///
+/// ドロップ時にロックファイルを削除して解放します。
+#[derive(Debug)]
+pub struct RepositoryLock {
+    path: PathBuf,
+    info: LockInfo,
+    stale_removed: Vec<LockInfo>,
+}
+
+impl RepositoryLock {
+    /// リポジトリロックを取得
+    ///
+    /// 古いロックを削除した上でロックファイルを作成し、競合するロックがあれば
+    /// 自身のロックファイルを削除して失敗します。削除した古いロックは
+    /// [`RepositoryLock::stale_removed`] で取得できます。
+    ///
+    /// # Errors
+    ///
+    /// 以下の場合にエラーを返します:
+    /// * 競合するロックが保持されている場合（[`BackupError::RepositoryLocked`]）
+    /// * ロックファイルの作成に失敗した場合
+    pub fn acquire(repo: &Path, kind: LockKind, operation: &str) -> Result<Self> {
+        let lock_dir = repo.join(LOCK_DIR);
+
+        let mut stale_removed = Vec::new();
+        for (path, info) in list_locks(repo) {
+            if info.is_stale() && fs::remove_file(path).is_ok() {
+                stale_removed.push(info);
+            }
+        }
+
+        let info = LockInfo {
+            kind,
+            operation: operation.to_string(),
+            pid: std::process::id(),
+            host: hostname(),
+            started_at: Utc::now(),
+        };
+        let path = lock_dir.join(format!(
+            "{}-{}-{}-{}.json",
+            kind.as_str(),
+            info.host,
+            info.pid,
+            info.started_at.timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let mut file = create_lock_file(&lock_dir, &path)?;
+        file.write_all(serde_json::to_string(&info)?.as_bytes())
+            .and_then(|()| file.sync_all())
+            .context("ロックファイル書き込み失敗")?;
+        let lock = Self {
+            path,
+            info,
+            stale_removed,
+        };
+
+        // 作成後に確認することで、同時に取得しようとした処理の双方が競合を検出できる
+        let conflicts: Vec<String> = list_locks(repo)
+            .into_iter()
+            .filter(|(path, other)| {
+                *path != lock.path
+                    && (kind == LockKind::Exclusive || other.kind == LockKind::Exclusive)
+            })
+            .map(|(_, other)| other.to_string())
+            .collect();
+        if !conflicts.is_empty() {
+            // lock はここでドロップされ、自身のロックファイルは削除される
+            return Err(BackupError::RepositoryLocked {
+                path: repo.to_path_buf(),
+                holders: conflicts.join(", "),
+            }
+            .into());
+        }
+
+        Ok(lock)
+    }
+
+    /// ロック情報を取得
+    #[must_use]
+    pub fn info(&self) -> &LockInfo {
+        &self.info
+    }
+
+    /// 取得時に削除した古いロック（保持プロセスが存在しないもの）
+    #[must_use]
+    pub fn stale_removed(&self) -> &[LockInfo] {
+        &self.stale_removed
+    }
+}
+
+impl Drop for RepositoryLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+        // 最後のロックであればディレクトリも削除（他のロックが残っていれば失敗するだけ）
+        if let Some(lock_dir) = self.path.parent() {
+            let _ = fs::remove_dir(lock_dir);
+        }
+    }
+}
+
+/// ロックファイルを新規作成
+///
+/// 他の処理の解放によってロックディレクトリが削除された直後でも作成できるよう、
+/// ディレクトリが見つからない場合は作り直して再試行します。
+fn create_lock_file(lock_dir: &Path, path: &Path) -> Result<fs::File> {
+    let mut attempts = 0;
+    loop {
+        fs::create_dir_all(lock_dir).context("ロックディレクトリ作成失敗")?;
+        match OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(file) => return Ok(file),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && attempts < 3 => {
+                attempts += 1;
+            }
+            Err(e) => return Err(e).context("ロックファイル作成失敗"),
+        }
+    }
+}
+
+/// リポジトリに存在するロックの一覧を取得
+///
+/// 読み込めないロックファイルは無視します。
+#[must_use]
+pub fn list_locks(repo: &Path) -> Vec<(PathBuf, LockInfo)> {
+    let Ok(entries) = fs::read_dir(repo.join(LOCK_DIR)) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let content = fs::read_to_string(&path).ok()?;
+            let info = serde_json::from_str(&content).ok()?;
+            Some((path, info))
+        })
+        .collect()
+}
+
+/// 全てのロックを強制的に解除
+///
+/// 保持プロセスの生死にかかわらずロックファイルを削除し、解除したロックの情報を返します。
+///
+/// # Errors
+///
+/// ロックファイルの削除に失敗した場合にエラーを返します。
+pub fn force_unlock(repo: &Path) -> Result<Vec<LockInfo>> {
+    let mut removed = Vec::new();
+    for (path, info) in list_locks(repo) {
+        fs::remove_file(&path)
+            .with_context(|| format!("ロックファイル削除失敗: {}", path.display()))?;
+        removed.push(info);
+    }
+    let _ = fs::remove_dir(repo.join(LOCK_DIR));
+    Ok(removed)
+}
+
+/// ホスト名を取得
+pub(crate) fn hostname() -> String {
+    #[cfg(unix)]
+    {
+        let mut buf = [0u8; 256];
+        // SAFETY: バッファ長を渡しており、gethostname はその範囲内にのみ書き込む
+        let ret = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+        if ret == 0 {
+            let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+            return String::from_utf8_lossy(&buf[..len]).into_owned();
+        }
+    }
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// プロセスが存在するかを確認
+pub(crate) fn process_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        let Ok(pid) = libc::pid_t::try_from(pid) else {
+            return false;
+        };
+        // SAFETY: シグナル0は存在確認のみで、プロセスに影響しない
+        let ret = unsafe { libc::kill(pid, 0) };
+        ret == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    }
+    #[cfg(not(unix))]
+    {
+        // 判定できないため保持中とみなす
+        let _ = pid;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_shared_and_exclusive_semantics() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+
+        let shared1 = RepositoryLock::acquire(repo, LockKind::Shared, "restore").unwrap();
+        let shared2 = RepositoryLock::acquire(repo, LockKind::Shared, "restore").unwrap();
+        let err = RepositoryLock::acquire(repo, LockKind::Exclusive, "cleanup").unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BackupError>(),
+            Some(BackupError::RepositoryLocked { .. })
+        ));
+        assert_eq!(list_locks(repo).len(), 2);
+
+        drop(shared1);
+        drop(shared2);
+        let exclusive = RepositoryLock::acquire(repo, LockKind::Exclusive, "backup").unwrap();
+        assert!(RepositoryLock::acquire(repo, LockKind::Shared, "restore").is_err());
+        drop(exclusive);
+        assert!(list_locks(repo).is_empty());
+        assert!(!repo.join(LOCK_DIR).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stale_lock_is_removed() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        fs::create_dir_all(repo.join(LOCK_DIR)).unwrap();
+
+        // 存在しないPIDの排他ロック
+        let stale = LockInfo {
+            kind: LockKind::Exclusive,
+            operation: "backup".to_string(),
+            pid: i32::MAX as u32,
+            host: hostname(),
+            started_at: Utc::now(),
+        };
+        assert!(stale.is_stale());
+        fs::write(
+            repo.join(LOCK_DIR).join("exclusive-stale.json"),
+            serde_json::to_string(&stale).unwrap(),
+        )
+        .unwrap();
+
+        let lock = RepositoryLock::acquire(repo, LockKind::Exclusive, "backup").unwrap();
+        assert_eq!(list_locks(repo).len(), 1);
+        assert_eq!(lock.stale_removed().len(), 1);
+        assert_eq!(lock.stale_removed()[0].pid, stale.pid);
+        drop(lock);
+    }
+
+    #[test]
+    fn test_force_unlock() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        let lock = RepositoryLock::acquire(repo, LockKind::Exclusive, "backup").unwrap();
+
+        let removed = force_unlock(repo).unwrap();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].pid, lock.info().pid);
+        assert!(list_locks(repo).is_empty());
+    }
+}
@@ -5,15 +5,30 @@
 //! # モジュール構成
 //!
 //! - **[`backup`]**: バックアップ実行エンジンと結果
+//! - **[`calendar`]**: スケジュールの実行時刻（cron式・`OnCalendar` 形式）
+//! - **[`cancel`]**: 実行中の処理のキャンセルと一時停止
+//! - **[`checkpoint`]**: 中断されたバックアップ再開のためのチェックポイント
 //! - **[`config`]**: 設定管理と永続化
 //! - **[`copy_engine`]**: 最適化されたファイルコピー
+//! - **[`daemon`]**: スケジュールされたバックアップを自身で実行するスケジューラデーモン
+//! - **[`events`]**: 進行状況を型付きのイベントとして通知するイベントシンク
 //! - **[`filter`]**: ファイル除外パターン
 //! - **[`history`]**: バックアップ履歴管理
+//! - **[`hooks`]**: バックアップ前後に実行するフックコマンド
+//! - **[`job`]**: 実行オプション一式を持つ名前付きバックアップジョブ
+//! - **[`lock`]**: 保存先への同時アクセスを制御するリポジトリロック
 //! - **[`logging`]**: ログファイル管理
+//! - **[`pack`]**: 小さなファイルをまとめるパックファイル
 //! - **[`pipeline`]**: 処理パイプライン（暗号化・圧縮）
+//! - **[`report`]**: バックアップ実行レポート（対象ごとの集計・種類付きのエラー）
+//! - **[`rpo`]**: 対象ごとの目標復旧時点（RPO）の確認
 //! - **[`scheduler`]**: スケジューリング機能（macOS/Linux）
+//! - **[`sqlite`]**: SQLiteデータベースのオンラインバックアップ
+//! - **[`special`]**: シンボリックリンク・ハードリンク・特殊ファイルの記録と復元
+//! - **[`staging`]**: ステージングディレクトリへの書き込みとアトミックな確定
 //! - **[`target`]**: バックアップ対象定義
 //! - **[`validation`]**: 入力検証とセキュリティ対策
+//! - **[`watch`]**: ファイル変更通知による継続的バックアップ（監視モード）
 //!
 //! # 使用例
 //!
@@ -40,32 +55,54 @@
 //! ```
 
 pub mod backup;
+pub mod calendar;
+pub mod cancel;
+pub mod checkpoint;
 pub mod cleanup;
 pub mod config;
 pub mod copy_engine;
+pub mod daemon;
+pub mod events;
 pub mod filter;
 pub mod history;
+pub mod hooks;
 pub mod incremental;
 pub mod integrity;
+pub mod job;
+pub mod lock;
 pub mod logging;
+pub mod pack;
 pub mod pipeline;
+pub mod report;
 pub mod restore;
+pub mod rpo;
 pub mod scheduler;
+pub mod special;
+pub mod sqlite;
+pub mod staging;
 pub mod target;
 pub mod validation;
+pub mod watch;
 
 pub use backup::{BackupResult, BackupRunner};
+pub use calendar::CalendarSpec;
+pub use cancel::CancellationToken;
 pub use cleanup::{CleanupEngine, CleanupPolicy, CleanupResult};
 pub use config::Config;
 pub use copy_engine::CopyEngine;
+pub use daemon::{Daemon, DaemonState};
+pub use events::{Event, EventSink, NullSink, Operation, Phase};
 pub use filter::{default_exclude_patterns, FileFilter};
 pub use history::BackupHistory;
+pub use hooks::{HookConfig, HookStage};
 pub use incremental::{resolve_backup_chain, BackupType, IncrementalBackupEngine};
-pub use integrity::{BackupMetadata, IntegrityChecker};
+pub use integrity::{BackupMetadata, HashAlgorithm, IntegrityChecker, StreamHasher};
+pub use job::{BackupJob, KeySource};
 pub use logging::{LogEntry, LogFormat, LogLevel, Logger};
 pub use pipeline::{
     PerformanceConfig, PipelineConfig, ProcessedData, ProcessingMetadata, ProcessingPipeline,
 };
 pub use restore::{RestoreEngine, RestoreResult};
 pub use scheduler::{Frequency, Platform, ScheduleStatus, Scheduler};
+pub use special::{EntryKind, HardlinkTracker, SpecialEntry};
 pub use target::{Priority, Target, TargetType};
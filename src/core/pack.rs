@@ -0,0 +1,274 @@
+//! # パックファイル
+//!
+//! 小さなファイルを大きなパックファイルにまとめて保存し、保存先のファイル数（inode）と
+//! ファイルごとのオーバーヘッドを減らします。クリーンアップ・サイズ計算・スナップショットの
+//! 別メディアへのコピーが、ファイル数に比例して遅くなるのを防ぎます。
+//!
+//! # 仕組み
+//!
+//! 1. バックアップは通常どおりファイルごとにステージングディレクトリへ書き込む
+//! 2. 確定前に [`PACK_FILE_THRESHOLD`] 以下のファイルを `.packs/pack-<番号>.pack` へ連結し、
+//!    元のファイルを削除する（パックは [`PACK_TARGET_SIZE`] を超えると次の番号に切り替え）
+//! 3. 各ファイルの位置（パック番号・オフセット・長さ）をマニフェスト（`.integrity`）の
+//!    `packed_files` に記録する
+//!
+//! 連結するのは圧縮・暗号化後の内容なので、[`read_entry`] で取り出したデータは
+//! 通常のファイルと同じように展開・復号できます。
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! use backup_suite::core::pack;
+//! use std::path::{Path, PathBuf};
+//!
+//! let backup_dir = Path::new("/backups/backup_20250107_120000");
+//! let index = pack::pack_files(backup_dir, &[PathBuf::from("docs/a.txt")]).unwrap();
+//! let data = pack::read_entry(backup_dir, &index[Path::new("docs/a.txt")]).unwrap();
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// バックアップ内でパックファイルを保存するディレクトリ名
+pub const PACK_DIR: &str = ".packs";
+
+/// パックにまとめるファイルの最大サイズ（書き込み後のサイズ）
+pub const PACK_FILE_THRESHOLD: u64 = 64 * 1024;
+
+/// 1つのパックファイルの目安サイズ（超えたら次のパックに切り替え）
+pub const PACK_TARGET_SIZE: u64 = 64 * 1024 * 1024;
+
+/// パック内のファイルの位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackEntry {
+    /// パック番号
+    pub pack: u32,
+    /// パック内の開始位置（バイト）
+    pub offset: u64,
+    /// 長さ（バイト）
+    pub length: u64,
+}
+
+/// パック番号に対応するパックファイルのパス
+#[must_use]
+pub fn pack_path(backup_dir: &Path, pack: u32) -> PathBuf {
+    backup_dir
+        .join(PACK_DIR)
+        .join(format!("pack-{pack:06}.pack"))
+}
+
+/// バックアップ内の小さなファイルをパックにまとめる
+///
+/// `relative_paths` のうち存在し、[`PACK_FILE_THRESHOLD`] 以下のファイルをパス順に連結し、
+/// パックを同期してから元のファイルと空になったディレクトリを削除します。
+/// 既存のパックディレクトリ（中断されたバックアップの残り）は作り直します。
+///
+/// # 戻り値
+///
+/// パックにまとめたファイルの相対パスと位置のマップ
+///
+/// # Errors
+///
+/// ファイルの読み込み、パックの書き込み・同期に失敗した場合にエラーを返します。
+pub fn pack_files(
+    backup_dir: &Path,
+    relative_paths: &[PathBuf],
+) -> Result<BTreeMap<PathBuf, PackEntry>> {
+    let mut candidates: Vec<&PathBuf> = relative_paths
+        .iter()
+        .filter(|rel| {
+            fs::symlink_metadata(backup_dir.join(rel))
+                .is_ok_and(|m| m.is_file() && m.len() <= PACK_FILE_THRESHOLD)
+        })
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+
+    let pack_dir = backup_dir.join(PACK_DIR);
+    if pack_dir.exists() {
+        fs::remove_dir_all(&pack_dir).context("既存のパックディレクトリの削除失敗")?;
+    }
+
+    let mut index = BTreeMap::new();
+    if candidates.is_empty() {
+        return Ok(index);
+    }
+    fs::create_dir_all(&pack_dir).context("パックディレクトリ作成失敗")?;
+
+    let mut writer = PackWriter::new(backup_dir);
+    for rel in candidates {
+        let path = backup_dir.join(rel);
+        let data = fs::read(&path)
+            .with_context(|| format!("パック対象の読み込み失敗: {}", path.display()))?;
+        let entry = writer.append(&data)?;
+        index.insert(rel.clone(), entry);
+    }
+    writer.finish()?;
+
+    // パックを同期してから元のファイルを削除
+    for rel in index.keys() {
+        let path = backup_dir.join(rel);
+        fs::remove_file(&path)
+            .with_context(|| format!("パック済みファイルの削除失敗: {}", path.display()))?;
+        // 空になったディレクトリを上へ向かって削除（空でなければ失敗して止まる）
+        for dir in path.ancestors().skip(1) {
+            if dir == backup_dir || fs::remove_dir(dir).is_err() {
+                break;
+            }
+        }
+    }
+
+    Ok(index)
+}
+
+/// パックからファイルの内容を取り出す
+///
+/// # Errors
+///
+/// パックファイルが存在しない場合、または記録された範囲を読み込めない場合にエラーを返します。
+pub fn read_entry(backup_dir: &Path, entry: &PackEntry) -> Result<Vec<u8>> {
+    let path = pack_path(backup_dir, entry.pack);
+    let mut file = File::open(&path)
+        .with_context(|| format!("パックファイルを開けません: {}", path.display()))?;
+    file.seek(SeekFrom::Start(entry.offset))?;
+    let length = usize::try_from(entry.length).context("パック内のファイルが大きすぎます")?;
+    let mut data = vec![0u8; length];
+    file.read_exact(&mut data)
+        .with_context(|| format!("パックファイルの読み込み失敗: {}", path.display()))?;
+    Ok(data)
+}
+
+/// パックファイルへの追記（目安サイズを超えたら次のパックに切り替え）
+struct PackWriter<'a> {
+    backup_dir: &'a Path,
+    current: Option<(u32, BufWriter<File>, u64)>,
+    next_pack: u32,
+}
+
+impl<'a> PackWriter<'a> {
+    fn new(backup_dir: &'a Path) -> Self {
+        Self {
+            backup_dir,
+            current: None,
+            next_pack: 0,
+        }
+    }
+
+    fn append(&mut self, data: &[u8]) -> Result<PackEntry> {
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|(_, _, size)| *size >= PACK_TARGET_SIZE)
+        {
+            self.close_current()?;
+        }
+        if self.current.is_none() {
+            let pack = self.next_pack;
+            self.next_pack += 1;
+            let path = pack_path(self.backup_dir, pack);
+            let file = File::create(&path)
+                .with_context(|| format!("パックファイル作成失敗: {}", path.display()))?;
+            self.current = Some((pack, BufWriter::new(file), 0));
+        }
+
+        let (pack, writer, size) = self
+            .current
+            .as_mut()
+            .context("パックファイルが開かれていません")?;
+        writer
+            .write_all(data)
+            .context("パックファイル書き込み失敗")?;
+        let entry = PackEntry {
+            pack: *pack,
+            offset: *size,
+            length: data.len() as u64,
+        };
+        *size += entry.length;
+        Ok(entry)
+    }
+
+    fn close_current(&mut self) -> Result<()> {
+        if let Some((_, writer, _)) = self.current.take() {
+            let file = writer
+                .into_inner()
+                .map_err(|e| anyhow::anyhow!("パックファイル書き込み失敗: {e}"))?;
+            file.sync_all().context("パックファイルの同期失敗")?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<()> {
+        self.close_current()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pack_and_read_entries() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::create_dir_all(dir.join("docs/nested")).unwrap();
+        fs::write(dir.join("docs/a.txt"), b"alpha").unwrap();
+        fs::write(dir.join("docs/nested/b.txt"), b"bravo!").unwrap();
+        let large = vec![7u8; (PACK_FILE_THRESHOLD + 1) as usize];
+        fs::write(dir.join("docs/large.bin"), &large).unwrap();
+
+        let paths = vec![
+            PathBuf::from("docs/nested/b.txt"),
+            PathBuf::from("docs/a.txt"),
+            PathBuf::from("docs/large.bin"),
+            PathBuf::from("docs/missing.txt"),
+        ];
+        let index = pack_files(dir, &paths).unwrap();
+
+        assert_eq!(index.len(), 2);
+        assert_eq!(
+            index[Path::new("docs/a.txt")],
+            PackEntry {
+                pack: 0,
+                offset: 0,
+                length: 5
+            }
+        );
+        assert_eq!(
+            read_entry(dir, &index[Path::new("docs/a.txt")]).unwrap(),
+            b"alpha"
+        );
+        assert_eq!(
+            read_entry(dir, &index[Path::new("docs/nested/b.txt")]).unwrap(),
+            b"bravo!"
+        );
+
+        // パック済みファイルと空のディレクトリは削除され、大きなファイルは残る
+        assert!(!dir.join("docs/a.txt").exists());
+        assert!(!dir.join("docs/nested").exists());
+        assert_eq!(fs::read(dir.join("docs/large.bin")).unwrap(), large);
+        assert!(pack_path(dir, 0).exists());
+    }
+
+    #[test]
+    fn test_pack_without_candidates_creates_no_packs() {
+        let temp = TempDir::new().unwrap();
+        let index = pack_files(temp.path(), &[PathBuf::from("missing")]).unwrap();
+        assert!(index.is_empty());
+        assert!(!temp.path().join(PACK_DIR).exists());
+    }
+
+    #[test]
+    fn test_read_entry_out_of_range_fails() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("a"), b"abc").unwrap();
+        let index = pack_files(temp.path(), &[PathBuf::from("a")]).unwrap();
+        let mut entry = index[Path::new("a")];
+        entry.length = 10;
+        assert!(read_entry(temp.path(), &entry).is_err());
+    }
+}
@@ -2,7 +2,12 @@
 //!
 //! 暗号化・圧縮・バックアップを統合した高性能処理パイプライン
 
-use crate::compression::{CompressedData, CompressionConfig, CompressionEngine, CompressionType};
+use super::copy_engine::{read_sparse, SparseMap};
+use super::integrity::HashAlgorithm;
+use crate::compression::{
+    header, AdaptiveDecision, CompressedData, CompressionConfig, CompressionEngine,
+    CompressionType, ZstdDictionary,
+};
 use crate::crypto::{EncryptedData, EncryptionConfig, EncryptionEngine, KeyManager, MasterKey};
 use crate::error::{BackupError, Result};
 use aes_gcm::aead::Aead;
@@ -24,6 +29,10 @@ pub struct PipelineConfig {
     pub compression_type: CompressionType,
     /// パフォーマンス設定
     pub performance: PerformanceConfig,
+    /// 元データのハッシュを計算するアルゴリズム（`None` の場合は計算しない）
+    pub source_hash: Option<HashAlgorithm>,
+    /// 小さなファイルのzstd圧縮に使う辞書
+    pub dictionary: Option<Arc<ZstdDictionary>>,
 }
 
 impl Default for PipelineConfig {
@@ -33,6 +42,8 @@ impl Default for PipelineConfig {
             compression: CompressionConfig::zstd_default(),
             compression_type: CompressionType::Zstd,
             performance: PerformanceConfig::default(),
+            source_hash: None,
+            dictionary: None,
         }
     }
 }
@@ -57,6 +68,20 @@ impl PipelineConfig {
         self
     }
 
+    /// 読み込んだ元データのハッシュも計算する（整合性検証用）
+    #[must_use]
+    pub fn with_source_hash(mut self, algorithm: HashAlgorithm) -> Self {
+        self.source_hash = Some(algorithm);
+        self
+    }
+
+    /// 小さなファイルをzstd辞書で圧縮する
+    #[must_use]
+    pub fn with_dictionary(mut self, dictionary: Arc<ZstdDictionary>) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
     /// 高速設定に変更
     #[must_use]
     pub fn fast(mut self) -> Self {
@@ -189,6 +214,12 @@ pub struct ProcessedData {
     pub compression_info: Option<CompressedData>,
     /// 暗号化情報
     pub encryption_info: Option<EncryptedData>,
+    /// 元データのハッシュ（[`PipelineConfig::with_source_hash`] 指定時）
+    pub source_hash: Option<String>,
+    /// 適応圧縮での判定結果（[`CompressionType::Adaptive`] 使用時）
+    pub compression_decision: Option<AdaptiveDecision>,
+    /// スパースファイルのデータ領域の配置（`data` はデータ領域のみを連結して処理したもの）
+    pub sparse: Option<SparseMap>,
     /// メタデータ
     pub metadata: ProcessingMetadata,
 }
@@ -196,7 +227,7 @@ pub struct ProcessedData {
 /// 処理メタデータ
 #[derive(Debug, Clone)]
 pub struct ProcessingMetadata {
-    /// 元のサイズ
+    /// 元のサイズ（スパースファイルはデータ領域の合計）
     pub original_size: u64,
     /// 圧縮後サイズ
     pub compressed_size: u64,
@@ -228,10 +259,12 @@ impl ProcessingPipeline {
             .as_ref()
             .map(|cfg| Arc::new(EncryptionEngine::new(cfg.clone())));
 
-        let compression_engine = Arc::new(CompressionEngine::new(
-            config.compression_type,
-            config.compression.clone(),
-        ));
+        let mut compression_engine =
+            CompressionEngine::new(config.compression_type, config.compression.clone());
+        if let Some(dictionary) = &config.dictionary {
+            compression_engine = compression_engine.with_dictionary(Arc::clone(dictionary));
+        }
+        let compression_engine = Arc::new(compression_engine);
 
         let key_manager = encryption_engine
             .as_ref()
@@ -275,6 +308,9 @@ impl ProcessingPipeline {
     }
 
     /// ファイルを処理（圧縮 → 暗号化）
+    ///
+    /// ファイル全体をメモリに読み込んで処理します。スパースファイルはデータ領域のみを読み込み、
+    /// 配置を [`ProcessedData::sparse`] に返すため、メモリ使用量は論理サイズではなくデータ量に比例します。
     pub fn process_file<P: AsRef<Path>>(
         &self,
         file_path: P,
@@ -284,18 +320,29 @@ impl ProcessingPipeline {
         let start_time = std::time::Instant::now();
         let file_path = file_path.as_ref().to_path_buf();
 
-        // ファイル読み込み
-        let original_data = std::fs::read(&file_path)?;
+        // ファイル読み込み（スパースファイルはホールを読まず、データ領域のみを連結）
+        let (sparse, original_data) = match read_sparse(&file_path)? {
+            Some((map, data)) => (Some(map), data),
+            None => (None, std::fs::read(&file_path)?),
+        };
         let original_size = original_data.len() as u64;
+        let source_hash = match (self.config.source_hash, &sparse) {
+            (Some(algorithm), Some(map)) => Some(map.hash(algorithm, &original_data)?),
+            (Some(algorithm), None) => Some(algorithm.hash_bytes(&original_data)),
+            (None, _) => None,
+        };
 
-        // Step 1: 圧縮
-        let (compressed_data, compression_info) =
+        // Step 1: 圧縮（適応圧縮ではファイル名も判定に使用）
+        // 復元時に形式を判別できるよう、圧縮データの先頭にコーデックヘッダーを付与
+        let (compressed_data, compression_info, compression_decision) =
             if self.config.compression_type != CompressionType::None {
-                let compressed = self.compression_engine.compress(&original_data)?;
-                let _compression_ratio = compressed.compression_percentage();
-                (compressed.data.clone(), Some(compressed))
+                let (compressed, decision) = self
+                    .compression_engine
+                    .compress_with_hint(&original_data, Some(&file_path))?;
+                let framed = header::encode(self.config.compression_type, &compressed.data);
+                (framed, Some(compressed), decision)
             } else {
-                (original_data, None)
+                (original_data, None, None)
             };
 
         let compressed_size = compressed_data.len() as u64;
@@ -332,11 +379,16 @@ impl ProcessingPipeline {
             data: final_data,
             compression_info,
             encryption_info,
+            source_hash,
+            compression_decision,
+            sparse,
             metadata,
         })
     }
 
     /// データを復元（復号化 → 展開）
+    ///
+    /// スパースファイルはデータ領域を連結したデータを返します（[`SparseMap::write`] で元の配置に書き込み）。
     pub fn restore_data(
         &self,
         processed_data: &ProcessedData,
@@ -720,6 +772,53 @@ mod tests {
         let _ = std::fs::remove_file(&temp_file);
     }
 
+    #[test]
+    fn test_pipeline_source_hash() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("test.txt");
+        std::fs::write(&test_file, b"hash me once").unwrap();
+
+        let pipeline = ProcessingPipeline::new(
+            PipelineConfig::default().with_source_hash(HashAlgorithm::Blake3),
+        );
+        let processed = pipeline.process_file(&test_file, None, None).unwrap();
+        assert_eq!(
+            processed.source_hash,
+            Some(HashAlgorithm::Blake3.hash_bytes(b"hash me once"))
+        );
+
+        let pipeline = ProcessingPipeline::new(PipelineConfig::default());
+        let processed = pipeline.process_file(&test_file, None, None).unwrap();
+        assert!(processed.source_hash.is_none());
+    }
+
+    #[test]
+    fn test_pipeline_adaptive_compression() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let text_file = temp_dir.path().join("notes.txt");
+        let media_file = temp_dir.path().join("clip.mp4");
+        let test_data = b"Adaptive pipeline test data. ".repeat(200);
+        std::fs::write(&text_file, &test_data).unwrap();
+        std::fs::write(&media_file, &test_data).unwrap();
+
+        let pipeline = ProcessingPipeline::new(PipelineConfig::default().with_compression(
+            CompressionType::Adaptive,
+            CompressionConfig::adaptive_default(),
+        ));
+
+        let processed = pipeline.process_file(&text_file, None, None).unwrap();
+        assert_eq!(processed.compression_decision, Some(AdaptiveDecision::High));
+        assert!(processed.metadata.compressed_size < processed.metadata.original_size);
+        assert_eq!(pipeline.restore_data(&processed, None).unwrap(), test_data);
+
+        let processed = pipeline.process_file(&media_file, None, None).unwrap();
+        assert_eq!(
+            processed.compression_decision,
+            Some(AdaptiveDecision::Store)
+        );
+        assert_eq!(pipeline.restore_data(&processed, None).unwrap(), test_data);
+    }
+
     #[test]
     fn test_pipeline_with_encryption() {
         let config = PipelineConfig::default()
@@ -0,0 +1,357 @@
+//! # バックアップ実行レポート
+//!
+//! 1回のバックアップ実行の詳細な集計（対象ごとのファイル数、読み込み・書き込みバイト数、
+//! 圧縮率、スキップ・除外数、処理時間とスループット、種類付きのエラー一覧）を
+//! スナップショット内の `.report.json` に保存します。
+//!
+//! 集計値（[`RunSummary`]）は履歴にも記録されるため、`history` から推移を確認できます。
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! use backup_suite::core::report::RunReport;
+//! use std::path::Path;
+//!
+//! let report = RunReport::load(Path::new("/backup/backup_20250105_120000")).unwrap();
+//! println!(
+//!     "{}ファイル, {}バイト読み込み, {}ms",
+//!     report.summary.totals.files, report.summary.totals.bytes_read, report.duration_ms
+//! );
+//! for error in &report.errors {
+//!     println!("{:?}: {}", error.kind, error.message);
+//! }
+//! ```
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::incremental::BackupType;
+use super::Priority;
+use crate::error::BackupError;
+
+/// スナップショット内のレポートファイル名
+pub const REPORT_FILE: &str = ".report.json";
+
+/// エラーの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// ファイルが見つからない（バックアップ中に削除された場合など）
+    NotFound,
+    /// 読み取り・書き込み権限がない
+    PermissionDenied,
+    /// その他のI/Oエラー
+    Io,
+    /// 圧縮・暗号化の失敗
+    Processing,
+    /// `pre_backup` フックの失敗による対象のスキップ
+    Hook,
+    /// コマンド出力・標準入力の取得失敗
+    Command,
+    /// 分類できないエラー
+    Other,
+}
+
+impl ErrorKind {
+    /// I/Oエラーの種類から分類
+    #[must_use]
+    pub fn from_io(error: &io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => Self::NotFound,
+            io::ErrorKind::PermissionDenied => Self::PermissionDenied,
+            _ => Self::Io,
+        }
+    }
+
+    /// エラーの原因をたどり、最初に分類できたI/Oエラーまたは [`BackupError`] から分類
+    #[must_use]
+    pub fn classify(error: &(dyn std::error::Error + 'static)) -> Self {
+        let mut current = Some(error);
+        while let Some(error) = current {
+            if let Some(io_error) = error.downcast_ref::<io::Error>() {
+                return Self::from_io(io_error);
+            }
+            match error.downcast_ref::<BackupError>() {
+                Some(BackupError::TargetNotFound { .. }) => return Self::NotFound,
+                Some(BackupError::PermissionDenied { .. }) => return Self::PermissionDenied,
+                Some(BackupError::EncryptionError(_) | BackupError::CompressionError(_)) => {
+                    return Self::Processing
+                }
+                _ => {}
+            }
+            current = error.source();
+        }
+        Self::Other
+    }
+}
+
+/// 種類付きのエラー
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportError {
+    /// 対象となった元ファイルまたはバックアップ対象のパス
+    pub path: PathBuf,
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+/// ファイル数とバイト数の集計
+///
+/// # フィールド
+///
+/// * `files` - バックアップを試みたファイル数（増分バックアップでは変更されたファイル数）
+/// * `successful` / `failed` - 成功・失敗したファイル数
+/// * `skipped` - 前回から変更がないためバックアップしなかったファイル数（増分バックアップ時）
+/// * `excluded` - 除外パターンに一致したファイル数
+/// * `linked` - 前回スナップショットからハードリンクしたファイル数
+/// * `offloaded` - リフリンク・`copy_file_range` でカーネル内コピーしたファイル数
+/// * `cancelled` - キャンセルにより処理しなかったファイル数
+/// * `bytes_read` - 元ファイルから読み込んだバイト数
+/// * `bytes_written` - バックアップ先に書き込んだバイト数（圧縮・暗号化後）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunCounts {
+    pub files: usize,
+    pub successful: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub excluded: usize,
+    pub linked: usize,
+    pub offloaded: usize,
+    pub cancelled: usize,
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+}
+
+impl RunCounts {
+    /// 別の集計を加算
+    pub fn add(&mut self, other: &Self) {
+        self.files += other.files;
+        self.successful += other.successful;
+        self.failed += other.failed;
+        self.skipped += other.skipped;
+        self.excluded += other.excluded;
+        self.linked += other.linked;
+        self.offloaded += other.offloaded;
+        self.cancelled += other.cancelled;
+        self.bytes_read += other.bytes_read;
+        self.bytes_written += other.bytes_written;
+    }
+
+    /// 書き込みバイト数 / 読み込みバイト数（読み込みがない場合は `None`）
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn compression_ratio(&self) -> Option<f64> {
+        (self.bytes_read > 0).then(|| self.bytes_written as f64 / self.bytes_read as f64)
+    }
+}
+
+/// 対象ごとの集計
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetReport {
+    pub path: PathBuf,
+    pub category: String,
+    pub priority: Priority,
+    #[serde(flatten)]
+    pub counts: RunCounts,
+    /// `pre_backup` フックやコマンドの失敗で対象全体をスキップしたか
+    #[serde(default)]
+    pub aborted: bool,
+}
+
+/// 実行全体の集計（履歴にも記録）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunSummary {
+    #[serde(flatten)]
+    pub totals: RunCounts,
+    /// 書き込みバイト数 / 読み込みバイト数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression_ratio: Option<f64>,
+    /// 1秒あたりの読み込みバイト数
+    #[serde(default)]
+    pub throughput_bytes_per_sec: u64,
+    /// エラーの種類ごとの件数
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub errors_by_kind: BTreeMap<ErrorKind, usize>,
+}
+
+/// バックアップ実行レポート
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunReport {
+    pub backup_name: String,
+    pub backup_type: BackupType,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    #[serde(flatten)]
+    pub summary: RunSummary,
+    pub targets: Vec<TargetReport>,
+    pub errors: Vec<ReportError>,
+}
+
+impl RunReport {
+    /// 対象ごとの集計とエラーからレポートを作成（合計・圧縮率・スループットを計算）
+    #[must_use]
+    pub fn new(
+        backup_name: String,
+        backup_type: BackupType,
+        started_at: DateTime<Utc>,
+        duration_ms: u64,
+        targets: Vec<TargetReport>,
+        errors: Vec<ReportError>,
+    ) -> Self {
+        let mut totals = RunCounts::default();
+        for target in &targets {
+            totals.add(&target.counts);
+        }
+        let mut errors_by_kind = BTreeMap::new();
+        for error in &errors {
+            *errors_by_kind.entry(error.kind).or_insert(0) += 1;
+        }
+        let summary = RunSummary {
+            compression_ratio: totals.compression_ratio(),
+            throughput_bytes_per_sec: totals.bytes_read.saturating_mul(1000) / duration_ms.max(1),
+            totals,
+            errors_by_kind,
+        };
+        Self {
+            backup_name,
+            backup_type,
+            started_at,
+            duration_ms,
+            summary,
+            targets,
+            errors,
+        }
+    }
+
+    /// バックアップディレクトリからレポートを読み込み
+    ///
+    /// # Errors
+    ///
+    /// レポートファイルが存在しない、または読み込み・解析に失敗した場合にエラーを返します。
+    pub fn load(backup_dir: &Path) -> Result<Self> {
+        let path = backup_dir.join(REPORT_FILE);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("レポート読み込み失敗: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("レポート解析失敗: {}", path.display()))
+    }
+
+    /// バックアップディレクトリにレポートを保存
+    ///
+    /// # Errors
+    ///
+    /// シリアライズまたは書き込みに失敗した場合にエラーを返します。
+    pub fn save(&self, backup_dir: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).context("レポートのシリアライズ失敗")?;
+        fs::write(backup_dir.join(REPORT_FILE), content).context("レポート書き込み失敗")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn counts(files: usize, failed: usize, bytes_read: u64, bytes_written: u64) -> RunCounts {
+        RunCounts {
+            files,
+            successful: files - failed,
+            failed,
+            bytes_read,
+            bytes_written,
+            ..RunCounts::default()
+        }
+    }
+
+    fn target(path: &str, counts: RunCounts) -> TargetReport {
+        TargetReport {
+            path: PathBuf::from(path),
+            category: "docs".to_string(),
+            priority: Priority::High,
+            counts,
+            aborted: false,
+        }
+    }
+
+    #[test]
+    fn test_classify_errors() {
+        let not_found = io::Error::new(io::ErrorKind::NotFound, "gone");
+        assert_eq!(ErrorKind::from_io(&not_found), ErrorKind::NotFound);
+
+        let wrapped = anyhow::Error::new(io::Error::new(io::ErrorKind::PermissionDenied, "denied"))
+            .context("コピー失敗");
+        assert_eq!(
+            ErrorKind::classify(wrapped.as_ref()),
+            ErrorKind::PermissionDenied
+        );
+
+        let backup_error = BackupError::IoError(io::Error::other("disk"));
+        assert_eq!(ErrorKind::classify(&backup_error), ErrorKind::Io);
+        let compression = BackupError::CompressionError("bad".to_string());
+        assert_eq!(ErrorKind::classify(&compression), ErrorKind::Processing);
+        assert_eq!(
+            ErrorKind::classify(anyhow::anyhow!("unknown").as_ref()),
+            ErrorKind::Other
+        );
+    }
+
+    #[test]
+    fn test_report_totals_and_roundtrip() {
+        let errors = vec![
+            ReportError {
+                path: PathBuf::from("/data/a/x"),
+                kind: ErrorKind::PermissionDenied,
+                message: "denied".to_string(),
+            },
+            ReportError {
+                path: PathBuf::from("/data/a/y"),
+                kind: ErrorKind::PermissionDenied,
+                message: "denied".to_string(),
+            },
+        ];
+        let report = RunReport::new(
+            "backup_20250105_120000".to_string(),
+            BackupType::Full,
+            Utc::now(),
+            2000,
+            vec![
+                target("/data/a", counts(10, 2, 3000, 1000)),
+                target("/data/b", counts(5, 0, 1000, 1000)),
+            ],
+            errors,
+        );
+
+        assert_eq!(report.summary.totals.files, 15);
+        assert_eq!(report.summary.totals.failed, 2);
+        assert_eq!(report.summary.totals.bytes_read, 4000);
+        assert_eq!(report.summary.compression_ratio, Some(0.5));
+        assert_eq!(report.summary.throughput_bytes_per_sec, 2000);
+        assert_eq!(
+            report
+                .summary
+                .errors_by_kind
+                .get(&ErrorKind::PermissionDenied),
+            Some(&2)
+        );
+
+        let dir = TempDir::new().unwrap();
+        report.save(dir.path()).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join(REPORT_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(json["bytes_written"], 2000);
+        assert_eq!(json["targets"][0]["failed"], 2);
+        assert_eq!(json["errors"][0]["kind"], "permission_denied");
+        assert_eq!(RunReport::load(dir.path()).unwrap(), report);
+    }
+
+    #[test]
+    fn test_compression_ratio_without_reads() {
+        assert_eq!(RunCounts::default().compression_ratio(), None);
+    }
+}
@@ -1,19 +1,32 @@
 use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use walkdir::WalkDir;
 
+use super::cancel::CancellationToken;
+use super::copy_engine::{write_sparse, SparseMap};
+use super::events::{Event, EventSink, Operation, Phase};
 use super::incremental::resolve_backup_chain;
 use super::integrity::BackupMetadata;
+use super::lock::{LockKind, RepositoryLock};
+use super::pack::{self, PackEntry, PACK_DIR};
+use super::report::{ErrorKind, REPORT_FILE};
+use super::special::{restore_special_entries, SpecialEntry};
+use crate::compression::dictionary::{self, DICTIONARY_DIR};
+use crate::compression::{header, CompressionType, ZstdDictionary};
 use crate::crypto::{EncryptedData, KeyManager};
+use crate::i18n::Language;
 use crate::security::{safe_join, safe_open, AuditEvent, AuditLog};
-use crate::ui::progress::BackupProgress;
+use crate::ui::ConsoleSink;
 
 /// 復元結果
 ///
 /// バックアップからの復元処理の結果を保持します。
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct RestoreResult {
     pub total_files: usize,
     pub restored: usize,
@@ -22,11 +35,66 @@ pub struct RestoreResult {
     pub verified_files: usize,
     pub verification_failures: usize,
     pub total_bytes: u64,
+    /// 再作成した特殊エントリ数（ディレクトリ・シンボリックリンク・ハードリンク等）
+    pub special_entries: usize,
     pub errors: Vec<String>,
+    /// キャンセルされたか（処理済みのファイルのみ復元）
+    pub cancelled: bool,
 }
 
 // RestoreResult は直接構築されるため、new() メソッドは不要
 
+/// 復元中のファイルのイベント送信と進捗の集計
+struct FileEvents<'a> {
+    events: &'a dyn EventSink,
+    total_files: usize,
+    files_done: usize,
+    bytes_done: u64,
+}
+
+impl FileEvents<'_> {
+    /// 復元の完了を送る（`bytes_read` はバックアップ上のサイズ、`bytes_written` は展開後のサイズ）
+    fn finished(&mut self, path: &Path, bytes_read: u64, bytes_written: u64) {
+        self.events.on_event(&Event::FileFinished {
+            path: path.to_path_buf(),
+            bytes_read,
+            bytes_written,
+        });
+        self.progress(bytes_read);
+    }
+
+    /// 復元の失敗を送る
+    fn failed(&mut self, path: &Path, kind: ErrorKind, message: &str) {
+        self.events.on_event(&Event::FileFailed {
+            path: path.to_path_buf(),
+            kind,
+            message: message.to_string(),
+        });
+        self.progress(0);
+    }
+
+    fn progress(&mut self, bytes: u64) {
+        self.files_done += 1;
+        self.bytes_done += bytes;
+        self.events.on_event(&Event::Progress {
+            files_done: self.files_done,
+            total_files: self.total_files,
+            bytes_done: self.bytes_done,
+        });
+    }
+}
+
+/// マニフェストに記録されたファイルの圧縮形式
+#[derive(Debug, Clone, Copy)]
+enum StoredCodec {
+    /// 記録のない旧形式のマニフェスト（ヘッダー・試行展開で判定）
+    Unknown,
+    /// 無圧縮で保存
+    Raw,
+    /// コーデックヘッダー付きで圧縮して保存
+    Framed(CompressionType),
+}
+
 /// 復元エンジン
 ///
 /// バックアップからファイルを復元します。
@@ -35,22 +103,35 @@ pub struct RestoreEngine {
     dry_run: bool,
     show_progress: bool,
     verify_integrity: bool,
+    quiet: bool,
+    event_sink: Option<Arc<dyn EventSink>>,
+    cancel: CancellationToken,
     audit_log: Option<AuditLog>,
+    /// 作成時の監査ログ初期化エラー（最初の `events()` 呼び出しで警告として送る）
+    audit_log_warning: Mutex<Option<String>>,
 }
 
 impl RestoreEngine {
     /// 新しいRestoreEngineを作成
     #[must_use]
     pub fn new(dry_run: bool) -> Self {
-        let audit_log = AuditLog::new()
-            .map_err(|e| eprintln!("警告: 監査ログの初期化に失敗しました: {e}"))
-            .ok();
+        let (audit_log, audit_log_warning) = match AuditLog::new() {
+            Ok(log) => (Some(log), None),
+            Err(e) => (
+                None,
+                Some(format!("警告: 監査ログの初期化に失敗しました: {e}")),
+            ),
+        };
 
         Self {
             dry_run,
             show_progress: true,
+            quiet: false,
             verify_integrity: true,
+            event_sink: None,
+            cancel: CancellationToken::new(),
             audit_log,
+            audit_log_warning: Mutex::new(audit_log_warning),
         }
     }
 
@@ -61,6 +142,52 @@ impl RestoreEngine {
         self
     }
 
+    /// 標準出力への情報表示（増分チェーンや復元対象の一覧など）を抑制
+    #[must_use]
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// 進行状況のイベントの送り先を設定
+    ///
+    /// 設定した場合はコンソールに表示しません（`with_progress`・`with_quiet` は無視されます）。
+    #[must_use]
+    pub fn with_event_sink(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(sink);
+        self
+    }
+
+    /// イベントの送り先（未設定の場合はコンソールに表示）
+    fn events(&self) -> Arc<dyn EventSink> {
+        let sink: Arc<dyn EventSink> = self.event_sink.clone().unwrap_or_else(|| {
+            Arc::new(
+                ConsoleSink::new(Language::detect())
+                    .with_progress(self.show_progress)
+                    .with_quiet(self.quiet),
+            )
+        });
+        if let Some(warning) = self
+            .audit_log_warning
+            .lock()
+            .ok()
+            .and_then(|mut w| w.take())
+        {
+            sink.warning(warning);
+        }
+        sink
+    }
+
+    /// キャンセル・一時停止のトークンを設定
+    ///
+    /// キャンセルされると次のファイルから復元を停止します（復元中のファイルは書き込みを完了）。
+    /// ディレクトリ・シンボリックリンクなどの特殊エントリは再作成しません。
+    #[must_use]
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
     /// 整合性検証の有効/無効を設定
     #[must_use]
     pub fn with_verification(mut self, verify: bool) -> Self {
@@ -85,26 +212,67 @@ impl RestoreEngine {
         dest_dir: &Path,
         password: Option<&str>,
     ) -> Result<RestoreResult> {
+        self.run(backup_dir, Some(dest_dir), password)
+    }
+
+    /// 復元せずにバックアップを検証
+    ///
+    /// 復元と同じ手順でファイルを読み込み・復号・展開し、マニフェストに記録されたハッシュと
+    /// Merkleルートに照合します（書き込みは行いません）。復元と同じく共有ロックを保持し、
+    /// キャンセル・一時停止のトークンに従います。結果の `restored` は展開できたファイル数、
+    /// `verified_files`・`verification_failures` は照合の結果です。
+    ///
+    /// # Errors
+    ///
+    /// バックアップディレクトリが存在しない場合、または他の処理が排他ロックを保持している場合に
+    /// エラーを返します。
+    pub fn verify(&mut self, backup_dir: &Path, password: Option<&str>) -> Result<RestoreResult> {
+        self.run(backup_dir, None, password)
+    }
+
+    /// 復元・検証の本体（`dest_dir` が `None` の場合は書き込まずに検証のみ行う）
+    fn run(
+        &mut self,
+        backup_dir: &Path,
+        dest_dir: Option<&Path>,
+        password: Option<&str>,
+    ) -> Result<RestoreResult> {
+        let events = self.events();
+        let events = &*events;
         let user = AuditLog::current_user();
         let target_desc = "backup_dir.display() → dest_dir.display()".to_string();
+        let verify_only = dest_dir.is_none();
+        let operation = if verify_only {
+            Operation::Verify
+        } else {
+            Operation::Restore
+        };
 
-        // 監査ログ: 復元開始
+        // 監査ログ: 復元・検証開始
         if let Some(ref mut audit_log) = self.audit_log {
+            let event = if verify_only {
+                AuditEvent::verify_started(&target_desc, &user)
+            } else {
+                AuditEvent::restore_started(&target_desc, &user)
+            };
             let _ = audit_log
-                .log(AuditEvent::restore_started(&target_desc, &user))
-                .map_err(|e| eprintln!("警告: 監査ログの記録に失敗しました: {e}"));
+                .log(event)
+                .map_err(|e| events.warning(format!("警告: 監査ログの記録に失敗しました: {e}")));
         }
 
         if !backup_dir.exists() {
-            // 監査ログ: 復元失敗
+            // 監査ログ: 復元・検証失敗
             if let Some(ref mut audit_log) = self.audit_log {
+                let message = "バックアップディレクトリが存在しません";
                 let _ = audit_log
-                    .log(AuditEvent::restore_failed(
-                        &target_desc,
-                        &user,
-                        "バックアップディレクトリが存在しません",
-                    ))
-                    .map_err(|e| eprintln!("警告: 監査ログの記録に失敗しました: {e}"));
+                    .log(if verify_only {
+                        AuditEvent::verify_failed(&target_desc, &user, message)
+                    } else {
+                        AuditEvent::restore_failed(&target_desc, &user, message)
+                    })
+                    .map_err(|e| {
+                        events.warning(format!("警告: 監査ログの記録に失敗しました: {e}"))
+                    });
             }
 
             return Err(anyhow::anyhow!(
@@ -112,30 +280,87 @@ impl RestoreEngine {
             ));
         }
 
+        // 復元・検証中にクリーンアップでチェーンが削除されないよう共有ロックを取得
+        // （保存先に書き込めない場合などロックを作成できないときは警告のみ）
+        let _repo_lock = match backup_dir.parent() {
+            Some(repo) => match RepositoryLock::acquire(
+                repo,
+                LockKind::Shared,
+                if verify_only { "verify" } else { "restore" },
+            ) {
+                Ok(lock) => {
+                    for stale in lock.stale_removed() {
+                        events.warning(format!("警告: 古いロックを削除しました: {stale}"));
+                    }
+                    Some(lock)
+                }
+                Err(e) => {
+                    if matches!(
+                        e.downcast_ref::<crate::error::BackupError>(),
+                        Some(crate::error::BackupError::RepositoryLocked { .. })
+                    ) {
+                        return Err(e);
+                    }
+                    events.warning(format!("警告: リポジトリロックを取得できません: {e}"));
+                    None
+                }
+            },
+            None => None,
+        };
+
         // 増分バックアップチェーンの解決
         let backup_chain = resolve_backup_chain(backup_dir)?;
 
+        // キャンセルされたバックアップは処理済みのファイルのみを含む（完全な復元ポイントではない）
+        for backup in backup_chain
+            .iter()
+            .filter(|b| BackupMetadata::is_partial(b))
+        {
+            events.warning(format!(
+                "警告: キャンセルされた部分的なバックアップです（一部のファイルのみを含みます）: {}",
+                backup.display()
+            ));
+        }
+
         if backup_chain.len() > 1 {
-            println!(
+            events.info(format!(
                 "📦 増分バックアップチェーン検出: {} 個のバックアップを順次復元",
                 backup_chain.len()
-            );
+            ));
             for (i, backup) in backup_chain.iter().enumerate() {
-                println!("  {}. {:?}", i + 1, backup.file_name().unwrap_or_default());
+                events.info(format!(
+                    "  {}. {:?}",
+                    i + 1,
+                    backup.file_name().unwrap_or_default()
+                ));
             }
         }
 
         // 復元先ディレクトリを作成
-        if !self.dry_run {
+        if let Some(dest_dir) = dest_dir.filter(|_| !self.dry_run) {
             std::fs::create_dir_all(dest_dir)
                 .context("復元先ディレクトリ作成失敗: dest_dir.display()".to_string())?;
         }
 
         // チェーン内のすべてのバックアップからファイル一覧を収集
         let mut all_files: Vec<(PathBuf, PathBuf)> = Vec::new(); // (source_backup_dir, file_path)
+                                                                 // バックアップごとのパックの索引（相対パス → パック内の位置）
+        let mut pack_indexes: HashMap<PathBuf, BTreeMap<PathBuf, PackEntry>> = HashMap::new();
+        // バックアップごとの圧縮形式の記録（記録のない旧形式のマニフェストは含まない）
+        let mut codec_indexes: HashMap<PathBuf, BTreeMap<PathBuf, CompressionType>> =
+            HashMap::new();
+        // バックアップごとのスパースファイルの配置（データ領域のみを保存したファイル）
+        let mut sparse_indexes: HashMap<PathBuf, BTreeMap<PathBuf, SparseMap>> = HashMap::new();
         for backup in &backup_chain {
             let files_in_backup: Vec<PathBuf> = WalkDir::new(backup)
                 .into_iter()
+                // zstd辞書・パックファイル・実行レポートは復元対象外（展開・読み込み時に使用）
+                .filter_entry(|e| {
+                    e.depth() != 1
+                        || (e.file_name() != DICTIONARY_DIR
+                            && e.file_name() != PACK_DIR
+                            && e.file_name() != REPORT_FILE)
+                })
                 .filter_map(std::result::Result::ok)
                 .filter(|e| e.file_type().is_file())
                 .filter(|e| {
@@ -148,19 +373,60 @@ impl RestoreEngine {
             for file_path in files_in_backup {
                 all_files.push((backup.clone(), file_path));
             }
+
+            // パックにまとめたファイル（マニフェストの索引から）
+            if let Ok(mut metadata) = BackupMetadata::load(backup) {
+                if metadata.records_codecs() {
+                    codec_indexes.insert(backup.clone(), std::mem::take(&mut metadata.file_codecs));
+                }
+                if !metadata.sparse_files.is_empty() {
+                    sparse_indexes
+                        .insert(backup.clone(), std::mem::take(&mut metadata.sparse_files));
+                }
+                if !metadata.packed_files.is_empty() {
+                    for relative in metadata.packed_files.keys() {
+                        all_files.push((backup.clone(), backup.join(relative)));
+                    }
+                    pack_indexes.insert(backup.clone(), metadata.packed_files);
+                }
+            }
         }
 
         let files: Vec<PathBuf> = all_files.iter().map(|(_, path)| path.clone()).collect();
 
+        // チェーン内のバックアップに保存されたzstd辞書（辞書ID → 辞書）
+        let mut dictionaries = HashMap::new();
+        for backup in &backup_chain {
+            match ZstdDictionary::load_all(backup) {
+                Ok(loaded) => dictionaries.extend(loaded),
+                Err(e) => events.warning(format!(
+                    "警告: zstd辞書の読み込みに失敗しました ({}): {e}",
+                    backup.display()
+                )),
+            }
+        }
+
         let total_files = files.len();
 
-        if self.dry_run {
-            println!("📋 ドライランモード: {total_files} ファイルを復元対象として検出");
+        // 特殊エントリは最新のバックアップ（チェーン末尾）のマニフェストに記録された状態を復元
+        let special_entries: std::collections::BTreeMap<PathBuf, SpecialEntry> = backup_chain
+            .last()
+            .and_then(|latest| BackupMetadata::load(latest).ok())
+            .map(|metadata| metadata.special_entries)
+            .unwrap_or_default();
+
+        if self.dry_run && !verify_only {
+            events.info(format!(
+                "📋 ドライランモード: {total_files} ファイルを復元対象として検出"
+            ));
             for (backup_src, file) in &all_files {
                 if let Ok(relative) = file.strip_prefix(backup_src) {
-                    println!("  {}", relative.display());
+                    events.info(format!("  {}", relative.display()));
                 }
             }
+            for relative in special_entries.keys() {
+                events.info(format!("  {}", relative.display()));
+            }
             return Ok(RestoreResult {
                 total_files,
                 restored: 0,
@@ -169,17 +435,18 @@ impl RestoreEngine {
                 verified_files: 0,
                 verification_failures: 0,
                 total_bytes: 0,
+                special_entries: 0,
                 errors: Vec::new(),
+                cancelled: false,
             });
         }
 
-        // プログレスバーの初期化
-        let progress = if self.show_progress {
-            let pb = BackupProgress::new(total_files as u64);
-            pb.set_message("復元中...");
-            Some(pb)
-        } else {
-            None
+        events.phase(operation, Phase::Processing { total_files });
+        let mut file_events = FileEvents {
+            events,
+            total_files,
+            files_done: 0,
+            bytes_done: 0,
         };
 
         let restored_count = AtomicUsize::new(0);
@@ -195,108 +462,141 @@ impl RestoreEngine {
         // 各バックアップディレクトリの整合性メタデータを読み込み
         let mut backup_metadata_map: std::collections::HashMap<PathBuf, BackupMetadata> =
             std::collections::HashMap::new();
-        if self.verify_integrity {
+        if self.verify_integrity || verify_only {
             for backup in &backup_chain {
                 match BackupMetadata::load(backup) {
                     Ok(metadata) => {
                         backup_metadata_map.insert(backup.clone(), metadata);
                     }
                     Err(e) => {
-                        eprintln!(
+                        events.warning(format!(
                             "警告: 整合性メタデータの読み込みに失敗しました ({}): {e}",
                             backup.display()
-                        );
+                        ));
                     }
                 }
             }
             if !backup_metadata_map.is_empty() {
-                println!(
+                events.info(format!(
                     "✓ 整合性メタデータを読み込みました（{}個のバックアップ）",
                     backup_metadata_map.len()
-                );
+                ));
             }
         }
+        if verify_only
+            && backup_metadata_map
+                .values()
+                .all(|metadata| metadata.file_hashes.is_empty())
+        {
+            events.warning(
+                "警告: ハッシュが記録されていないため、読み込み・復号・展開できることのみを確認します",
+            );
+        }
 
         let mut errors = Vec::new();
 
+        // マニフェスト自体の改ざん検出（記録されたMerkleルートとファイルハッシュの照合）
+        for (backup, metadata) in &backup_metadata_map {
+            if metadata.verify_merkle_root() == Some(false) {
+                verification_failed_count.fetch_add(1, Ordering::Relaxed);
+                errors.push(format!(
+                    "⚠ 整合性マニフェストのMerkleルートが一致しません（マニフェストが改ざんされています）: {}",
+                    backup.display()
+                ));
+            }
+        }
+
+        let mut cancelled = false;
         for (source_backup_dir, source_path) in &all_files {
-            // プログレス更新
-            if let Some(ref pb) = progress {
-                if let Some(file_name) = source_path.file_name() {
-                    pb.set_message(&format!("復元中: {file_name:?}"));
-                }
+            // キャンセル後は次のファイルを復元しない（一時停止中は再開まで待機）
+            if self.cancel.should_stop() {
+                cancelled = true;
+                events.phase(operation, Phase::Cancelled);
+                break;
             }
+            events.on_event(&Event::FileStarted {
+                path: source_path.clone(),
+            });
 
             // 相対パスを取得（元のバックアップディレクトリを基準に）
             let relative_path = match source_path.strip_prefix(source_backup_dir) {
                 Ok(r) => r,
                 Err(e) => {
-                    errors.push(format!("相対パス取得失敗: source_path.display(): {e}"));
+                    let message = format!("相対パス取得失敗: source_path.display(): {e}");
+                    file_events.failed(source_path, ErrorKind::Other, &message);
+                    errors.push(message);
                     failed_count.fetch_add(1, Ordering::Relaxed);
-                    if let Some(ref pb) = progress {
-                        pb.inc(1);
-                    }
                     continue;
                 }
             };
 
-            // 復元先パスを安全に結合（パストラバーサル対策）
-            let dest_path = match safe_join(dest_dir, relative_path) {
-                Ok(p) => p,
-                Err(e) => {
-                    errors.push(format!(
-                        "パストラバーサル検出: relative_path.display(): {e}"
-                    ));
+            // 復元先パスを安全に結合（パストラバーサル対策、検証のみの場合は書き込まない）
+            let dest_path = match dest_dir.map(|dest_dir| safe_join(dest_dir, relative_path)) {
+                None => None,
+                Some(Ok(p)) => Some(p),
+                Some(Err(e)) => {
+                    let message = format!("パストラバーサル検出: relative_path.display(): {e}");
+                    file_events.failed(source_path, ErrorKind::PermissionDenied, &message);
+                    errors.push(message);
                     failed_count.fetch_add(1, Ordering::Relaxed);
-                    if let Some(ref pb) = progress {
-                        pb.inc(1);
-                    }
                     continue;
                 }
             };
 
             // 親ディレクトリを作成
-            if let Some(parent) = dest_path.parent() {
+            if let Some(parent) = dest_path.as_deref().and_then(Path::parent) {
                 if let Err(e) = std::fs::create_dir_all(parent) {
-                    errors.push(format!("ディレクトリ作成失敗: {}: {e}", parent.display()));
+                    let message = format!("ディレクトリ作成失敗: {}: {e}", parent.display());
+                    file_events.failed(source_path, ErrorKind::from_io(&e), &message);
+                    errors.push(message);
                     failed_count.fetch_add(1, Ordering::Relaxed);
-                    if let Some(ref pb) = progress {
-                        pb.inc(1);
-                    }
                     continue;
                 }
             }
 
-            // ファイルを安全に読み込み（シンボリックリンク攻撃対策）
-            let file_data = match safe_open(source_path) {
-                Ok(mut file) => {
-                    let mut buffer = Vec::new();
-                    match file.read_to_end(&mut buffer) {
-                        Ok(_) => buffer,
-                        Err(e) => {
-                            errors.push(format!("ファイル読み込み失敗: {e}"));
-                            failed_count.fetch_add(1, Ordering::Relaxed);
-                            if let Some(ref pb) = progress {
-                                pb.inc(1);
-                            }
-                            continue;
-                        }
+            // パックにまとめたファイルはパックから取り出し、
+            // それ以外はファイルを安全に読み込み（シンボリックリンク攻撃対策）
+            let packed_entry = pack_indexes
+                .get(source_backup_dir)
+                .and_then(|index| index.get(relative_path));
+            let read_result = if let Some(entry) = packed_entry {
+                pack::read_entry(source_backup_dir, entry)
+                    .map_err(|e| format!("パックファイル読み込み失敗: {e:#}"))
+            } else {
+                match safe_open(source_path) {
+                    Ok(mut file) => {
+                        let mut buffer = Vec::new();
+                        file.read_to_end(&mut buffer)
+                            .map(|_| buffer)
+                            .map_err(|e| format!("ファイル読み込み失敗: {e}"))
                     }
+                    Err(e) => Err(format!(
+                        "ファイルオープン失敗（シンボリックリンク検出の可能性）: {e}"
+                    )),
                 }
+            };
+            let file_data = match read_result {
+                Ok(data) => data,
                 Err(e) => {
-                    errors.push(format!(
-                        "ファイルオープン失敗（シンボリックリンク検出の可能性）: {e}"
-                    ));
+                    file_events.failed(source_path, ErrorKind::Io, &e);
+                    errors.push(e);
                     failed_count.fetch_add(1, Ordering::Relaxed);
-                    if let Some(ref pb) = progress {
-                        pb.inc(1);
-                    }
                     continue;
                 }
             };
 
+            // マニフェストに記録された圧縮形式
+            let stored =
+                codec_indexes
+                    .get(source_backup_dir)
+                    .map_or(StoredCodec::Unknown, |codecs| {
+                        codecs
+                            .get(relative_path)
+                            .map_or(StoredCodec::Raw, |&codec| StoredCodec::Framed(codec))
+                    });
+
             // 暗号化データかどうか判定して復号
-            let final_data = if let Ok(encrypted_data) = EncryptedData::from_bytes(&file_data) {
+            let decoded = if let Ok(encrypted_data) = EncryptedData::from_bytes(&file_data) {
                 // 暗号化されたファイル
                 encrypted_count.fetch_add(1, Ordering::Relaxed);
 
@@ -305,13 +605,11 @@ impl RestoreEngine {
                     let pwd = match password {
                         Some(p) => p.to_string(),
                         None => {
-                            errors.push(
-                                "暗号化されたファイルですがパスワードが未指定: relative_path.display()".to_string()
-                            );
+                            let message =
+                                "暗号化されたファイルですがパスワードが未指定: relative_path.display()".to_string();
+                            file_events.failed(source_path, ErrorKind::Processing, &message);
+                            errors.push(message);
                             failed_count.fetch_add(1, Ordering::Relaxed);
-                            if let Some(ref pb) = progress {
-                                pb.inc(1);
-                            }
                             continue;
                         }
                     };
@@ -323,11 +621,10 @@ impl RestoreEngine {
                             master_key_opt = Some(std::sync::Arc::new(mk));
                         }
                         Err(e) => {
-                            errors.push(format!("マスターキー復元失敗: {e}"));
+                            let message = format!("マスターキー復元失敗: {e}");
+                            file_events.failed(source_path, ErrorKind::Processing, &message);
+                            errors.push(message);
                             failed_count.fetch_add(1, Ordering::Relaxed);
-                            if let Some(ref pb) = progress {
-                                pb.inc(1);
-                            }
                             continue;
                         }
                     }
@@ -340,67 +637,105 @@ impl RestoreEngine {
                 match encryption_engine.decrypt(&encrypted_data, master_key) {
                     Ok(decrypted_data) => {
                         // 復号化されたデータを展開（圧縮されている可能性）
-                        self.decompress_if_needed(&decrypted_data)?
+                        self.decompress_if_needed(&decrypted_data, stored, &dictionaries)
                     }
                     Err(e) => {
-                        errors.push(format!("復号化失敗: relative_path.display(): {e}"));
+                        let message = format!("復号化失敗: relative_path.display(): {e}");
+                        file_events.failed(source_path, ErrorKind::Processing, &message);
+                        errors.push(message);
                         failed_count.fetch_add(1, Ordering::Relaxed);
-                        if let Some(ref pb) = progress {
-                            pb.inc(1);
-                        }
                         continue;
                     }
                 }
             } else {
                 // 通常のファイル（暗号化されていない）
                 // 圧縮されている可能性を確認
-                self.decompress_if_needed(&file_data)?
+                self.decompress_if_needed(&file_data, stored, &dictionaries)
+            };
+            let final_data = match decoded {
+                Ok(data) => data,
+                Err(e) => {
+                    let message = format!("展開失敗: {}: {e:#}", relative_path.display());
+                    file_events.failed(source_path, ErrorKind::Processing, &message);
+                    errors.push(message);
+                    failed_count.fetch_add(1, Ordering::Relaxed);
+                    continue;
+                }
             };
 
-            // 復元先に書き込み
-            match std::fs::write(&dest_path, &final_data) {
-                Ok(_) => {
+            // 復元先に書き込み（ゼロ領域・記録されたホールはホールとして再作成）
+            let sparse = sparse_indexes
+                .get(source_backup_dir)
+                .and_then(|maps| maps.get(relative_path));
+            let written = match (&dest_path, sparse) {
+                (Some(dest_path), Some(map)) => map.write(dest_path, &final_data),
+                (Some(dest_path), None) => write_sparse(dest_path, &final_data).map(drop),
+                (None, _) => Ok(()),
+            };
+            match written {
+                Ok(()) => {
+                    let restored_len = sparse.map_or(final_data.len() as u64, |map| map.len);
                     restored_count.fetch_add(1, Ordering::Relaxed);
-                    total_bytes.fetch_add(final_data.len(), Ordering::Relaxed);
+                    total_bytes.fetch_add(restored_len as usize, Ordering::Relaxed);
+                    file_events.finished(source_path, file_data.len() as u64, restored_len);
 
                     // 整合性検証（該当するバックアップディレクトリのメタデータを使用）
-                    if let Some(metadata) = backup_metadata_map.get(source_backup_dir) {
-                        match metadata.verify_file(relative_path, &dest_path) {
-                            Ok(true) => {
-                                verified_count.fetch_add(1, Ordering::Relaxed);
-                            }
-                            Ok(false) => {
-                                verification_failed_count.fetch_add(1, Ordering::Relaxed);
-                                errors.push(
+                    // ハッシュが記録されていないマニフェスト（整合性検証無効時）は検証対象外
+                    if let Some(metadata) = backup_metadata_map
+                        .get(source_backup_dir)
+                        .filter(|m| !m.file_hashes.is_empty())
+                    {
+                        if metadata
+                            .inconsistent_files
+                            .iter()
+                            .any(|p| p == relative_path)
+                        {
+                            events.warning(format!(
+                                "警告: バックアップ中に変更されていたため整合性を検証できません: {}",
+                                relative_path.display()
+                            ));
+                        } else {
+                            let verified = match &dest_path {
+                                Some(dest_path) => metadata.verify_file(relative_path, dest_path),
+                                None => metadata.verify_data(relative_path, &final_data),
+                            };
+                            match verified {
+                                Ok(true) => {
+                                    verified_count.fetch_add(1, Ordering::Relaxed);
+                                }
+                                Ok(false) => {
+                                    verification_failed_count.fetch_add(1, Ordering::Relaxed);
+                                    errors.push(
                                     "⚠ 整合性検証失敗（ファイルが改ざんされています）: relative_path.display()".to_string()
                                 );
-                            }
-                            Err(e) => {
-                                eprintln!("警告: 整合性検証エラー: relative_path.display(): {e}");
+                                }
+                                Err(e) => {
+                                    events.warning(format!(
+                                        "警告: 整合性検証エラー: relative_path.display(): {e}"
+                                    ));
+                                }
                             }
                         }
                     }
                 }
                 Err(e) => {
-                    errors.push(format!("ファイル書き込み失敗: dest_path.display(): {e}"));
+                    let message = format!("ファイル書き込み失敗: dest_path.display(): {e}");
+                    file_events.failed(source_path, ErrorKind::classify(&e), &message);
+                    errors.push(message);
                     failed_count.fetch_add(1, Ordering::Relaxed);
                 }
             }
-
-            if let Some(ref pb) = progress {
-                pb.inc(1);
-            }
         }
 
-        // プログレスバー完了
-        if let Some(pb) = progress {
-            let failed = failed_count.load(Ordering::Relaxed);
-            if failed == 0 {
-                pb.finish("✓ 復元完了");
-            } else {
-                pb.finish(&format!("⚠ 復元完了（{failed}件失敗）"));
+        // ディレクトリ・シンボリックリンク・ハードリンク・特殊ファイルを再作成
+        let special_restored = match dest_dir {
+            Some(dest_dir) if !cancelled => {
+                let special_outcome = restore_special_entries(dest_dir, &special_entries);
+                errors.extend(special_outcome.errors);
+                special_outcome.restored
             }
-        }
+            _ => 0,
+        };
 
         let result = RestoreResult {
             total_files,
@@ -410,8 +745,16 @@ impl RestoreEngine {
             verified_files: verified_count.load(Ordering::Relaxed),
             verification_failures: verification_failed_count.load(Ordering::Relaxed),
             total_bytes: total_bytes.load(Ordering::Relaxed) as u64,
+            special_entries: special_restored,
             errors,
+            cancelled,
         };
+        events.phase(
+            operation,
+            Phase::Finished {
+                failed: result.failed,
+            },
+        );
 
         // 監査ログ: 復元完了 or 失敗
         if let Some(ref mut audit_log) = self.audit_log {
@@ -423,9 +766,27 @@ impl RestoreEngine {
                 "verified_files": result.verified_files,
                 "verification_failures": result.verification_failures,
                 "total_bytes": result.total_bytes,
+                "special_entries": result.special_entries,
             });
 
-            let event = if result.failed == 0 {
+            let event = if verify_only {
+                if result.cancelled {
+                    AuditEvent::verify_cancelled(&target_desc, &user, metadata)
+                } else if result.failed == 0 && result.verification_failures == 0 {
+                    AuditEvent::verify_completed(&target_desc, &user, metadata)
+                } else {
+                    AuditEvent::verify_failed(
+                        &target_desc,
+                        &user,
+                        format!(
+                            "{}件のファイルでエラー、{}件で整合性検証の失敗が発生しました",
+                            result.failed, result.verification_failures
+                        ),
+                    )
+                }
+            } else if result.cancelled {
+                AuditEvent::restore_cancelled(&target_desc, &user, metadata)
+            } else if result.failed == 0 {
                 AuditEvent::restore_completed(&target_desc, &user, metadata)
             } else {
                 AuditEvent::restore_failed(
@@ -437,14 +798,44 @@ impl RestoreEngine {
 
             let _ = audit_log
                 .log(event)
-                .map_err(|e| eprintln!("警告: 監査ログの記録に失敗しました: {e}"));
+                .map_err(|e| events.warning(format!("警告: 監査ログの記録に失敗しました: {e}")));
         }
 
         Ok(result)
     }
 
     /// 圧縮されている場合に展開
-    fn decompress_if_needed(&self, data: &[u8]) -> Result<Vec<u8>> {
+    fn decompress_if_needed(
+        &self,
+        data: &[u8],
+        stored: StoredCodec,
+        dictionaries: &HashMap<u32, ZstdDictionary>,
+    ) -> Result<Vec<u8>> {
+        match stored {
+            // 無圧縮で保存されたファイルは内容にかかわらずそのまま復元
+            StoredCodec::Raw => return Ok(data.to_vec()),
+            StoredCodec::Framed(codec) => {
+                if header::codec_of(data) != Some(codec) {
+                    anyhow::bail!(
+                        "コーデックヘッダーがマニフェストの記録（{codec:?}）と一致しません"
+                    );
+                }
+            }
+            StoredCodec::Unknown => {}
+        }
+        // zstd辞書で圧縮されたファイルはフレームの辞書IDに対応する辞書で展開
+        if let Some(decoded) = dictionary::decode(data, dictionaries) {
+            return Ok(decoded?);
+        }
+        // コーデックヘッダーがあれば記録された形式で展開
+        if let Some(decoded) = header::decode(data) {
+            return Ok(decoded?);
+        }
+        // 以下は圧縮形式の記録・コーデックヘッダー導入前のバックアップ向け
+        // 適応圧縮はヘッダーの判定に従って展開（保存されたzstd・gzipファイルを展開しない）
+        if let Some(decoded) = crate::compression::adaptive::decode(data) {
+            return Ok(decoded?);
+        }
         // zstd → gzip → 無圧縮の順で試す
         if let Ok(decompressed) = zstd::decode_all(data) {
             Ok(decompressed)
@@ -0,0 +1,142 @@
+//! # 目標復旧時点（RPO）
+//!
+//! 対象ごとに「最後に成功したバックアップからの許容経過時間」を確認します
+//! （例: 高優先度のデータは24時間以上古いバックアップしかない状態にしない）。
+//!
+//! 許容経過時間は対象の `max_age_hours`、未設定の場合は `[rpo]` の優先度ごとの設定を使用します。
+//! 最後に成功したバックアップは履歴の対象ごとの結果（[`TargetRecord`](super::history::TargetRecord)）
+//! から判定するため、対象ごとの結果を記録していない古い履歴エントリは考慮しません。
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! use backup_suite::core::rpo;
+//! use backup_suite::{BackupHistory, Config};
+//!
+//! let config = Config::load().unwrap();
+//! let history = BackupHistory::load_all().unwrap();
+//! for status in rpo::evaluate(&config, &history, chrono::Utc::now()) {
+//!     if status.violated {
+//!         println!("{}: RPO超過", status.path.display());
+//!     }
+//! }
+//! ```
+
+use chrono::{DateTime, Duration, Utc};
+use std::path::PathBuf;
+
+use super::config::Config;
+use super::history::BackupHistory;
+use super::target::Priority;
+
+/// 対象のRPOの状態
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpoStatus {
+    /// バックアップ対象のパス
+    pub path: PathBuf,
+    /// 対象のカテゴリ
+    pub category: String,
+    /// 対象の優先度
+    pub priority: Priority,
+    /// 許容経過時間（時間）
+    pub max_age_hours: u64,
+    /// 最後に成功したバックアップの日時
+    pub last_success: Option<DateTime<Utc>>,
+    /// 最後に成功したバックアップからの経過時間
+    pub age: Option<Duration>,
+    /// 許容経過時間を超過しているか（成功したバックアップがない場合も超過）
+    pub violated: bool,
+}
+
+/// 許容経過時間が設定された対象のRPOの状態を `now` 時点で評価
+#[must_use]
+pub fn evaluate(config: &Config, history: &[BackupHistory], now: DateTime<Utc>) -> Vec<RpoStatus> {
+    config
+        .targets
+        .iter()
+        .filter_map(|target| {
+            let max_age_hours = target
+                .max_age_hours
+                .or_else(|| config.rpo.max_age_hours(&target.priority))?;
+            let last_success =
+                BackupHistory::last_success_for(history, &target.path).map(|h| h.timestamp);
+            let age = last_success.map(|timestamp| now.signed_duration_since(timestamp));
+            let max_age = Duration::hours(i64::try_from(max_age_hours).unwrap_or(i64::MAX / 3600));
+            Some(RpoStatus {
+                path: target.path.clone(),
+                category: target.category.clone(),
+                priority: target.priority,
+                max_age_hours,
+                last_success,
+                age,
+                violated: age.is_none_or(|age| age > max_age),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::history::TargetRecord;
+    use crate::core::Target;
+
+    fn success(path: &str, hours_ago: i64, now: DateTime<Utc>) -> BackupHistory {
+        let mut entry = BackupHistory::new(PathBuf::from("/backup"), 1, 1, true, false, false)
+            .with_targets(vec![TargetRecord {
+                path: PathBuf::from(path),
+                category: "docs".to_string(),
+                priority: Priority::High,
+                files: 1,
+                failed: 0,
+                success: true,
+            }]);
+        entry.timestamp = now - Duration::hours(hours_ago);
+        entry
+    }
+
+    #[test]
+    fn test_evaluate_rpo() {
+        let now = Utc::now();
+        let mut config = Config::default();
+        config.rpo.high_hours = Some(24);
+        let target =
+            |path: &str, priority| Target::new(PathBuf::from(path), priority, "docs".to_string());
+        config.targets = vec![
+            target("/data/fresh", Priority::High),
+            target("/data/stale", Priority::High),
+            target("/data/never", Priority::High),
+            target("/data/untracked", Priority::Low),
+        ];
+        let mut custom = target("/data/custom", Priority::Low);
+        custom.max_age_hours = Some(1);
+        config.targets.push(custom);
+
+        let mut failed = success("/data/stale", 1, now);
+        failed.targets[0].success = false;
+        let history = vec![
+            success("/data/fresh", 2, now),
+            success("/data/stale", 30, now),
+            failed,
+            success("/data/custom", 3, now),
+        ];
+
+        let statuses = evaluate(&config, &history, now);
+        let violated: Vec<(&str, bool)> = statuses
+            .iter()
+            .map(|s| (s.path.to_str().unwrap(), s.violated))
+            .collect();
+        assert_eq!(
+            violated,
+            [
+                ("/data/fresh", false),
+                ("/data/stale", true),
+                ("/data/never", true),
+                ("/data/custom", true),
+            ]
+        );
+        assert_eq!(statuses[1].age, Some(Duration::hours(30)));
+        assert_eq!(statuses[2].last_success, None);
+        assert_eq!(statuses[3].max_age_hours, 1);
+    }
+}
@@ -8,6 +8,7 @@
 //! - **macOS launchd統合**: plist設定ファイル生成と管理
 //! - **Linux systemd統合**: service/timer ユニット管理
 //! - **優先度別スケジュール**: High/Medium/Low で異なる頻度設定
+//! - **ジョブ別スケジュール**: `schedule` を設定した名前付きジョブを `run --job <名前>` で実行
 //! - **設定の検証**: スケジュール設定の妥当性チェック
 //!
 //! # 使用例
@@ -32,8 +33,11 @@
 //! ```
 
 use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use super::calendar::CalendarSpec;
 use super::config::Config;
 use super::target::Priority;
 
@@ -81,7 +85,9 @@ impl Platform {
     }
 }
 
-/// スケジュール頻度
+/// スケジュール頻度（キーワード）
+///
+/// cron式などの任意の時刻は [`CalendarSpec`] で指定します。
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Frequency {
     /// 毎日実行（午前2時）
@@ -118,6 +124,90 @@ impl Frequency {
     }
 }
 
+/// スケジュールの単位（優先度ごと、または名前付きジョブごと）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScheduleUnit<'a> {
+    Priority(Priority),
+    Job(&'a str),
+}
+
+impl<'a> ScheduleUnit<'a> {
+    /// スケジュール対象のユニット一覧（全優先度と `schedule` が設定されたジョブ）
+    pub(crate) fn all(config: &'a Config) -> Vec<Self> {
+        [Priority::High, Priority::Medium, Priority::Low]
+            .into_iter()
+            .map(ScheduleUnit::Priority)
+            .chain(
+                config
+                    .jobs
+                    .iter()
+                    .filter(|(_, job)| job.schedule.is_some())
+                    .map(|(name, _)| ScheduleUnit::Job(name.as_str())),
+            )
+            .collect()
+    }
+
+    /// ユニット名・ファイル名に使用する識別子（例: `high`、`job-nightly`）
+    pub(crate) fn id(&self) -> String {
+        match self {
+            ScheduleUnit::Priority(priority) => Scheduler::priority_to_string(priority).to_string(),
+            ScheduleUnit::Job(name) => format!("job-{name}"),
+        }
+    }
+
+    /// ユニットの説明
+    fn description(&self) -> String {
+        match self {
+            ScheduleUnit::Priority(priority) => {
+                format!(
+                    "{} Priority Backup",
+                    Scheduler::priority_to_string(priority)
+                )
+            }
+            ScheduleUnit::Job(name) => format!("Job {name} Backup"),
+        }
+    }
+
+    /// `backup-suite` に渡す引数
+    pub(crate) fn run_args(&self) -> [&'a str; 3] {
+        match *self {
+            ScheduleUnit::Priority(priority) => [
+                "run",
+                "--priority",
+                Scheduler::priority_to_string(&priority),
+            ],
+            ScheduleUnit::Job(name) => ["run", "--job", name],
+        }
+    }
+
+    /// 実行時刻（優先度は `[schedule]` の頻度、ジョブは `schedule`）
+    pub(crate) fn calendar(&self, config: &Config) -> Result<CalendarSpec> {
+        match self {
+            ScheduleUnit::Priority(priority) => CalendarSpec::parse(match priority {
+                Priority::High => &config.schedule.high_frequency,
+                Priority::Medium => &config.schedule.medium_frequency,
+                Priority::Low => &config.schedule.low_frequency,
+            }),
+            ScheduleUnit::Job(name) => config
+                .job(name)?
+                .calendar()?
+                .ok_or_else(|| anyhow::anyhow!("ジョブ {name} に schedule が設定されていません")),
+        }
+    }
+
+    /// 実行開始をランダムに遅らせる最大秒数（ジョブの設定がなければ `[schedule]` の設定）
+    pub(crate) fn randomized_delay_secs(&self, config: &Config) -> u64 {
+        let job_delay = match self {
+            ScheduleUnit::Job(name) => config
+                .jobs
+                .get(*name)
+                .and_then(|job| job.randomized_delay_secs),
+            ScheduleUnit::Priority(_) => None,
+        };
+        job_delay.unwrap_or(config.schedule.randomized_delay_secs)
+    }
+}
+
 impl Scheduler {
     /// 新しいSchedulerインスタンスを作成
     ///
@@ -164,6 +254,12 @@ impl Scheduler {
             }
         }
 
+        for name in self.scheduled_jobs() {
+            if let Err(e) = self.setup_job(name) {
+                eprintln!("警告: ジョブ {name} のスケジュール設定失敗: {e}");
+            }
+        }
+
         Ok(())
     }
 
@@ -177,11 +273,37 @@ impl Scheduler {
     ///
     /// 成功時は `Ok(())`、失敗時はエラー
     pub fn setup_priority(&self, priority: &Priority) -> Result<()> {
-        let frequency = self.get_frequency(priority)?;
+        let calendar = self.get_calendar(priority)?;
+        self.setup_unit(&ScheduleUnit::Priority(*priority), &calendar)
+    }
+
+    /// 名前付きジョブのスケジュールをセットアップ
+    ///
+    /// 生成されるユニットは `backup-suite run --job <名前>` を実行するため、
+    /// ジョブに定義した暗号化・圧縮・増分設定がそのまま使用されます。
+    ///
+    /// # 引数
+    ///
+    /// * `name` - ジョブ名
+    ///
+    /// # 戻り値
+    ///
+    /// 成功時は `Ok(())`、失敗時はエラー
+    ///
+    /// # エラー
+    ///
+    /// * ジョブが定義されていない、または定義が不正な場合
+    /// * ジョブに `schedule` が設定されていない場合
+    pub fn setup_job(&self, name: &str) -> Result<()> {
+        let calendar = self.get_job_calendar(name)?;
+        self.setup_unit(&ScheduleUnit::Job(name), &calendar)
+    }
 
+    /// ユニットのスケジュールをセットアップ
+    fn setup_unit(&self, unit: &ScheduleUnit, calendar: &CalendarSpec) -> Result<()> {
         match self.platform {
-            Platform::MacOS => self.setup_launchd(priority, frequency),
-            Platform::Linux => self.setup_systemd(priority, frequency),
+            Platform::MacOS => self.setup_launchd(unit, calendar),
+            Platform::Linux => self.setup_systemd(unit, calendar),
             Platform::Unsupported => Err(anyhow::anyhow!("サポートされていないプラットフォーム")),
         }
     }
@@ -200,6 +322,12 @@ impl Scheduler {
             }
         }
 
+        for name in self.scheduled_jobs() {
+            if let Err(e) = self.enable_job(name) {
+                eprintln!("警告: ジョブ {name} の有効化失敗: {e}");
+            }
+        }
+
         Ok(())
     }
 
@@ -213,9 +341,27 @@ impl Scheduler {
     ///
     /// 成功時は `Ok(())`、失敗時はエラー
     pub fn enable_priority(&self, priority: &Priority) -> Result<()> {
+        self.enable_unit(&ScheduleUnit::Priority(*priority))
+    }
+
+    /// 名前付きジョブのスケジュールを有効化
+    ///
+    /// # 引数
+    ///
+    /// * `name` - ジョブ名
+    ///
+    /// # 戻り値
+    ///
+    /// 成功時は `Ok(())`、失敗時はエラー
+    pub fn enable_job(&self, name: &str) -> Result<()> {
+        self.enable_unit(&ScheduleUnit::Job(name))
+    }
+
+    /// ユニットのスケジュールを有効化
+    fn enable_unit(&self, unit: &ScheduleUnit) -> Result<()> {
         match self.platform {
-            Platform::MacOS => self.enable_launchd(priority),
-            Platform::Linux => self.enable_systemd(priority),
+            Platform::MacOS => self.enable_launchd(unit),
+            Platform::Linux => self.enable_systemd(unit),
             Platform::Unsupported => Err(anyhow::anyhow!("サポートされていないプラットフォーム")),
         }
     }
@@ -234,6 +380,12 @@ impl Scheduler {
             }
         }
 
+        for name in self.scheduled_jobs() {
+            if let Err(e) = self.disable_job(name) {
+                eprintln!("警告: ジョブ {name} の無効化失敗: {e}");
+            }
+        }
+
         Ok(())
     }
 
@@ -247,9 +399,27 @@ impl Scheduler {
     ///
     /// 成功時は `Ok(())`、失敗時はエラー
     pub fn disable_priority(&self, priority: &Priority) -> Result<()> {
+        self.disable_unit(&ScheduleUnit::Priority(*priority))
+    }
+
+    /// 名前付きジョブのスケジュールを無効化
+    ///
+    /// # 引数
+    ///
+    /// * `name` - ジョブ名
+    ///
+    /// # 戻り値
+    ///
+    /// 成功時は `Ok(())`、失敗時はエラー
+    pub fn disable_job(&self, name: &str) -> Result<()> {
+        self.disable_unit(&ScheduleUnit::Job(name))
+    }
+
+    /// ユニットのスケジュールを無効化
+    fn disable_unit(&self, unit: &ScheduleUnit) -> Result<()> {
         match self.platform {
-            Platform::MacOS => self.disable_launchd(priority),
-            Platform::Linux => self.disable_systemd(priority),
+            Platform::MacOS => self.disable_launchd(unit),
+            Platform::Linux => self.disable_systemd(unit),
             Platform::Unsupported => Err(anyhow::anyhow!("サポートされていないプラットフォーム")),
         }
     }
@@ -258,16 +428,12 @@ impl Scheduler {
     ///
     /// # 戻り値
     ///
-    /// 各優先度の有効/無効状態を返す
+    /// 各優先度・スケジュール設定済みジョブの有効/無効状態を返す
     pub fn check_status(&self) -> Result<ScheduleStatus> {
         let mut status = ScheduleStatus::default();
 
         for priority in &[Priority::High, Priority::Medium, Priority::Low] {
-            let enabled = match self.platform {
-                Platform::MacOS => self.is_launchd_enabled(priority)?,
-                Platform::Linux => self.is_systemd_enabled(priority)?,
-                Platform::Unsupported => false,
-            };
+            let enabled = self.is_unit_enabled(&ScheduleUnit::Priority(*priority))?;
 
             match priority {
                 Priority::High => status.high_enabled = enabled,
@@ -276,18 +442,45 @@ impl Scheduler {
             }
         }
 
+        for name in self.scheduled_jobs() {
+            let enabled = self.is_unit_enabled(&ScheduleUnit::Job(name))?;
+            status.jobs.insert(name.to_string(), enabled);
+        }
+
         Ok(status)
     }
 
-    /// 優先度に対応する頻度を取得
-    fn get_frequency(&self, priority: &Priority) -> Result<Frequency> {
-        let freq_str = match priority {
-            Priority::High => &self.config.schedule.high_frequency,
-            Priority::Medium => &self.config.schedule.medium_frequency,
-            Priority::Low => &self.config.schedule.low_frequency,
-        };
+    /// ユニットのスケジュールが有効かチェック
+    fn is_unit_enabled(&self, unit: &ScheduleUnit) -> Result<bool> {
+        match self.platform {
+            Platform::MacOS => self.is_launchd_enabled(unit),
+            Platform::Linux => self.is_systemd_enabled(unit),
+            Platform::Unsupported => Ok(false),
+        }
+    }
+
+    /// `schedule` が設定されたジョブ名の一覧
+    fn scheduled_jobs(&self) -> impl Iterator<Item = &str> {
+        self.config
+            .jobs
+            .iter()
+            .filter(|(_, job)| job.schedule.is_some())
+            .map(|(name, _)| name.as_str())
+    }
 
-        Frequency::parse(freq_str)
+    /// ジョブに設定された実行時刻を取得
+    fn get_job_calendar(&self, name: &str) -> Result<CalendarSpec> {
+        ScheduleUnit::Job(name).calendar(&self.config)
+    }
+
+    /// 優先度に対応する実行時刻を取得
+    fn get_calendar(&self, priority: &Priority) -> Result<CalendarSpec> {
+        ScheduleUnit::Priority(*priority).calendar(&self.config)
+    }
+
+    /// 実行開始をランダムに遅らせる最大秒数
+    fn randomized_delay_secs(&self, unit: &ScheduleUnit) -> u64 {
+        unit.randomized_delay_secs(&self.config)
     }
 
     /// 優先度を文字列に変換
@@ -302,18 +495,15 @@ impl Scheduler {
     // ========== macOS launchd 実装 ==========
 
     /// launchd plist ファイルのパスを取得
-    fn get_launchd_plist_path(&self, priority: &Priority) -> Result<PathBuf> {
+    fn get_launchd_plist_path(&self, unit: &ScheduleUnit) -> Result<PathBuf> {
         let home = dirs::home_dir().context("ホームディレクトリが見つかりません")?;
-        let filename = format!(
-            "com.backup-suite.{}.plist",
-            Self::priority_to_string(priority)
-        );
+        let filename = format!("com.backup-suite.{}.plist", unit.id());
         Ok(home.join("Library/LaunchAgents").join(filename))
     }
 
     /// launchd スケジュールをセットアップ
-    fn setup_launchd(&self, priority: &Priority, frequency: Frequency) -> Result<()> {
-        let plist_path = self.get_launchd_plist_path(priority)?;
+    fn setup_launchd(&self, unit: &ScheduleUnit, calendar: &CalendarSpec) -> Result<()> {
+        let plist_path = self.get_launchd_plist_path(unit)?;
 
         // ディレクトリを作成
         if let Some(parent) = plist_path.parent() {
@@ -321,7 +511,7 @@ impl Scheduler {
         }
 
         // plist コンテンツを生成
-        let plist_content = self.generate_plist_content(priority, frequency)?;
+        let plist_content = self.generate_plist_content(unit, calendar)?;
 
         // ファイルに書き込み
         std::fs::write(&plist_path, plist_content)
@@ -331,45 +521,53 @@ impl Scheduler {
     }
 
     /// launchd plist コンテンツを生成
-    fn generate_plist_content(&self, priority: &Priority, frequency: Frequency) -> Result<String> {
+    fn generate_plist_content(
+        &self,
+        unit: &ScheduleUnit,
+        calendar: &CalendarSpec,
+    ) -> Result<String> {
         let backup_suite_path = std::env::current_exe()?;
-        let priority_str = Self::priority_to_string(priority);
-
-        let calendar_interval = match frequency {
-            Frequency::Daily => {
-                r"<dict>
-        <key>Hour</key>
-        <integer>2</integer>
-        <key>Minute</key>
-        <integer>0</integer>
-    </dict>"
-            }
-            Frequency::Weekly => {
-                r"<dict>
-        <key>Hour</key>
-        <integer>2</integer>
-        <key>Minute</key>
-        <integer>0</integer>
-        <key>Weekday</key>
-        <integer>0</integer>
-    </dict>"
-            }
-            Frequency::Monthly => {
-                r"<dict>
-        <key>Hour</key>
-        <integer>2</integer>
-        <key>Minute</key>
-        <integer>0</integer>
-        <key>Day</key>
-        <integer>1</integer>
-    </dict>"
-            }
-            Frequency::Hourly => {
-                r"<dict>
-        <key>Minute</key>
-        <integer>0</integer>
-    </dict>"
-            }
+        let id = unit.id();
+
+        // launchd にはランダム遅延がないため、シェルで待機してから実行する
+        let delay = self.randomized_delay_secs(unit);
+        let mut program_arguments: Vec<String> = Vec::new();
+        if delay > 0 {
+            program_arguments.extend([
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                format!(
+                    "sleep $(( (RANDOM * 32768 + RANDOM) % {} )); exec \"$0\" \"$@\"",
+                    delay + 1
+                ),
+            ]);
+        }
+        program_arguments.push(backup_suite_path.display().to_string());
+        program_arguments.extend(unit.run_args().iter().map(ToString::to_string));
+        let program_arguments = program_arguments
+            .iter()
+            .map(|arg| format!("        <string>{}</string>", escape_xml(arg)))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // 1件は dict、複数件は dict の配列
+        let dicts: Vec<String> = calendar
+            .launchd_intervals()?
+            .iter()
+            .map(|entry| {
+                let keys: String = entry
+                    .iter()
+                    .map(|(key, value)| {
+                        format!("\n        <key>{key}</key>\n        <integer>{value}</integer>")
+                    })
+                    .collect();
+                format!("<dict>{keys}\n    </dict>")
+            })
+            .collect();
+        let calendar_interval = if dicts.len() == 1 {
+            dicts[0].clone()
+        } else {
+            format!("<array>\n    {}\n    </array>", dicts.join("\n    "))
         };
 
         let plist = format!(
@@ -378,14 +576,11 @@ impl Scheduler {
 <plist version="1.0">
 <dict>
     <key>Label</key>
-    <string>com.backup-suite.{priority}</string>
+    <string>com.backup-suite.{id}</string>
 
     <key>ProgramArguments</key>
     <array>
-        <string>{backup_suite_path}</string>
-        <string>run</string>
-        <string>--priority</string>
-        <string>{priority}</string>
+{program_arguments}
     </array>
 
     <key>StartCalendarInterval</key>
@@ -395,10 +590,10 @@ impl Scheduler {
     <false/>
 
     <key>StandardOutPath</key>
-    <string>/tmp/backup-suite-{priority}.log</string>
+    <string>/tmp/backup-suite-{id}.log</string>
 
     <key>StandardErrorPath</key>
-    <string>/tmp/backup-suite-{priority}.error.log</string>
+    <string>/tmp/backup-suite-{id}.error.log</string>
 
     <key>EnvironmentVariables</key>
     <dict>
@@ -406,18 +601,15 @@ impl Scheduler {
         <string>/usr/local/bin:/usr/bin:/bin:/usr/sbin:/sbin</string>
     </dict>
 </dict>
-</plist>"#,
-            priority = priority_str,
-            backup_suite_path = backup_suite_path.display(),
-            calendar_interval = calendar_interval
+</plist>"#
         );
 
         Ok(plist)
     }
 
     /// launchd スケジュールを有効化
-    fn enable_launchd(&self, priority: &Priority) -> Result<()> {
-        let plist_path = self.get_launchd_plist_path(priority)?;
+    fn enable_launchd(&self, unit: &ScheduleUnit) -> Result<()> {
+        let plist_path = self.get_launchd_plist_path(unit)?;
 
         if !plist_path.exists() {
             return Err(anyhow::anyhow!(
@@ -440,8 +632,8 @@ impl Scheduler {
     }
 
     /// launchd スケジュールを無効化
-    fn disable_launchd(&self, priority: &Priority) -> Result<()> {
-        let plist_path = self.get_launchd_plist_path(priority)?;
+    fn disable_launchd(&self, unit: &ScheduleUnit) -> Result<()> {
+        let plist_path = self.get_launchd_plist_path(unit)?;
 
         let output = std::process::Command::new("launchctl")
             .args(["unload", &plist_path.to_string_lossy()])
@@ -464,8 +656,8 @@ impl Scheduler {
     }
 
     /// launchd スケジュールが有効かチェック
-    fn is_launchd_enabled(&self, priority: &Priority) -> Result<bool> {
-        let label = format!("com.backup-suite.{}", Self::priority_to_string(priority));
+    fn is_launchd_enabled(&self, unit: &ScheduleUnit) -> Result<bool> {
+        let label = format!("com.backup-suite.{}", unit.id());
 
         let output = std::process::Command::new("launchctl")
             .args(["list", &label])
@@ -478,26 +670,23 @@ impl Scheduler {
     // ========== Linux systemd 実装 ==========
 
     /// systemd service ファイルのパスを取得
-    fn get_systemd_service_path(&self, priority: &Priority) -> Result<PathBuf> {
+    fn get_systemd_service_path(&self, unit: &ScheduleUnit) -> Result<PathBuf> {
         let home = dirs::home_dir().context("ホームディレクトリが見つかりません")?;
-        let filename = format!(
-            "backup-suite-{}.service",
-            Self::priority_to_string(priority)
-        );
+        let filename = format!("backup-suite-{}.service", unit.id());
         Ok(home.join(".config/systemd/user").join(filename))
     }
 
     /// systemd timer ファイルのパスを取得
-    fn get_systemd_timer_path(&self, priority: &Priority) -> Result<PathBuf> {
+    fn get_systemd_timer_path(&self, unit: &ScheduleUnit) -> Result<PathBuf> {
         let home = dirs::home_dir().context("ホームディレクトリが見つかりません")?;
-        let filename = format!("backup-suite-{}.timer", Self::priority_to_string(priority));
+        let filename = format!("backup-suite-{}.timer", unit.id());
         Ok(home.join(".config/systemd/user").join(filename))
     }
 
     /// systemd スケジュールをセットアップ
-    fn setup_systemd(&self, priority: &Priority, frequency: Frequency) -> Result<()> {
-        let service_path = self.get_systemd_service_path(priority)?;
-        let timer_path = self.get_systemd_timer_path(priority)?;
+    fn setup_systemd(&self, unit: &ScheduleUnit, calendar: &CalendarSpec) -> Result<()> {
+        let service_path = self.get_systemd_service_path(unit)?;
+        let timer_path = self.get_systemd_timer_path(unit)?;
 
         // ディレクトリを作成
         if let Some(parent) = service_path.parent() {
@@ -505,12 +694,12 @@ impl Scheduler {
         }
 
         // service ファイルを生成
-        let service_content = self.generate_systemd_service_content(priority)?;
+        let service_content = self.generate_systemd_service_content(unit)?;
         std::fs::write(&service_path, service_content)
             .context("serviceファイル書き込み失敗: service_path.display()".to_string())?;
 
         // timer ファイルを生成
-        let timer_content = self.generate_systemd_timer_content(priority, frequency)?;
+        let timer_content = self.generate_systemd_timer_content(unit, calendar)?;
         std::fs::write(&timer_path, timer_content)
             .context("timerファイル書き込み失敗: timer_path.display()".to_string())?;
 
@@ -524,25 +713,25 @@ impl Scheduler {
     }
 
     /// systemd service コンテンツを生成
-    fn generate_systemd_service_content(&self, priority: &Priority) -> Result<String> {
+    fn generate_systemd_service_content(&self, unit: &ScheduleUnit) -> Result<String> {
         let backup_suite_path = std::env::current_exe()?;
-        let priority_str = Self::priority_to_string(priority);
 
         let service = format!(
             r"[Unit]
-Description=Backup Suite - {priority} Priority Backup
+Description=Backup Suite - {description}
 After=network.target
 
 [Service]
 Type=oneshot
-ExecStart={backup_suite_path} run --priority {priority}
+ExecStart={backup_suite_path} {args}
 StandardOutput=journal
 StandardError=journal
 
 [Install]
 WantedBy=default.target
 ",
-            priority = priority_str,
+            description = unit.description(),
+            args = unit.run_args().join(" "),
             backup_suite_path = backup_suite_path.display()
         );
 
@@ -552,25 +741,23 @@ WantedBy=default.target
     /// systemd timer コンテンツを生成
     fn generate_systemd_timer_content(
         &self,
-        priority: &Priority,
-        frequency: Frequency,
+        unit: &ScheduleUnit,
+        calendar: &CalendarSpec,
     ) -> Result<String> {
-        let priority_str = Self::priority_to_string(priority);
-
-        let on_calendar = match frequency {
-            Frequency::Daily => "*-*-* 02:00:00",
-            Frequency::Weekly => "Sun *-*-* 02:00:00",
-            Frequency::Monthly => "*-*-01 02:00:00",
-            Frequency::Hourly => "*-*-* *:00:00",
+        let description = unit.description();
+        let on_calendar = calendar.to_on_calendar();
+        let delay = match self.randomized_delay_secs(unit) {
+            0 => String::new(),
+            secs => format!("RandomizedDelaySec={secs}\n"),
         };
 
         let timer = format!(
             r"[Unit]
-Description=Backup Suite - {priority_str} Priority Backup Timer
+Description=Backup Suite - {description} Timer
 
 [Timer]
 OnCalendar={on_calendar}
-Persistent=true
+{delay}Persistent=true
 
 [Install]
 WantedBy=timers.target
@@ -581,8 +768,8 @@ WantedBy=timers.target
     }
 
     /// systemd スケジュールを有効化
-    fn enable_systemd(&self, priority: &Priority) -> Result<()> {
-        let timer_name = format!("backup-suite-{}.timer", Self::priority_to_string(priority));
+    fn enable_systemd(&self, unit: &ScheduleUnit) -> Result<()> {
+        let timer_name = format!("backup-suite-{}.timer", unit.id());
 
         // timerを有効化
         let output = std::process::Command::new("systemctl")
@@ -610,8 +797,8 @@ WantedBy=timers.target
     }
 
     /// systemd スケジュールを無効化
-    fn disable_systemd(&self, priority: &Priority) -> Result<()> {
-        let timer_name = format!("backup-suite-{}.timer", Self::priority_to_string(priority));
+    fn disable_systemd(&self, unit: &ScheduleUnit) -> Result<()> {
+        let timer_name = format!("backup-suite-{}.timer", unit.id());
 
         // timerを停止
         let _output = std::process::Command::new("systemctl")
@@ -626,8 +813,8 @@ WantedBy=timers.target
             .context("systemctl disable 失敗")?;
 
         // ファイルを削除
-        let service_path = self.get_systemd_service_path(priority)?;
-        let timer_path = self.get_systemd_timer_path(priority)?;
+        let service_path = self.get_systemd_service_path(unit)?;
+        let timer_path = self.get_systemd_timer_path(unit)?;
 
         if service_path.exists() {
             std::fs::remove_file(&service_path)?;
@@ -646,8 +833,8 @@ WantedBy=timers.target
     }
 
     /// systemd スケジュールが有効かチェック
-    fn is_systemd_enabled(&self, priority: &Priority) -> Result<bool> {
-        let timer_name = format!("backup-suite-{}.timer", Self::priority_to_string(priority));
+    fn is_systemd_enabled(&self, unit: &ScheduleUnit) -> Result<bool> {
+        let timer_name = format!("backup-suite-{}.timer", unit.id());
 
         let output = std::process::Command::new("systemctl")
             .args(["--user", "is-enabled", &timer_name])
@@ -658,8 +845,15 @@ WantedBy=timers.target
     }
 }
 
+/// plist に埋め込む文字列をエスケープ
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
 /// スケジュール状態
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ScheduleStatus {
     /// 高優先度が有効か
     pub high_enabled: bool,
@@ -667,11 +861,14 @@ pub struct ScheduleStatus {
     pub medium_enabled: bool,
     /// 低優先度が有効か
     pub low_enabled: bool,
+    /// スケジュール設定済みジョブごとの有効状態（ジョブ名 → 有効か）
+    pub jobs: BTreeMap<String, bool>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::job::BackupJob;
 
     #[test]
     fn test_platform_detection() {
@@ -764,16 +961,16 @@ mod tests {
             let scheduler = Scheduler::new(config).unwrap();
 
             assert_eq!(
-                scheduler.get_frequency(&Priority::High).unwrap(),
-                Frequency::Hourly
+                scheduler.get_calendar(&Priority::High).unwrap(),
+                CalendarSpec::from(Frequency::Hourly)
             );
             assert_eq!(
-                scheduler.get_frequency(&Priority::Medium).unwrap(),
-                Frequency::Daily
+                scheduler.get_calendar(&Priority::Medium).unwrap(),
+                CalendarSpec::from(Frequency::Daily)
             );
             assert_eq!(
-                scheduler.get_frequency(&Priority::Low).unwrap(),
-                Frequency::Weekly
+                scheduler.get_calendar(&Priority::Low).unwrap(),
+                CalendarSpec::from(Frequency::Weekly)
             );
         }
     }
@@ -786,7 +983,7 @@ mod tests {
         #[cfg(any(target_os = "macos", target_os = "linux"))]
         {
             let scheduler = Scheduler::new(config).unwrap();
-            assert!(scheduler.get_frequency(&Priority::High).is_err());
+            assert!(scheduler.get_calendar(&Priority::High).is_err());
         }
     }
 
@@ -797,7 +994,10 @@ mod tests {
         let scheduler = Scheduler::new(config).unwrap();
 
         let content = scheduler
-            .generate_plist_content(&Priority::High, Frequency::Daily)
+            .generate_plist_content(
+                &ScheduleUnit::Priority(Priority::High),
+                &Frequency::Daily.into(),
+            )
             .unwrap();
 
         assert!(content.contains("com.backup-suite.high"));
@@ -813,7 +1013,10 @@ mod tests {
         let scheduler = Scheduler::new(config).unwrap();
 
         let content = scheduler
-            .generate_plist_content(&Priority::Medium, Frequency::Weekly)
+            .generate_plist_content(
+                &ScheduleUnit::Priority(Priority::Medium),
+                &Frequency::Weekly.into(),
+            )
             .unwrap();
 
         assert!(content.contains("com.backup-suite.medium"));
@@ -828,7 +1031,10 @@ mod tests {
         let scheduler = Scheduler::new(config).unwrap();
 
         let content = scheduler
-            .generate_plist_content(&Priority::Low, Frequency::Monthly)
+            .generate_plist_content(
+                &ScheduleUnit::Priority(Priority::Low),
+                &Frequency::Monthly.into(),
+            )
             .unwrap();
 
         assert!(content.contains("com.backup-suite.low"));
@@ -843,7 +1049,10 @@ mod tests {
         let scheduler = Scheduler::new(config).unwrap();
 
         let content = scheduler
-            .generate_plist_content(&Priority::High, Frequency::Hourly)
+            .generate_plist_content(
+                &ScheduleUnit::Priority(Priority::High),
+                &Frequency::Hourly.into(),
+            )
             .unwrap();
 
         assert!(content.contains("<key>Minute</key>"));
@@ -858,7 +1067,9 @@ mod tests {
         let config = Config::default();
         let scheduler = Scheduler::new(config).unwrap();
 
-        let path = scheduler.get_launchd_plist_path(&Priority::High).unwrap();
+        let path = scheduler
+            .get_launchd_plist_path(&ScheduleUnit::Priority(Priority::High))
+            .unwrap();
         let path_str = path.to_string_lossy();
 
         assert!(path_str.contains("Library/LaunchAgents"));
@@ -872,7 +1083,7 @@ mod tests {
         let scheduler = Scheduler::new(config).unwrap();
 
         let content = scheduler
-            .generate_systemd_service_content(&Priority::High)
+            .generate_systemd_service_content(&ScheduleUnit::Priority(Priority::High))
             .unwrap();
 
         assert!(content.contains("[Unit]"));
@@ -889,7 +1100,10 @@ mod tests {
         let scheduler = Scheduler::new(config).unwrap();
 
         let content = scheduler
-            .generate_systemd_timer_content(&Priority::High, Frequency::Daily)
+            .generate_systemd_timer_content(
+                &ScheduleUnit::Priority(Priority::High),
+                &Frequency::Daily.into(),
+            )
             .unwrap();
 
         assert!(content.contains("[Unit]"));
@@ -905,7 +1119,10 @@ mod tests {
         let scheduler = Scheduler::new(config).unwrap();
 
         let content = scheduler
-            .generate_systemd_timer_content(&Priority::Medium, Frequency::Weekly)
+            .generate_systemd_timer_content(
+                &ScheduleUnit::Priority(Priority::Medium),
+                &Frequency::Weekly.into(),
+            )
             .unwrap();
 
         assert!(content.contains("OnCalendar=Sun *-*-* 02:00:00"));
@@ -918,7 +1135,10 @@ mod tests {
         let scheduler = Scheduler::new(config).unwrap();
 
         let content = scheduler
-            .generate_systemd_timer_content(&Priority::Low, Frequency::Monthly)
+            .generate_systemd_timer_content(
+                &ScheduleUnit::Priority(Priority::Low),
+                &Frequency::Monthly.into(),
+            )
             .unwrap();
 
         assert!(content.contains("OnCalendar=*-*-01 02:00:00"));
@@ -931,7 +1151,10 @@ mod tests {
         let scheduler = Scheduler::new(config).unwrap();
 
         let content = scheduler
-            .generate_systemd_timer_content(&Priority::High, Frequency::Hourly)
+            .generate_systemd_timer_content(
+                &ScheduleUnit::Priority(Priority::High),
+                &Frequency::Hourly.into(),
+            )
             .unwrap();
 
         assert!(content.contains("OnCalendar=*-*-* *:00:00"));
@@ -943,8 +1166,9 @@ mod tests {
         let config = Config::default();
         let scheduler = Scheduler::new(config).unwrap();
 
-        let service_path = scheduler.get_systemd_service_path(&Priority::High).unwrap();
-        let timer_path = scheduler.get_systemd_timer_path(&Priority::High).unwrap();
+        let unit = ScheduleUnit::Priority(Priority::High);
+        let service_path = scheduler.get_systemd_service_path(&unit).unwrap();
+        let timer_path = scheduler.get_systemd_timer_path(&unit).unwrap();
 
         let service_str = service_path.to_string_lossy();
         let timer_str = timer_path.to_string_lossy();
@@ -954,6 +1178,130 @@ mod tests {
         assert!(timer_str.contains("backup-suite-high.timer"));
     }
 
+    fn config_with_jobs() -> Config {
+        let mut config = Config::default();
+        config.jobs.insert(
+            "nightly".to_string(),
+            BackupJob {
+                schedule: Some("daily".to_string()),
+                ..Default::default()
+            },
+        );
+        config
+            .jobs
+            .insert("manual".to_string(), BackupJob::default());
+        config
+    }
+
+    #[test]
+    fn test_schedule_unit_for_job() {
+        let unit = ScheduleUnit::Job("nightly");
+        assert_eq!(unit.id(), "job-nightly");
+        assert_eq!(unit.run_args(), ["run", "--job", "nightly"]);
+        assert_eq!(
+            ScheduleUnit::Priority(Priority::Low).run_args(),
+            ["run", "--priority", "low"]
+        );
+    }
+
+    #[test]
+    fn test_scheduled_jobs_and_frequency() {
+        #[cfg(any(target_os = "macos", target_os = "linux"))]
+        {
+            let scheduler = Scheduler::new(config_with_jobs()).unwrap();
+
+            assert_eq!(scheduler.scheduled_jobs().collect::<Vec<_>>(), ["nightly"]);
+            assert_eq!(
+                scheduler.get_job_calendar("nightly").unwrap(),
+                CalendarSpec::from(Frequency::Daily)
+            );
+            // scheduleのないジョブ・未定義のジョブはセットアップできない
+            assert!(scheduler.get_job_calendar("manual").is_err());
+            assert!(scheduler.setup_job("missing").is_err());
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_generate_systemd_units_for_job() {
+        let scheduler = Scheduler::new(config_with_jobs()).unwrap();
+        let unit = ScheduleUnit::Job("nightly");
+
+        let service = scheduler.generate_systemd_service_content(&unit).unwrap();
+        assert!(service.contains("run --job nightly"));
+        assert!(!service.contains("--priority"));
+
+        let timer = scheduler
+            .generate_systemd_timer_content(&unit, &Frequency::Daily.into())
+            .unwrap();
+        assert!(timer.contains("Job nightly Backup Timer"));
+
+        let service_path = scheduler.get_systemd_service_path(&unit).unwrap();
+        assert!(service_path
+            .to_string_lossy()
+            .contains("backup-suite-job-nightly.service"));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_generate_systemd_timer_custom_schedule_and_delay() {
+        let mut config = config_with_jobs();
+        config.schedule.randomized_delay_secs = 300;
+        if let Some(job) = config.jobs.get_mut("nightly") {
+            job.schedule = Some("0 9,18 * * mon-fri".to_string());
+            job.randomized_delay_secs = Some(900);
+        }
+        let scheduler = Scheduler::new(config).unwrap();
+
+        let calendar = scheduler.get_job_calendar("nightly").unwrap();
+        let timer = scheduler
+            .generate_systemd_timer_content(&ScheduleUnit::Job("nightly"), &calendar)
+            .unwrap();
+        assert!(timer.contains("OnCalendar=Mon,Tue,Wed,Thu,Fri *-*-* 09,18:00:00"));
+        assert!(timer.contains("RandomizedDelaySec=900"));
+
+        // 優先度のスケジュールは [schedule] の遅延を使用
+        let timer = scheduler
+            .generate_systemd_timer_content(
+                &ScheduleUnit::Priority(Priority::High),
+                &Frequency::Daily.into(),
+            )
+            .unwrap();
+        assert!(timer.contains("RandomizedDelaySec=300"));
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn test_generate_plist_content_custom_schedule_and_delay() {
+        let mut config = Config::default();
+        config.schedule.randomized_delay_secs = 600;
+        let scheduler = Scheduler::new(config).unwrap();
+
+        let calendar = CalendarSpec::parse("30 9,18 * * *").unwrap();
+        let content = scheduler
+            .generate_plist_content(&ScheduleUnit::Priority(Priority::High), &calendar)
+            .unwrap();
+
+        assert!(content.contains("<array>\n    <dict>"));
+        assert!(content.contains("<integer>18</integer>"));
+        assert!(content.contains("<string>/bin/sh</string>"));
+        assert!(content.contains("% 601"));
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn test_generate_plist_content_for_job() {
+        let scheduler = Scheduler::new(config_with_jobs()).unwrap();
+
+        let content = scheduler
+            .generate_plist_content(&ScheduleUnit::Job("nightly"), &Frequency::Daily.into())
+            .unwrap();
+
+        assert!(content.contains("com.backup-suite.job-nightly"));
+        assert!(content.contains("<string>--job</string>"));
+        assert!(content.contains("<string>nightly</string>"));
+    }
+
     #[test]
     fn test_schedule_status_default() {
         let status = ScheduleStatus::default();
@@ -0,0 +1,663 @@
+//! # 特殊エントリモジュール
+//!
+//! 通常ファイル以外のエントリをバックアップのマニフェスト（`.integrity`）に記録し、
+//! 復元時に再作成する機能を提供します。
+//!
+//! # 対象エントリ
+//!
+//! - **ディレクトリ**: 空ディレクトリを含め、パーミッションとともに記録
+//! - **シンボリックリンク**: リンク先パスを記録（リンク先の内容は追跡しない）。
+//!   復元先の外を指すリンクもそのまま復元する（復元時にリンクをたどらないため）
+//! - **ハードリンク**: 同一inodeの2つ目以降のパスを、最初のパスへの参照として記録
+//! - **FIFO・デバイスファイル**: 種別とデバイス番号を記録（Unix系のみ）
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! use backup_suite::core::special::{restore_special_entries, SpecialEntry};
+//! use std::collections::BTreeMap;
+//! use std::path::{Path, PathBuf};
+//!
+//! let mut entries = BTreeMap::new();
+//! if let Some(entry) = SpecialEntry::from_path(Path::new("/home/user/docs/latest")) {
+//!     entries.insert(PathBuf::from("docs/latest"), entry);
+//! }
+//!
+//! let outcome = restore_special_entries(Path::new("/restore"), &entries);
+//! println!("{}件の特殊エントリを復元", outcome.restored);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::security::safe_join;
+
+/// 特殊エントリの種別
+///
+/// # バリアント
+///
+/// * Directory - ディレクトリ
+/// * Symlink - シンボリックリンク（`target` はリンク先）
+/// * Hardlink - ハードリンク（`target` は同一inodeの最初のエントリのバックアップ内相対パス）
+/// * Fifo - 名前付きパイプ
+/// * CharDevice - キャラクタデバイス（`rdev` はデバイス番号）
+/// * BlockDevice - ブロックデバイス（`rdev` はデバイス番号）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EntryKind {
+    Directory,
+    Symlink { target: PathBuf },
+    Hardlink { target: PathBuf },
+    Fifo,
+    CharDevice { rdev: u64 },
+    BlockDevice { rdev: u64 },
+}
+
+/// マニフェストに記録される特殊エントリ
+///
+/// # フィールド
+///
+/// * `kind` - エントリ種別
+/// * `mode` - パーミッション（Unix系のみ、下位12ビット）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecialEntry {
+    #[serde(flatten)]
+    pub kind: EntryKind,
+    #[serde(default)]
+    pub mode: Option<u32>,
+}
+
+impl SpecialEntry {
+    /// 新しいSpecialEntryを作成
+    #[must_use]
+    pub fn new(kind: EntryKind, mode: Option<u32>) -> Self {
+        Self { kind, mode }
+    }
+
+    /// パスから特殊エントリを生成
+    ///
+    /// シンボリックリンクを追跡せずにメタデータを取得し、通常ファイル以外であれば
+    /// 対応する `SpecialEntry` を返します。通常ファイルや取得失敗時は `None` を返します。
+    ///
+    /// # 引数
+    ///
+    /// * `path` - 判定対象のパス
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let metadata = fs::symlink_metadata(path).ok()?;
+        let file_type = metadata.file_type();
+        let mode = mode_of(&metadata);
+
+        if file_type.is_dir() {
+            return Some(Self::new(EntryKind::Directory, mode));
+        }
+        if file_type.is_symlink() {
+            let target = fs::read_link(path).ok()?;
+            return Some(Self::new(EntryKind::Symlink { target }, None));
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{FileTypeExt, MetadataExt};
+            if file_type.is_fifo() {
+                return Some(Self::new(EntryKind::Fifo, mode));
+            }
+            if file_type.is_char_device() {
+                return Some(Self::new(
+                    EntryKind::CharDevice {
+                        rdev: metadata.rdev(),
+                    },
+                    mode,
+                ));
+            }
+            if file_type.is_block_device() {
+                return Some(Self::new(
+                    EntryKind::BlockDevice {
+                        rdev: metadata.rdev(),
+                    },
+                    mode,
+                ));
+            }
+        }
+
+        None
+    }
+}
+
+/// パーミッションを取得（Unix系のみ）
+fn mode_of(metadata: &fs::Metadata) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        Some(metadata.permissions().mode() & 0o7777)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+/// ハードリンク検出器
+///
+/// バックアップ対象の走査中に同一inodeを持つファイルを検出し、
+/// 2つ目以降のパスを最初のパスへの参照として扱えるようにします。
+#[derive(Debug, Default)]
+pub struct HardlinkTracker {
+    seen: HashMap<(u64, u64), PathBuf>,
+}
+
+impl HardlinkTracker {
+    /// 新しいHardlinkTrackerを作成
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ファイルを登録し、既出のinodeであれば最初のパスを返す
+    ///
+    /// リンク数が1のファイルは常に `None` を返します。
+    ///
+    /// # 引数
+    ///
+    /// * `metadata` - ファイルのメタデータ
+    /// * `relative_path` - バックアップ内の相対パス
+    ///
+    /// # 戻り値
+    ///
+    /// 同一inodeのファイルが既に登録されている場合はそのパス、それ以外は `None`
+    pub fn register(&mut self, metadata: &fs::Metadata, relative_path: &Path) -> Option<PathBuf> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            if metadata.nlink() < 2 {
+                return None;
+            }
+            let key = (metadata.dev(), metadata.ino());
+            if let Some(first) = self.seen.get(&key) {
+                return Some(first.clone());
+            }
+            self.seen.insert(key, relative_path.to_path_buf());
+            None
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (metadata, relative_path);
+            None
+        }
+    }
+}
+
+/// 特殊エントリ復元結果
+#[derive(Debug, Default)]
+pub struct SpecialRestoreOutcome {
+    pub restored: usize,
+    pub errors: Vec<String>,
+}
+
+/// 特殊エントリを復元先に再作成
+///
+/// 通常ファイルの復元後に呼び出します。以下の順序で処理します:
+///
+/// 1. ディレクトリ作成
+/// 2. ハードリンク・FIFO・デバイスファイル作成
+/// 3. シンボリックリンク作成（書き込み経路として悪用されないよう最後に作成）
+/// 4. ディレクトリのパーミッション設定（深い階層から順に）
+///
+/// すべてのパスは [`safe_join`] で復元先配下に制限され、親ディレクトリが
+/// シンボリックリンクのパスには作成しません（リンクを経由して復元先の外に書き込まないため）。
+/// シンボリックリンクは作成するだけでたどらないため、リンク先（復元先の外を指す絶対パス・
+/// 相対パスを含む）は記録されたとおりに復元します。
+///
+/// # 引数
+///
+/// * `dest_dir` - 復元先ディレクトリ
+/// * `entries` - バックアップ内相対パスと特殊エントリのマップ
+pub fn restore_special_entries(
+    dest_dir: &Path,
+    entries: &BTreeMap<PathBuf, SpecialEntry>,
+) -> SpecialRestoreOutcome {
+    let mut outcome = SpecialRestoreOutcome::default();
+
+    let mut resolved: Vec<(&PathBuf, PathBuf, &SpecialEntry)> = Vec::new();
+    for (relative, entry) in entries {
+        match safe_join(dest_dir, relative) {
+            Ok(path) => resolved.push((relative, path, entry)),
+            Err(e) => outcome
+                .errors
+                .push(format!("パストラバーサル検出: {}: {e}", relative.display())),
+        }
+    }
+
+    // 1. ディレクトリ
+    for (relative, path, entry) in &resolved {
+        if entry.kind == EntryKind::Directory {
+            match ensure_real_dir_path(dest_dir, path).and_then(|()| fs::create_dir_all(path)) {
+                Ok(()) => outcome.restored += 1,
+                Err(e) => outcome
+                    .errors
+                    .push(format!("ディレクトリ作成失敗: {}: {e}", relative.display())),
+            }
+        }
+    }
+
+    // 2. ハードリンク・特殊ファイル
+    for (relative, path, entry) in &resolved {
+        let result = match &entry.kind {
+            EntryKind::Hardlink { target } => restore_hardlink(dest_dir, path, target),
+            EntryKind::Fifo | EntryKind::CharDevice { .. } | EntryKind::BlockDevice { .. } => {
+                ensure_no_symlink_parent(dest_dir, path).and_then(|()| create_node(path, entry))
+            }
+            _ => continue,
+        };
+        match result {
+            Ok(()) => outcome.restored += 1,
+            Err(e) => outcome
+                .errors
+                .push(format!("特殊エントリ復元失敗: {}: {e}", relative.display())),
+        }
+    }
+
+    // 3. シンボリックリンク
+    for (relative, path, entry) in &resolved {
+        if let EntryKind::Symlink { target } = &entry.kind {
+            match restore_symlink(dest_dir, path, target) {
+                Ok(()) => outcome.restored += 1,
+                Err(e) => outcome.errors.push(format!(
+                    "シンボリックリンク復元失敗: {}: {e}",
+                    relative.display()
+                )),
+            }
+        }
+    }
+
+    // 4. ディレクトリのパーミッション（読み取り専用ディレクトリで後続の書き込みが失敗しないよう最後に設定）
+    for (relative, path, entry) in resolved.iter().rev() {
+        if entry.kind == EntryKind::Directory {
+            if let Err(e) =
+                ensure_real_dir_path(dest_dir, path).and_then(|()| apply_mode(path, entry.mode))
+            {
+                outcome.errors.push(format!(
+                    "パーミッション設定失敗: {}: {e}",
+                    relative.display()
+                ));
+            }
+        }
+    }
+
+    outcome
+}
+
+/// 復元先からパスの親ディレクトリまでにシンボリックリンクが含まれていないか確認
+///
+/// 復元済み・既存のリンクを経由して、復元先の外に作成・削除しないようにします。
+fn ensure_no_symlink_parent(dest_dir: &Path, path: &Path) -> std::io::Result<()> {
+    let parent = path
+        .parent()
+        .and_then(|p| p.strip_prefix(dest_dir).ok())
+        .ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "復元先の外のパスです")
+        })?;
+    let mut current = dest_dir.to_path_buf();
+    for component in parent.components() {
+        current.push(component);
+        if fs::symlink_metadata(&current).is_ok_and(|m| m.file_type().is_symlink()) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!(
+                    "親ディレクトリがシンボリックリンクです: {}",
+                    current.display()
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// ディレクトリのパスとその親ディレクトリがシンボリックリンクでないか確認
+///
+/// 既存のリンクをたどって復元先の外のディレクトリを作成・パーミッション変更しないようにします。
+fn ensure_real_dir_path(dest_dir: &Path, path: &Path) -> std::io::Result<()> {
+    ensure_no_symlink_parent(dest_dir, path)?;
+    if fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink()) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            "同名のシンボリックリンクが存在します",
+        ));
+    }
+    Ok(())
+}
+
+/// 既存のファイル・リンクを削除（ディレクトリは削除しない）
+fn remove_existing(path: &Path) -> std::io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(m) if m.is_dir() => Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            "同名のディレクトリが存在します",
+        )),
+        Ok(_) => fs::remove_file(path),
+        Err(_) => Ok(()),
+    }
+}
+
+fn restore_hardlink(dest_dir: &Path, path: &Path, target: &Path) -> std::io::Result<()> {
+    let source = safe_join(dest_dir, target)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+    ensure_no_symlink_parent(dest_dir, &source)?;
+    ensure_no_symlink_parent(dest_dir, path)?;
+
+    // リンク元は復元済みの通常ファイルに限定（シンボリックリンク経由のリンク作成を防止）
+    if !fs::symlink_metadata(&source)?.is_file() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "ハードリンク元が通常ファイルではありません",
+        ));
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    remove_existing(path)?;
+    fs::hard_link(&source, path)
+}
+
+fn restore_symlink(dest_dir: &Path, path: &Path, target: &Path) -> std::io::Result<()> {
+    if target.as_os_str().is_empty() || target.to_string_lossy().contains('\0') {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "リンク先が不正です",
+        ));
+    }
+    ensure_no_symlink_parent(dest_dir, path)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    remove_existing(path)?;
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, path)
+    }
+    #[cfg(windows)]
+    {
+        if dest_dir.join(target).is_dir() {
+            std::os::windows::fs::symlink_dir(target, path)
+        } else {
+            std::os::windows::fs::symlink_file(target, path)
+        }
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "このプラットフォームではシンボリックリンクを作成できません",
+        ))
+    }
+}
+
+#[cfg(unix)]
+fn create_node(path: &Path, entry: &SpecialEntry) -> std::io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    remove_existing(path)?;
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let mode = entry.mode.unwrap_or(0o644) as libc::mode_t;
+
+    // SAFETY: c_path は有効なNUL終端文字列で、呼び出し中は生存している
+    let ret = unsafe {
+        match entry.kind {
+            EntryKind::Fifo => libc::mkfifo(c_path.as_ptr(), mode),
+            EntryKind::CharDevice { rdev } => {
+                libc::mknod(c_path.as_ptr(), libc::S_IFCHR | mode, rdev as libc::dev_t)
+            }
+            EntryKind::BlockDevice { rdev } => {
+                libc::mknod(c_path.as_ptr(), libc::S_IFBLK | mode, rdev as libc::dev_t)
+            }
+            _ => return Ok(()),
+        }
+    };
+
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(unix))]
+fn create_node(_path: &Path, _entry: &SpecialEntry) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "このプラットフォームでは特殊ファイルを作成できません",
+    ))
+}
+
+fn apply_mode(path: &Path, mode: Option<u32>) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Some(mode) = mode {
+            fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+        }
+        Ok(())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (path, mode);
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_from_path_detects_kinds() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("empty");
+        fs::create_dir(&dir).unwrap();
+        let link = temp.path().join("link");
+        std::os::unix::fs::symlink("empty", &link).unwrap();
+        let file = temp.path().join("file.txt");
+        fs::write(&file, b"data").unwrap();
+
+        assert_eq!(
+            SpecialEntry::from_path(&dir).unwrap().kind,
+            EntryKind::Directory
+        );
+        assert_eq!(
+            SpecialEntry::from_path(&link).unwrap().kind,
+            EntryKind::Symlink {
+                target: PathBuf::from("empty")
+            }
+        );
+        assert!(SpecialEntry::from_path(&file).is_none());
+    }
+
+    #[test]
+    fn test_hardlink_tracker() {
+        let temp = TempDir::new().unwrap();
+        let a = temp.path().join("a");
+        let b = temp.path().join("b");
+        fs::write(&a, b"shared").unwrap();
+        fs::hard_link(&a, &b).unwrap();
+
+        let mut tracker = HardlinkTracker::new();
+        let meta_a = fs::metadata(&a).unwrap();
+        let meta_b = fs::metadata(&b).unwrap();
+        assert_eq!(tracker.register(&meta_a, Path::new("a")), None);
+        assert_eq!(
+            tracker.register(&meta_b, Path::new("b")),
+            Some(PathBuf::from("a"))
+        );
+    }
+
+    #[test]
+    fn test_restore_special_entries() {
+        use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+        let temp = TempDir::new().unwrap();
+        let dest = temp.path().join("restore");
+        fs::create_dir_all(dest.join("data")).unwrap();
+        fs::write(dest.join("data/original.txt"), b"content").unwrap();
+
+        let mut entries = BTreeMap::new();
+        entries.insert(
+            PathBuf::from("data/empty"),
+            SpecialEntry::new(EntryKind::Directory, Some(0o750)),
+        );
+        entries.insert(
+            PathBuf::from("data/link"),
+            SpecialEntry::new(
+                EntryKind::Symlink {
+                    target: PathBuf::from("original.txt"),
+                },
+                None,
+            ),
+        );
+        entries.insert(
+            PathBuf::from("data/hard"),
+            SpecialEntry::new(
+                EntryKind::Hardlink {
+                    target: PathBuf::from("data/original.txt"),
+                },
+                None,
+            ),
+        );
+        entries.insert(
+            PathBuf::from("data/pipe"),
+            SpecialEntry::new(EntryKind::Fifo, Some(0o600)),
+        );
+
+        let outcome = restore_special_entries(&dest, &entries);
+        assert!(outcome.errors.is_empty(), "{:?}", outcome.errors);
+        assert_eq!(outcome.restored, 4);
+
+        assert!(dest.join("data/empty").is_dir());
+        assert_eq!(
+            fs::read_link(dest.join("data/link")).unwrap(),
+            PathBuf::from("original.txt")
+        );
+        let original = fs::metadata(dest.join("data/original.txt")).unwrap();
+        let hard = fs::metadata(dest.join("data/hard")).unwrap();
+        assert_eq!(original.ino(), hard.ino());
+        assert!(fs::symlink_metadata(dest.join("data/pipe"))
+            .unwrap()
+            .file_type()
+            .is_fifo());
+    }
+
+    #[test]
+    fn test_restore_keeps_symlinks_outside_dest() {
+        let temp = TempDir::new().unwrap();
+        let dest = temp.path().join("restore");
+        fs::create_dir_all(&dest).unwrap();
+
+        let mut entries = BTreeMap::new();
+        for (name, target) in [
+            ("escape", PathBuf::from("../../outside")),
+            ("etc", PathBuf::from("/etc")),
+            ("inside", dest.join("data")),
+        ] {
+            entries.insert(
+                PathBuf::from(name),
+                SpecialEntry::new(EntryKind::Symlink { target }, None),
+            );
+        }
+
+        // リンクは作成するだけでたどらないため、記録されたリンク先のまま復元する
+        let outcome = restore_special_entries(&dest, &entries);
+        assert!(outcome.errors.is_empty(), "{:?}", outcome.errors);
+        assert_eq!(outcome.restored, 3);
+        assert_eq!(
+            fs::read_link(dest.join("escape")).unwrap(),
+            PathBuf::from("../../outside")
+        );
+        assert_eq!(
+            fs::read_link(dest.join("etc")).unwrap(),
+            PathBuf::from("/etc")
+        );
+        assert_eq!(
+            fs::read_link(dest.join("inside")).unwrap(),
+            dest.join("data")
+        );
+    }
+
+    #[test]
+    fn test_restore_does_not_write_through_symlinks() {
+        let temp = TempDir::new().unwrap();
+        let dest = temp.path().join("restore");
+        let outside = temp.path().join("outside");
+        fs::create_dir_all(&dest).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("victim"), b"keep").unwrap();
+
+        // `x -> outside` を作成した後、`x/` 配下のエントリで外側を書き換えようとする
+        let mut entries = BTreeMap::new();
+        entries.insert(
+            PathBuf::from("x"),
+            SpecialEntry::new(
+                EntryKind::Symlink {
+                    target: outside.clone(),
+                },
+                None,
+            ),
+        );
+        entries.insert(
+            PathBuf::from("x/victim"),
+            SpecialEntry::new(
+                EntryKind::Symlink {
+                    target: PathBuf::from("/etc/passwd"),
+                },
+                None,
+            ),
+        );
+
+        // 既存のリンクを経由する場合も同じ
+        std::os::unix::fs::symlink(&outside, dest.join("y")).unwrap();
+        entries.insert(
+            PathBuf::from("y/pipe"),
+            SpecialEntry::new(EntryKind::Fifo, Some(0o600)),
+        );
+        entries.insert(
+            PathBuf::from("y/sub"),
+            SpecialEntry::new(EntryKind::Directory, Some(0o755)),
+        );
+        entries.insert(
+            PathBuf::from("y/victim"),
+            SpecialEntry::new(
+                EntryKind::Symlink {
+                    target: PathBuf::from("/etc/passwd"),
+                },
+                None,
+            ),
+        );
+
+        // ディレクトリとして記録されたパスが既存のリンクの場合、リンク先を変更しない
+        std::os::unix::fs::symlink(&outside, dest.join("z")).unwrap();
+        entries.insert(
+            PathBuf::from("z"),
+            SpecialEntry::new(EntryKind::Directory, Some(0o700)),
+        );
+        let outside_mode = fs::metadata(&outside).unwrap().permissions();
+
+        let outcome = restore_special_entries(&dest, &entries);
+        assert_eq!(outcome.restored, 1);
+        assert_eq!(outcome.errors.len(), 5, "{:?}", outcome.errors);
+        assert_eq!(fs::metadata(&outside).unwrap().permissions(), outside_mode);
+        assert_eq!(fs::read(outside.join("victim")).unwrap(), b"keep");
+        assert!(fs::symlink_metadata(outside.join("pipe")).is_err());
+        assert!(fs::symlink_metadata(outside.join("sub")).is_err());
+    }
+}
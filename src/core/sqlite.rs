@@ -0,0 +1,199 @@
+//! # SQLiteモジュール
+//!
+//! 稼働中のSQLiteデータベースを一貫した状態でバックアップする機能を提供します。
+//!
+//! # 仕組み
+//!
+//! データベースファイルをそのままコピーすると、書き込み中のトランザクションの
+//! 途中状態を取り込む可能性があります。先頭16バイトのマジックヘッダー
+//! （`SQLite format 3\0`）でデータベースを検出し、SQLiteのオンラインバックアップAPIで
+//! 一時ファイルに複製してから暗号化・圧縮パイプラインに渡します。
+//!
+//! - WALモードのデータベースは、WALにコミット済みの内容も含めて複製されます
+//! - 複製はジャーナルモードを `DELETE` に戻し、単一ファイルで完結させます
+//! - 内容が複製に取り込まれるため、`-wal` / `-shm` / `-journal` ファイルはバックアップしません
+//!   （複製に失敗したデータベースは通常コピーとなり、付随ファイルもそのままコピーします）
+//!
+//! オンラインバックアップは `sqlite` フィーチャー（デフォルトで有効）で利用できます。
+//! 無効な場合は通常のファイルとしてコピーされます。
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! use backup_suite::core::sqlite;
+//! use std::path::Path;
+//!
+//! let db = Path::new("/home/user/app/state.db");
+//! if sqlite::is_sqlite_database(db) {
+//!     sqlite::snapshot_database(db, Path::new("/tmp/state.db")).unwrap();
+//! }
+//! ```
+
+use anyhow::Result;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// SQLiteデータベースファイルのマジックヘッダー
+pub const SQLITE_MAGIC: &[u8; 16] = b"SQLite format 3\0";
+
+/// データベースとともに作成される付随ファイルの接尾辞
+const SIDECAR_SUFFIXES: &[&str] = &["-wal", "-shm", "-journal"];
+
+/// マジックヘッダーでSQLiteデータベースかどうかを判定
+///
+/// 拡張子には依存しません。読み込めないファイルは `false` になります。
+#[must_use]
+pub fn is_sqlite_database(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok()
+        && &header == SQLITE_MAGIC
+}
+
+/// オンラインバックアップで内容が取り込まれる付随ファイルであれば、そのデータベースのパスを返す
+///
+/// `state.db-wal` のように、接尾辞を除いたパスがSQLiteデータベースである場合に
+/// `Some(state.db)` を返します。`sqlite` フィーチャーが無効な場合は常に `None` です
+/// （データベースを通常コピーするため、WALも必要）。
+#[must_use]
+pub fn sidecar_database(path: &Path) -> Option<PathBuf> {
+    if !cfg!(feature = "sqlite") {
+        return None;
+    }
+    let name = path.file_name()?.to_string_lossy().into_owned();
+    SIDECAR_SUFFIXES.iter().find_map(|suffix| {
+        name.strip_suffix(suffix)
+            .filter(|base| !base.is_empty())
+            .map(|base| path.with_file_name(base))
+            .filter(|database| is_sqlite_database(database))
+    })
+}
+
+/// オンラインバックアップで内容が取り込まれる付随ファイルかどうかを判定
+///
+/// 判定条件は [`sidecar_database`] と同じです。
+#[must_use]
+pub fn is_snapshot_sidecar(path: &Path) -> bool {
+    sidecar_database(path).is_some()
+}
+
+/// オンラインバックアップAPIでデータベースの一貫した複製を作成
+///
+/// `source` を読み取り専用で開き、`dest` に複製します。複製中に他の接続から
+/// 書き込みがあった場合、SQLiteが自動的に複製をやり直します。
+///
+/// # Errors
+///
+/// 以下の場合にエラーを返します:
+/// * データベースを開けない場合（破損、ロック待ちのタイムアウトなど）
+/// * 複製先の作成・書き込みに失敗した場合
+/// * `sqlite` フィーチャーが無効な場合
+#[cfg(feature = "sqlite")]
+pub fn snapshot_database(source: &Path, dest: &Path) -> Result<()> {
+    use anyhow::Context;
+    use rusqlite::{backup::Backup, Connection, OpenFlags};
+    use std::time::Duration;
+
+    let src = Connection::open_with_flags(
+        source,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("データベースを開けません: {}", source.display()))?;
+    src.busy_timeout(Duration::from_secs(30))?;
+
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent).context("複製先ディレクトリ作成失敗")?;
+    }
+    remove_with_sidecars(dest);
+
+    let mut dst = Connection::open(dest)
+        .with_context(|| format!("複製先を作成できません: {}", dest.display()))?;
+    Backup::new(&src, &mut dst)?
+        .run_to_completion(256, Duration::from_millis(10), None)
+        .context("オンラインバックアップ失敗")?;
+
+    // WALモードの設定を引き継がず、単一ファイルで完結させる
+    dst.query_row("PRAGMA journal_mode = DELETE", [], |_| Ok(()))?;
+    dst.close().map_err(|(_, e)| e)?;
+    Ok(())
+}
+
+/// オンラインバックアップAPIでデータベースの一貫した複製を作成
+///
+/// # Errors
+///
+/// `sqlite` フィーチャーが無効なため常にエラーを返します。
+#[cfg(not(feature = "sqlite"))]
+pub fn snapshot_database(source: &Path, _dest: &Path) -> Result<()> {
+    anyhow::bail!(
+        "sqlite フィーチャーが無効なためオンラインバックアップできません: {}",
+        source.display()
+    )
+}
+
+/// 複製先と付随ファイルを削除（中断時の残りを片付ける）
+#[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+fn remove_with_sidecars(path: &Path) {
+    let _ = std::fs::remove_file(path);
+    for suffix in SIDECAR_SUFFIXES {
+        let mut sidecar = path.as_os_str().to_owned();
+        sidecar.push(suffix);
+        let _ = std::fs::remove_file(PathBuf::from(sidecar));
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_snapshot_includes_wal_contents() {
+        let temp = TempDir::new().unwrap();
+        let db = temp.path().join("state.db");
+        let copy = temp.path().join("copy/state.db");
+
+        // WALモードで書き込み、チェックポイント前の状態で接続を保持
+        let conn = Connection::open(&db).unwrap();
+        conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))
+            .unwrap();
+        conn.execute_batch(
+            "PRAGMA wal_autocheckpoint = 0;
+             CREATE TABLE t (v TEXT);
+             INSERT INTO t VALUES ('committed');",
+        )
+        .unwrap();
+        let wal = temp.path().join("state.db-wal");
+        assert!(wal.exists());
+
+        assert!(is_sqlite_database(&db));
+        assert!(is_snapshot_sidecar(&wal));
+        assert!(!is_snapshot_sidecar(&db));
+        assert_eq!(sidecar_database(&wal), Some(db.clone()));
+
+        snapshot_database(&db, &copy).unwrap();
+        drop(conn);
+
+        let restored = Connection::open(&copy).unwrap();
+        let value: String = restored
+            .query_row("SELECT v FROM t", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(value, "committed");
+        let mode: String = restored
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(mode, "delete");
+    }
+
+    #[test]
+    fn test_non_database_is_not_detected() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("notes.db");
+        std::fs::write(&path, b"plain text, not a database").unwrap();
+        assert!(!is_sqlite_database(&path));
+        assert!(!is_snapshot_sidecar(&temp.path().join("notes.db-wal")));
+    }
+}
@@ -0,0 +1,235 @@
+//! # ステージングモジュール
+//!
+//! バックアップを一時的なステージングディレクトリに書き込み、完了後に
+//! アトミックなリネームで確定（コミット）する機能を提供します。
+//!
+//! # 仕組み
+//!
+//! 1. `<保存先>/.staging_backup_<timestamp>` にファイルとマニフェストを書き込む
+//! 2. 書き込んだファイル・マニフェスト・ディレクトリを fsync
+//! 3. `backup_<timestamp>` へリネームし、保存先ディレクトリを fsync
+//!
+//! 途中でプロセスが停止した場合はステージングディレクトリだけが残り、
+//! 増分バックアップの親・クリーンアップ・履歴一覧の対象にはなりません。
+//! 残ったディレクトリは [`find_stale_staging`] で検出し、`cleanup` で削除します。
+//! ただし `run --resume` で再開できるもの（[`is_resumable`]）は保持します。
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! use backup_suite::core::staging;
+//! use std::path::Path;
+//!
+//! let dest = Path::new("/backups");
+//! let staging_dir = staging::staging_path(dest, "backup_20250107_120000");
+//! // ... staging_dir にファイルを書き込む ...
+//! let final_dir = staging::commit(&staging_dir, &dest.join("backup_20250107_120000")).unwrap();
+//! println!("確定: {:?}", final_dir);
+//! ```
+
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use walkdir::WalkDir;
+
+use super::checkpoint::{Checkpoint, CHECKPOINT_FILE};
+
+/// ステージングディレクトリ名の接頭辞
+pub const STAGING_PREFIX: &str = ".staging_";
+
+/// バックアップ名に対応するステージングディレクトリのパスを返す
+#[must_use]
+pub fn staging_path(dest_base: &Path, backup_name: &str) -> PathBuf {
+    dest_base.join(format!("{STAGING_PREFIX}{backup_name}"))
+}
+
+/// パスがステージングディレクトリかどうかを判定
+#[must_use]
+pub fn is_staging_dir(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with(STAGING_PREFIX))
+}
+
+/// 保存先に残っている未完了のステージングディレクトリを検索
+///
+/// 中断されたバックアップが残したディレクトリを、名前順（古い順）で返します。
+/// 保存先が存在しない場合や読み込めない場合は空のリストを返します。
+#[must_use]
+pub fn find_stale_staging(dest_base: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dest_base) else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false))
+        .map(|entry| entry.path())
+        .filter(|path| is_staging_dir(path))
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// ステージングディレクトリが `run --resume` で再開可能かを判定
+///
+/// 読み込めるチェックポイントがあり、その最終更新から `max_age` 以内であれば再開可能と
+/// みなします。`max_age` が `None` の場合は経過時間を問いません。
+#[must_use]
+pub fn is_resumable(staging_dir: &Path, max_age: Option<Duration>) -> bool {
+    if Checkpoint::load(staging_dir).is_none() {
+        return false;
+    }
+    let Some(max_age) = max_age else {
+        return true;
+    };
+    fs::metadata(staging_dir.join(CHECKPOINT_FILE))
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_none_or(|elapsed| elapsed <= max_age)
+}
+
+/// ファイルの内容をディスクへ同期（fsync）
+///
+/// # Errors
+///
+/// ファイルのオープンまたは同期に失敗した場合にエラーを返します。
+pub fn sync_file(path: &Path) -> std::io::Result<()> {
+    File::open(path)?.sync_all()
+}
+
+/// ディレクトリエントリをディスクへ同期
+///
+/// Windowsではディレクトリを開いて同期できないため何もしません。
+fn sync_dir(path: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        File::open(path)?.sync_all()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Ok(())
+    }
+}
+
+/// ステージングディレクトリを確定
+///
+/// マニフェストとディレクトリツリーを fsync した後、`final_dir` へアトミックにリネームし、
+/// 親ディレクトリを fsync します。ファイル本体の fsync は書き込み時に済ませておく必要があります。
+///
+/// # Errors
+///
+/// 以下の場合にエラーを返します:
+/// * `final_dir` が既に存在する場合
+/// * マニフェストまたはディレクトリの同期に失敗した場合
+/// * リネームに失敗した場合
+pub fn commit(staging_dir: &Path, final_dir: &Path) -> Result<PathBuf> {
+    if final_dir.exists() {
+        anyhow::bail!(
+            "確定先のバックアップが既に存在します: {}",
+            final_dir.display()
+        );
+    }
+
+    let manifest = staging_dir.join(".integrity");
+    if manifest.exists() {
+        sync_file(&manifest).context("マニフェストの同期失敗")?;
+    }
+
+    // 深い階層から順にディレクトリエントリを同期
+    for entry in WalkDir::new(staging_dir)
+        .contents_first(true)
+        .into_iter()
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.file_type().is_dir())
+    {
+        sync_dir(entry.path())
+            .with_context(|| format!("ディレクトリの同期失敗: {}", entry.path().display()))?;
+    }
+
+    fs::rename(staging_dir, final_dir).with_context(|| {
+        format!(
+            "ステージングディレクトリの確定失敗: {} → {}",
+            staging_dir.display(),
+            final_dir.display()
+        )
+    })?;
+
+    if let Some(parent) = final_dir.parent() {
+        sync_dir(parent).context("保存先ディレクトリの同期失敗")?;
+    }
+
+    Ok(final_dir.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_commit_renames_staging() {
+        let temp = TempDir::new().unwrap();
+        let staging = staging_path(temp.path(), "backup_20250101_000000");
+        fs::create_dir_all(staging.join("docs")).unwrap();
+        fs::write(staging.join("docs/a.txt"), b"data").unwrap();
+        fs::write(staging.join(".integrity"), b"{}").unwrap();
+
+        assert_eq!(find_stale_staging(temp.path()), vec![staging.clone()]);
+
+        let final_dir = temp.path().join("backup_20250101_000000");
+        commit(&staging, &final_dir).unwrap();
+
+        assert!(!staging.exists());
+        assert_eq!(fs::read(final_dir.join("docs/a.txt")).unwrap(), b"data");
+        assert!(find_stale_staging(temp.path()).is_empty());
+    }
+
+    #[test]
+    fn test_commit_refuses_existing_destination() {
+        let temp = TempDir::new().unwrap();
+        let staging = staging_path(temp.path(), "backup_x");
+        let final_dir = temp.path().join("backup_x");
+        fs::create_dir_all(&staging).unwrap();
+        fs::create_dir_all(&final_dir).unwrap();
+
+        assert!(commit(&staging, &final_dir).is_err());
+        assert!(staging.exists());
+    }
+
+    #[test]
+    fn test_is_resumable_requires_recent_checkpoint() {
+        use super::super::checkpoint::CheckpointHeader;
+        use super::super::integrity::HashAlgorithm;
+
+        let temp = TempDir::new().unwrap();
+        let staging = staging_path(temp.path(), "backup_20250101_000000");
+        fs::create_dir_all(&staging).unwrap();
+        assert!(!is_resumable(&staging, None));
+
+        let header = CheckpointHeader {
+            compression: "none".to_string(),
+            target_compression: Default::default(),
+            encrypted: false,
+            snapshot: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+        };
+        drop(Checkpoint::create(&staging, &header).unwrap());
+        let day = Duration::from_secs(24 * 60 * 60);
+        assert!(is_resumable(&staging, None));
+        assert!(is_resumable(&staging, Some(day)));
+
+        // 保持期間を過ぎたチェックポイントは放棄されたものとみなす
+        let old = std::time::SystemTime::now() - day * 2;
+        File::options()
+            .write(true)
+            .open(staging.join(CHECKPOINT_FILE))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+        assert!(!is_resumable(&staging, Some(day)));
+        assert!(is_resumable(&staging, None));
+    }
+}
@@ -2,6 +2,9 @@ use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use super::hooks::HookConfig;
+use crate::compression::CompressionType;
+
 /// バックアップの優先度
 ///
 /// バックアップ対象の重要度を3段階で定義します。
@@ -41,6 +44,7 @@ pub enum Priority {
 ///
 /// * File - 単一ファイル
 /// * Directory - ディレクトリ（配下のファイルを再帰的にバックアップ）
+/// * Command - コマンドの標準出力（`path` を名前とする仮想ファイルとしてバックアップ）
 ///
 /// # 使用例
 ///
@@ -55,6 +59,7 @@ pub enum Priority {
 pub enum TargetType {
     File,
     Directory,
+    Command,
 }
 
 /// バックアップ対象の定義
@@ -63,12 +68,16 @@ pub enum TargetType {
 ///
 /// # フィールド
 ///
-/// * `path` - バックアップ対象のパス
+/// * `path` - バックアップ対象のパス（`Command` の場合は仮想ファイル名）
 /// * `priority` - 優先度（High/Medium/Low）
 /// * `target_type` - 種別（File/Directory）
 /// * `category` - カテゴリ名（ユーザー定義の分類）
 /// * `added_date` - 設定追加日時
 /// * `exclude_patterns` - 除外する正規表現パターンのリスト
+/// * `hooks` - この対象のバックアップ前後に実行するフック
+/// * `command` - `Command` の場合に実行するシェルコマンド（未設定の場合は標準入力）
+/// * `compression` / `compression_level` - この対象の圧縮形式とレベル（未設定の場合は実行時の設定）
+/// * `max_age_hours` - 最後に成功したバックアップの許容経過時間（RPO、未設定の場合は優先度ごとの設定）
 ///
 /// # 使用例
 ///
@@ -97,6 +106,16 @@ pub struct Target {
     pub category: String,
     pub added_date: chrono::DateTime<chrono::Utc>,
     pub exclude_patterns: Vec<String>,
+    #[serde(default, skip_serializing_if = "HookConfig::is_empty")]
+    pub hooks: HookConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<CompressionType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression_level: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_hours: Option<u64>,
 }
 
 impl Target {
@@ -141,6 +160,44 @@ impl Target {
             category,
             added_date: chrono::Utc::now(),
             exclude_patterns: vec![],
+            hooks: HookConfig::default(),
+            command: None,
+            compression: None,
+            compression_level: None,
+            max_age_hours: None,
+        }
+    }
+
+    /// コマンドの出力をバックアップする対象を作成
+    ///
+    /// バックアップ時に `command` をシェル経由で実行し、標準出力を `name` という名前の
+    /// 仮想ファイルとしてスナップショットに保存します。`command` が `None` の場合は
+    /// 標準入力を読み込みます（`backup-suite run --stdin`）。
+    ///
+    /// # 使用例
+    ///
+    /// ```no_run
+    /// use backup_suite::{Target, Priority};
+    /// use std::path::PathBuf;
+    ///
+    /// let target = Target::from_command(
+    ///     PathBuf::from("crontab.txt"),
+    ///     Some("crontab -l".to_string()),
+    ///     Priority::Medium,
+    ///     "system".to_string()
+    /// );
+    /// ```
+    #[must_use]
+    pub fn from_command(
+        name: PathBuf,
+        command: Option<String>,
+        priority: Priority,
+        category: String,
+    ) -> Self {
+        Self {
+            target_type: TargetType::Command,
+            command,
+            ..Self::new(name, priority, category)
         }
     }
 }
@@ -0,0 +1,691 @@
+//! # 監視モード（継続的バックアップ）
+//!
+//! バックアップ対象のファイル変更通知（Linux の inotify）を監視し、変更されたファイルのみを
+//! 比較する小さな増分バックアップを作成します（`backup-suite watch`）。
+//! 1日1回のバックアップでは失われる作業中のソースコード・ドキュメントを保護します。
+//!
+//! # 仕組み
+//!
+//! - ディレクトリ対象は配下の全ディレクトリ（`exclude_patterns` に一致するものを除く）、
+//!   ファイル対象は親ディレクトリを監視し、作成されたディレクトリも監視に追加する
+//! - 除外パターンに一致するパスとバックアップ先ディレクトリ内の変更は無視する
+//! - 変更は [`ChangeBatcher`] でまとめ、[`WatchConfig`] の間隔に従って
+//!   増分バックアップを1回作成する（`git checkout` やビルドによる連続作成を防止）
+//! - 変更されたパスが多すぎる場合、または通知キューが溢れた場合は対象全体を比較する
+//! - バックアップが失敗・キャンセルされた場合は変更パスを戻し、失敗した場合は
+//!   [`RETRY_DELAY`] から倍々に（最大 [`MAX_RETRY_DELAY`]）間隔を空けて再試行する
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! use backup_suite::core::{watch, CancellationToken, NullSink};
+//! use backup_suite::{BackupRunner, Config};
+//!
+//! let config = Config::load().unwrap();
+//! let targets: Vec<_> = config.targets.iter().collect();
+//! let cancel = CancellationToken::new();
+//! let (destination, events) = (&config.backup.destination, &NullSink);
+//! watch::watch(&config.watch, &targets, destination, &cancel, events, |paths| {
+//!     let mut runner = BackupRunner::new(Config::load()?, false)
+//!         .with_incremental(true)
+//!         .with_changed_paths(paths.iter().cloned().collect())
+//!         .with_cancellation(cancel.clone());
+//!     runner.run(None, None)?;
+//!     Ok(())
+//! })
+//! .unwrap();
+//! ```
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::cancel::CancellationToken;
+use super::config::WatchConfig;
+use super::events::EventSink;
+use super::target::Target;
+
+/// 変更通知を確認する間隔
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 1回のバックアップで個別に比較する変更パスの上限（超えた場合は対象全体を比較）
+pub const MAX_PENDING_PATHS: usize = 10_000;
+
+/// バックアップが失敗した後、再試行するまでの最初の待ち時間（失敗が続くと倍にする）
+pub const RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// 再試行するまでの待ち時間の上限
+pub const MAX_RETRY_DELAY: Duration = Duration::from_secs(3600);
+
+/// 変更されたパスをまとめ、バックアップを作成する時期を判定
+///
+/// 時刻は引数で受け取るため、実際の待ち時間なしでテストできます。
+#[derive(Debug)]
+pub struct ChangeBatcher {
+    debounce: Duration,
+    min_interval: Duration,
+    max_delay: Duration,
+    roots: Vec<PathBuf>,
+    pending: BTreeSet<PathBuf>,
+    overflowed: bool,
+    first_change: Option<Instant>,
+    last_change: Option<Instant>,
+    last_snapshot: Option<Instant>,
+    /// 取り出す前の `last_snapshot`（失敗・キャンセルした場合に戻す）
+    previous_snapshot: Option<Instant>,
+    /// 連続して失敗した回数
+    failures: u32,
+    /// 失敗後、この時刻まで再試行しない
+    retry_after: Option<Instant>,
+}
+
+impl ChangeBatcher {
+    /// 監視設定と対象のパス（変更パスが多すぎる場合に使用）から作成
+    #[must_use]
+    pub fn new(config: &WatchConfig, roots: Vec<PathBuf>) -> Self {
+        Self {
+            debounce: Duration::from_secs(config.debounce_secs),
+            min_interval: Duration::from_secs(config.min_interval_secs),
+            max_delay: Duration::from_secs(config.max_delay_secs),
+            roots,
+            pending: BTreeSet::new(),
+            overflowed: false,
+            first_change: None,
+            last_change: None,
+            last_snapshot: None,
+            previous_snapshot: None,
+            failures: 0,
+            retry_after: None,
+        }
+    }
+
+    /// 変更されたパスを記録
+    pub fn record(&mut self, path: PathBuf, now: Instant) {
+        if !self.overflowed {
+            self.pending.insert(path);
+            if self.pending.len() > MAX_PENDING_PATHS {
+                self.record_overflow(now);
+                return;
+            }
+        }
+        self.first_change.get_or_insert(now);
+        self.last_change = Some(now);
+    }
+
+    /// 個々の変更を追跡できなくなったことを記録（次回は対象全体を比較）
+    pub fn record_overflow(&mut self, now: Instant) {
+        self.overflowed = true;
+        self.pending = self.roots.iter().cloned().collect();
+        self.first_change.get_or_insert(now);
+        self.last_change = Some(now);
+    }
+
+    /// 記録済みの変更パスの数
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// バックアップを作成する時期か
+    ///
+    /// 前回のバックアップから `min_interval` が経過しており、最後の変更から `debounce`、
+    /// または最初の変更から `max_delay` が経過している場合に `true` を返します。
+    /// 失敗後の再試行時刻（[`record_failure`](Self::record_failure)）までは `false` を返します。
+    #[must_use]
+    pub fn is_due(&self, now: Instant) -> bool {
+        let (Some(first), Some(last)) = (self.first_change, self.last_change) else {
+            return false;
+        };
+        if self.retry_after.is_some_and(|retry| now < retry) {
+            return false;
+        }
+        if self
+            .last_snapshot
+            .is_some_and(|previous| now.duration_since(previous) < self.min_interval)
+        {
+            return false;
+        }
+        now.duration_since(last) >= self.debounce || now.duration_since(first) >= self.max_delay
+    }
+
+    /// 記録済みの変更パスを取り出し、`now` をバックアップ作成時刻として記録
+    pub fn take(&mut self, now: Instant) -> BTreeSet<PathBuf> {
+        self.overflowed = false;
+        self.first_change = None;
+        self.last_change = None;
+        self.previous_snapshot = self.last_snapshot.replace(now);
+        std::mem::take(&mut self.pending)
+    }
+
+    /// 取り出した変更パスのバックアップが完了したことを記録（再試行の待ち時間をリセット）
+    pub fn record_success(&mut self) {
+        self.failures = 0;
+        self.retry_after = None;
+    }
+
+    /// 取り出した変更パスのバックアップが失敗したことを記録
+    ///
+    /// 変更パスを戻し、連続して失敗した回数に応じて再試行を遅らせます。
+    pub fn record_failure(&mut self, paths: BTreeSet<PathBuf>, now: Instant) {
+        self.requeue(paths, now);
+        let backoff = RETRY_DELAY.saturating_mul(2u32.saturating_pow(self.failures));
+        self.failures = self.failures.saturating_add(1);
+        self.retry_after = Some(now + backoff.min(MAX_RETRY_DELAY));
+    }
+
+    /// 取り出した変更パスを戻す（キャンセルされたバックアップなど）
+    ///
+    /// 作成されなかったバックアップは `min_interval` の判定に含めません。
+    pub fn requeue(&mut self, paths: BTreeSet<PathBuf>, now: Instant) {
+        if paths.is_empty() {
+            return;
+        }
+        self.last_snapshot = self.previous_snapshot;
+        if !self.overflowed {
+            self.pending.extend(paths);
+            if self.pending.len() > MAX_PENDING_PATHS {
+                self.record_overflow(now);
+                return;
+            }
+        }
+        self.first_change.get_or_insert(now);
+        self.last_change.get_or_insert(now);
+    }
+}
+
+/// 対象の変更を監視し、まとめた変更パスごとに `on_batch` を呼び出し続ける
+///
+/// `on_batch` には変更されたファイル・ディレクトリのパス（ディレクトリの場合は配下すべてが対象）が
+/// 渡されます。`on_batch` がエラーを返した場合は警告を送り、変更パスを戻して間隔を空けて再試行します。
+/// `cancel` がキャンセルされた場合は監視を終了します（キャンセルされたバックアップの変更パスが
+/// 残っている場合は警告を送ります）。
+/// 監視を追加できないディレクトリなどの警告は `events` に送ります。
+///
+/// # Errors
+///
+/// Linux 以外の場合、監視対象がない場合、または inotify の初期化・読み込みに失敗した場合に
+/// エラーを返します。
+pub fn watch(
+    config: &WatchConfig,
+    targets: &[&Target],
+    destination: &Path,
+    cancel: &CancellationToken,
+    events: &dyn EventSink,
+    mut on_batch: impl FnMut(&BTreeSet<PathBuf>) -> Result<()>,
+) -> Result<()> {
+    let mut watcher = platform::Watcher::new(targets, destination)?;
+    let mut batcher = ChangeBatcher::new(config, watcher.roots());
+
+    while !cancel.should_stop() {
+        let now = Instant::now();
+        let changes = watcher.poll();
+        for warning in watcher.take_warnings() {
+            events.warning(warning);
+        }
+        match changes? {
+            platform::Changes::Paths(paths) => {
+                for path in paths {
+                    batcher.record(path, now);
+                }
+            }
+            platform::Changes::Overflow => batcher.record_overflow(now),
+        }
+        if batcher.is_due(now) {
+            let paths = batcher.take(now);
+            match on_batch(&paths) {
+                // キャンセルされたバックアップは変更パスを取りこぼさないよう戻す
+                Ok(()) if cancel.is_cancelled() => batcher.requeue(paths, Instant::now()),
+                Ok(()) => batcher.record_success(),
+                Err(e) => {
+                    events.warning(format!(
+                        "バックアップに失敗しました（変更 {} 件は後で再試行します）: {e:#}",
+                        paths.len()
+                    ));
+                    batcher.record_failure(paths, Instant::now());
+                }
+            }
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+    if batcher.pending() > 0 {
+        events.warning(format!(
+            "バックアップされていない変更 {} 件を残して監視を終了しました",
+            batcher.pending()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use anyhow::{Context, Result};
+    use inotify::{EventMask, Inotify, WatchMask};
+    use std::collections::HashMap;
+    use std::ffi::{OsStr, OsString};
+    use std::io::ErrorKind;
+    use std::path::{Path, PathBuf};
+    use walkdir::WalkDir;
+
+    use crate::core::filter::FileFilter;
+    use crate::core::target::{Target, TargetType};
+
+    /// 1回の確認で得られた変更
+    pub(super) enum Changes {
+        Paths(Vec<PathBuf>),
+        /// 通知キューが溢れたため個々の変更が不明
+        Overflow,
+    }
+
+    /// 監視中の対象
+    struct WatchedTarget {
+        path: PathBuf,
+        is_file: bool,
+        filter: Option<FileFilter>,
+    }
+
+    impl WatchedTarget {
+        /// 対象に含まれ、除外パターンに一致しないパスか（バックアップ時の判定と同じ基準）
+        fn includes(&self, path: &Path) -> bool {
+            if self.is_file {
+                return path == self.path
+                    && self.filter.as_ref().is_none_or(|f| !f.should_exclude(path));
+            }
+            if !path.starts_with(&self.path) {
+                return false;
+            }
+            let base = self.path.parent().unwrap_or(&self.path);
+            let relative = path.strip_prefix(base).unwrap_or(path);
+            self.filter
+                .as_ref()
+                .is_none_or(|f| !f.should_exclude(relative))
+        }
+    }
+
+    pub(super) struct Watcher {
+        inotify: Inotify,
+        targets: Vec<WatchedTarget>,
+        destination: PathBuf,
+        /// 監視記述子 → 監視中のディレクトリ
+        dirs: HashMap<i32, PathBuf>,
+        buffer: Vec<u8>,
+        /// 呼び出し元に渡していない警告
+        warnings: Vec<String>,
+    }
+
+    const MASK: WatchMask = WatchMask::CLOSE_WRITE
+        .union(WatchMask::CREATE)
+        .union(WatchMask::MOVED_TO)
+        .union(WatchMask::MOVED_FROM)
+        .union(WatchMask::DELETE)
+        .union(WatchMask::ATTRIB);
+
+    impl Watcher {
+        pub(super) fn new(targets: &[&Target], destination: &Path) -> Result<Self> {
+            let inotify = Inotify::init().context("inotify の初期化に失敗しました")?;
+            let mut watcher = Self {
+                inotify,
+                targets: Vec::new(),
+                destination: destination.to_path_buf(),
+                dirs: HashMap::new(),
+                buffer: vec![0u8; 64 * 1024],
+                warnings: Vec::new(),
+            };
+
+            for target in targets {
+                let is_file = match target.target_type {
+                    TargetType::File => true,
+                    TargetType::Directory => false,
+                    // コマンド出力は変更通知の対象外
+                    TargetType::Command => continue,
+                };
+                let filter = if target.exclude_patterns.is_empty() {
+                    None
+                } else {
+                    match FileFilter::new(&target.exclude_patterns) {
+                        Ok(f) => Some(f),
+                        Err(e) => {
+                            watcher
+                                .warnings
+                                .push(format!("除外パターンの処理に失敗: {e}"));
+                            None
+                        }
+                    }
+                };
+                watcher.targets.push(WatchedTarget {
+                    path: target.path.clone(),
+                    is_file,
+                    filter,
+                });
+
+                if is_file {
+                    if let Some(parent) = target.path.parent() {
+                        watcher.add_dir(parent);
+                    }
+                } else {
+                    watcher.add_tree(&target.path);
+                }
+            }
+
+            if watcher.dirs.is_empty() {
+                anyhow::bail!("監視できるバックアップ対象がありません");
+            }
+            Ok(watcher)
+        }
+
+        pub(super) fn roots(&self) -> Vec<PathBuf> {
+            self.targets.iter().map(|t| t.path.clone()).collect()
+        }
+
+        /// 前回の呼び出し以降の警告（監視を追加できないディレクトリなど）を取り出す
+        pub(super) fn take_warnings(&mut self) -> Vec<String> {
+            std::mem::take(&mut self.warnings)
+        }
+
+        fn is_relevant(&self, path: &Path) -> bool {
+            !path.starts_with(&self.destination) && self.targets.iter().any(|t| t.includes(path))
+        }
+
+        fn add_dir(&mut self, dir: &Path) {
+            match self.inotify.watches().add(dir, MASK) {
+                Ok(wd) => {
+                    self.dirs
+                        .insert(wd.get_watch_descriptor_id(), dir.to_path_buf());
+                }
+                Err(e) => self
+                    .warnings
+                    .push(format!("監視を追加できません: {}: {e}", dir.display())),
+            }
+        }
+
+        /// ディレクトリとその配下（除外されたもの・バックアップ先を除く）を監視に追加
+        fn add_tree(&mut self, root: &Path) {
+            let dirs: Vec<PathBuf> = WalkDir::new(root)
+                .into_iter()
+                .filter_entry(|entry| !entry.file_type().is_dir() || self.is_relevant(entry.path()))
+                .filter_map(std::result::Result::ok)
+                .filter(|entry| entry.file_type().is_dir())
+                .map(walkdir::DirEntry::into_path)
+                .collect();
+            for dir in dirs {
+                self.add_dir(&dir);
+            }
+        }
+
+        /// 届いている変更通知を読み込む（待機しない）
+        pub(super) fn poll(&mut self) -> Result<Changes> {
+            let mut changed = Vec::new();
+            let mut new_dirs = Vec::new();
+            loop {
+                let events: Vec<(i32, EventMask, Option<OsString>)> =
+                    match self.inotify.read_events(&mut self.buffer) {
+                        Ok(events) => events
+                            .map(|event| {
+                                (
+                                    event.wd.get_watch_descriptor_id(),
+                                    event.mask,
+                                    event.name.map(OsStr::to_os_string),
+                                )
+                            })
+                            .collect(),
+                        Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                        Err(e) => return Err(e).context("変更通知の読み込みに失敗しました"),
+                    };
+                for (wd, mask, name) in events {
+                    if mask.contains(EventMask::Q_OVERFLOW) {
+                        return Ok(Changes::Overflow);
+                    }
+                    if mask.contains(EventMask::IGNORED) {
+                        self.dirs.remove(&wd);
+                        continue;
+                    }
+                    let (Some(dir), Some(name)) = (self.dirs.get(&wd), name) else {
+                        continue;
+                    };
+                    let path = dir.join(name);
+                    if !self.is_relevant(&path) {
+                        continue;
+                    }
+                    if mask.contains(EventMask::ISDIR)
+                        && mask.intersects(EventMask::CREATE | EventMask::MOVED_TO)
+                    {
+                        new_dirs.push(path.clone());
+                    }
+                    changed.push(path);
+                }
+            }
+            // 作成・移動されたディレクトリを監視に追加（中身は変更パスとして比較される）
+            for dir in new_dirs {
+                self.add_tree(&dir);
+            }
+            Ok(Changes::Paths(changed))
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    use anyhow::Result;
+    use std::path::{Path, PathBuf};
+
+    use crate::core::target::Target;
+
+    pub(super) enum Changes {
+        Paths(Vec<PathBuf>),
+        Overflow,
+    }
+
+    pub(super) struct Watcher;
+
+    impl Watcher {
+        pub(super) fn new(_targets: &[&Target], _destination: &Path) -> Result<Self> {
+            anyhow::bail!("監視モードは Linux でのみサポートされています")
+        }
+
+        pub(super) fn roots(&self) -> Vec<PathBuf> {
+            Vec::new()
+        }
+
+        pub(super) fn take_warnings(&mut self) -> Vec<String> {
+            Vec::new()
+        }
+
+        pub(super) fn poll(&mut self) -> Result<Changes> {
+            Ok(Changes::Paths(Vec::new()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> WatchConfig {
+        WatchConfig {
+            debounce_secs: 10,
+            min_interval_secs: 300,
+            max_delay_secs: 1800,
+        }
+    }
+
+    #[test]
+    fn test_batcher_debounces_bursts() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut batcher = ChangeBatcher::new(&config(), vec![PathBuf::from("/src")]);
+        assert!(!batcher.is_due(at(0)));
+
+        batcher.record(PathBuf::from("/src/a.rs"), at(0));
+        batcher.record(PathBuf::from("/src/b.rs"), at(5));
+        batcher.record(PathBuf::from("/src/a.rs"), at(8));
+        assert!(!batcher.is_due(at(12)));
+        assert!(batcher.is_due(at(18)));
+
+        let paths = batcher.take(at(18));
+        assert_eq!(
+            paths.into_iter().collect::<Vec<_>>(),
+            [PathBuf::from("/src/a.rs"), PathBuf::from("/src/b.rs")]
+        );
+        assert_eq!(batcher.pending(), 0);
+        assert!(!batcher.is_due(at(100)));
+    }
+
+    #[test]
+    fn test_batcher_rate_limits_snapshots() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut batcher = ChangeBatcher::new(&config(), vec![PathBuf::from("/src")]);
+
+        batcher.record(PathBuf::from("/src/a.rs"), at(0));
+        batcher.take(at(10));
+
+        // 前回のバックアップから min_interval が経過するまで作成しない
+        batcher.record(PathBuf::from("/src/a.rs"), at(20));
+        assert!(!batcher.is_due(at(60)));
+        assert!(batcher.is_due(at(310)));
+
+        // 変更が続いていても max_delay が経過したら作成する
+        batcher.take(at(310));
+        for secs in (320..2200).step_by(5) {
+            batcher.record(PathBuf::from("/src/build.o"), at(secs));
+            if batcher.is_due(at(secs)) {
+                assert!(secs >= 320 + 1800);
+                return;
+            }
+        }
+        panic!("max_delay を過ぎてもバックアップが作成されませんでした");
+    }
+
+    #[test]
+    fn test_batcher_overflow_falls_back_to_roots() {
+        let start = Instant::now();
+        let roots = vec![PathBuf::from("/src"), PathBuf::from("/docs")];
+        let mut batcher = ChangeBatcher::new(&config(), roots);
+
+        for i in 0..=MAX_PENDING_PATHS {
+            batcher.record(PathBuf::from(format!("/src/gen/{i}.rs")), start);
+        }
+        batcher.record(PathBuf::from("/src/late.rs"), start);
+        assert_eq!(
+            batcher.take(start).into_iter().collect::<Vec<_>>(),
+            [PathBuf::from("/docs"), PathBuf::from("/src")]
+        );
+
+        batcher.record(PathBuf::from("/src/a.rs"), start);
+        assert_eq!(batcher.pending(), 1);
+    }
+
+    #[test]
+    fn test_batcher_retries_failed_batches_with_backoff() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut batcher = ChangeBatcher::new(&config(), vec![PathBuf::from("/src")]);
+
+        batcher.record(PathBuf::from("/src/a.rs"), at(0));
+        let paths = batcher.take(at(10));
+        batcher.record(PathBuf::from("/src/b.rs"), at(12));
+
+        // 失敗した変更パスは戻され、新しい変更とまとめて再試行する
+        batcher.record_failure(paths, at(15));
+        assert_eq!(batcher.pending(), 2);
+        assert!(!batcher.is_due(at(15 + 59)));
+        assert!(batcher.is_due(at(15 + 60)));
+
+        // 続けて失敗すると待ち時間が倍になる
+        let paths = batcher.take(at(75));
+        batcher.record_failure(paths, at(80));
+        assert!(!batcher.is_due(at(80 + 119)));
+        assert!(batcher.is_due(at(80 + 120)));
+        for _ in 0..20 {
+            let paths = batcher.take(at(200));
+            batcher.record_failure(paths, at(200));
+        }
+        assert!(batcher.is_due(at(200) + MAX_RETRY_DELAY));
+
+        // 成功したら待ち時間をリセット
+        let paths = batcher.take(at(4000));
+        assert_eq!(
+            paths.into_iter().collect::<Vec<_>>(),
+            [PathBuf::from("/src/a.rs"), PathBuf::from("/src/b.rs")]
+        );
+        batcher.record_success();
+        batcher.record(PathBuf::from("/src/c.rs"), at(4400));
+        assert!(batcher.is_due(at(4410)));
+    }
+
+    #[test]
+    fn test_batcher_requeues_cancelled_batches() {
+        let start = Instant::now();
+        let mut batcher = ChangeBatcher::new(&config(), vec![PathBuf::from("/src")]);
+
+        batcher.record(PathBuf::from("/src/a.rs"), start);
+        let paths = batcher.take(start);
+        batcher.requeue(paths, start);
+        assert_eq!(batcher.pending(), 1);
+        assert!(!batcher.is_due(start));
+
+        // 戻した変更パスが多すぎる場合は対象全体を比較する
+        let many = (0..=MAX_PENDING_PATHS)
+            .map(|i| PathBuf::from(format!("/src/gen/{i}.rs")))
+            .collect();
+        batcher.requeue(many, start);
+        assert_eq!(
+            batcher.take(start).into_iter().collect::<Vec<_>>(),
+            [PathBuf::from("/src")]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_watcher_reports_relevant_changes() {
+        use crate::core::target::Priority;
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("project");
+        let destination = source.join("backups");
+        std::fs::create_dir_all(source.join("target")).unwrap();
+        std::fs::create_dir_all(&destination).unwrap();
+
+        let mut target = Target::new(source.clone(), Priority::High, "code".to_string());
+        target.exclude_patterns = vec![r"^project/target(/|$)".to_string()];
+        let mut watcher = platform::Watcher::new(&[&target], &destination).unwrap();
+
+        std::fs::write(source.join("main.rs"), "fn main() {}").unwrap();
+        std::fs::write(source.join("target/app"), "binary").unwrap();
+        std::fs::write(destination.join("backup.bin"), "data").unwrap();
+        std::fs::create_dir(source.join("src")).unwrap();
+
+        let platform::Changes::Paths(first) = watcher.poll().unwrap() else {
+            panic!("通知キューが溢れました");
+        };
+        assert!(first.contains(&source.join("main.rs")));
+        assert!(first.contains(&source.join("src")));
+        assert!(!first.iter().any(|p| p.starts_with(source.join("target"))));
+        assert!(!first.iter().any(|p| p.starts_with(&destination)));
+
+        // 作成されたディレクトリも監視される
+        std::fs::write(source.join("src/lib.rs"), "").unwrap();
+        let platform::Changes::Paths(second) = watcher.poll().unwrap() else {
+            panic!("通知キューが溢れました");
+        };
+        assert!(second.contains(&source.join("src/lib.rs")));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_watcher_returns_warnings() {
+        use crate::core::target::Priority;
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        let mut target = Target::new(temp.path().to_path_buf(), Priority::High, "code".into());
+        target.exclude_patterns = vec!["(".to_string()];
+        let mut watcher = platform::Watcher::new(&[&target], &temp.path().join("backups")).unwrap();
+
+        let warnings = watcher.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("除外パターン"));
+        assert!(watcher.take_warnings().is_empty());
+    }
+}
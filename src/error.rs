@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -67,6 +68,10 @@ pub enum BackupError {
     #[error("圧縮エラー: {0}")]
     CompressionError(String),
 
+    /// リポジトリが他の処理にロックされている場合
+    #[error("リポジトリは他の処理によってロックされています: {path} [{holders}]")]
+    RepositoryLocked { path: PathBuf, holders: String },
+
     /// その他のエラー（anyhowからの変換用）
     #[error("エラー: {0}")]
     Other(#[from] anyhow::Error),
@@ -163,11 +168,100 @@ impl BackupError {
                     to.display()
                 )
             }
+            BackupError::RepositoryLocked { path, holders } => {
+                format!(
+                    "リポジトリは他の処理によってロックされています: {}\n\
+                     保持者: {holders}\n\
+                     対処法: 処理の終了を待つか、保持プロセスが存在しない場合は \
+                     `backup-suite unlock --force` を実行してください。",
+                    path.display()
+                )
+            }
             _ => self.to_string(),
         }
     }
 }
 
+/// CLIの終了コードに対応するエラーの分類
+///
+/// | 分類 | 終了コード | 内容 |
+/// |------|-----------|------|
+/// | `general` | 1 | 分類できないエラー |
+/// | `usage` | 2 | コマンドライン引数の誤り |
+/// | `partial` | 3 | 一部のファイルが失敗した、またはRPOを超過した対象がある |
+/// | `config` | 4 | 設定ファイルの読み込み・検証エラー |
+/// | `locked` | 5 | リポジトリが他の処理にロックされている |
+/// | `not_found` | 6 | 対象・バックアップが見つからない |
+/// | `permission_denied` | 7 | 権限がない、または許可されていないパス |
+/// | `crypto` | 8 | 暗号化・復号化の失敗（パスワードの誤りなど） |
+/// | `cancelled` | 130 | SIGINT/SIGTERM などでキャンセルされた（処理済みの結果は保存済み） |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    General,
+    Usage,
+    Partial,
+    Config,
+    Locked,
+    NotFound,
+    PermissionDenied,
+    Crypto,
+    Cancelled,
+}
+
+impl ErrorClass {
+    /// 終了コード
+    #[must_use]
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::General => 1,
+            Self::Usage => 2,
+            Self::Partial => 3,
+            Self::Config => 4,
+            Self::Locked => 5,
+            Self::NotFound => 6,
+            Self::PermissionDenied => 7,
+            Self::Crypto => 8,
+            Self::Cancelled => 130,
+        }
+    }
+
+    /// エラーの原因をたどり、最初に分類できた [`BackupError`] またはI/Oエラーから分類
+    #[must_use]
+    pub fn classify(error: &anyhow::Error) -> Self {
+        for cause in error.chain() {
+            if let Some(backup_error) = cause.downcast_ref::<BackupError>() {
+                match backup_error {
+                    BackupError::HomeDirectoryNotFound
+                    | BackupError::ConfigLoadError(_)
+                    | BackupError::ConfigParseError { .. }
+                    | BackupError::ConfigValidationError { .. }
+                    | BackupError::RegexError { .. } => return Self::Config,
+                    BackupError::TargetNotFound { .. }
+                    | BackupError::ParentDirectoryNotFound { .. } => return Self::NotFound,
+                    BackupError::PermissionDenied { .. }
+                    | BackupError::PathTraversalDetected { .. } => return Self::PermissionDenied,
+                    BackupError::EncryptionError(_) => return Self::Crypto,
+                    BackupError::RepositoryLocked { .. } => return Self::Locked,
+                    // I/Oエラー・ラップされたエラーは原因をさらにたどる
+                    _ => {}
+                }
+            }
+            if let Some(io_error) = cause.downcast_ref::<std::io::Error>() {
+                return match io_error.kind() {
+                    std::io::ErrorKind::NotFound => Self::NotFound,
+                    std::io::ErrorKind::PermissionDenied => Self::PermissionDenied,
+                    _ => Self::General,
+                };
+            }
+            if cause.is::<toml::de::Error>() {
+                return Self::Config;
+            }
+        }
+        Self::General
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -205,4 +299,32 @@ mod tests {
         assert!(message.contains("対処法"));
         assert!(message.contains("/nonexistent"));
     }
+
+    #[test]
+    fn test_error_class() {
+        let locked = anyhow::Error::new(BackupError::RepositoryLocked {
+            path: PathBuf::from("/backup"),
+            holders: "backup (pid 1)".to_string(),
+        })
+        .context("バックアップ開始失敗");
+        assert_eq!(ErrorClass::classify(&locked), ErrorClass::Locked);
+        assert_eq!(ErrorClass::classify(&locked).exit_code(), 5);
+        assert_eq!(ErrorClass::Cancelled.exit_code(), 130);
+
+        let wrapped_io = anyhow::Error::new(BackupError::IoError(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "denied",
+        )));
+        assert_eq!(
+            ErrorClass::classify(&wrapped_io),
+            ErrorClass::PermissionDenied
+        );
+
+        let crypto = anyhow::Error::new(BackupError::EncryptionError("tag".to_string()));
+        assert_eq!(ErrorClass::classify(&crypto), ErrorClass::Crypto);
+        assert_eq!(
+            ErrorClass::classify(&anyhow::anyhow!("unknown")),
+            ErrorClass::General
+        );
+    }
 }
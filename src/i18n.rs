@@ -469,6 +469,44 @@ pub enum MessageKey {
     FilesDetected,
     FullBackupMode,
     IncrementalBackupMode,
+    SnapshotBackupMode,
+    HardlinkedFiles,
+    ResumingBackup,
+    NoResumableBackup,
+    ResumedFiles,
+    NoRepositoryLocks,
+    RepositoryLocksHeld,
+    StaleLockLabel,
+    UnlockForceHint,
+    RepositoryLocksRemoved,
+    InconsistentFiles,
+    MerkleRoot,
+    CompressionSaved,
+    AdaptiveStored,
+    AdaptiveFast,
+    AdaptiveHigh,
+    PackedFiles,
+    Job,
+    SchedulerDaemon,
+    DaemonRunning,
+    DaemonStopped,
+    DaemonStarted,
+    LastRunLabel,
+    NextRunLabel,
+    WatchStarted,
+    WatchSnapshotCreated,
+    RpoTitle,
+    RpoNotConfigured,
+    RpoViolations,
+    RpoAllMet,
+    VerifyStart,
+    VerifySuccess,
+    VerifyFailed,
+    VerifiedFileCount,
+    CancelRequested,
+    OperationCancelled,
+    ReadWrittenLabel,
+    SkippedExcludedLabel,
     BackupComplete,
     BackupCompleteWithFailures,
     BackupResultTitle,
@@ -1065,6 +1103,44 @@ impl MessageKey {
             MessageKey::FilesDetected => "files detected",
             MessageKey::FullBackupMode => "Full Backup Mode (all files)",
             MessageKey::IncrementalBackupMode => "Incremental Backup Mode (changed files only)",
+            MessageKey::SnapshotBackupMode => "Snapshot Mode (unchanged files hardlinked from previous snapshot)",
+            MessageKey::HardlinkedFiles => "Hardlinked from previous snapshot",
+            MessageKey::ResumingBackup => "Resuming interrupted backup",
+            MessageKey::NoResumableBackup => "No interrupted backup to resume; starting a new backup",
+            MessageKey::ResumedFiles => "Files already written",
+            MessageKey::NoRepositoryLocks => "No repository locks held",
+            MessageKey::RepositoryLocksHeld => "Repository locks held",
+            MessageKey::StaleLockLabel => "stale",
+            MessageKey::UnlockForceHint => "Use `backup-suite unlock --force` to remove these locks",
+            MessageKey::RepositoryLocksRemoved => "Repository locks removed",
+            MessageKey::InconsistentFiles => "Files changed during backup (may be inconsistent)",
+            MessageKey::MerkleRoot => "Merkle root",
+            MessageKey::CompressionSaved => "Saved by compression",
+            MessageKey::AdaptiveStored => "Stored (incompressible)",
+            MessageKey::AdaptiveFast => "Fast zstd",
+            MessageKey::AdaptiveHigh => "High zstd",
+            MessageKey::PackedFiles => "Files stored in pack files",
+            MessageKey::Job => "Job",
+            MessageKey::SchedulerDaemon => "Built-in scheduler daemon",
+            MessageKey::DaemonRunning => "Running",
+            MessageKey::DaemonStopped => "Stopped",
+            MessageKey::DaemonStarted => "Scheduler daemon started",
+            MessageKey::LastRunLabel => "Last Run",
+            MessageKey::NextRunLabel => "Next Run",
+            MessageKey::WatchStarted => "Watching {} targets for changes (Ctrl+C to stop)",
+            MessageKey::WatchSnapshotCreated => "Incremental snapshot created",
+            MessageKey::RpoTitle => "⏱️ Recovery Point Objectives",
+            MessageKey::RpoNotConfigured => "No RPO configured (set [rpo] in config.toml or --max-age-hours on a target)",
+            MessageKey::RpoViolations => "{} target(s) exceed their maximum backup age",
+            MessageKey::RpoAllMet => "All targets meet their RPO",
+            MessageKey::VerifyStart => "Verifying backup",
+            MessageKey::VerifySuccess => "Backup verified",
+            MessageKey::VerifyFailed => "Backup verification failed",
+            MessageKey::VerifiedFileCount => "Verified files:",
+            MessageKey::CancelRequested => "Cancelling: finishing files in progress (press Ctrl+C again to force quit)",
+            MessageKey::OperationCancelled => "Cancelled (results processed so far were saved)",
+            MessageKey::ReadWrittenLabel => "Read / written",
+            MessageKey::SkippedExcludedLabel => "Skipped / excluded",
             MessageKey::BackupComplete => "Backup complete",
             MessageKey::BackupCompleteWithFailures => "Backup complete (with failures)",
             MessageKey::BackupResultTitle => "Backup Result",
@@ -1663,6 +1739,44 @@ impl MessageKey {
             MessageKey::FilesDetected => "ファイルを検出",
             MessageKey::FullBackupMode => "📦 フルバックアップモード（全ファイル）",
             MessageKey::IncrementalBackupMode => "📦 増分バックアップモード（変更ファイルのみ）",
+            MessageKey::SnapshotBackupMode => "📦 スナップショットモード（未変更ファイルは前回からハードリンク）",
+            MessageKey::HardlinkedFiles => "前回スナップショットからハードリンク",
+            MessageKey::ResumingBackup => "⏯️ 中断されたバックアップを再開",
+            MessageKey::NoResumableBackup => "再開可能なバックアップがありません。新規にバックアップします",
+            MessageKey::ResumedFiles => "書き込み済みファイル",
+            MessageKey::NoRepositoryLocks => "リポジトリロックはありません",
+            MessageKey::RepositoryLocksHeld => "保持中のリポジトリロック",
+            MessageKey::StaleLockLabel => "古いロック",
+            MessageKey::UnlockForceHint => "`backup-suite unlock --force` でロックを解除できます",
+            MessageKey::RepositoryLocksRemoved => "リポジトリロックを解除しました",
+            MessageKey::InconsistentFiles => "バックアップ中に変更されたファイル（不整合の可能性）",
+            MessageKey::MerkleRoot => "Merkleルート",
+            MessageKey::CompressionSaved => "圧縮による削減",
+            MessageKey::AdaptiveStored => "保存（圧縮不可）",
+            MessageKey::AdaptiveFast => "高速zstd",
+            MessageKey::AdaptiveHigh => "高圧縮zstd",
+            MessageKey::PackedFiles => "パックファイルにまとめたファイル",
+            MessageKey::Job => "ジョブ",
+            MessageKey::SchedulerDaemon => "内蔵スケジューラデーモン",
+            MessageKey::DaemonRunning => "実行中",
+            MessageKey::DaemonStopped => "停止中",
+            MessageKey::DaemonStarted => "スケジューラデーモンを開始しました",
+            MessageKey::LastRunLabel => "最終実行",
+            MessageKey::NextRunLabel => "次回実行",
+            MessageKey::WatchStarted => "{}件の対象の変更を監視しています（Ctrl+C で終了）",
+            MessageKey::WatchSnapshotCreated => "増分バックアップを作成しました",
+            MessageKey::RpoTitle => "⏱️ 目標復旧時点（RPO）",
+            MessageKey::RpoNotConfigured => "RPOが設定されていません（config.tomlの[rpo]または対象の--max-age-hoursで設定）",
+            MessageKey::RpoViolations => "{}件の対象が許容経過時間を超過しています",
+            MessageKey::RpoAllMet => "すべての対象がRPOを満たしています",
+            MessageKey::VerifyStart => "バックアップを検証中",
+            MessageKey::VerifySuccess => "バックアップを検証しました",
+            MessageKey::VerifyFailed => "バックアップの検証に失敗しました",
+            MessageKey::VerifiedFileCount => "検証ファイル数:",
+            MessageKey::CancelRequested => "キャンセルしています: 処理中のファイルを完了させます（もう一度 Ctrl+C で強制終了）",
+            MessageKey::OperationCancelled => "キャンセルしました（処理済みの結果は保存済み）",
+            MessageKey::ReadWrittenLabel => "読み込み / 書き込み",
+            MessageKey::SkippedExcludedLabel => "スキップ / 除外",
             MessageKey::BackupComplete => "✓ バックアップ完了",
             MessageKey::BackupCompleteWithFailures => "⚠ バックアップ完了（失敗あり）",
             MessageKey::BackupResultTitle => "📈 バックアップ結果",
@@ -2012,6 +2126,44 @@ impl MessageKey {
             MessageKey::FilesDetected => "检测到文件",
             MessageKey::FullBackupMode => "📦 完全备份模式（所有文件）",
             MessageKey::IncrementalBackupMode => "📦 增量备份模式（仅变更文件）",
+            MessageKey::SnapshotBackupMode => "📦 快照模式（未变更文件从上次快照硬链接）",
+            MessageKey::HardlinkedFiles => "从上次快照硬链接",
+            MessageKey::ResumingBackup => "⏯️ 恢复中断的备份",
+            MessageKey::NoResumableBackup => "没有可恢复的中断备份，开始新的备份",
+            MessageKey::ResumedFiles => "已写入的文件",
+            MessageKey::NoRepositoryLocks => "没有持有的仓库锁",
+            MessageKey::RepositoryLocksHeld => "持有的仓库锁",
+            MessageKey::StaleLockLabel => "过期锁",
+            MessageKey::UnlockForceHint => "使用 `backup-suite unlock --force` 解除这些锁",
+            MessageKey::RepositoryLocksRemoved => "已解除仓库锁",
+            MessageKey::InconsistentFiles => "备份期间被修改的文件（可能不一致）",
+            MessageKey::MerkleRoot => "Merkle 根",
+            MessageKey::CompressionSaved => "压缩节省",
+            MessageKey::AdaptiveStored => "存储（不可压缩）",
+            MessageKey::AdaptiveFast => "快速 zstd",
+            MessageKey::AdaptiveHigh => "高压缩 zstd",
+            MessageKey::PackedFiles => "打包存储的文件",
+            MessageKey::Job => "作业",
+            MessageKey::SchedulerDaemon => "内置调度守护进程",
+            MessageKey::DaemonRunning => "运行中",
+            MessageKey::DaemonStopped => "已停止",
+            MessageKey::DaemonStarted => "调度守护进程已启动",
+            MessageKey::LastRunLabel => "上次运行",
+            MessageKey::NextRunLabel => "下次运行",
+            MessageKey::WatchStarted => "正在监视 {} 个目标的变更（按 Ctrl+C 退出）",
+            MessageKey::WatchSnapshotCreated => "已创建增量快照",
+            MessageKey::RpoTitle => "⏱️ 恢复点目标（RPO）",
+            MessageKey::RpoNotConfigured => "未配置RPO（在config.toml中设置[rpo]或为目标设置--max-age-hours）",
+            MessageKey::RpoViolations => "{} 个目标超过了最大备份间隔",
+            MessageKey::RpoAllMet => "所有目标均满足RPO",
+            MessageKey::VerifyStart => "正在验证备份",
+            MessageKey::VerifySuccess => "备份验证通过",
+            MessageKey::VerifyFailed => "备份验证失败",
+            MessageKey::VerifiedFileCount => "已验证文件数:",
+            MessageKey::CancelRequested => "正在取消：等待处理中的文件完成（再次按 Ctrl+C 强制退出）",
+            MessageKey::OperationCancelled => "已取消（已处理的结果已保存）",
+            MessageKey::ReadWrittenLabel => "读取 / 写入",
+            MessageKey::SkippedExcludedLabel => "跳过 / 排除",
             MessageKey::BackupComplete => "✓ 备份完成",
             MessageKey::BackupCompleteWithFailures => "⚠ 备份完成（有失败）",
             MessageKey::BackupResultTitle => "📈 备份结果",
@@ -2394,6 +2546,44 @@ impl MessageKey {
             MessageKey::FilesDetected => "檢測到檔案",
             MessageKey::FullBackupMode => "📦 完全備份模式（所有檔案）",
             MessageKey::IncrementalBackupMode => "📦 增量備份模式（僅變更檔案）",
+            MessageKey::SnapshotBackupMode => "📦 快照模式（未變更檔案從上次快照硬連結）",
+            MessageKey::HardlinkedFiles => "從上次快照硬連結",
+            MessageKey::ResumingBackup => "⏯️ 恢復中斷的備份",
+            MessageKey::NoResumableBackup => "沒有可恢復的中斷備份，開始新的備份",
+            MessageKey::ResumedFiles => "已寫入的檔案",
+            MessageKey::NoRepositoryLocks => "沒有持有的儲存庫鎖",
+            MessageKey::RepositoryLocksHeld => "持有的儲存庫鎖",
+            MessageKey::StaleLockLabel => "過期鎖",
+            MessageKey::UnlockForceHint => "使用 `backup-suite unlock --force` 解除這些鎖",
+            MessageKey::RepositoryLocksRemoved => "已解除儲存庫鎖",
+            MessageKey::InconsistentFiles => "備份期間被修改的檔案（可能不一致）",
+            MessageKey::MerkleRoot => "Merkle 根",
+            MessageKey::CompressionSaved => "壓縮節省",
+            MessageKey::AdaptiveStored => "儲存（不可壓縮）",
+            MessageKey::AdaptiveFast => "快速 zstd",
+            MessageKey::AdaptiveHigh => "高壓縮 zstd",
+            MessageKey::PackedFiles => "打包儲存的檔案",
+            MessageKey::Job => "作業",
+            MessageKey::SchedulerDaemon => "內建排程常駐程式",
+            MessageKey::DaemonRunning => "執行中",
+            MessageKey::DaemonStopped => "已停止",
+            MessageKey::DaemonStarted => "排程常駐程式已啟動",
+            MessageKey::LastRunLabel => "上次執行",
+            MessageKey::NextRunLabel => "下次執行",
+            MessageKey::WatchStarted => "正在監視 {} 個目標的變更（按 Ctrl+C 結束）",
+            MessageKey::WatchSnapshotCreated => "已建立增量快照",
+            MessageKey::RpoTitle => "⏱️ 復原點目標（RPO）",
+            MessageKey::RpoNotConfigured => "未設定RPO（在config.toml中設定[rpo]或為目標設定--max-age-hours）",
+            MessageKey::RpoViolations => "{} 個目標超過了最大備份間隔",
+            MessageKey::RpoAllMet => "所有目標均符合RPO",
+            MessageKey::VerifyStart => "正在驗證備份",
+            MessageKey::VerifySuccess => "備份驗證通過",
+            MessageKey::VerifyFailed => "備份驗證失敗",
+            MessageKey::VerifiedFileCount => "已驗證檔案數:",
+            MessageKey::CancelRequested => "正在取消：等待處理中的檔案完成（再次按 Ctrl+C 強制結束）",
+            MessageKey::OperationCancelled => "已取消（已處理的結果已儲存）",
+            MessageKey::ReadWrittenLabel => "讀取 / 寫入",
+            MessageKey::SkippedExcludedLabel => "略過 / 排除",
             MessageKey::BackupComplete => "✓ 備份完成",
             MessageKey::BackupCompleteWithFailures => "⚠ 備份完成（有失敗）",
             MessageKey::BackupResultTitle => "📈 備份結果",
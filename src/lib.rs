@@ -50,6 +50,7 @@
 //! - **I/O最適化**: [`CopyEngine`]によるバッファリング・ストリーミング
 //! - **メモリ効率**: 大容量ファイルの低メモリ処理
 //! - **プログレス表示**: リアルタイム進捗・統計情報
+//! - **イベント通知**: [`EventSink`](core::EventSink) で進捗・警告を独自のUIやログに転送
 //!
 //! ### エンタープライズ機能
 //! - **優先度管理**: High/Medium/Low による重要度別管理
@@ -39,13 +39,22 @@ use is_terminal::IsTerminal;
 use std::env;
 use std::io::{self};
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use backup_suite::core::{BackupHistory, BackupRunner, Scheduler};
+use backup_suite::core::{
+    rpo, BackupHistory, BackupRunner, CancellationToken, Daemon, DaemonState, Scheduler,
+};
+use backup_suite::error::ErrorClass;
 use backup_suite::i18n::{get_message, Language, MessageKey};
 use backup_suite::security::{safe_join, validate_path_safety};
 use backup_suite::typo::{find_similar_command, format_did_you_mean, VALID_COMMANDS};
+use backup_suite::ui::output::{
+    self, CleanupOutput, RestoreOutput, RunOutput, ScheduleFrequencies, ScheduleOutput,
+    StatusOutput, VerifyOutput,
+};
 use backup_suite::ui::{
-    display_backup_result, display_dashboard, display_history, display_targets, ColorTheme,
+    display_backup_result, display_dashboard, display_history, display_rpo_status, display_targets,
+    ColorTheme, ConsoleSink, OutputFormat,
 };
 use backup_suite::{Config, Priority, Target};
 
@@ -58,6 +67,27 @@ fn supports_color() -> bool {
             .unwrap_or(true)
 }
 
+/// 対象ごとの圧縮レベルが圧縮形式の範囲内か確認
+fn check_target_compression(
+    compress: Option<backup_suite::compression::CompressionType>,
+    compress_level: Option<i32>,
+) -> Result<()> {
+    if let (Some(compression_type), Some(level)) = (compress, compress_level) {
+        if let Some(range) = compression_type.level_range() {
+            if !range.contains(&level) {
+                anyhow::bail!(
+                    "{} の compress-level は {}-{} の範囲で指定してください（指定値: {}）",
+                    compression_type.to_str(),
+                    range.start(),
+                    range.end(),
+                    level
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
 // カラーコードを返す関数（カラーサポートに応じて切り替え）
 fn get_color(color_code: &str, no_color: bool) -> &'static str {
     if no_color || !supports_color() {
@@ -94,6 +124,10 @@ struct Cli {
     #[arg(long = "no-color", global = true)]
     /// Disable colored output
     no_color: bool,
+
+    #[arg(long = "output", value_enum, default_value_t = OutputFormat::Text, global = true)]
+    /// Output format for list/status/history/run/restore/cleanup/schedule status (text/json/ndjson)
+    output: OutputFormat,
 }
 
 #[derive(Subcommand)]
@@ -112,6 +146,18 @@ enum Commands {
         #[arg(long = "exclude")]
         /// Exclude patterns (regex or glob, can be specified multiple times)
         exclude_patterns: Vec<String>,
+        #[arg(long, requires = "path", conflicts_with = "interactive")]
+        /// Back up the standard output of this shell command (PATH becomes the file name in the backup)
+        command: Option<String>,
+        #[arg(long, value_enum)]
+        /// Compression algorithm for this target (overrides `run --compress`)
+        compress: Option<backup_suite::compression::CompressionType>,
+        #[arg(long)]
+        /// Compression level for this target (default: the algorithm's default level)
+        compress_level: Option<i32>,
+        #[arg(long)]
+        /// Maximum allowed hours since the last successful backup (overrides `[rpo]`)
+        max_age_hours: Option<u64>,
     },
     /// List backup targets
     #[command(alias = "ls")]
@@ -140,6 +186,15 @@ enum Commands {
         #[arg(long = "exclude")]
         /// New exclude patterns (if not specified, keeps current value)
         exclude_patterns: Vec<String>,
+        #[arg(long, value_enum)]
+        /// New compression algorithm for this target (if not specified, keeps current value)
+        compress: Option<backup_suite::compression::CompressionType>,
+        #[arg(long)]
+        /// New compression level for this target (if not specified, keeps current value)
+        compress_level: Option<i32>,
+        #[arg(long)]
+        /// New maximum age in hours since the last successful backup (if not specified, keeps current value)
+        max_age_hours: Option<u64>,
     },
     /// Clear all backup targets
     #[command(alias = "rm")]
@@ -151,6 +206,16 @@ enum Commands {
     },
     /// Run backup (with encryption and compression support)
     Run {
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "priority", "category", "encrypt", "password", "generate_password", "compress",
+                "compress_level", "zstd_dict", "pack", "incremental", "snapshot", "checksum",
+                "stdin"
+            ]
+        )]
+        /// Run a named job defined in config.toml with all of its settings
+        job: Option<String>,
         #[arg(long, value_enum)]
         priority: Option<Priority>,
         #[arg(long)]
@@ -167,14 +232,35 @@ enum Commands {
         /// Generate a strong random password (use with --encrypt)
         generate_password: bool,
         #[arg(long, default_value_t = backup_suite::compression::CompressionType::Zstd, value_enum)]
-        /// Compression algorithm: zstd, gzip, none
+        /// Compression algorithm: zstd, gzip, lz4, xz, brotli, adaptive (per-file store/fast/high zstd), none
         compress: backup_suite::compression::CompressionType,
-        #[arg(long, default_value = "3")]
-        /// Compression level (1-22 for zstd, 1-9 for gzip)
-        compress_level: i32,
+        #[arg(long)]
+        /// Compression level (1-22 for zstd/adaptive, 1-9 for gzip, 0-9 for xz, 0-11 for brotli; lz4 has no levels)
+        compress_level: Option<i32>,
+        #[arg(long, conflicts_with = "encrypt")]
+        /// Train a zstd dictionary per target and use it for small files (zstd targets only)
+        zstd_dict: bool,
+        #[arg(long)]
+        /// Store small files in large pack files (fewer files on the destination)
+        pack: bool,
         #[arg(long)]
         /// Enable incremental backup (only changed files)
         incremental: bool,
+        #[arg(long)]
+        /// Snapshot mode: hardlink unchanged files from the previous snapshot (requires --compress none, no --encrypt)
+        snapshot: bool,
+        #[arg(long, requires = "snapshot")]
+        /// Compare file contents instead of size and mtime before hardlinking (slower, reads both copies)
+        checksum: bool,
+        #[arg(long)]
+        /// Resume the most recent interrupted backup instead of starting a new one
+        resume: bool,
+        #[arg(long, requires = "name")]
+        /// Back up data read from standard input instead of the configured targets
+        stdin: bool,
+        #[arg(long, requires = "stdin")]
+        /// File name for the data read with --stdin (e.g. db.sql)
+        name: Option<String>,
     },
     /// Restore from backup
     Restore {
@@ -186,6 +272,14 @@ enum Commands {
         /// Password for decryption (will prompt if not provided and file is encrypted)
         password: Option<String>,
     },
+    /// Verify a backup against its recorded hashes without restoring it
+    Verify {
+        #[arg(long)]
+        from: Option<String>,
+        #[arg(long)]
+        /// Password for decryption (will prompt if not provided and file is encrypted)
+        password: Option<String>,
+    },
     /// Clean up old backups
     Cleanup {
         #[arg(long, default_value = "30")]
@@ -193,6 +287,12 @@ enum Commands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Show or remove repository locks held on the backup destination
+    Unlock {
+        #[arg(long)]
+        /// Remove all locks, even if their holders may still be running
+        force: bool,
+    },
     /// Show backup status
     Status,
     /// Show backup history
@@ -222,6 +322,21 @@ enum Commands {
         #[command(subcommand)]
         action: ScheduleAction,
     },
+    /// Watch targets for changes and create incremental snapshots continuously (Linux)
+    Watch {
+        #[arg(long, value_enum)]
+        priority: Option<Priority>,
+        #[arg(long)]
+        category: Option<String>,
+    },
+    /// Check recovery point objectives (exit code 3 if any target is overdue)
+    Check,
+    /// Run the built-in scheduler (alternative to systemd/launchd)
+    Daemon {
+        /// Check and run due schedules once, then exit (for cron)
+        #[arg(long)]
+        once: bool,
+    },
     /// Configuration management
     Config {
         #[command(subcommand)]
@@ -242,22 +357,32 @@ enum ScheduleAction {
     Enable {
         #[arg(long, value_enum)]
         priority: Option<Priority>,
+        #[arg(long, conflicts_with = "priority")]
+        /// Schedule a named job (runs `backup-suite run --job NAME`)
+        job: Option<String>,
     },
     /// Disable automatic backup
     Disable {
         #[arg(long, value_enum)]
         priority: Option<Priority>,
+        #[arg(long, conflicts_with = "priority")]
+        /// Disable the schedule of a named job
+        job: Option<String>,
     },
     /// Show schedule status
     Status,
     /// Setup backup schedule
     Setup {
         #[arg(long, default_value = "daily")]
+        /// hourly/daily/weekly/monthly, a cron expression ("0 9,18 * * 1-5") or OnCalendar ("Mon..Fri 09:00")
         high: String,
         #[arg(long, default_value = "weekly")]
         medium: String,
         #[arg(long, default_value = "monthly")]
         low: String,
+        #[arg(long)]
+        /// Delay each scheduled run by a random time of up to this many seconds
+        random_delay: Option<u64>,
     },
     /// Show help for schedule commands
     Help,
@@ -466,7 +591,7 @@ fn detect_language(lang_arg: Option<&str>) -> Language {
             None => {
                 eprintln!("❌ Invalid language code: '{}'", lang_str);
                 eprintln!("Valid options: en, ja, zh-cn, zh-tw");
-                std::process::exit(1);
+                std::process::exit(ErrorClass::Usage.exit_code());
             }
         }
     }
@@ -1422,7 +1547,7 @@ fn parse_cli_with_typo_detection() -> Cli {
                     if let Some(suggestion) = find_similar_command(&typo, VALID_COMMANDS, 2) {
                         let with_color = supports_color();
                         eprintln!("{}", format_did_you_mean(&typo, &suggestion, with_color));
-                        std::process::exit(2);
+                        std::process::exit(ErrorClass::Usage.exit_code());
                     }
                 }
             }
@@ -1443,9 +1568,185 @@ fn extract_typo_from_error(err_msg: &str) -> Option<String> {
     Some(remaining[..end].to_string())
 }
 
-fn main() -> Result<()> {
+/// エラー出力に記録するコマンド名
+fn command_name(command: Option<&Commands>) -> &'static str {
+    match command {
+        None => "",
+        Some(Commands::Add { .. }) => "add",
+        Some(Commands::List { .. }) => "list",
+        Some(Commands::Remove { .. }) => "remove",
+        Some(Commands::Update { .. }) => "update",
+        Some(Commands::Clear { .. }) => "clear",
+        Some(Commands::Run { .. }) => "run",
+        Some(Commands::Restore { .. }) => "restore",
+        Some(Commands::Verify { .. }) => "verify",
+        Some(Commands::Cleanup { .. }) => "cleanup",
+        Some(Commands::Unlock { .. }) => "unlock",
+        Some(Commands::Status) => "status",
+        Some(Commands::History { .. }) => "history",
+        Some(Commands::Dashboard) => "dashboard",
+        Some(Commands::Open) => "open",
+        Some(Commands::Completion { .. }) => "completion",
+        Some(Commands::Schedule {
+            action: ScheduleAction::Status,
+        }) => "schedule status",
+        Some(Commands::Schedule { .. }) => "schedule",
+        Some(Commands::Watch { .. }) => "watch",
+        Some(Commands::Check) => "check",
+        Some(Commands::Daemon { .. }) => "daemon",
+        Some(Commands::Config { .. }) => "config",
+        Some(Commands::Smart { .. }) => "smart",
+    }
+}
+
+/// 補足メッセージを表示（機械可読な出力では標準出力を結果のみにするため標準エラー出力に表示）
+fn print_notice(format: OutputFormat, message: &str) {
+    if format.is_text() {
+        println!("{message}");
+    } else {
+        eprintln!("{message}");
+    }
+}
+
+/// 失敗したファイルがある場合などに、結果の出力後に部分的な失敗の終了コードで終了
+fn exit_if_partial(partial: bool) {
+    if partial {
+        std::process::exit(ErrorClass::Partial.exit_code());
+    }
+}
+
+/// SIGINT/SIGTERM でキャンセルを要求するトークンを作成
+///
+/// 1回目のシグナルでは処理中のファイルを完了させてから停止し、2回目のシグナルで即座に終了します。
+fn cancel_on_signal(lang: Language) -> CancellationToken {
+    let token = CancellationToken::new();
+    let handle = token.clone();
+    let installed = ctrlc::set_handler(move || {
+        if handle.is_cancelled() {
+            std::process::exit(ErrorClass::Cancelled.exit_code());
+        }
+        eprintln!("\n⚠️ {}", get_message(MessageKey::CancelRequested, lang));
+        handle.cancel();
+    });
+    if let Err(e) = installed {
+        eprintln!("警告: シグナルハンドラを設定できません: {e}");
+    }
+    token
+}
+
+/// キャンセルされた場合に、結果の出力後にキャンセルの終了コードで終了
+fn exit_if_cancelled(format: OutputFormat, lang: Language, cancelled: bool) {
+    if cancelled {
+        print_notice(
+            format,
+            &format!(
+                "{}⚠️ {}{}",
+                get_color("yellow", false),
+                get_message(MessageKey::OperationCancelled, lang),
+                get_color("reset", false)
+            ),
+        );
+        std::process::exit(ErrorClass::Cancelled.exit_code());
+    }
+}
+
+/// 復元・検証の対象とするバックアップを選択
+///
+/// パターン指定時は名前に含むものを選び、未指定時はキャンセル等で中断されていない最新のバックアップを選びます。
+fn select_backup_dir(dirs: &[PathBuf], from: Option<String>) -> Result<PathBuf> {
+    use backup_suite::core::BackupMetadata;
+
+    if let Some(pattern) = from {
+        return dirs
+            .iter()
+            .find(|d| d.to_string_lossy().contains(&pattern))
+            .cloned()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("バックアップが見つかりません: {pattern}"),
+                )
+                .into()
+            });
+    }
+    // 途中で中断されたバックアップは完全な復旧ポイントではないため、完了したものを優先
+    Ok(dirs
+        .iter()
+        .find(|d| !BackupMetadata::is_partial(d))
+        .unwrap_or(&dirs[0])
+        .clone())
+}
+
+/// 暗号化されたファイルがあり、パスワードが未指定の場合は対話的に入力
+fn password_if_encrypted(
+    backup_dir: &std::path::Path,
+    password: Option<String>,
+    lang: Language,
+) -> Result<Option<String>> {
+    // 暗号化されたファイルが存在するかをチェック（再帰的に探索）
+    use backup_suite::compression::dictionary::DICTIONARY_DIR;
+    use backup_suite::core::pack::{self, PACK_DIR};
+    use backup_suite::core::report::REPORT_FILE;
+    use backup_suite::crypto::EncryptedData;
+    let has_encrypted_files = walkdir::WalkDir::new(backup_dir)
+            .into_iter()
+            // パックファイル・zstd辞書・実行レポートは個々のバックアップファイルではないため除外
+            .filter_entry(|e| {
+                e.depth() != 1
+                    || (e.file_name() != PACK_DIR
+                        && e.file_name() != DICTIONARY_DIR
+                        && e.file_name() != REPORT_FILE)
+            })
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| e.file_name() != ".integrity") // .integrityファイルを除外
+            .take(5) // 最初の5ファイルのみチェック（効率化）
+            .any(|e| {
+                // ファイルを読み込んで暗号化データかどうか判定
+                if let Ok(data) = std::fs::read(e.path()) {
+                    EncryptedData::from_bytes(&data).is_ok()
+                } else {
+                    false
+                }
+            })
+            // パックにまとめたファイルも先頭の数件を確認
+            || backup_suite::core::BackupMetadata::load(backup_dir).is_ok_and(|metadata| {
+                metadata.packed_files.values().take(5).any(|entry| {
+                    pack::read_entry(backup_dir, entry)
+                        .is_ok_and(|data| EncryptedData::from_bytes(&data).is_ok())
+                })
+            });
+
+    if !has_encrypted_files || password.is_some() {
+        return Ok(password);
+    }
+
+    use dialoguer::Password;
+
+    let input = Password::new()
+        .with_prompt(format!(
+            "{}{}{}",
+            get_color("yellow", false),
+            get_message(MessageKey::EncryptionPassword, lang),
+            get_color("reset", false)
+        ))
+        .interact()?;
+
+    Ok(Some(input))
+}
+
+fn main() {
     let cli = parse_cli_with_typo_detection();
+    let format = cli.output;
+    let command = command_name(cli.command.as_ref());
 
+    if let Err(e) = run(cli) {
+        let class = output::emit_error(format, command, &e);
+        std::process::exit(class.exit_code());
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
     // --no-color フラグが指定された場合、NO_COLOR 環境変数を設定
     // これにより、console クレートと comfy_table が色を無効化します
     if cli.no_color {
@@ -1454,6 +1755,7 @@ fn main() -> Result<()> {
 
     // Detect language from CLI arg or environment
     let lang = detect_language(cli.lang.as_deref());
+    let format = cli.output;
 
     // --version フラグの処理
     if cli.version {
@@ -1468,7 +1770,52 @@ fn main() -> Result<()> {
             category,
             interactive,
             exclude_patterns,
+            command,
+            compress,
+            compress_level,
+            max_age_hours,
         }) => {
+            check_target_compression(compress, compress_level)?;
+
+            // コマンド出力: パスは仮想ファイル名として扱う
+            if let (Some(command), Some(name)) = (command, path.as_ref()) {
+                let is_plain_name = name.components().count() == 1
+                    && matches!(
+                        name.components().next(),
+                        Some(std::path::Component::Normal(_))
+                    );
+                if !is_plain_name {
+                    anyhow::bail!(
+                        "コマンド出力の名前にはファイル名のみ指定できます: {}",
+                        name.display()
+                    );
+                }
+
+                let mut config = Config::load()?;
+                let mut target =
+                    Target::from_command(name.clone(), Some(command), priority, category);
+                target.compression = compress;
+                target.compression_level = compress_level;
+                target.max_age_hours = max_age_hours;
+                if config.add_target(target) {
+                    config.save()?;
+                    println!(
+                        "{}✅ {}{}",
+                        get_color("green", false),
+                        get_message(MessageKey::Added, lang),
+                        get_color("reset", false)
+                    );
+                } else {
+                    println!(
+                        "{}⚠️ このパスは既に登録されています: {:?}{}",
+                        get_color("yellow", false),
+                        name,
+                        get_color("reset", false)
+                    );
+                }
+                return Ok(());
+            }
+
             // パスを決定（pathが指定されていない場合、またはinteractiveフラグが立っている場合はskin選択）
             let target_path = if let Some(p) = path {
                 if interactive {
@@ -1530,6 +1877,9 @@ fn main() -> Result<()> {
 
             let mut config = Config::load()?;
             let mut target = Target::new(normalized_path.clone(), priority, category);
+            target.compression = compress;
+            target.compression_level = compress_level;
+            target.max_age_hours = max_age_hours;
 
             // 除外パターンを追加
             if !exclude_patterns.is_empty() {
@@ -1569,6 +1919,10 @@ fn main() -> Result<()> {
                 config.targets.iter().collect()
             };
 
+            if !format.is_text() {
+                return output::emit_list(format, "list", &targets);
+            }
+
             display_targets(
                 &targets.iter().map(|&t| t.clone()).collect::<Vec<_>>(),
                 &theme,
@@ -1670,7 +2024,11 @@ fn main() -> Result<()> {
             priority,
             category,
             exclude_patterns,
+            compress,
+            compress_level,
+            max_age_hours,
         }) => {
+            check_target_compression(compress, compress_level)?;
             let mut config = Config::load()?;
 
             // セキュリティ検証（パストラバーサル対策）
@@ -1691,6 +2049,22 @@ fn main() -> Result<()> {
             };
 
             if config.update_target(&normalized_path, priority, category, exclude_opt) {
+                // 圧縮設定の更新（指定された項目のみ）
+                if let Some(target) = config
+                    .targets
+                    .iter_mut()
+                    .find(|t| t.path == normalized_path)
+                {
+                    if compress.is_some() {
+                        target.compression = compress;
+                    }
+                    if compress_level.is_some() {
+                        target.compression_level = compress_level;
+                    }
+                    if max_age_hours.is_some() {
+                        target.max_age_hours = max_age_hours;
+                    }
+                }
                 config.save()?;
                 println!(
                     "{}✅ {}{}",
@@ -1723,6 +2097,13 @@ fn main() -> Result<()> {
                             target.exclude_patterns.join(", ")
                         );
                     }
+                    if let Some(compression_type) = target.compression {
+                        println!(
+                            "  {}: {}",
+                            get_message(MessageKey::Compression, lang),
+                            compression_type.to_str()
+                        );
+                    }
                 }
             } else {
                 println!(
@@ -1833,6 +2214,7 @@ fn main() -> Result<()> {
             );
         }
         Some(Commands::Run {
+            job,
             priority,
             category,
             dry_run,
@@ -1841,48 +2223,100 @@ fn main() -> Result<()> {
             generate_password,
             compress,
             compress_level,
+            zstd_dict,
+            pack,
             incremental,
+            snapshot,
+            checksum,
+            resume,
+            stdin,
+            name,
         }) => {
-            let config = Config::load()?;
+            let mut config = Config::load()?;
             let theme = ColorTheme::from_no_color(cli.no_color);
 
+            // 名前付きジョブ: 対象の選択・実行オプションをジョブの定義で置き換える
+            let job = job
+                .map(|name| config.job(&name).cloned().map(|job| (name, job)))
+                .transpose()?;
+            let (
+                priority,
+                category,
+                encrypt,
+                password,
+                compress,
+                compress_level,
+                zstd_dict,
+                pack,
+                incremental,
+                snapshot,
+                checksum,
+            ) = match &job {
+                Some((_, job)) => (
+                    job.priority,
+                    job.category.clone(),
+                    job.encryption.is_some(),
+                    job.encryption.as_ref().map(|k| k.resolve()).transpose()?,
+                    job.compression_type(),
+                    Some(job.compression_level()),
+                    job.zstd_dict,
+                    job.pack,
+                    job.incremental,
+                    job.snapshot,
+                    job.checksum,
+                ),
+                None => (
+                    priority,
+                    category,
+                    encrypt,
+                    password,
+                    compress,
+                    compress_level,
+                    zstd_dict,
+                    pack,
+                    incremental,
+                    snapshot,
+                    checksum,
+                ),
+            };
+            if let Some((_, job)) = &job {
+                if !job.hooks.is_empty() {
+                    config.hooks = job.hooks.clone();
+                }
+            }
+
             // 圧縮タイプ（既に CompressionType 型）
             use backup_suite::compression::CompressionType;
             let compression_type = compress;
+            // 未指定の場合は圧縮形式ごとのデフォルトレベル（適応圧縮は高圧縮zstdのレベル）
+            let compress_level = compress_level.unwrap_or(compression_type.default_level());
 
             // Validate compress-level based on compression type
-            match compression_type {
-                CompressionType::Zstd => {
-                    if !(1..=22).contains(&compress_level) {
-                        eprintln!(
-                            "{}❌ {}{}: zstd の compress-level は 1-22 の範囲で指定してください（指定値: {}）",
-                            get_color("red", false),
-                            get_message(MessageKey::Error, lang),
-                            get_color("reset", false),
-                            compress_level
-                        );
-                        std::process::exit(1);
-                    }
-                }
-                CompressionType::Gzip => {
-                    if !(1..=9).contains(&compress_level) {
-                        eprintln!(
-                            "{}❌ {}{}: gzip の compress-level は 1-9 の範囲で指定してください（指定値: {}）",
-                            get_color("red", false),
-                            get_message(MessageKey::Error, lang),
-                            get_color("reset", false),
-                            compress_level
-                        );
-                        std::process::exit(1);
-                    }
-                }
-                CompressionType::None => {
-                    // No validation needed for no compression
+            if let Some(range) = compression_type.level_range() {
+                if !range.contains(&compress_level) {
+                    eprintln!(
+                        "{}❌ {}{}: {} の compress-level は {}-{} の範囲で指定してください（指定値: {}）",
+                        get_color("red", false),
+                        get_message(MessageKey::Error, lang),
+                        get_color("reset", false),
+                        compression_type.to_str(),
+                        range.start(),
+                        range.end(),
+                        compress_level
+                    );
+                    std::process::exit(ErrorClass::Usage.exit_code());
                 }
             }
 
             // 暗号化・圧縮オプションの表示
             let mut options_info: Vec<String> = Vec::new();
+            if let Some((ref job_name, _)) = job {
+                options_info.push(format!(
+                    "{}: {}",
+                    get_message(MessageKey::Job, lang),
+                    job_name
+                ));
+            }
             if dry_run {
                 options_info.push(get_message(MessageKey::DryRun, lang).to_string());
             }
@@ -1896,17 +2330,13 @@ fn main() -> Result<()> {
             if encrypt {
                 options_info.push(get_message(MessageKey::Encryption, lang).to_string());
             }
-            // 実際の圧縮タイプに基づいて表示
-            match compression_type {
-                CompressionType::Zstd => options_info.push(format!(
-                    "{}: zstd",
-                    get_message(MessageKey::Compression, lang)
-                )),
-                CompressionType::Gzip => options_info.push(format!(
-                    "{}: gzip",
-                    get_message(MessageKey::Compression, lang)
-                )),
-                CompressionType::None => {} // 無圧縮の場合は表示しない
+            // 実際の圧縮タイプに基づいて表示（無圧縮の場合は表示しない）
+            if compression_type != CompressionType::None {
+                options_info.push(format!(
+                    "{}: {}",
+                    get_message(MessageKey::Compression, lang),
+                    compression_type.to_str()
+                ));
             }
 
             let options_str = if options_info.is_empty() {
@@ -1915,25 +2345,45 @@ fn main() -> Result<()> {
                 format!("（{}）", options_info.join("、"))
             };
 
-            println!(
-                "{}{}{}{}",
-                get_color("green", false),
-                get_message(MessageKey::BackupRunning, lang),
-                options_str,
-                get_color("reset", false)
-            );
+            if format.is_text() {
+                println!(
+                    "{}{}{}{}",
+                    get_color("green", false),
+                    get_message(MessageKey::BackupRunning, lang),
+                    options_str,
+                    get_color("reset", false)
+                );
+            }
 
             // BackupRunnerを構築
-            let mut runner = BackupRunner::new(config, dry_run);
+            let mut runner = BackupRunner::new(config, dry_run).with_quiet(!format.is_text());
 
             // 圧縮設定
-            runner = runner.with_compression(compression_type, compress_level);
+            runner = runner
+                .with_compression(compression_type, compress_level)
+                .with_dictionary_training(zstd_dict)
+                .with_pack_files(pack);
 
             // 増分バックアップ設定
             if incremental {
                 runner = runner.with_incremental(true);
             }
 
+            // スナップショットモード設定
+            if snapshot {
+                runner = runner.with_snapshot(true).with_checksum(checksum);
+            }
+
+            // 中断されたバックアップの再開設定
+            if resume {
+                runner = runner.with_resume(true);
+            }
+
+            // 標準入力のバックアップ設定
+            if let (true, Some(name)) = (stdin, name) {
+                runner = runner.with_stdin(name, std::io::stdin());
+            }
+
             // 暗号化設定
             if encrypt {
                 use backup_suite::crypto::{PasswordPolicy, PasswordStrength};
@@ -1944,18 +2394,18 @@ fn main() -> Result<()> {
                     let generated = policy.generate_password(20);
                     let pwd_str = generated.to_string();
 
-                    println!(
-                        "{}🔐 {}{}: {}",
-                        get_color("green", false),
-                        get_message(MessageKey::EncryptionPassword, lang),
-                        get_color("reset", false),
-                        pwd_str
-                    );
-                    println!(
-                        "{}{}{}",
-                        get_color("yellow", false),
-                        get_message(MessageKey::SavePasswordSecurely, lang),
-                        get_color("reset", false)
+                    print_notice(
+                        format,
+                        &format!(
+                            "{}🔐 {}{}: {}\n{}{}{}",
+                            get_color("green", false),
+                            get_message(MessageKey::EncryptionPassword, lang),
+                            get_color("reset", false),
+                            pwd_str,
+                            get_color("yellow", false),
+                            get_message(MessageKey::SavePasswordSecurely, lang),
+                            get_color("reset", false)
+                        ),
                     );
 
                     pwd_str
@@ -1965,18 +2415,24 @@ fn main() -> Result<()> {
                     let strength = policy.evaluate(&p);
 
                     if !matches!(strength, PasswordStrength::Strong) {
-                        println!(
-                            "{}{}{}",
-                            get_color("yellow", false),
-                            policy.display_report(&p),
-                            get_color("reset", false)
+                        print_notice(
+                            format,
+                            &format!(
+                                "{}{}{}",
+                                get_color("yellow", false),
+                                policy.display_report(&p),
+                                get_color("reset", false)
+                            ),
                         );
                     } else {
-                        println!(
-                            "{}✅ Password Strength: {}{}",
-                            get_color("green", false),
-                            strength.display(),
-                            get_color("reset", false)
+                        print_notice(
+                            format,
+                            &format!(
+                                "{}✅ Password Strength: {}{}",
+                                get_color("green", false),
+                                strength.display(),
+                                get_color("reset", false)
+                            ),
                         );
                     }
 
@@ -1999,18 +2455,24 @@ fn main() -> Result<()> {
                     let strength = policy.evaluate(&input);
 
                     if !matches!(strength, PasswordStrength::Strong) {
-                        println!(
-                            "{}{}{}",
-                            get_color("yellow", false),
-                            policy.display_report(&input),
-                            get_color("reset", false)
+                        print_notice(
+                            format,
+                            &format!(
+                                "{}{}{}",
+                                get_color("yellow", false),
+                                policy.display_report(&input),
+                                get_color("reset", false)
+                            ),
                         );
                     } else {
-                        println!(
-                            "{}✅ Password Strength: {}{}",
-                            get_color("green", false),
-                            strength.display(),
-                            get_color("reset", false)
+                        print_notice(
+                            format,
+                            &format!(
+                                "{}✅ Password Strength: {}{}",
+                                get_color("green", false),
+                                strength.display(),
+                                get_color("reset", false)
+                            ),
                         );
                     }
 
@@ -2022,8 +2484,49 @@ fn main() -> Result<()> {
             // 言語設定
             runner = runner.with_language(lang);
 
+            if let Some((ref job_name, _)) = job {
+                runner = runner.with_job(job_name);
+            }
+
+            // パスワード入力後に Ctrl+C / SIGTERM でのキャンセルを有効化
+            runner = runner.with_cancellation(cancel_on_signal(lang));
+
             let result = runner.run(priority.as_ref(), category.as_deref())?;
 
+            if !format.is_text() {
+                // ジョブの保持期間: バックアップ後に古いバックアップを削除（キャンセル時は行わない）
+                let retention = job
+                    .as_ref()
+                    .and_then(|(name, job)| job.keep_days.map(|days| (name, days)));
+                let retention_cleanup = match retention {
+                    Some((name, days)) if !dry_run && !result.cancelled => {
+                        use backup_suite::{CleanupEngine, CleanupPolicy};
+
+                        let policy = CleanupPolicy::retention_days(days).with_job(name);
+                        Some(
+                            CleanupEngine::new(policy, false)
+                                .with_quiet(true)
+                                .cleanup()?,
+                        )
+                    }
+                    _ => None,
+                };
+                let partial = result.failed > 0;
+                let cancelled = result.cancelled;
+                output::emit(
+                    format,
+                    "run",
+                    &RunOutput {
+                        dry_run,
+                        result,
+                        retention_cleanup,
+                    },
+                )?;
+                exit_if_cancelled(format, lang, cancelled);
+                exit_if_partial(partial);
+                return Ok(());
+            }
+
             if !dry_run {
                 display_backup_result(
                     result.total_files,
@@ -2034,6 +2537,91 @@ fn main() -> Result<()> {
                     lang,
                 );
 
+                if result.linked_files > 0 {
+                    println!(
+                        "{}🔗 {}{}: {}",
+                        get_color("gray", false),
+                        get_message(MessageKey::HardlinkedFiles, lang),
+                        get_color("reset", false),
+                        result.linked_files
+                    );
+                }
+
+                if result.resumed_files > 0 {
+                    println!(
+                        "{}⏯️ {}{}: {}",
+                        get_color("gray", false),
+                        get_message(MessageKey::ResumedFiles, lang),
+                        get_color("reset", false),
+                        result.resumed_files
+                    );
+                }
+
+                if result.packed_files > 0 {
+                    println!(
+                        "{}📦 {}{}: {}",
+                        get_color("gray", false),
+                        get_message(MessageKey::PackedFiles, lang),
+                        get_color("reset", false),
+                        result.packed_files
+                    );
+                }
+
+                let stats = &result.compression_stats;
+                if stats.original_bytes > 0 {
+                    println!(
+                        "{}🗜️ {}{}: {:.2} MB ({:.1}%)",
+                        get_color("gray", false),
+                        get_message(MessageKey::CompressionSaved, lang),
+                        get_color("reset", false),
+                        stats.saved_bytes() as f64 / 1024.0 / 1024.0,
+                        stats.saved_percentage()
+                    );
+                    if compression_type == CompressionType::Adaptive {
+                        println!(
+                            "  {}: {} / {}: {} / {}: {}",
+                            get_message(MessageKey::AdaptiveStored, lang),
+                            stats.stored_files,
+                            get_message(MessageKey::AdaptiveFast, lang),
+                            stats.fast_files,
+                            get_message(MessageKey::AdaptiveHigh, lang),
+                            stats.high_files
+                        );
+                    }
+                }
+
+                if let Some(ref report) = result.report {
+                    println!(
+                        "{}⏱️ {}{}: {:.1}s ({:.2} MB/s)",
+                        get_color("gray", false),
+                        get_message(MessageKey::DurationLabel, lang),
+                        get_color("reset", false),
+                        report.duration_ms as f64 / 1000.0,
+                        report.summary.throughput_bytes_per_sec as f64 / 1024.0 / 1024.0
+                    );
+                }
+
+                if let Some(ref root) = result.merkle_root {
+                    println!(
+                        "{}🌳 {}{}: {root}",
+                        get_color("gray", false),
+                        get_message(MessageKey::MerkleRoot, lang),
+                        get_color("reset", false)
+                    );
+                }
+
+                if !result.inconsistent_files.is_empty() {
+                    println!(
+                        "\n{}⚠️ {}{}",
+                        get_color("yellow", false),
+                        get_message(MessageKey::InconsistentFiles, lang),
+                        get_color("reset", false)
+                    );
+                    for path in &result.inconsistent_files {
+                        println!("  - {}", path.display());
+                    }
+                }
+
                 if !result.errors.is_empty() {
                     println!(
                         "\n{}⚠️ {}{}",
@@ -2045,6 +2633,28 @@ fn main() -> Result<()> {
                         println!("  {}. {}", i + 1, error);
                     }
                 }
+
+                exit_if_cancelled(format, lang, result.cancelled);
+
+                // ジョブの保持期間: バックアップ後にこのジョブの古いバックアップを削除
+                if let Some((name, days)) = job
+                    .as_ref()
+                    .and_then(|(name, job)| job.keep_days.map(|days| (name, days)))
+                {
+                    use backup_suite::{CleanupEngine, CleanupPolicy};
+
+                    let policy = CleanupPolicy::retention_days(days).with_job(name);
+                    let cleanup = CleanupEngine::new(policy, false).cleanup()?;
+                    println!(
+                        "{}🧹 {} {}{}",
+                        get_color("gray", false),
+                        cleanup.deleted,
+                        get_message(MessageKey::CountDeleted, lang),
+                        get_color("reset", false)
+                    );
+                }
+
+                exit_if_partial(result.failed > 0);
             } else {
                 println!(
                     "{}📋 {}{}: {} {}",
@@ -2060,6 +2670,13 @@ fn main() -> Result<()> {
             use backup_suite::RestoreEngine;
 
             let dirs = BackupHistory::list_backup_dirs()?;
+            if dirs.is_empty() && !format.is_text() {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    get_message(MessageKey::NoBackups, lang),
+                )
+                .into());
+            }
             if dirs.is_empty() {
                 println!(
                     "{}❌ {}{}",
@@ -2070,13 +2687,7 @@ fn main() -> Result<()> {
                 return Ok(());
             }
 
-            let backup_dir = if let Some(pattern) = from {
-                dirs.iter()
-                    .find(|d| d.to_string_lossy().contains(&pattern))
-                    .ok_or_else(|| anyhow::anyhow!("バックアップが見つかりません: {pattern}"))?
-            } else {
-                &dirs[0] // 最新
-            };
+            let backup_dir = &select_backup_dir(&dirs, from)?;
 
             // バックアップ名をディレクトリ名から取得
             let backup_name = backup_dir
@@ -2088,53 +2699,42 @@ fn main() -> Result<()> {
             let base_dest = to.unwrap_or_else(|| PathBuf::from("./.restored"));
             let dest = base_dest.join(backup_name);
 
-            println!(
-                "{}🔄 {}{}: {:?} → {:?}",
-                get_color("green", false),
-                get_message(MessageKey::RestoreStart, lang),
-                get_color("reset", false),
-                backup_dir,
-                dest
+            print_notice(
+                format,
+                &format!(
+                    "{}🔄 {}{}: {:?} → {:?}",
+                    get_color("green", false),
+                    get_message(MessageKey::RestoreStart, lang),
+                    get_color("reset", false),
+                    backup_dir,
+                    dest
+                ),
             );
 
-            // 暗号化されたファイルが存在するかをチェック（再帰的に探索）
-            let has_encrypted_files = walkdir::WalkDir::new(backup_dir)
-                .into_iter()
-                .filter_map(Result::ok)
-                .filter(|e| e.file_type().is_file())
-                .filter(|e| e.file_name() != ".integrity") // .integrityファイルを除外
-                .take(5) // 最初の5ファイルのみチェック（効率化）
-                .any(|e| {
-                    // ファイルを読み込んで暗号化データかどうか判定
-                    if let Ok(data) = std::fs::read(e.path()) {
-                        use backup_suite::crypto::EncryptedData;
-                        EncryptedData::from_bytes(&data).is_ok()
-                    } else {
-                        false
-                    }
-                });
-
-            // 暗号化されたファイルがあり、パスワードが未指定の場合は対話的に入力
-            let password_for_restore = if has_encrypted_files && password.is_none() {
-                use dialoguer::Password;
-
-                let input = Password::new()
-                    .with_prompt(format!(
-                        "{}{}{}",
-                        get_color("yellow", false),
-                        get_message(MessageKey::EncryptionPassword, lang),
-                        get_color("reset", false)
-                    ))
-                    .interact()?;
-
-                Some(input)
-            } else {
-                password
-            };
+            let password_for_restore = password_if_encrypted(backup_dir, password, lang)?;
 
             // RestoreEngineを使用して復元
-            let mut engine = RestoreEngine::new(false);
+            let mut engine = RestoreEngine::new(false)
+                .with_quiet(!format.is_text())
+                .with_cancellation(cancel_on_signal(lang));
             let result = engine.restore(backup_dir, &dest, password_for_restore.as_deref())?;
+            let partial = result.failed > 0 || result.verification_failures > 0;
+            let cancelled = result.cancelled;
+
+            if !format.is_text() {
+                output::emit(
+                    format,
+                    "restore",
+                    &RestoreOutput {
+                        backup: backup_dir.clone(),
+                        destination: dest,
+                        result,
+                    },
+                )?;
+                exit_if_cancelled(format, lang, cancelled);
+                exit_if_partial(partial);
+                return Ok(());
+            }
 
             println!(
                 "\n{}✅ {} {:?}{}",
@@ -2164,6 +2764,91 @@ fn main() -> Result<()> {
                     println!("  - {error}");
                 }
             }
+            exit_if_cancelled(format, lang, cancelled);
+            exit_if_partial(partial);
+        }
+        Some(Commands::Verify { from, password }) => {
+            use backup_suite::RestoreEngine;
+
+            let dirs = BackupHistory::list_backup_dirs()?;
+            if dirs.is_empty() && !format.is_text() {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    get_message(MessageKey::NoBackups, lang),
+                )
+                .into());
+            }
+            if dirs.is_empty() {
+                println!(
+                    "{}❌ {}{}",
+                    get_color("red", false),
+                    get_message(MessageKey::NoBackups, lang),
+                    get_color("reset", false)
+                );
+                return Ok(());
+            }
+
+            let backup_dir = select_backup_dir(&dirs, from)?;
+
+            print_notice(
+                format,
+                &format!(
+                    "{}🔍 {}{}: {:?}",
+                    get_color("green", false),
+                    get_message(MessageKey::VerifyStart, lang),
+                    get_color("reset", false),
+                    backup_dir
+                ),
+            );
+
+            let password = password_if_encrypted(&backup_dir, password, lang)?;
+
+            let mut engine = RestoreEngine::new(false)
+                .with_quiet(!format.is_text())
+                .with_cancellation(cancel_on_signal(lang));
+            let result = engine.verify(&backup_dir, password.as_deref())?;
+            let partial = result.failed > 0 || result.verification_failures > 0;
+            let cancelled = result.cancelled;
+
+            if !format.is_text() {
+                output::emit(
+                    format,
+                    "verify",
+                    &VerifyOutput {
+                        backup: backup_dir,
+                        result,
+                    },
+                )?;
+                exit_if_cancelled(format, lang, cancelled);
+                exit_if_partial(partial);
+                return Ok(());
+            }
+
+            if partial {
+                println!(
+                    "\n{}⚠️ {}{}",
+                    get_color("yellow", false),
+                    get_message(MessageKey::VerifyFailed, lang),
+                    get_color("reset", false)
+                );
+                for error in &result.errors {
+                    println!("  - {error}");
+                }
+            } else {
+                println!(
+                    "\n{}✅ {}{}",
+                    get_color("green", false),
+                    get_message(MessageKey::VerifySuccess, lang),
+                    get_color("reset", false)
+                );
+            }
+            println!(
+                "  {}: {}",
+                get_message(MessageKey::VerifiedFileCount, lang),
+                result.verified_files
+            );
+            exit_if_cancelled(format, lang, cancelled);
+            exit_if_partial(partial);
         }
         Some(Commands::Cleanup { days, dry_run }) => {
             use backup_suite::{CleanupEngine, CleanupPolicy};
@@ -2177,7 +2862,7 @@ fn main() -> Result<()> {
                     get_color("reset", false),
                     get_message(MessageKey::DaysOutOfRange, lang).replace("{}", &days.to_string())
                 );
-                std::process::exit(1);
+                std::process::exit(ErrorClass::Usage.exit_code());
             }
 
             // パフォーマンス最適化: 確認プロンプトをスキャン前に表示
@@ -2206,19 +2891,41 @@ fn main() -> Result<()> {
                 };
 
                 if !should_proceed {
-                    println!(
-                        "{}{}{}",
-                        get_color("yellow", false),
-                        get_message(MessageKey::SelectionCancelled, lang),
-                        get_color("reset", false)
+                    print_notice(
+                        format,
+                        &format!(
+                            "{}{}{}",
+                            get_color("yellow", false),
+                            get_message(MessageKey::SelectionCancelled, lang),
+                            get_color("reset", false)
+                        ),
                     );
                     return Ok(());
                 }
             }
 
             let policy = CleanupPolicy::retention_days(days);
-            let mut engine = CleanupEngine::new(policy, dry_run);
+            let mut engine = CleanupEngine::new(policy, dry_run)
+                .with_quiet(!format.is_text())
+                .with_cancellation(cancel_on_signal(lang));
             let result = engine.cleanup()?;
+            let partial = !result.errors.is_empty();
+            let cancelled = result.cancelled;
+
+            if !format.is_text() {
+                output::emit(
+                    format,
+                    "cleanup",
+                    &CleanupOutput {
+                        dry_run,
+                        retention_days: days,
+                        result,
+                    },
+                )?;
+                exit_if_cancelled(format, lang, cancelled);
+                exit_if_partial(partial);
+                return Ok(());
+            }
 
             println!(
                 "{}✅ {} {}{}{}",
@@ -2233,6 +2940,15 @@ fn main() -> Result<()> {
                 get_color("reset", false)
             );
 
+            if result.stale_removed > 0 {
+                println!(
+                    "  {}未完了バックアップ: {}件{}",
+                    get_color("gray", false),
+                    result.stale_removed,
+                    get_color("reset", false)
+                );
+            }
+
             if result.freed_bytes > 0 {
                 let freed_mb = result.freed_bytes as f64 / 1024.0 / 1024.0;
                 println!(
@@ -2254,9 +2970,77 @@ fn main() -> Result<()> {
                     println!("  - {error}");
                 }
             }
+            exit_if_cancelled(format, lang, cancelled);
+            exit_if_partial(partial);
+        }
+        Some(Commands::Unlock { force }) => {
+            use backup_suite::core::lock;
+
+            let config = Config::load()?;
+            let repo = &config.backup.destination;
+
+            if force {
+                let removed = lock::force_unlock(repo)?;
+                println!(
+                    "{}✅ {}: {}{}",
+                    get_color("green", false),
+                    get_message(MessageKey::RepositoryLocksRemoved, lang),
+                    removed.len(),
+                    get_color("reset", false)
+                );
+                for info in &removed {
+                    println!("  - {info}");
+                }
+                return Ok(());
+            }
+
+            let locks = lock::list_locks(repo);
+            if locks.is_empty() {
+                println!("{}", get_message(MessageKey::NoRepositoryLocks, lang));
+                return Ok(());
+            }
+
+            println!(
+                "{}🔒 {}{}",
+                get_color("yellow", false),
+                get_message(MessageKey::RepositoryLocksHeld, lang),
+                get_color("reset", false)
+            );
+            for (_, info) in &locks {
+                if info.is_stale() {
+                    println!(
+                        "  - {info} [{}]",
+                        get_message(MessageKey::StaleLockLabel, lang)
+                    );
+                } else {
+                    println!("  - {info}");
+                }
+            }
+            println!("{}", get_message(MessageKey::UnlockForceHint, lang));
         }
         Some(Commands::Status) => {
             let config = Config::load()?;
+            let count = |priority: Priority| {
+                config
+                    .targets
+                    .iter()
+                    .filter(|t| t.priority == priority)
+                    .count()
+            };
+            if !format.is_text() {
+                return output::emit(
+                    format,
+                    "status",
+                    &StatusOutput {
+                        destination: config.backup.destination.clone(),
+                        total_targets: config.targets.len(),
+                        high: count(Priority::High),
+                        medium: count(Priority::Medium),
+                        low: count(Priority::Low),
+                    },
+                );
+            }
+
             println!(
                 "\n\x1b[1m📊 {}\x1b[0m\n",
                 get_message(MessageKey::StatusTitle, lang)
@@ -2271,21 +3055,9 @@ fn main() -> Result<()> {
 
             // 各優先度の正確な件数をカウント（== 比較）
             let total_targets = config.targets.len();
-            let high_count = config
-                .targets
-                .iter()
-                .filter(|t| t.priority == Priority::High)
-                .count();
-            let medium_count = config
-                .targets
-                .iter()
-                .filter(|t| t.priority == Priority::Medium)
-                .count();
-            let low_count = config
-                .targets
-                .iter()
-                .filter(|t| t.priority == Priority::Low)
-                .count();
+            let high_count = count(Priority::High);
+            let medium_count = count(Priority::Medium);
+            let low_count = count(Priority::Low);
 
             let mut table = Table::new();
             table
@@ -2350,6 +3122,10 @@ fn main() -> Result<()> {
                 history = filtered.into_iter().cloned().collect();
             }
 
+            if !format.is_text() {
+                return output::emit_list(format, "history", &history);
+            }
+
             println!(
                 "\n\x1b[1m📜 {}\x1b[0m（{}{}）",
                 get_message(MessageKey::BackupHistory, lang),
@@ -2425,6 +3201,24 @@ fn main() -> Result<()> {
                             get_message(MessageKey::SecondsUnit, lang)
                         );
                     }
+                    if let Some(ref summary) = entry.summary {
+                        println!(
+                            "📈 {}: {:.2} MB / {:.2} MB ({:.2} MB/s)",
+                            get_message(MessageKey::ReadWrittenLabel, lang),
+                            summary.totals.bytes_read as f64 / 1024.0 / 1024.0,
+                            summary.totals.bytes_written as f64 / 1024.0 / 1024.0,
+                            summary.throughput_bytes_per_sec as f64 / 1024.0 / 1024.0
+                        );
+                        println!(
+                            "⏭️  {}: {} / {}",
+                            get_message(MessageKey::SkippedExcludedLabel, lang),
+                            summary.totals.skipped,
+                            summary.totals.excluded
+                        );
+                        for (kind, count) in &summary.errors_by_kind {
+                            println!("   ❌ {kind:?}: {count}");
+                        }
+                    }
                     if let Some(ref err) = entry.error_message {
                         println!(
                             "{}❌ エラー: {}{}",
@@ -2462,16 +3256,158 @@ fn main() -> Result<()> {
             let mut cmd = Cli::command();
             print_completions(shell, &mut cmd);
         }
+        Some(Commands::Watch { priority, category }) => {
+            let config = Config::load()?;
+            let mut targets: Vec<&Target> = match priority {
+                Some(ref prio) => config.filter_by_priority(prio),
+                None => config.targets.iter().collect(),
+            };
+            if let Some(ref cat) = category {
+                targets.retain(|t| &t.category == cat);
+            }
+
+            println!(
+                "{}👀 {}{}",
+                get_color("green", false),
+                get_message(MessageKey::WatchStarted, lang)
+                    .replace("{}", &targets.len().to_string()),
+                get_color("reset", false)
+            );
+
+            // Ctrl+C / SIGTERM で実行中のバックアップを確定させてから監視を終了
+            let cancel = cancel_on_signal(lang);
+            backup_suite::core::watch::watch(
+                &config.watch,
+                &targets,
+                &config.backup.destination,
+                &cancel,
+                &ConsoleSink::new(lang).with_progress(false),
+                |paths| {
+                    // 設定の変更を反映するため、バックアップごとに読み込み直す
+                    // （失敗した場合は watch が警告を表示して後で再試行する）
+                    let result = BackupRunner::new(Config::load()?, false)
+                        .with_progress(false)
+                        .with_incremental(true)
+                        .with_changed_paths(paths.iter().cloned().collect())
+                        .with_language(lang)
+                        .with_cancellation(cancel.clone())
+                        .run(priority.as_ref(), category.as_deref())?;
+                    if !result.backup_name.is_empty() {
+                        println!(
+                            "{}✅ {}{}: {} ({}/{})",
+                            get_color("green", false),
+                            get_message(MessageKey::WatchSnapshotCreated, lang),
+                            get_color("reset", false),
+                            result.backup_name,
+                            result.successful,
+                            result.total_files
+                        );
+                    }
+                    Ok(())
+                },
+            )?;
+        }
+        Some(Commands::Check) => {
+            let config = Config::load()?;
+            let history = BackupHistory::load_all()?;
+            let statuses = rpo::evaluate(&config, &history, chrono::Utc::now());
+            let violations = statuses.iter().filter(|s| s.violated).count();
+            if !format.is_text() {
+                let items: Vec<output::RpoOutput> =
+                    statuses.iter().map(output::RpoOutput::from).collect();
+                output::emit_list(format, "check", &items)?;
+                exit_if_partial(violations > 0);
+                return Ok(());
+            }
+            if statuses.is_empty() {
+                println!(
+                    "{}ℹ️ {}{}",
+                    get_color("yellow", false),
+                    get_message(MessageKey::RpoNotConfigured, lang),
+                    get_color("reset", false)
+                );
+                return Ok(());
+            }
+
+            display_rpo_status(&statuses, lang);
+            if violations > 0 {
+                eprintln!(
+                    "{}❌ {}{}",
+                    get_color("red", false),
+                    get_message(MessageKey::RpoViolations, lang)
+                        .replace("{}", &violations.to_string()),
+                    get_color("reset", false)
+                );
+                std::process::exit(ErrorClass::Partial.exit_code());
+            }
+            println!(
+                "{}✅ {}{}",
+                get_color("green", false),
+                get_message(MessageKey::RpoAllMet, lang),
+                get_color("reset", false)
+            );
+        }
+        Some(Commands::Daemon { once }) => {
+            let mut daemon = Daemon::from_current_exe()?
+                .with_event_sink(Arc::new(ConsoleSink::new(lang).with_progress(false)));
+            let print_run = |run: &backup_suite::core::daemon::UnitRun| {
+                if run.success {
+                    println!(
+                        "{}✅ {}{}: {}",
+                        get_color("green", false),
+                        get_message(MessageKey::BackupComplete, lang),
+                        get_color("reset", false),
+                        run.unit
+                    );
+                } else {
+                    eprintln!(
+                        "{}❌ {}{}: {} ({})",
+                        get_color("red", false),
+                        get_message(MessageKey::Error, lang),
+                        get_color("reset", false),
+                        run.unit,
+                        run.exit_code
+                            .map_or_else(|| "signal".to_string(), |code| code.to_string())
+                    );
+                }
+            };
+
+            if once {
+                for run in daemon.run_once(&Config::load()?)? {
+                    print_run(&run);
+                }
+            } else {
+                println!(
+                    "{}✅ {}{} (PID: {})",
+                    get_color("green", false),
+                    get_message(MessageKey::DaemonStarted, lang),
+                    get_color("reset", false),
+                    std::process::id()
+                );
+                daemon.run_forever(print_run)?;
+            }
+        }
         Some(Commands::Schedule { action }) => {
             let mut config = Config::load()?;
             match action {
-                ScheduleAction::Enable { priority } => {
+                ScheduleAction::Enable { priority, job } => {
                     config.schedule.enabled = true;
                     config.save()?;
 
                     let scheduler = Scheduler::new(config)?;
 
-                    if let Some(ref prio) = priority {
+                    if let Some(ref name) = job {
+                        scheduler.setup_job(name)?;
+                        scheduler.enable_job(name)?;
+                        println!(
+                            "{}✅ {}{} ({}: {})",
+                            get_color("green", false),
+                            get_message(MessageKey::AutoBackupEnabled, lang),
+                            get_color("reset", false),
+                            get_message(MessageKey::Job, lang),
+                            name
+                        );
+                    } else if let Some(ref prio) = priority {
                         scheduler.setup_priority(prio)?;
                         scheduler.enable_priority(prio)?;
                         println!(
@@ -2492,10 +3428,20 @@ fn main() -> Result<()> {
                         );
                     }
                 }
-                ScheduleAction::Disable { priority } => {
+                ScheduleAction::Disable { priority, job } => {
                     let scheduler = Scheduler::new(Config::load()?)?;
 
-                    if let Some(ref prio) = priority {
+                    if let Some(ref name) = job {
+                        scheduler.disable_job(name)?;
+                        println!(
+                            "{}⏸️  {}{} ({}: {})",
+                            get_color("yellow", false),
+                            get_message(MessageKey::AutoBackupDisabled, lang),
+                            get_color("reset", false),
+                            get_message(MessageKey::Job, lang),
+                            name
+                        );
+                    } else if let Some(ref prio) = priority {
                         scheduler.disable_priority(prio)?;
                         println!(
                             "{}⏸️  {}{} ({:?})",
@@ -2517,15 +3463,46 @@ fn main() -> Result<()> {
                     }
                 }
                 ScheduleAction::Status => {
-                    println!(
-                        "\n\x1b[1m📅 {}\x1b[0m\n",
-                        get_message(MessageKey::ScheduleHeaderLabel, lang)
-                    );
-
                     // frequency値を事前に抽出（Scheduler::new()でconfigがmoveされる前）
                     let high_freq = config.schedule.high_frequency.clone();
                     let medium_freq = config.schedule.medium_frequency.clone();
                     let low_freq = config.schedule.low_frequency.clone();
+                    let job_freqs: std::collections::BTreeMap<String, String> = config
+                        .jobs
+                        .iter()
+                        .filter_map(|(name, job)| {
+                            job.schedule.clone().map(|freq| (name.clone(), freq))
+                        })
+                        .collect();
+
+                    if !format.is_text() {
+                        let enabled = config.schedule.enabled;
+                        let status = Scheduler::new(config)
+                            .and_then(|scheduler| scheduler.check_status())
+                            .unwrap_or_default();
+                        let daemon = DaemonState::load(&DaemonState::path()?)?;
+                        return output::emit(
+                            format,
+                            "schedule status",
+                            &ScheduleOutput {
+                                enabled,
+                                frequencies: ScheduleFrequencies {
+                                    high: high_freq,
+                                    medium: medium_freq,
+                                    low: low_freq,
+                                },
+                                job_frequencies: job_freqs,
+                                status,
+                                daemon_running: daemon.is_running(),
+                                daemon,
+                            },
+                        );
+                    }
+
+                    println!(
+                        "\n\x1b[1m📅 {}\x1b[0m\n",
+                        get_message(MessageKey::ScheduleHeaderLabel, lang)
+                    );
 
                     // 設定状態を表外に表示（チェックマーク位置修正: ✅を先に）
                     println!(
@@ -2543,9 +3520,10 @@ fn main() -> Result<()> {
                         }
                     );
 
-                    // 実際の状態確認
-                    let scheduler = Scheduler::new(config)?;
-                    let status = scheduler.check_status()?;
+                    // 実際の状態確認（systemctl/launchctl が使えない環境では全て無効として表示）
+                    let status = Scheduler::new(config)
+                        .and_then(|scheduler| scheduler.check_status())
+                        .unwrap_or_default();
 
                     let mut table = Table::new();
                     table
@@ -2617,9 +3595,111 @@ fn main() -> Result<()> {
                         }),
                     ]);
 
+                    // スケジュール設定済みの名前付きジョブ
+                    for (name, enabled) in &status.jobs {
+                        table.add_row(vec![
+                            Cell::new(format!("{}: {}", get_message(MessageKey::Job, lang), name)),
+                            Cell::new(job_freqs.get(name).map_or("", String::as_str)),
+                            Cell::new(format!(
+                                "{} {}",
+                                if *enabled { "✅" } else { "❌" },
+                                if *enabled {
+                                    get_message(MessageKey::EnabledLabel, lang)
+                                } else {
+                                    get_message(MessageKey::Disabled, lang)
+                                }
+                            ))
+                            .fg(if *enabled {
+                                Color::Green
+                            } else {
+                                Color::Red
+                            }),
+                        ]);
+                    }
+
                     println!("{table}");
+
+                    // 内蔵スケジューラデーモンの状態
+                    let daemon_state = DaemonState::load(&DaemonState::path()?)?;
+                    if daemon_state.pid.is_some() || !daemon_state.units.is_empty() {
+                        let running = daemon_state.is_running();
+                        println!(
+                            "\n{}: {} {}{}\n",
+                            get_message(MessageKey::SchedulerDaemon, lang),
+                            if running { "✅" } else { "❌" },
+                            if running {
+                                get_message(MessageKey::DaemonRunning, lang)
+                            } else {
+                                get_message(MessageKey::DaemonStopped, lang)
+                            },
+                            match daemon_state.pid {
+                                Some(pid) if running => format!(" (PID: {pid})"),
+                                _ => String::new(),
+                            }
+                        );
+
+                        let format_time = |time: Option<chrono::DateTime<chrono::Utc>>| {
+                            time.map_or_else(
+                                || "-".to_string(),
+                                |t| {
+                                    t.with_timezone(&chrono::Local)
+                                        .format("%Y-%m-%d %H:%M")
+                                        .to_string()
+                                },
+                            )
+                        };
+                        let mut table = Table::new();
+                        table
+                            .load_preset(UTF8_FULL)
+                            .set_content_arrangement(ContentArrangement::Dynamic)
+                            .set_header(vec![
+                                Cell::new(get_message(MessageKey::ScheduleHeaderLabel, lang)),
+                                Cell::new(get_message(MessageKey::LastRunLabel, lang)),
+                                Cell::new(get_message(MessageKey::StatusHistoryLabel, lang)),
+                                Cell::new(get_message(MessageKey::NextRunLabel, lang)),
+                            ]);
+                        for (unit, state) in &daemon_state.units {
+                            table.add_row(vec![
+                                Cell::new(format!("{unit} ({})", state.schedule)),
+                                Cell::new(format_time(state.last_run)),
+                                match state.last_success {
+                                    Some(true) => Cell::new("✅").fg(Color::Green),
+                                    Some(false) => Cell::new(format!(
+                                        "❌ {}",
+                                        state
+                                            .last_exit_code
+                                            .map_or_else(String::new, |code| code.to_string())
+                                    ))
+                                    .fg(Color::Red),
+                                    None => Cell::new("-"),
+                                },
+                                Cell::new(format_time(state.next_run)),
+                            ]);
+                        }
+                        println!("{table}");
+                    }
                 }
-                ScheduleAction::Setup { high, medium, low } => {
+                ScheduleAction::Setup {
+                    high,
+                    medium,
+                    low,
+                    random_delay,
+                } => {
+                    // 保存前にスケジュールの書式を検証
+                    for frequency in [&high, &medium, &low] {
+                        if let Err(e) = backup_suite::core::CalendarSpec::parse(frequency) {
+                            eprintln!(
+                                "{}❌ {}{}: {e:#}",
+                                get_color("red", false),
+                                get_message(MessageKey::Error, lang),
+                                get_color("reset", false)
+                            );
+                            std::process::exit(ErrorClass::Usage.exit_code());
+                        }
+                    }
+                    if let Some(secs) = random_delay {
+                        config.schedule.randomized_delay_secs = secs;
+                    }
                     config.schedule.high_frequency = high.clone();
                     config.schedule.medium_frequency = medium.clone();
                     config.schedule.low_frequency = low.clone();
@@ -2754,7 +3834,7 @@ fn main() -> Result<()> {
                             get_message(MessageKey::KeepDaysOutOfRange, lang),
                             days
                         );
-                        std::process::exit(1);
+                        std::process::exit(ErrorClass::Usage.exit_code());
                     }
 
                     let old_days = config.backup.keep_days;
@@ -2866,7 +3946,7 @@ fn main() -> Result<()> {
                             get_color("reset", false),
                             days
                         );
-                        std::process::exit(1);
+                        std::process::exit(ErrorClass::Usage.exit_code());
                     }
 
                     println!(
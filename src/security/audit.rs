@@ -54,18 +54,34 @@ pub enum EventType {
     BackupCompleted,
     /// バックアップ失敗
     BackupFailed,
+    /// バックアップのキャンセル
+    BackupCancelled,
     /// 復元開始
     RestoreStarted,
     /// 復元完了
     RestoreCompleted,
     /// 復元失敗
     RestoreFailed,
+    /// 復元のキャンセル
+    RestoreCancelled,
+    /// 検証開始
+    VerifyStarted,
+    /// 検証完了
+    VerifyCompleted,
+    /// 検証失敗
+    VerifyFailed,
+    /// 検証のキャンセル
+    VerifyCancelled,
     /// クリーンアップ開始
     CleanupStarted,
     /// クリーンアップ完了
     CleanupCompleted,
     /// クリーンアップ失敗
     CleanupFailed,
+    /// クリーンアップのキャンセル
+    CleanupCancelled,
+    /// フック実行
+    HookExecuted,
     /// 設定変更
     ConfigurationChanged,
     /// セキュリティ警告
@@ -80,12 +96,20 @@ impl std::fmt::Display for EventType {
             EventType::BackupStarted => write!(f, "BACKUP_STARTED"),
             EventType::BackupCompleted => write!(f, "BACKUP_COMPLETED"),
             EventType::BackupFailed => write!(f, "BACKUP_FAILED"),
+            EventType::BackupCancelled => write!(f, "BACKUP_CANCELLED"),
             EventType::RestoreStarted => write!(f, "RESTORE_STARTED"),
             EventType::RestoreCompleted => write!(f, "RESTORE_COMPLETED"),
             EventType::RestoreFailed => write!(f, "RESTORE_FAILED"),
+            EventType::RestoreCancelled => write!(f, "RESTORE_CANCELLED"),
+            EventType::VerifyStarted => write!(f, "VERIFY_STARTED"),
+            EventType::VerifyCompleted => write!(f, "VERIFY_COMPLETED"),
+            EventType::VerifyFailed => write!(f, "VERIFY_FAILED"),
+            EventType::VerifyCancelled => write!(f, "VERIFY_CANCELLED"),
             EventType::CleanupStarted => write!(f, "CLEANUP_STARTED"),
             EventType::CleanupCompleted => write!(f, "CLEANUP_COMPLETED"),
             EventType::CleanupFailed => write!(f, "CLEANUP_FAILED"),
+            EventType::CleanupCancelled => write!(f, "CLEANUP_CANCELLED"),
+            EventType::HookExecuted => write!(f, "HOOK_EXECUTED"),
             EventType::ConfigurationChanged => write!(f, "CONFIGURATION_CHANGED"),
             EventType::SecurityWarning => write!(f, "SECURITY_WARNING"),
             EventType::PermissionDenied => write!(f, "PERMISSION_DENIED"),
@@ -154,6 +178,21 @@ impl AuditEvent {
         )
     }
 
+    /// バックアップのキャンセルイベントを作成（`metadata` はキャンセルまでの処理結果）
+    #[must_use]
+    pub fn backup_cancelled(
+        target: impl Into<String>,
+        user: impl Into<String>,
+        metadata: serde_json::Value,
+    ) -> Self {
+        Self::new(
+            EventType::BackupCancelled,
+            user.into(),
+            Some(target.into()),
+            Some(metadata),
+        )
+    }
+
     /// 復元開始イベントを作成
     #[must_use]
     pub fn restore_started(target: impl Into<String>, user: impl Into<String>) -> Self {
@@ -194,6 +233,78 @@ impl AuditEvent {
         )
     }
 
+    /// 復元のキャンセルイベントを作成（`metadata` はキャンセルまでの処理結果）
+    #[must_use]
+    pub fn restore_cancelled(
+        target: impl Into<String>,
+        user: impl Into<String>,
+        metadata: serde_json::Value,
+    ) -> Self {
+        Self::new(
+            EventType::RestoreCancelled,
+            user.into(),
+            Some(target.into()),
+            Some(metadata),
+        )
+    }
+
+    /// 検証開始イベントを作成
+    #[must_use]
+    pub fn verify_started(target: impl Into<String>, user: impl Into<String>) -> Self {
+        Self::new(
+            EventType::VerifyStarted,
+            user.into(),
+            Some(target.into()),
+            None,
+        )
+    }
+
+    /// 検証完了イベントを作成
+    #[must_use]
+    pub fn verify_completed(
+        target: impl Into<String>,
+        user: impl Into<String>,
+        metadata: serde_json::Value,
+    ) -> Self {
+        Self::new(
+            EventType::VerifyCompleted,
+            user.into(),
+            Some(target.into()),
+            Some(metadata),
+        )
+    }
+
+    /// 検証失敗イベントを作成
+    #[must_use]
+    pub fn verify_failed(
+        target: impl Into<String>,
+        user: impl Into<String>,
+        error: impl Into<String>,
+    ) -> Self {
+        let metadata = serde_json::json!({ "error": error.into() });
+        Self::new(
+            EventType::VerifyFailed,
+            user.into(),
+            Some(target.into()),
+            Some(metadata),
+        )
+    }
+
+    /// 検証のキャンセルイベントを作成（`metadata` はキャンセルまでの処理結果）
+    #[must_use]
+    pub fn verify_cancelled(
+        target: impl Into<String>,
+        user: impl Into<String>,
+        metadata: serde_json::Value,
+    ) -> Self {
+        Self::new(
+            EventType::VerifyCancelled,
+            user.into(),
+            Some(target.into()),
+            Some(metadata),
+        )
+    }
+
     /// クリーンアップ開始イベントを作成
     #[must_use]
     pub fn cleanup_started(user: impl Into<String>, days: u32) -> Self {
@@ -219,6 +330,29 @@ impl AuditEvent {
         Self::new(EventType::CleanupFailed, user.into(), None, Some(metadata))
     }
 
+    /// クリーンアップのキャンセルイベントを作成（`metadata` はキャンセルまでの処理結果）
+    #[must_use]
+    pub fn cleanup_cancelled(user: impl Into<String>, metadata: serde_json::Value) -> Self {
+        Self::new(
+            EventType::CleanupCancelled,
+            user.into(),
+            None,
+            Some(metadata),
+        )
+    }
+
+    /// フック実行イベントを作成
+    ///
+    /// `target` はターゲットフックの場合は対象パス、グローバルフックの場合は `None` です。
+    #[must_use]
+    pub fn hook_executed(
+        target: Option<String>,
+        user: impl Into<String>,
+        outcome: serde_json::Value,
+    ) -> Self {
+        Self::new(EventType::HookExecuted, user.into(), target, Some(outcome))
+    }
+
     /// セキュリティ警告イベントを作成
     #[must_use]
     pub fn security_warning(message: impl Into<String>, user: impl Into<String>) -> Self {
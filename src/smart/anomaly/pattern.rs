@@ -107,7 +107,7 @@ impl PatternAnalyzer {
             .collect();
 
         // 発生回数順にソート
-        patterns.sort_by(|a, b| b.count.cmp(&a.count));
+        patterns.sort_by_key(|b| std::cmp::Reverse(b.count));
 
         Ok(patterns)
     }
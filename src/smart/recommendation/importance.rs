@@ -2,6 +2,7 @@
 //!
 //! ルールベーススコアリングによるファイル重要度の自動判定を提供します。
 
+use crate::core::sqlite::is_sqlite_database;
 use crate::core::Priority;
 use crate::i18n::{get_message, Language, MessageKey};
 use crate::security::path::validate_path_safety;
@@ -11,6 +12,12 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
+/// SQLiteデータベースの基本スコア（アプリケーションの状態を保持するため高重要度）
+const DATABASE_SCORE: u8 = 85;
+
+/// データベースのカテゴリ名
+const DATABASE_CATEGORY: &str = "データベース";
+
 /// 低優先度ディレクトリパターン（キャッシュ・アーカイブ等）
 const LOW_PRIORITY_DIR_PATTERNS: &[&str] = &[
     "cache",
@@ -403,8 +410,15 @@ impl ImportanceEvaluator {
         let mut best_score = 0u8; // ルールマッチ前は0、マッチしなければ後でデフォルト30を設定
         let mut file_name_matched = false;
 
+        // SQLiteデータベース（拡張子に依存せずマジックヘッダーで判定）
+        if is_sqlite_database(path) {
+            best_score = DATABASE_SCORE;
+            file_name_matched = true;
+        }
+
         for rule in &self.rules {
-            if !file_name.is_empty()
+            if !file_name_matched
+                && !file_name.is_empty()
                 && rule
                     .file_names
                     .iter()
@@ -602,8 +616,8 @@ impl ImportanceEvaluator {
         }
 
         // サンプリング結果に基づいてスコアを調整
-        if file_count > 0 {
-            let avg_score = (total_score / file_count) as u8;
+        if let Some(avg) = total_score.checked_div(file_count) {
+            let avg_score = avg as u8;
             let high_ratio = high_importance_count as f64 / file_count as f64;
 
             // 高重要度ファイルが50%以上なら高スコア
@@ -733,6 +747,12 @@ impl ImportanceEvaluator {
             .and_then(|e| e.to_str())
             .map(|s| s.to_lowercase());
 
+        // SQLiteデータベース
+        if is_sqlite_database(path) {
+            let reason = format!("{DATABASE_CATEGORY} (SQLite, スコア: {})", importance.get());
+            return (DATABASE_CATEGORY.to_string(), reason);
+        }
+
         // ファイル名マッチング（最優先）
         if !file_name.is_empty() {
             for rule in &self.rules {
@@ -1026,6 +1046,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_evaluate_sqlite_database() {
+        let temp = tempfile::TempDir::new().unwrap();
+        // 拡張子がなくてもマジックヘッダーで判定される
+        let path = temp.path().join("state");
+        let mut content = crate::core::sqlite::SQLITE_MAGIC.to_vec();
+        content.resize(4096, 0);
+        std::fs::write(&path, content).unwrap();
+
+        let evaluator = ImportanceEvaluator::new();
+        let result = evaluator.evaluate(&path).unwrap();
+        assert!(result.score().is_high());
+        assert_eq!(result.category(), "データベース");
+        assert!(result.reason().contains("SQLite"));
+    }
+
     #[test]
     fn test_nonexistent_file() {
         let evaluator = ImportanceEvaluator::new();
@@ -151,6 +151,7 @@ pub const VALID_COMMANDS: &[&str] = &[
     "rm",
     "run",
     "restore",
+    "verify",
     "cleanup",
     "status",
     "history",
@@ -158,6 +159,9 @@ pub const VALID_COMMANDS: &[&str] = &[
     "open",
     "completion",
     "schedule",
+    "watch",
+    "daemon",
+    "check",
     "config",
     "smart",
 ];
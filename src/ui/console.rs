@@ -0,0 +1,174 @@
+//! # コンソール出力
+//!
+//! エンジンのイベントを端末に表示する既定の [`EventSink`] です。
+//! 情報は標準出力、警告は標準エラー出力に表示し、ファイルの処理中は進捗バーを表示します。
+
+use std::sync::Mutex;
+
+use super::progress::BackupProgress;
+use crate::core::events::{Event, EventSink, Operation, Phase};
+use crate::i18n::{get_message, Language, MessageKey};
+
+/// 端末に表示するイベントシンク
+///
+/// # 使用例
+///
+/// ```no_run
+/// use backup_suite::i18n::Language;
+/// use backup_suite::ui::ConsoleSink;
+/// use backup_suite::{BackupRunner, Config};
+/// use std::sync::Arc;
+///
+/// // 進捗バーなし、警告のみ表示
+/// let sink = ConsoleSink::new(Language::English)
+///     .with_progress(false)
+///     .with_quiet(true);
+/// let config = Config::load().unwrap();
+/// let mut runner = BackupRunner::new(config, false).with_event_sink(Arc::new(sink));
+/// ```
+pub struct ConsoleSink {
+    lang: Language,
+    show_progress: bool,
+    quiet: bool,
+    state: Mutex<ConsoleState>,
+}
+
+/// 表示中のスピナー・進捗バー
+#[derive(Default)]
+struct ConsoleState {
+    operation: Option<Operation>,
+    spinner: Option<BackupProgress>,
+    bar: Option<BackupProgress>,
+    failed: usize,
+}
+
+impl ConsoleSink {
+    /// 進捗バーと情報表示が有効なシンクを作成
+    #[must_use]
+    pub fn new(lang: Language) -> Self {
+        Self {
+            lang,
+            show_progress: true,
+            quiet: false,
+            state: Mutex::new(ConsoleState::default()),
+        }
+    }
+
+    /// 進捗バー・スピナーの表示有無を設定
+    #[must_use]
+    pub fn with_progress(mut self, show_progress: bool) -> Self {
+        self.show_progress = show_progress;
+        self
+    }
+
+    /// 情報（処理モード・対象一覧など）の標準出力への表示を抑制（警告は表示）
+    #[must_use]
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    fn phase_changed(&self, state: &mut ConsoleState, operation: Operation, phase: Phase) {
+        state.operation = Some(operation);
+        match phase {
+            Phase::Scanning if operation == Operation::Backup => {
+                let spinner = BackupProgress::new_spinner();
+                spinner.set_message("Collecting backup target files...");
+                state.spinner = Some(spinner);
+            }
+            Phase::Planning { found_files } => {
+                if let Some(spinner) = state.spinner.take() {
+                    spinner.finish(&format!(
+                        "{found_files} {}",
+                        get_message(MessageKey::FilesDetected, self.lang)
+                    ));
+                }
+            }
+            Phase::Processing { total_files } if operation != Operation::Cleanup => {
+                let bar = BackupProgress::with_language(total_files as u64, self.lang);
+                match operation {
+                    Operation::Restore => bar.set_message("復元中..."),
+                    Operation::Verify => bar.set_message("検証中..."),
+                    _ => {}
+                }
+                state.bar = Some(bar);
+                state.failed = 0;
+            }
+            Phase::Cancelled => {
+                if let Some(bar) = state.bar.take() {
+                    bar.finish("⚠ キャンセルされました（処理済みのファイルを保存します）");
+                }
+            }
+            Phase::Finalizing | Phase::Finished { .. } => {
+                if let Some(bar) = state.bar.take() {
+                    bar.finish(&self.finish_message(operation, state.failed));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn finish_message(&self, operation: Operation, failed: usize) -> String {
+        match (operation, failed) {
+            (Operation::Restore, 0) => "✓ 復元完了".to_string(),
+            (Operation::Restore, _) => format!("⚠ 復元完了（{failed}件失敗）"),
+            (Operation::Verify, 0) => "✓ 検証完了".to_string(),
+            (Operation::Verify, _) => format!("⚠ 検証完了（{failed}件失敗）"),
+            (_, 0) => get_message(MessageKey::BackupComplete, self.lang).to_string(),
+            _ => format!(
+                "{} ({} {})",
+                get_message(MessageKey::BackupCompleteWithFailures, self.lang)
+                    .replace("（失敗あり）", "")
+                    .replace("（有失败）", "")
+                    .replace("（有失敗）", "")
+                    .replace("(with failures)", ""),
+                failed,
+                get_message(MessageKey::FailedLabel, self.lang)
+            ),
+        }
+    }
+}
+
+impl EventSink for ConsoleSink {
+    fn on_event(&self, event: &Event) {
+        match event {
+            Event::Warning(message) => eprintln!("{message}"),
+            Event::Info(message) => {
+                if !self.quiet {
+                    println!("{message}");
+                }
+            }
+            Event::PhaseChanged { operation, phase } => {
+                if self.show_progress {
+                    if let Ok(mut state) = self.state.lock() {
+                        self.phase_changed(&mut state, *operation, *phase);
+                    }
+                }
+            }
+            Event::FileStarted { path } => {
+                let Ok(state) = self.state.lock() else {
+                    return;
+                };
+                if let (Some(bar), Some(file_name)) = (&state.bar, path.file_name()) {
+                    let label = match state.operation {
+                        Some(Operation::Restore) => "復元中",
+                        Some(Operation::Verify) => "検証中",
+                        _ => "処理中",
+                    };
+                    bar.set_message(&format!("{label}: {file_name:?}"));
+                }
+            }
+            Event::FileFinished { .. } | Event::FileFailed { .. } => {
+                if let Ok(mut state) = self.state.lock() {
+                    if matches!(event, Event::FileFailed { .. }) {
+                        state.failed += 1;
+                    }
+                    if let Some(bar) = &state.bar {
+                        bar.inc(1);
+                    }
+                }
+            }
+            Event::Progress { .. } => {}
+        }
+    }
+}
@@ -8,8 +8,8 @@ use comfy_table::{presets::UTF8_FULL, Cell, CellAlignment, Color, ContentArrange
 use std::fs;
 
 use super::colors::ColorTheme;
-use super::table::display_history;
-use crate::core::{BackupHistory, Config, Priority};
+use super::table::{display_history, display_rpo_status};
+use crate::core::{rpo, BackupHistory, Config, Priority, TargetType};
 use crate::i18n::{get_message, MessageKey};
 
 /// ダッシュボード表示
@@ -57,6 +57,11 @@ pub fn display_dashboard(lang: crate::i18n::Language) -> Result<()> {
 
     println!();
 
+    // RPO（設定されている場合のみ）
+    if display_rpo(&theme, lang)? {
+        println!();
+    }
+
     // エラー・警告サマリー
     display_warnings_summary(&theme, lang)?;
 
@@ -470,6 +475,26 @@ fn display_recent_backups(theme: &ColorTheme, lang: crate::i18n::Language) -> Re
     Ok(())
 }
 
+/// RPOの状態（許容経過時間が設定された対象がない場合は何も表示せず `false` を返す）
+fn display_rpo(theme: &ColorTheme, lang: crate::i18n::Language) -> Result<bool> {
+    let config = Config::load()?;
+    let history = BackupHistory::load_all()?;
+    let statuses = rpo::evaluate(&config, &history, Utc::now());
+    if statuses.is_empty() {
+        return Ok(false);
+    }
+
+    println!(
+        "{}",
+        theme
+            .header()
+            .apply_to(get_message(MessageKey::RpoTitle, lang))
+    );
+    display_rpo_status(&statuses, lang);
+
+    Ok(true)
+}
+
 /// エラー・警告サマリー
 #[allow(clippy::cast_precision_loss)]
 fn display_warnings_summary(theme: &ColorTheme, lang: crate::i18n::Language) -> Result<()> {
@@ -478,7 +503,7 @@ fn display_warnings_summary(theme: &ColorTheme, lang: crate::i18n::Language) ->
 
     // バックアップ対象が存在しない場合の警告
     for target in &config.targets {
-        if !target.path.exists() {
+        if target.target_type != TargetType::Command && !target.path.exists() {
             warnings.push(
                 get_message(MessageKey::WarningTargetNotExists, lang)
                     .replace("{}", &target.path.display().to_string()),
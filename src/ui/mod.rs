@@ -2,9 +2,12 @@
 ///
 /// Phase 2: プログレスバー、インタラクティブプロンプト
 /// Phase 3: ダッシュボード、テーブル表示、カラースキーム
+/// 機械可読な出力: `--output json|ndjson`
 pub mod colors;
+pub mod console;
 pub mod dashboard;
 pub mod interactive;
+pub mod output;
 pub mod progress;
 pub mod table;
 
@@ -17,5 +20,7 @@ pub use progress::{create_progress_bar, create_spinner, BackupProgress};
 
 // Phase 3: 高度なUI機能
 pub use colors::{ColorScheme, ColorTheme};
+pub use console::ConsoleSink;
 pub use dashboard::display_dashboard;
-pub use table::{display_backup_result, display_history, display_targets};
+pub use output::OutputFormat;
+pub use table::{display_backup_result, display_history, display_rpo_status, display_targets};
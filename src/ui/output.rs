@@ -0,0 +1,281 @@
+//! # 機械可読な出力
+//!
+//! グローバルオプション `--output json|ndjson` で、`list`・`status`・`history`・`run`・
+//! `restore`・`cleanup`・`schedule status`・`check` の結果を表やアイコンの代わりにJSONで
+//! 標準出力に書き出します。
+//!
+//! - `json`: 1つのJSONドキュメント（整形済み）
+//! - `ndjson`: 1行に1つのJSON。一覧（`list`・`history`・`check`）は要素ごとに1行、それ以外は1行
+//!
+//! どちらの形式も各ドキュメントを [`SCHEMA_VERSION`] とコマンド名を含む封筒に入れます。
+//! スキーマの詳細は `docs/OUTPUT_SCHEMA.md` を参照してください。
+//!
+//! ```json
+//! {"schema_version": 1, "command": "list", "data": [ ... ]}
+//! ```
+//!
+//! エラー時は `{"schema_version": 1, "command": "run", "error": {"class": "locked", "exit_code": 5, "message": "..."}}`
+//! を標準エラー出力に書き出し、[`ErrorClass`] の終了コードで終了します。
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::core::cleanup::CleanupResult;
+use crate::core::restore::RestoreResult;
+use crate::core::rpo::RpoStatus;
+use crate::core::{BackupResult, DaemonState, Priority, ScheduleStatus};
+use crate::error::ErrorClass;
+
+/// 出力スキーマのバージョン（互換性のない変更時に更新）
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// 出力形式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// 表・アイコン付きのテキスト
+    #[default]
+    Text,
+    /// 整形済みのJSONドキュメント
+    Json,
+    /// 1行に1つのJSON（改行区切りJSON）
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// テキスト出力か
+    #[must_use]
+    pub fn is_text(self) -> bool {
+        self == Self::Text
+    }
+}
+
+/// 出力の封筒
+#[derive(Debug, Serialize)]
+struct Envelope<'a, T: Serialize> {
+    schema_version: u32,
+    command: &'a str,
+    data: T,
+}
+
+/// エラー出力の封筒
+#[derive(Debug, Serialize)]
+struct ErrorEnvelope<'a> {
+    schema_version: u32,
+    command: &'a str,
+    error: ErrorBody,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorBody {
+    class: ErrorClass,
+    exit_code: i32,
+    message: String,
+}
+
+/// 1つの結果を書き出し（テキスト出力の場合は何もしない）
+///
+/// # Errors
+///
+/// シリアライズまたは書き込みに失敗した場合にエラーを返します。
+pub fn emit<T: Serialize>(format: OutputFormat, command: &str, data: &T) -> Result<()> {
+    let envelope = Envelope {
+        schema_version: SCHEMA_VERSION,
+        command,
+        data,
+    };
+    let mut stdout = std::io::stdout().lock();
+    match format {
+        OutputFormat::Text => return Ok(()),
+        OutputFormat::Json => serde_json::to_writer_pretty(&mut stdout, &envelope),
+        OutputFormat::Ndjson => serde_json::to_writer(&mut stdout, &envelope),
+    }
+    .context("出力のシリアライズ失敗")?;
+    writeln!(stdout).context("出力の書き込み失敗")
+}
+
+/// 一覧を書き出し（`ndjson` では要素ごとに1行）
+///
+/// # Errors
+///
+/// シリアライズまたは書き込みに失敗した場合にエラーを返します。
+pub fn emit_list<T: Serialize>(format: OutputFormat, command: &str, items: &[T]) -> Result<()> {
+    if format != OutputFormat::Ndjson {
+        return emit(format, command, &items);
+    }
+    items
+        .iter()
+        .try_for_each(|item| emit(format, command, item))
+}
+
+/// エラーを標準エラー出力に書き出し、分類を返す
+#[must_use]
+pub fn emit_error(format: OutputFormat, command: &str, error: &anyhow::Error) -> ErrorClass {
+    let class = ErrorClass::classify(error);
+    if format.is_text() {
+        eprintln!("Error: {error:?}");
+        return class;
+    }
+    let envelope = ErrorEnvelope {
+        schema_version: SCHEMA_VERSION,
+        command,
+        error: ErrorBody {
+            class,
+            exit_code: class.exit_code(),
+            message: format!("{error:#}"),
+        },
+    };
+    // シリアライズできない値を含まないため失敗しない
+    if let Ok(line) = serde_json::to_string(&envelope) {
+        eprintln!("{line}");
+    }
+    class
+}
+
+/// `status` の結果
+#[derive(Debug, Serialize)]
+pub struct StatusOutput {
+    pub destination: PathBuf,
+    pub total_targets: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+}
+
+/// `run` の結果
+#[derive(Debug, Serialize)]
+pub struct RunOutput {
+    pub dry_run: bool,
+    #[serde(flatten)]
+    pub result: BackupResult,
+    /// ジョブの保持期間（`keep_days`）による削除結果
+    pub retention_cleanup: Option<CleanupResult>,
+}
+
+/// `restore` の結果
+#[derive(Debug, Serialize)]
+pub struct RestoreOutput {
+    /// 復元したバックアップのディレクトリ
+    pub backup: PathBuf,
+    /// 復元先ディレクトリ
+    pub destination: PathBuf,
+    #[serde(flatten)]
+    pub result: RestoreResult,
+}
+
+/// `verify` の結果
+#[derive(Debug, Serialize)]
+pub struct VerifyOutput {
+    /// 検証したバックアップのディレクトリ
+    pub backup: PathBuf,
+    #[serde(flatten)]
+    pub result: RestoreResult,
+}
+
+/// `cleanup` の結果
+#[derive(Debug, Serialize)]
+pub struct CleanupOutput {
+    pub dry_run: bool,
+    pub retention_days: u32,
+    #[serde(flatten)]
+    pub result: CleanupResult,
+}
+
+/// 優先度ごとの実行頻度
+#[derive(Debug, Serialize)]
+pub struct ScheduleFrequencies {
+    pub high: String,
+    pub medium: String,
+    pub low: String,
+}
+
+/// `schedule status` の結果
+#[derive(Debug, Serialize)]
+pub struct ScheduleOutput {
+    /// 設定ファイルでスケジュールが有効か
+    pub enabled: bool,
+    pub frequencies: ScheduleFrequencies,
+    /// スケジュール設定済みジョブの実行頻度（ジョブ名 → 頻度）
+    pub job_frequencies: BTreeMap<String, String>,
+    /// systemd/launchd に登録されたスケジュールの有効状態
+    pub status: ScheduleStatus,
+    /// 内蔵スケジューラデーモンが実行中か
+    pub daemon_running: bool,
+    pub daemon: DaemonState,
+}
+
+/// `check` の対象ごとの結果
+#[derive(Debug, Serialize)]
+pub struct RpoOutput {
+    pub path: PathBuf,
+    pub category: String,
+    pub priority: Priority,
+    pub max_age_hours: u64,
+    pub last_success: Option<DateTime<Utc>>,
+    /// 最後に成功したバックアップからの経過秒数
+    pub age_secs: Option<i64>,
+    pub violated: bool,
+}
+
+impl From<&RpoStatus> for RpoOutput {
+    fn from(status: &RpoStatus) -> Self {
+        Self {
+            path: status.path.clone(),
+            category: status.category.clone(),
+            priority: status.priority,
+            max_age_hours: status.max_age_hours,
+            last_success: status.last_success,
+            age_secs: status.age.map(|age| age.num_seconds()),
+            violated: status.violated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_shape() {
+        let envelope = Envelope {
+            schema_version: SCHEMA_VERSION,
+            command: "status",
+            data: StatusOutput {
+                destination: PathBuf::from("/backup"),
+                total_targets: 3,
+                high: 1,
+                medium: 1,
+                low: 1,
+            },
+        };
+        let json = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(json["schema_version"], 1);
+        assert_eq!(json["command"], "status");
+        assert_eq!(json["data"]["destination"], "/backup");
+        assert_eq!(json["data"]["total_targets"], 3);
+    }
+
+    #[test]
+    fn test_cleanup_output_is_flattened() {
+        let output = CleanupOutput {
+            dry_run: true,
+            retention_days: 30,
+            result: CleanupResult {
+                total_checked: 4,
+                deleted: 2,
+                freed_bytes: 1024,
+                stale_removed: 0,
+                errors: Vec::new(),
+                cancelled: false,
+            },
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["dry_run"], true);
+        assert_eq!(json["deleted"], 2);
+        assert_eq!(json["freed_bytes"], 1024);
+    }
+}
@@ -1,4 +1,5 @@
 use super::colors::ColorTheme;
+use crate::core::rpo::RpoStatus;
 use crate::core::{BackupHistory, Priority, Target, TargetType};
 use crate::i18n::{get_message, Language, MessageKey};
 /// テーブル表示モジュール
@@ -104,6 +105,12 @@ pub fn display_targets(targets: &[Target], theme: &ColorTheme, lang: Language) {
                 Language::SimplifiedChinese => "📁 目录",
                 Language::TraditionalChinese => "📁 目錄",
             }),
+            TargetType::Command => Cell::new(match lang {
+                Language::English => "⚙️ Command",
+                Language::Japanese => "⚙️ コマンド",
+                Language::SimplifiedChinese => "⚙️ 命令",
+                Language::TraditionalChinese => "⚙️ 命令",
+            }),
         };
 
         let exclude_count = if target.exclude_patterns.is_empty() {
@@ -233,6 +240,95 @@ pub fn display_history(history: &[BackupHistory], theme: &ColorTheme, lang: Lang
     println!("{table}\n");
 }
 
+/// 対象ごとのRPOの状態をテーブル表示
+pub fn display_rpo_status(statuses: &[RpoStatus], lang: Language) {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new(match lang {
+                Language::English => "Path",
+                Language::Japanese => "パス",
+                Language::SimplifiedChinese => "路径",
+                Language::TraditionalChinese => "路徑",
+            }),
+            Cell::new(match lang {
+                Language::English => "Priority",
+                Language::Japanese => "優先度",
+                Language::SimplifiedChinese => "优先级",
+                Language::TraditionalChinese => "優先級",
+            })
+            .set_alignment(CellAlignment::Center),
+            Cell::new(match lang {
+                Language::English => "Max Age",
+                Language::Japanese => "許容経過時間",
+                Language::SimplifiedChinese => "最大间隔",
+                Language::TraditionalChinese => "最大間隔",
+            })
+            .set_alignment(CellAlignment::Right),
+            Cell::new(match lang {
+                Language::English => "Last Success",
+                Language::Japanese => "最終成功",
+                Language::SimplifiedChinese => "最后成功",
+                Language::TraditionalChinese => "最後成功",
+            }),
+            Cell::new(match lang {
+                Language::English => "Age",
+                Language::Japanese => "経過時間",
+                Language::SimplifiedChinese => "已过时间",
+                Language::TraditionalChinese => "已過時間",
+            })
+            .set_alignment(CellAlignment::Right),
+            Cell::new(match lang {
+                Language::English => "Status",
+                Language::Japanese => "状態",
+                Language::SimplifiedChinese => "状态",
+                Language::TraditionalChinese => "狀態",
+            })
+            .set_alignment(CellAlignment::Center),
+        ]);
+
+    for status in statuses {
+        let last_success = status.last_success.map_or_else(
+            || "-".to_string(),
+            |timestamp| {
+                timestamp
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            },
+        );
+        let age = status.age.map_or_else(
+            || "-".to_string(),
+            |age| {
+                let hours = age.num_hours().max(0);
+                if hours >= 24 {
+                    format!("{}d {}h", hours / 24, hours % 24)
+                } else {
+                    format!("{hours}h")
+                }
+            },
+        );
+        let status_cell = if status.violated {
+            Cell::new("❌").fg(Color::Red)
+        } else {
+            Cell::new("✅").fg(Color::Green)
+        };
+
+        table.add_row(vec![
+            Cell::new(status.path.display().to_string()),
+            Cell::new(format!("{:?}", status.priority)).set_alignment(CellAlignment::Center),
+            Cell::new(format!("{}h", status.max_age_hours)).set_alignment(CellAlignment::Right),
+            Cell::new(last_success),
+            Cell::new(age).set_alignment(CellAlignment::Right),
+            status_cell.set_alignment(CellAlignment::Center),
+        ]);
+    }
+
+    println!("{table}");
+}
+
 /// バックアップ結果をテーブル表示
 pub fn display_backup_result(
     total_files: usize,
@@ -445,6 +541,10 @@ mod tests {
             compressed: true,
             encrypted: true,
             error_message: None,
+            inconsistent_files: Vec::new(),
+            targets: Vec::new(),
+            summary: None,
+            job: None,
         }];
         let theme = ColorTheme::auto();
 
@@ -467,6 +567,10 @@ mod tests {
             compressed: false,
             encrypted: false,
             error_message: Some("Test error".to_string()),
+            inconsistent_files: Vec::new(),
+            targets: Vec::new(),
+            summary: None,
+            job: None,
         }];
         let theme = ColorTheme::auto();
 
@@ -490,6 +594,10 @@ mod tests {
                 compressed: true,
                 encrypted: true,
                 error_message: None,
+                inconsistent_files: Vec::new(),
+                targets: Vec::new(),
+                summary: None,
+                job: None,
             },
             BackupHistory {
                 timestamp: Utc::now(),
@@ -504,6 +612,10 @@ mod tests {
                 compressed: false,
                 encrypted: false,
                 error_message: Some("Error".to_string()),
+                inconsistent_files: Vec::new(),
+                targets: Vec::new(),
+                summary: None,
+                job: None,
             },
         ];
         let theme = ColorTheme::auto();
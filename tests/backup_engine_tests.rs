@@ -1252,3 +1252,1789 @@ fn test_backup_result_no_targets_match() -> Result<()> {
 
     Ok(())
 }
+
+/// Test 31: Symlinks, hardlinks and empty directories round-trip
+///
+/// Tests that non-regular entries are recorded in the manifest instead of
+/// being dropped, and that RestoreEngine recreates them.
+#[cfg(unix)]
+#[test]
+fn test_backup_special_entries_round_trip() -> Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("source");
+    let backup_dest = temp.path().join("backup");
+    let restore_dest = temp.path().join("restore");
+
+    fs::create_dir_all(source.join("empty"))?;
+    fs::write(source.join("original.txt"), "shared content")?;
+    fs::hard_link(source.join("original.txt"), source.join("hardlink.txt"))?;
+    std::os::unix::fs::symlink("original.txt", source.join("link.txt"))?;
+
+    let mut config = Config::default();
+    config.backup.destination = backup_dest.clone();
+    config.targets.push(Target::new(
+        source.clone(),
+        Priority::High,
+        "special-test".to_string(),
+    ));
+
+    let mut runner = BackupRunner::new(config, false)
+        .with_progress(false)
+        .with_compression(CompressionType::None, 0);
+    let result = runner.run(None, None)?;
+
+    // Only one copy of the hardlinked content is stored
+    assert_eq!(result.total_files, 1);
+    assert_eq!(result.successful, 1);
+
+    let backup_dir = backup_dest.join(&result.backup_name);
+    let metadata = backup_suite::core::BackupMetadata::load(&backup_dir)?;
+    assert!(metadata
+        .special_entries
+        .contains_key(std::path::Path::new("special-test/source/empty")));
+    assert!(metadata
+        .special_entries
+        .contains_key(std::path::Path::new("special-test/source/link.txt")));
+
+    let mut engine = RestoreEngine::new(false).with_progress(false);
+    let restored = engine.restore(&backup_dir, &restore_dest, None)?;
+    assert_eq!(restored.failed, 0);
+    assert!(restored.errors.is_empty(), "{:?}", restored.errors);
+
+    let root = restore_dest.join("special-test/source");
+    assert!(root.join("empty").is_dir());
+    assert_eq!(
+        fs::read_link(root.join("link.txt"))?,
+        std::path::PathBuf::from("original.txt")
+    );
+    assert_eq!(
+        fs::metadata(root.join("original.txt"))?.ino(),
+        fs::metadata(root.join("hardlink.txt"))?.ino()
+    );
+
+    Ok(())
+}
+
+/// Test 32: Snapshot mode hardlinks unchanged files
+///
+/// Tests that a second snapshot shares inodes with the previous one for
+/// unchanged files, copies changed files, and still restores as a full tree.
+#[cfg(unix)]
+#[test]
+fn test_snapshot_mode_links_unchanged_files() -> Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("source");
+    let backup_dest = temp.path().join("backup");
+    let restore_dest = temp.path().join("restore");
+
+    fs::create_dir_all(&source)?;
+    fs::write(source.join("unchanged.txt"), "stays the same")?;
+    fs::write(source.join("changed.txt"), "version 1")?;
+
+    let run_snapshot = || -> Result<BackupResult> {
+        let mut config = Config::default();
+        config.backup.destination = backup_dest.clone();
+        config.targets.push(Target::new(
+            source.clone(),
+            Priority::High,
+            "snapshot-test".to_string(),
+        ));
+        let mut runner = BackupRunner::new(config, false)
+            .with_progress(false)
+            .with_compression(CompressionType::None, 0)
+            .with_verification(false)
+            .with_snapshot(true);
+        runner.run(None, None)
+    };
+
+    let first = run_snapshot()?;
+    assert_eq!(first.successful, 2);
+    assert_eq!(first.linked_files, 0);
+
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    fs::write(source.join("changed.txt"), "version 2")?;
+
+    let second = run_snapshot()?;
+    assert_eq!(second.successful, 2);
+    assert_eq!(second.linked_files, 1);
+    assert_eq!(second.total_bytes, "version 2".len() as u64);
+
+    let first_dir = backup_dest
+        .join(&first.backup_name)
+        .join("snapshot-test/source");
+    let second_dir = backup_dest
+        .join(&second.backup_name)
+        .join("snapshot-test/source");
+    assert_eq!(
+        fs::metadata(first_dir.join("unchanged.txt"))?.ino(),
+        fs::metadata(second_dir.join("unchanged.txt"))?.ino()
+    );
+    assert_ne!(
+        fs::metadata(first_dir.join("changed.txt"))?.ino(),
+        fs::metadata(second_dir.join("changed.txt"))?.ino()
+    );
+
+    let metadata =
+        backup_suite::core::BackupMetadata::load(&backup_dest.join(&second.backup_name))?;
+    assert!(metadata.snapshot);
+    assert_eq!(
+        metadata.link_dest.as_deref(),
+        Some(first.backup_name.as_str())
+    );
+
+    let mut engine = RestoreEngine::new(false).with_progress(false);
+    let restored = engine.restore(&backup_dest.join(&second.backup_name), &restore_dest, None)?;
+    assert_eq!(restored.failed, 0);
+    let root = restore_dest.join("snapshot-test/source");
+    assert_eq!(
+        fs::read_to_string(root.join("unchanged.txt"))?,
+        "stays the same"
+    );
+    assert_eq!(fs::read_to_string(root.join("changed.txt"))?, "version 2");
+
+    Ok(())
+}
+
+/// Test 33: Interrupted backups stay in staging
+///
+/// Tests that a completed backup leaves no staging directory behind and that
+/// a stale staging directory from an interrupted run is never picked as the
+/// parent of an incremental backup.
+#[test]
+fn test_stale_staging_is_not_a_backup() -> Result<()> {
+    use backup_suite::core::incremental::IncrementalBackupEngine;
+    use backup_suite::core::staging;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("source");
+    let backup_dest = temp.path().join("backup");
+
+    fs::create_dir_all(&source)?;
+    fs::write(source.join("file.txt"), "content")?;
+
+    let mut config = Config::default();
+    config.backup.destination = backup_dest.clone();
+    config.targets.push(Target::new(
+        source.clone(),
+        Priority::High,
+        "staging-test".to_string(),
+    ));
+
+    let mut runner = BackupRunner::new(config, false)
+        .with_progress(false)
+        .with_compression(CompressionType::None, 0);
+    let result = runner.run(None, None)?;
+
+    assert!(backup_dest
+        .join(&result.backup_name)
+        .join(".integrity")
+        .exists());
+    assert!(staging::find_stale_staging(&backup_dest).is_empty());
+
+    // Simulate a crash after the manifest was written but before the commit
+    let stale = staging::staging_path(&backup_dest, "backup_99991231_235959");
+    fs::create_dir_all(stale.join("staging-test"))?;
+    fs::copy(
+        backup_dest.join(&result.backup_name).join(".integrity"),
+        stale.join(".integrity"),
+    )?;
+
+    assert_eq!(staging::find_stale_staging(&backup_dest), vec![stale]);
+    let latest = IncrementalBackupEngine::new(backup_dest.clone()).find_latest_backup()?;
+    assert_eq!(latest, Some(backup_dest.join(&result.backup_name)));
+
+    Ok(())
+}
+
+/// Test 34: Resuming an interrupted backup
+///
+/// Tests that `with_resume` reuses the staging directory of an interrupted
+/// run, skips files recorded in its checkpoint after validating them, and
+/// rewrites files whose checkpointed copy no longer matches. Runs whose
+/// run-level or per-target codecs differ from the checkpoint are refused.
+#[test]
+fn test_resume_interrupted_backup() -> Result<()> {
+    use backup_suite::core::checkpoint::{self, Checkpoint, CheckpointEntry, CheckpointHeader};
+    use backup_suite::core::staging;
+    use backup_suite::core::HashAlgorithm;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("source");
+    let backup_dest = temp.path().join("backup");
+
+    fs::create_dir_all(&source)?;
+    fs::write(source.join("done.txt"), "already written")?;
+    fs::write(source.join("corrupt.txt"), "written but damaged")?;
+    fs::write(source.join("pending.txt"), "not yet written")?;
+
+    // Simulate a run that was killed after checkpointing two files
+    let backup_name = "backup_20240101_000000";
+    let staging_dir = staging::staging_path(&backup_dest, backup_name);
+    let rel_dir = std::path::Path::new("resume-test/source");
+    fs::create_dir_all(staging_dir.join(rel_dir))?;
+    let checkpoint = Checkpoint::create(
+        &staging_dir,
+        &CheckpointHeader {
+            compression: "none".to_string(),
+            target_compression: Default::default(),
+            encrypted: false,
+            snapshot: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+        },
+    )?;
+    for (name, content) in [
+        ("done.txt", "already written"),
+        ("corrupt.txt", "written but damaged"),
+    ] {
+        fs::copy(source.join(name), staging_dir.join(rel_dir).join(name))?;
+        let (len, mtime) = checkpoint::source_stamp(&source.join(name)).unwrap();
+        let hash = HashAlgorithm::Sha256.hash_bytes(content.as_bytes());
+        checkpoint.record(&CheckpointEntry {
+            path: rel_dir.join(name),
+            source_len: len,
+            source_mtime_ns: mtime,
+            source_hash: Some(hash.clone()),
+            written_len: content.len() as u64,
+            written_hash: Some(hash),
+            sparse: None,
+        })?;
+    }
+    drop(checkpoint);
+    fs::write(
+        staging_dir.join(rel_dir).join("corrupt.txt"),
+        "written but DAMAGED",
+    )?;
+
+    let make_runner = |compression, target_compression| {
+        let mut config = Config::default();
+        config.backup.destination = backup_dest.clone();
+        let mut target = Target::new(source.clone(), Priority::High, "resume-test".to_string());
+        target.compression = target_compression;
+        config.targets.push(target);
+        BackupRunner::new(config, false)
+            .with_progress(false)
+            .with_compression(compression, 3)
+            .with_resume(true)
+    };
+
+    // Different settings cannot reuse the staged files
+    assert!(make_runner(CompressionType::Zstd, None)
+        .run(None, None)
+        .is_err());
+    // A per-target codec override counts as a different setting too
+    assert!(
+        make_runner(CompressionType::None, Some(CompressionType::Zstd))
+            .run(None, None)
+            .is_err()
+    );
+
+    let result = make_runner(CompressionType::None, None).run(None, None)?;
+    assert_eq!(result.backup_name, backup_name);
+    assert_eq!(result.successful, 3);
+    assert_eq!(result.resumed_files, 1);
+    assert!(staging::find_stale_staging(&backup_dest).is_empty());
+
+    let final_dir = backup_dest.join(backup_name).join(rel_dir);
+    assert_eq!(
+        fs::read_to_string(final_dir.join("corrupt.txt"))?,
+        "written but damaged"
+    );
+    assert_eq!(
+        fs::read_to_string(final_dir.join("pending.txt"))?,
+        "not yet written"
+    );
+    assert!(!backup_dest.join(backup_name).join(".checkpoint").exists());
+
+    let metadata = backup_suite::core::BackupMetadata::load(&backup_dest.join(backup_name))?;
+    assert_eq!(metadata.file_hashes.len(), 3);
+
+    Ok(())
+}
+
+/// Test 35: Repository lock blocks concurrent runs
+///
+/// Tests that a backup refuses to start while another process holds the
+/// repository lock, and that the lock directory is gone once released.
+#[test]
+fn test_backup_respects_repository_lock() -> Result<()> {
+    use backup_suite::core::lock::{LockKind, RepositoryLock, LOCK_DIR};
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("source");
+    let backup_dest = temp.path().join("backup");
+
+    fs::create_dir_all(&source)?;
+    fs::write(source.join("file.txt"), "content")?;
+
+    let make_runner = || {
+        let mut config = Config::default();
+        config.backup.destination = backup_dest.clone();
+        config.targets.push(Target::new(
+            source.clone(),
+            Priority::High,
+            "lock-test".to_string(),
+        ));
+        BackupRunner::new(config, false)
+            .with_progress(false)
+            .with_compression(CompressionType::None, 0)
+    };
+
+    let held = RepositoryLock::acquire(&backup_dest, LockKind::Shared, "restore")?;
+    let err = make_runner().run(None, None).unwrap_err();
+    assert!(err.to_string().contains("ロック"), "{err}");
+    drop(held);
+
+    let result = make_runner().run(None, None)?;
+    assert_eq!(result.successful, 1);
+    assert!(!backup_dest.join(LOCK_DIR).exists());
+
+    Ok(())
+}
+
+/// Test 36: Pre/post/on_failure hooks
+///
+/// Tests that hooks receive the run description via environment variables,
+/// and that a failing pre_backup hook skips its target and triggers on_failure.
+#[cfg(unix)]
+#[test]
+fn test_backup_hooks() -> Result<()> {
+    use backup_suite::core::hooks::HookConfig;
+
+    let temp = TempDir::new()?;
+    let good = temp.path().join("good");
+    let bad = temp.path().join("bad");
+    let backup_dest = temp.path().join("backup");
+    let log = temp.path().join("hooks.log");
+
+    fs::create_dir_all(&good)?;
+    fs::create_dir_all(&bad)?;
+    fs::write(good.join("file.txt"), "content")?;
+    fs::write(bad.join("file.txt"), "content")?;
+
+    let append = |line: &str| format!("echo \"{line}\" >> {}", log.display());
+
+    let mut config = Config::default();
+    config.backup.destination = backup_dest.clone();
+    config.hooks = HookConfig {
+        post_backup: Some(append(
+            "global post $BACKUP_SUITE_TOTAL_FILES $BACKUP_SUITE_STATUS",
+        )),
+        on_failure: Some(append("global failure")),
+        ..Default::default()
+    };
+    let mut good_target = Target::new(good.clone(), Priority::High, "good".to_string());
+    good_target.hooks = HookConfig {
+        pre_backup: Some(append("good pre $BACKUP_SUITE_TARGET_CATEGORY")),
+        post_backup: Some(append("good post $BACKUP_SUITE_SUCCESSFUL")),
+        ..Default::default()
+    };
+    let mut bad_target = Target::new(bad.clone(), Priority::High, "bad".to_string());
+    bad_target.hooks = HookConfig {
+        pre_backup: Some("exit 1".to_string()),
+        on_failure: Some(append("bad failure")),
+        ..Default::default()
+    };
+    config.targets.push(good_target);
+    config.targets.push(bad_target);
+
+    let result = BackupRunner::new(config, false)
+        .with_progress(false)
+        .with_compression(CompressionType::None, 0)
+        .run(None, None)?;
+
+    assert_eq!(result.successful, 1);
+    assert!(result.errors.iter().any(|e| e.contains("pre_backup")));
+    let backup_dir = backup_dest.join(&result.backup_name);
+    assert!(backup_dir.join("good/good/file.txt").exists());
+    assert!(!backup_dir.join("bad").exists());
+
+    let lines: Vec<String> = fs::read_to_string(&log)?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            "good pre good",
+            "bad failure",
+            "good post 1",
+            "global post 1 failure",
+            "global failure",
+        ]
+    );
+
+    Ok(())
+}
+
+/// Test 37: Command output and stdin as virtual files
+///
+/// Tests that a command target's stdout and a stdin stream are compressed,
+/// recorded in the manifest like normal files, and restored with RestoreEngine.
+#[cfg(unix)]
+#[test]
+fn test_backup_command_output_and_stdin() -> Result<()> {
+    let temp = TempDir::new()?;
+    let backup_dest = temp.path().join("backup");
+    let restore_dir = temp.path().join("restore");
+
+    let mut config = Config::default();
+    config.backup.destination = backup_dest.clone();
+    config.targets.push(Target::from_command(
+        std::path::PathBuf::from("packages.txt"),
+        Some("printf 'pkg-a\\npkg-b\\n'".to_string()),
+        Priority::High,
+        "system".to_string(),
+    ));
+    config.targets.push(Target::from_command(
+        std::path::PathBuf::from("broken.txt"),
+        Some("exit 2".to_string()),
+        Priority::High,
+        "system".to_string(),
+    ));
+
+    let result = BackupRunner::new(config, false)
+        .with_progress(false)
+        .with_compression(CompressionType::Zstd, 3)
+        .run(None, None)?;
+    assert_eq!(result.successful, 1);
+    assert!(result.errors.iter().any(|e| e.contains("exit 2")));
+
+    let backup_dir = backup_dest.join(&result.backup_name);
+    let metadata = backup_suite::core::BackupMetadata::load(&backup_dir)?;
+    assert!(metadata
+        .file_hashes
+        .contains_key(std::path::Path::new("system/packages.txt")));
+    assert!(!backup_dir.join(".virtual").exists());
+
+    let mut engine = RestoreEngine::new(false).with_progress(false);
+    engine.restore(&backup_dir, &restore_dir, None)?;
+    assert_eq!(
+        fs::read_to_string(restore_dir.join("system/packages.txt"))?,
+        "pkg-a\npkg-b\n"
+    );
+
+    // --stdin: configured targets are ignored
+    let mut config = Config::default();
+    config.backup.destination = backup_dest.clone();
+    config.targets.push(Target::new(
+        temp.path().to_path_buf(),
+        Priority::High,
+        "ignored".to_string(),
+    ));
+    let result = BackupRunner::new(config, false)
+        .with_progress(false)
+        .with_compression(CompressionType::Zstd, 3)
+        .with_stdin(
+            "db.sql".to_string(),
+            std::io::Cursor::new(b"CREATE TABLE t;".to_vec()),
+        )
+        .run(None, None)?;
+    assert_eq!(result.total_files, 1);
+    assert_eq!(result.successful, 1);
+
+    let stdin_restore = temp.path().join("restore_stdin");
+    let mut engine = RestoreEngine::new(false).with_progress(false);
+    engine.restore(&backup_dest.join(&result.backup_name), &stdin_restore, None)?;
+    assert_eq!(
+        fs::read_to_string(stdin_restore.join("stdin/db.sql"))?,
+        "CREATE TABLE t;"
+    );
+
+    Ok(())
+}
+
+/// Test 38: Consistent SQLite backups
+///
+/// Tests that a live WAL-mode database is copied through the online backup API,
+/// so the backup holds committed WAL contents without the -wal/-shm files.
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_sqlite_database_online_backup() -> Result<()> {
+    use rusqlite::Connection;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("app");
+    let backup_dest = temp.path().join("backup");
+    let restore_dir = temp.path().join("restore");
+    fs::create_dir_all(&source)?;
+
+    let conn = Connection::open(source.join("state.db"))?;
+    conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))?;
+    conn.execute_batch(
+        "PRAGMA wal_autocheckpoint = 0;
+         CREATE TABLE t (v TEXT);
+         INSERT INTO t VALUES ('live');",
+    )?;
+    assert!(source.join("state.db-wal").exists());
+
+    let mut config = Config::default();
+    config.backup.destination = backup_dest.clone();
+    config.targets.push(Target::new(
+        source.clone(),
+        Priority::High,
+        "apps".to_string(),
+    ));
+    let result = BackupRunner::new(config, false)
+        .with_progress(false)
+        .with_compression(CompressionType::Zstd, 3)
+        .run(None, None)?;
+    drop(conn);
+
+    assert_eq!(result.total_files, 1);
+    assert_eq!(result.successful, 1);
+
+    let backup_dir = backup_dest.join(&result.backup_name);
+    let mut engine = RestoreEngine::new(false).with_progress(false);
+    engine.restore(&backup_dir, &restore_dir, None)?;
+
+    let restored_db = restore_dir.join("apps/app/state.db");
+    assert!(!restore_dir.join("apps/app/state.db-wal").exists());
+    let restored = Connection::open(&restored_db)?;
+    let value: String = restored.query_row("SELECT v FROM t", [], |row| row.get(0))?;
+    assert_eq!(value, "live");
+
+    Ok(())
+}
+
+/// Test 39: Files modified during backup
+///
+/// Tests that a file that keeps changing while it is read is retried and then
+/// reported as inconsistent, while stable files are backed up normally.
+#[test]
+fn test_backup_flags_files_modified_during_backup() -> Result<()> {
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("logs");
+    let backup_dest = temp.path().join("backup");
+    fs::create_dir_all(&source)?;
+    fs::write(source.join("stable.txt"), "stable")?;
+    let growing = source.join("growing.log");
+    fs::write(&growing, vec![b'x'; 4 * 1024 * 1024])?;
+
+    // バックアップが終わるまで書き込みを続ける
+    let done = Arc::new(AtomicBool::new(false));
+    let writer = {
+        let done = Arc::clone(&done);
+        let growing = growing.clone();
+        std::thread::spawn(move || {
+            let mut file = fs::OpenOptions::new().append(true).open(growing).unwrap();
+            while !done.load(Ordering::Relaxed) {
+                file.write_all(b"line\n").unwrap();
+            }
+        })
+    };
+
+    let mut config = Config::default();
+    config.backup.destination = backup_dest.clone();
+    config.backup.change_retries = 1;
+    config.targets.push(Target::new(
+        source.clone(),
+        Priority::High,
+        "logs".to_string(),
+    ));
+    let result = BackupRunner::new(config, false)
+        .with_progress(false)
+        .run(None, None);
+    done.store(true, Ordering::Relaxed);
+    writer.join().unwrap();
+    let result = result?;
+
+    assert_eq!(result.total_files, 2);
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.inconsistent_files, vec![growing]);
+
+    // 不整合なファイルには整合性ハッシュを記録しない
+    let backup_dir = backup_dest.join(&result.backup_name);
+    let metadata = backup_suite::core::BackupMetadata::load(&backup_dir)?;
+    assert_eq!(
+        metadata.inconsistent_files,
+        vec![PathBuf::from("logs/logs/growing.log")]
+    );
+    assert!(metadata
+        .file_hashes
+        .contains_key(&PathBuf::from("logs/logs/stable.txt")));
+    assert!(!metadata
+        .file_hashes
+        .contains_key(&PathBuf::from("logs/logs/growing.log")));
+
+    Ok(())
+}
+
+/// Test 40: BLAKE3 manifests and Merkle roots
+///
+/// Tests that the configured hash algorithm is recorded in the manifest and
+/// used for restore verification, and that replicas of the same content share
+/// a Merkle root regardless of compression while a tampered manifest is detected.
+#[test]
+fn test_blake3_manifest_merkle_root() -> Result<()> {
+    use backup_suite::core::{BackupMetadata, HashAlgorithm};
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("docs");
+    fs::create_dir_all(source.join("nested"))?;
+    fs::write(source.join("a.txt"), "alpha")?;
+    fs::write(source.join("nested/b.txt"), "bravo")?;
+
+    let run_to = |dest: &std::path::Path, compression: CompressionType| -> Result<BackupResult> {
+        let mut config = Config::default();
+        config.backup.destination = dest.to_path_buf();
+        config.backup.hash_algorithm = HashAlgorithm::Blake3;
+        config.targets.push(Target::new(
+            source.clone(),
+            Priority::High,
+            "docs".to_string(),
+        ));
+        BackupRunner::new(config, false)
+            .with_progress(false)
+            .with_compression(compression, 3)
+            .run(None, None)
+    };
+
+    let primary_dest = temp.path().join("primary");
+    let replica_dest = temp.path().join("replica");
+    let primary = run_to(&primary_dest, CompressionType::None)?;
+    let replica = run_to(&replica_dest, CompressionType::Zstd)?;
+
+    let primary_dir = primary_dest.join(&primary.backup_name);
+    let metadata = BackupMetadata::load(&primary_dir)?;
+    assert_eq!(metadata.hash_algorithm, HashAlgorithm::Blake3);
+    assert_eq!(
+        metadata.file_hashes[&std::path::PathBuf::from("docs/docs/a.txt")],
+        HashAlgorithm::Blake3.hash_bytes(b"alpha")
+    );
+    assert!(primary.merkle_root.is_some());
+    assert_eq!(metadata.merkle_root, primary.merkle_root);
+    assert_eq!(replica.merkle_root, primary.merkle_root);
+
+    let mut engine = RestoreEngine::new(false).with_progress(false);
+    let restored = engine.restore(&primary_dir, &temp.path().join("restore"), None)?;
+    assert_eq!(restored.verified_files, 2);
+    assert_eq!(restored.verification_failures, 0);
+
+    // マニフェストのハッシュを書き換えるとMerkleルートが一致しなくなる
+    let mut tampered = metadata.clone();
+    tampered.file_hashes.insert(
+        std::path::PathBuf::from("docs/docs/a.txt"),
+        HashAlgorithm::Blake3.hash_bytes(b"forged"),
+    );
+    tampered.save(&primary_dir)?;
+    let restored = engine.restore(&primary_dir, &temp.path().join("restore2"), None)?;
+    assert!(restored.verification_failures >= 1);
+    assert!(restored.errors.iter().any(|e| e.contains("Merkle")));
+
+    Ok(())
+}
+
+/// Test 41: Adaptive per-file compression
+///
+/// Tests that adaptive compression stores already-compressed files as-is,
+/// compresses text at a high level, reports the savings, and restores both
+/// byte-for-byte (a stored zstd file must not be decompressed on restore).
+#[test]
+fn test_adaptive_compression_backup() -> Result<()> {
+    use backup_suite::compression::adaptive::{self, AdaptiveDecision};
+    use backup_suite::compression::header;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("mixed");
+    fs::create_dir_all(&source)?;
+    let text = "adaptive compression keeps text small. ".repeat(500);
+    let packed = zstd::encode_all(text.as_bytes(), 3)?;
+    fs::write(source.join("notes.txt"), &text)?;
+    fs::write(source.join("archive.zst"), &packed)?;
+
+    let dest = temp.path().join("backups");
+    let mut config = Config::default();
+    config.backup.destination = dest.clone();
+    config.targets.push(Target::new(
+        source.clone(),
+        Priority::High,
+        "mixed".to_string(),
+    ));
+    let result = BackupRunner::new(config, false)
+        .with_progress(false)
+        .with_compression(CompressionType::Adaptive, 19)
+        .run(None, None)?;
+
+    assert_eq!(result.successful, 2);
+    let stats = &result.compression_stats;
+    assert_eq!((stats.stored_files, stats.high_files), (1, 1));
+    assert_eq!(stats.original_bytes, (text.len() + packed.len()) as u64);
+    assert!(stats.saved_bytes() > 0);
+
+    let backup_dir = dest.join(&result.backup_name);
+    let stored = fs::read(backup_dir.join("mixed/mixed/archive.zst"))?;
+    assert_eq!(header::codec_of(&stored), Some(CompressionType::Adaptive));
+    assert_eq!(
+        adaptive::decision_of(&stored[header::HEADER_SIZE..]),
+        Some(AdaptiveDecision::Store)
+    );
+
+    let restore_dir = temp.path().join("restore");
+    let mut engine = RestoreEngine::new(false).with_progress(false);
+    let restored = engine.restore(&backup_dir, &restore_dir, None)?;
+    assert_eq!(restored.restored, 2);
+    assert_eq!(restored.verification_failures, 0);
+    assert_eq!(
+        fs::read(restore_dir.join("mixed/mixed/archive.zst"))?,
+        packed
+    );
+    assert_eq!(
+        fs::read_to_string(restore_dir.join("mixed/mixed/notes.txt"))?,
+        text
+    );
+
+    Ok(())
+}
+
+/// Test 42: Per-target compression codecs
+///
+/// Tests that a target-level codec overrides the run's codec, that every
+/// backed-up file records its codec in the header, and that restore picks
+/// the right decoder for each file.
+#[test]
+fn test_per_target_compression_codecs() -> Result<()> {
+    use backup_suite::compression::header;
+
+    let temp = TempDir::new()?;
+    let logs = temp.path().join("logs");
+    let docs = temp.path().join("docs");
+    fs::create_dir_all(&logs)?;
+    fs::create_dir_all(&docs)?;
+    let log_text = "GET /index.html 200\n".repeat(400);
+    let doc_text = "per-target codecs are selected in the config. ".repeat(300);
+    fs::write(logs.join("access.log"), &log_text)?;
+    fs::write(docs.join("guide.md"), &doc_text)?;
+
+    let dest = temp.path().join("backups");
+    let mut config = Config::default();
+    config.backup.destination = dest.clone();
+    let mut log_target = Target::new(logs.clone(), Priority::High, "logs".to_string());
+    log_target.compression = Some(CompressionType::Xz);
+    log_target.compression_level = Some(9);
+    let mut doc_target = Target::new(docs.clone(), Priority::High, "docs".to_string());
+    doc_target.compression = Some(CompressionType::Brotli);
+    config.targets.push(log_target);
+    config.targets.push(doc_target);
+
+    let result = BackupRunner::new(config, false)
+        .with_progress(false)
+        .with_compression(CompressionType::Lz4, 0)
+        .run(None, None)?;
+    assert_eq!(result.successful, 2);
+
+    let backup_dir = dest.join(&result.backup_name);
+    let stored_log = fs::read(backup_dir.join("logs/logs/access.log"))?;
+    let stored_doc = fs::read(backup_dir.join("docs/docs/guide.md"))?;
+    assert_eq!(header::codec_of(&stored_log), Some(CompressionType::Xz));
+    assert_eq!(header::codec_of(&stored_doc), Some(CompressionType::Brotli));
+    assert!(stored_log.len() < log_text.len());
+
+    let restore_dir = temp.path().join("restore");
+    let mut engine = RestoreEngine::new(false).with_progress(false);
+    let restored = engine.restore(&backup_dir, &restore_dir, None)?;
+    assert_eq!(restored.restored, 2);
+    assert_eq!(restored.verification_failures, 0);
+    assert_eq!(
+        fs::read_to_string(restore_dir.join("logs/logs/access.log"))?,
+        log_text
+    );
+    assert_eq!(
+        fs::read_to_string(restore_dir.join("docs/docs/guide.md"))?,
+        doc_text
+    );
+
+    Ok(())
+}
+
+/// Test 43: Zstd dictionary for small files
+///
+/// Tests that dictionary training stores one dictionary in the snapshot,
+/// compresses small files with it (the dictionary ID is in the zstd frame),
+/// and that restore finds the dictionary and skips the dictionary directory.
+#[test]
+fn test_zstd_dictionary_backup() -> Result<()> {
+    use backup_suite::compression::{dictionary, header, ZstdDictionary};
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("configs");
+    fs::create_dir_all(&source)?;
+    for i in 0..200 {
+        fs::write(
+            source.join(format!("service-{i}.json")),
+            format!(
+                r#"{{"name": "service-{i}", "replicas": {}, "image": "registry.local/app:{}", "healthcheck": {{"path": "/health", "interval": 30}}}}"#,
+                i % 4,
+                i % 9
+            ),
+        )?;
+    }
+
+    let dest = temp.path().join("backups");
+    let mut config = Config::default();
+    config.backup.destination = dest.clone();
+    config.targets.push(Target::new(
+        source.clone(),
+        Priority::High,
+        "configs".to_string(),
+    ));
+    let result = BackupRunner::new(config, false)
+        .with_progress(false)
+        .with_compression(CompressionType::Zstd, 3)
+        .with_dictionary_training(true)
+        .run(None, None)?;
+    assert_eq!(result.successful, 200);
+
+    let backup_dir = dest.join(&result.backup_name);
+    let dictionaries = ZstdDictionary::load_all(&backup_dir)?;
+    assert_eq!(dictionaries.len(), 1);
+    let id = *dictionaries.keys().next().unwrap();
+
+    let original = fs::read(source.join("service-7.json"))?;
+    let stored = fs::read(backup_dir.join("configs/configs/service-7.json"))?;
+    assert_eq!(header::codec_of(&stored), Some(CompressionType::Zstd));
+    let body = &stored[header::HEADER_SIZE..];
+    assert_eq!(dictionary::frame_dictionary_id(body), Some(id));
+    assert!(body.len() < zstd::encode_all(original.as_slice(), 3)?.len());
+
+    let restore_dir = temp.path().join("restore");
+    let mut engine = RestoreEngine::new(false).with_progress(false);
+    let restored = engine.restore(&backup_dir, &restore_dir, None)?;
+    assert_eq!(restored.restored, 200);
+    assert_eq!(restored.verification_failures, 0);
+    assert!(!restore_dir.join(dictionary::DICTIONARY_DIR).exists());
+    assert_eq!(
+        fs::read(restore_dir.join("configs/configs/service-7.json"))?,
+        original
+    );
+
+    Ok(())
+}
+
+/// Test 44: Pack files for small files
+///
+/// Tests that small files are concatenated into pack files indexed in the
+/// manifest, large files stay as individual files, and restore (plain and
+/// encrypted, with verification) reads packed files transparently.
+#[test]
+fn test_pack_small_files_backup() -> Result<()> {
+    use backup_suite::core::pack::{self, PACK_DIR};
+    use backup_suite::core::BackupMetadata;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("many");
+    fs::create_dir_all(source.join("sub"))?;
+    for i in 0..50 {
+        fs::write(
+            source.join(format!("sub/file-{i}.txt")),
+            format!("small {i}"),
+        )?;
+    }
+    let large: Vec<u8> = (0..100_000u32).map(|i| (i * 7919 % 251) as u8).collect();
+    fs::write(source.join("large.bin"), &large)?;
+
+    for password in [None, Some("pack-password")] {
+        let dest = temp.path().join(format!("backups-{}", password.is_some()));
+        let mut config = Config::default();
+        config.backup.destination = dest.clone();
+        config.targets.push(Target::new(
+            source.clone(),
+            Priority::High,
+            "many".to_string(),
+        ));
+        let mut runner = BackupRunner::new(config, false)
+            .with_progress(false)
+            .with_compression(CompressionType::None, 0)
+            .with_pack_files(true);
+        if let Some(password) = password {
+            runner = runner.with_encryption(password.to_string());
+        }
+        let result = runner.run(None, None)?;
+        assert_eq!(result.successful, 51);
+        assert_eq!(result.packed_files, 50);
+
+        let backup_dir = dest.join(&result.backup_name);
+        let metadata = BackupMetadata::load(&backup_dir)?;
+        assert_eq!(metadata.packed_files.len(), 50);
+        assert!(pack::pack_path(&backup_dir, 0).exists());
+        assert!(!backup_dir.join("many/many/sub").exists());
+        assert!(backup_dir.join("many/many/large.bin").exists());
+        assert_eq!(
+            fs::read_dir(backup_dir.join(PACK_DIR))?.count(),
+            1,
+            "small files should share a single pack"
+        );
+
+        let restore_dir = temp.path().join(format!("restore-{}", password.is_some()));
+        let mut engine = RestoreEngine::new(false).with_progress(false);
+        let restored = engine.restore(&backup_dir, &restore_dir, password)?;
+        assert_eq!(restored.restored, 51);
+        assert_eq!(restored.verified_files, 51);
+        assert_eq!(restored.verification_failures, 0);
+        assert!(!restore_dir.join(PACK_DIR).exists());
+        assert_eq!(
+            fs::read_to_string(restore_dir.join("many/many/sub/file-7.txt"))?,
+            "small 7"
+        );
+        assert_eq!(fs::read(restore_dir.join("many/many/large.bin"))?, large);
+    }
+
+    Ok(())
+}
+
+/// Test 45: Named job run through the CLI
+///
+/// `run --job` must apply the job's target selection, encryption key source,
+/// compression and hooks exactly as defined in config.toml.
+#[test]
+#[cfg(unix)]
+fn test_run_named_job_via_cli() -> Result<()> {
+    use backup_suite::core::{BackupJob, KeySource};
+    use backup_suite::crypto::EncryptedData;
+
+    let temp = TempDir::new()?;
+    let home = temp.path().join("home");
+    let high = temp.path().join("high");
+    let low = temp.path().join("low");
+    fs::create_dir_all(&high)?;
+    fs::create_dir_all(&low)?;
+    fs::write(high.join("important.txt"), "important data")?;
+    fs::write(low.join("scratch.txt"), "scratch data")?;
+
+    let dest = temp.path().join("backups");
+    let job_marker = temp.path().join("job-hook");
+    let global_marker = temp.path().join("global-hook");
+    let mut config = Config::default();
+    config.backup.destination = dest.clone();
+    config.hooks.post_backup = Some(format!("touch {}", global_marker.display()));
+    config
+        .targets
+        .push(Target::new(high, Priority::High, "high".to_string()));
+    config
+        .targets
+        .push(Target::new(low, Priority::Low, "low".to_string()));
+    let mut job = BackupJob {
+        priority: Some(Priority::High),
+        compress: Some(CompressionType::Gzip),
+        compress_level: Some(9),
+        encryption: Some(KeySource::PasswordEnv(
+            "BACKUP_SUITE_TEST_JOB_PASSWORD".to_string(),
+        )),
+        keep_days: Some(30),
+        ..Default::default()
+    };
+    job.hooks.post_backup = Some(format!("touch {}", job_marker.display()));
+    config.jobs.insert("nightly".to_string(), job);
+
+    let config_dir = home.join(".config/backup-suite");
+    fs::create_dir_all(&config_dir)?;
+    fs::write(config_dir.join("config.toml"), toml::to_string(&config)?)?;
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_backup-suite"))
+        .args(["--lang", "en", "run", "--job", "nightly"])
+        .env("HOME", &home)
+        .env("BACKUP_SUITE_TEST_JOB_PASSWORD", "Job-Passw0rd!2024")
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // ジョブのフックだけが実行される
+    assert!(job_marker.exists());
+    assert!(!global_marker.exists());
+
+    let backups: Vec<_> = fs::read_dir(&dest)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir() && !p.file_name().unwrap().to_string_lossy().starts_with('.'))
+        .collect();
+    assert_eq!(backups.len(), 1);
+    let backup_dir = &backups[0];
+    let files: Vec<_> = walkdir::WalkDir::new(backup_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_type().is_file()
+                && e.file_name() != ".integrity"
+                && e.file_name() != backup_suite::core::report::REPORT_FILE
+        })
+        .collect();
+    assert_eq!(files.len(), 1, "only the high priority target is selected");
+    assert!(files[0].path().ends_with("important.txt"));
+    assert!(EncryptedData::from_bytes(&fs::read(files[0].path())?).is_ok());
+
+    let restore_dir = temp.path().join("restore");
+    let mut engine = RestoreEngine::new(false).with_progress(false);
+    let restored = engine.restore(backup_dir, &restore_dir, Some("Job-Passw0rd!2024"))?;
+    assert_eq!(restored.restored, 1);
+
+    // 未定義のジョブはエラー
+    let missing = std::process::Command::new(env!("CARGO_BIN_EXE_backup-suite"))
+        .args(["--lang", "en", "run", "--job", "missing"])
+        .env("HOME", &home)
+        .output()?;
+    assert!(!missing.status.success());
+
+    Ok(())
+}
+
+/// Test 46: Incremental backup limited to changed paths (watch mode)
+///
+/// `with_changed_paths` must compare only the notified paths, carry the
+/// hashes of the other files over from the previous manifest, and skip
+/// creating a backup when none of the notified files changed.
+#[test]
+fn test_incremental_backup_with_changed_paths() -> Result<()> {
+    use backup_suite::core::BackupMetadata;
+    use std::path::PathBuf;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("project");
+    fs::create_dir_all(source.join("src"))?;
+    fs::write(source.join("src/main.rs"), "fn main() {}")?;
+    fs::write(source.join("src/lib.rs"), "pub fn lib() {}")?;
+    fs::write(source.join("README.md"), "# project")?;
+    let dest = temp.path().join("backups");
+
+    let runner = |changed: Option<Vec<PathBuf>>| {
+        let mut config = Config::default();
+        config.backup.destination = dest.clone();
+        config.targets.push(Target::new(
+            source.clone(),
+            Priority::High,
+            "code".to_string(),
+        ));
+        let runner = BackupRunner::new(config, false)
+            .with_progress(false)
+            .with_incremental(true);
+        match changed {
+            Some(paths) => runner.with_changed_paths(paths),
+            None => runner,
+        }
+    };
+
+    let full = runner(None).run(None, None)?;
+    assert_eq!(full.successful, 3);
+
+    // README.md も変更されているが、通知されたパスのみ比較する
+    fs::write(source.join("src/main.rs"), "fn main() { println!(); }")?;
+    fs::write(source.join("README.md"), "# project v2")?;
+    let result = runner(Some(vec![source.join("src")])).run(None, None)?;
+    assert_eq!(result.successful, 1);
+
+    let metadata = BackupMetadata::load(&dest.join(&result.backup_name))?;
+    assert_eq!(metadata.changed_files.len(), 1);
+    assert_eq!(metadata.file_hashes.len(), 3);
+    assert_eq!(
+        metadata.parent_backup.as_deref(),
+        Some(full.backup_name.as_str())
+    );
+
+    // 通知されたファイルが変更されていなければバックアップを作成しない
+    let backups_before = fs::read_dir(&dest)?.count();
+    let unchanged = runner(Some(vec![source.join("src/lib.rs")])).run(None, None)?;
+    assert_eq!(unchanged.total_files, 0);
+    assert!(unchanged.backup_name.is_empty());
+    assert_eq!(fs::read_dir(&dest)?.count(), backups_before);
+
+    Ok(())
+}
+
+/// Test 47: Per-run report with per-target breakdown
+///
+/// Each run must produce a report with per-target file counts, exclusion
+/// counts and bytes read, and save the same report inside the snapshot.
+#[test]
+fn test_run_report_per_target_breakdown() -> Result<()> {
+    use backup_suite::core::report::{RunReport, REPORT_FILE};
+
+    let temp = TempDir::new()?;
+    let docs = temp.path().join("docs");
+    let logs = temp.path().join("logs");
+    fs::create_dir_all(&docs)?;
+    fs::create_dir_all(&logs)?;
+    fs::write(docs.join("a.txt"), "a".repeat(1000))?;
+    fs::write(docs.join("b.tmp"), "temporary")?;
+    fs::write(logs.join("app.log"), "log line")?;
+    let dest = temp.path().join("backups");
+
+    let mut config = Config::default();
+    config.backup.destination = dest.clone();
+    let mut docs_target = Target::new(docs.clone(), Priority::High, "docs".to_string());
+    docs_target.exclude_patterns = vec![r"\.tmp$".to_string()];
+    config.targets.push(docs_target);
+    config
+        .targets
+        .push(Target::new(logs.clone(), Priority::Low, "logs".to_string()));
+
+    let mut runner = BackupRunner::new(config, false).with_progress(false);
+    let result = runner.run(None, None)?;
+
+    let report = result.report.expect("実行レポートが作成されること");
+    assert_eq!(report.backup_name, result.backup_name);
+    assert_eq!(report.summary.totals.files, 2);
+    assert_eq!(report.summary.totals.excluded, 1);
+    assert_eq!(report.summary.totals.bytes_read, 1008);
+    assert!(report.summary.compression_ratio.is_some());
+    assert!(report.errors.is_empty());
+
+    assert_eq!(report.targets.len(), 2);
+    assert_eq!(report.targets[0].path, docs);
+    assert_eq!(report.targets[0].counts.files, 1);
+    assert_eq!(report.targets[0].counts.excluded, 1);
+    assert_eq!(report.targets[0].counts.bytes_read, 1000);
+    assert_eq!(report.targets[1].path, logs);
+    assert_eq!(report.targets[1].counts.successful, 1);
+
+    // スナップショット内に保存されたレポートと一致
+    let backup_dir = dest.join(&result.backup_name);
+    assert!(backup_dir.join(REPORT_FILE).exists());
+    assert_eq!(RunReport::load(&backup_dir)?, report);
+
+    Ok(())
+}
+
+/// Test 48: Machine-readable JSON output and exit codes through the CLI
+///
+/// Hook output must not leak into the JSON written to stdout.
+#[test]
+fn test_cli_json_output() -> Result<()> {
+    let temp = TempDir::new()?;
+    let home = temp.path().join("home");
+    let docs = temp.path().join("docs");
+    fs::create_dir_all(&docs)?;
+    fs::write(docs.join("a.txt"), "alpha")?;
+    fs::write(docs.join("b.txt"), "beta")?;
+
+    let dest = temp.path().join("backups");
+    let mut config = Config::default();
+    config.backup.destination = dest.clone();
+    config.hooks.pre_backup = Some("echo pre-hook output".to_string());
+    config.hooks.post_backup = Some("echo post-hook output".to_string());
+    let mut target = Target::new(docs.clone(), Priority::High, "docs".to_string());
+    target.max_age_hours = Some(24);
+    config.targets.push(target);
+    let config_dir = home.join(".config/backup-suite");
+    fs::create_dir_all(&config_dir)?;
+    fs::write(config_dir.join("config.toml"), toml::to_string(&config)?)?;
+
+    let cli = |args: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_backup-suite"))
+            .args(args)
+            .env("HOME", &home)
+            .output()
+    };
+
+    // run: 標準出力は結果のJSONのみ
+    let output = cli(&["run", "--output", "json"])?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let run: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(run["schema_version"], 1);
+    assert_eq!(run["command"], "run");
+    assert_eq!(run["data"]["successful"], 2);
+    assert_eq!(run["data"]["dry_run"], false);
+    assert_eq!(run["data"]["report"]["targets"][0]["files"], 2);
+    // フックの出力は標準エラー出力へ
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("pre-hook output"));
+    assert!(stderr.contains("post-hook output"));
+
+    // check: RPOの状態の配列
+    let output = cli(&["check", "--output", "json"])?;
+    assert!(output.status.success());
+    let check: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(check["command"], "check");
+    assert_eq!(check["data"][0]["max_age_hours"], 24);
+    assert_eq!(check["data"][0]["violated"], false);
+
+    // list / history: ndjson は要素ごとに1行
+    let output = cli(&["--output", "ndjson", "list"])?;
+    let lines: Vec<serde_json::Value> = String::from_utf8(output.stdout)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["data"]["priority"], "high");
+
+    let output = cli(&["history", "--output", "ndjson"])?;
+    let history: serde_json::Value =
+        serde_json::from_str(String::from_utf8(output.stdout)?.lines().next().unwrap())?;
+    assert_eq!(history["command"], "history");
+    assert_eq!(history["data"]["status"], "success");
+
+    // restore
+    let restored = temp.path().join("restored");
+    let output = cli(&[
+        "restore",
+        "--to",
+        restored.to_str().unwrap(),
+        "--output",
+        "json",
+    ])?;
+    assert!(output.status.success());
+    let restore: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(restore["data"]["restored"], 2);
+
+    // 見つからないバックアップ: 分類付きのエラーを標準エラー出力に書き出し、終了コード6
+    let output = cli(&["restore", "--from", "no-such-backup", "--output", "json"])?;
+    assert_eq!(output.status.code(), Some(6));
+    assert!(output.stdout.is_empty());
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr)?;
+    assert_eq!(error["error"]["class"], "not_found");
+    assert_eq!(error["error"]["exit_code"], 6);
+
+    Ok(())
+}
+
+/// Test 49: Typed events delivered to a custom event sink
+///
+/// Backup and restore must report phases, per-file start/finish events and
+/// cumulative progress to the configured sink instead of the console.
+#[test]
+fn test_event_sink_receives_typed_events() -> Result<()> {
+    use backup_suite::core::events::{Event, EventSink, Operation, Phase};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<Event>>);
+
+    impl EventSink for Recorder {
+        fn on_event(&self, event: &Event) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("source");
+    fs::create_dir_all(&source)?;
+    for i in 0..3 {
+        fs::write(source.join(format!("file{i}.txt")), "x".repeat(100))?;
+    }
+    let dest = temp.path().join("backups");
+
+    let mut config = Config::default();
+    config.backup.destination = dest.clone();
+    config
+        .targets
+        .push(Target::new(source, Priority::High, "docs".to_string()));
+
+    let recorder = Arc::new(Recorder::default());
+    let mut runner = BackupRunner::new(config, false).with_event_sink(recorder.clone());
+    let result = runner.run(None, None)?;
+    assert_eq!(result.successful, 3);
+
+    let events = std::mem::take(&mut *recorder.0.lock().unwrap());
+    let phases: Vec<Phase> = events
+        .iter()
+        .filter_map(|event| match event {
+            Event::PhaseChanged {
+                operation: Operation::Backup,
+                phase,
+            } => Some(*phase),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(phases.first(), Some(&Phase::Scanning));
+    assert!(phases.contains(&Phase::Processing { total_files: 3 }));
+    assert_eq!(phases.last(), Some(&Phase::Finished { failed: 0 }));
+    let started = events
+        .iter()
+        .filter(|event| matches!(event, Event::FileStarted { .. }))
+        .count();
+    let finished = events
+        .iter()
+        .filter(|event| {
+            matches!(
+                event,
+                Event::FileFinished {
+                    bytes_read: 100,
+                    ..
+                }
+            )
+        })
+        .count();
+    assert_eq!((started, finished), (3, 3));
+    assert!(events.contains(&Event::Progress {
+        files_done: 3,
+        total_files: 3,
+        bytes_done: 300,
+    }));
+
+    // 復元も同じシンクに送られる
+    let restore_dir = temp.path().join("restored");
+    let mut engine = RestoreEngine::new(false).with_event_sink(recorder.clone());
+    let restored = engine.restore(&dest.join(&result.backup_name), &restore_dir, None)?;
+    assert_eq!(restored.restored, 3);
+
+    let events = recorder.0.lock().unwrap();
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| matches!(event, Event::FileFinished { .. }))
+            .count(),
+        3
+    );
+    assert_eq!(
+        events.last(),
+        Some(&Event::PhaseChanged {
+            operation: Operation::Restore,
+            phase: Phase::Finished { failed: 0 },
+        })
+    );
+
+    Ok(())
+}
+
+/// Test 50: Cancellation stops scheduling files and keeps a partial snapshot
+///
+/// Cancelling after the first file must leave a committed snapshot that holds
+/// only the processed file and is marked partial in its manifest, verify must
+/// check it without writing, and a cancelled token must stop restore as well.
+#[test]
+fn test_cancellation_keeps_partial_snapshot() -> Result<()> {
+    use backup_suite::core::events::{Event, EventSink};
+    use backup_suite::core::{BackupMetadata, CancellationToken};
+    use std::sync::Arc;
+
+    struct CancelAfterFirstFile(CancellationToken);
+
+    impl EventSink for CancelAfterFirstFile {
+        fn on_event(&self, event: &Event) {
+            if matches!(event, Event::FileFinished { .. }) {
+                self.0.cancel();
+            }
+        }
+    }
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("source");
+    fs::create_dir_all(&source)?;
+    for i in 0..5 {
+        fs::write(source.join(format!("file{i}.txt")), "x".repeat(100))?;
+    }
+    let dest = temp.path().join("backups");
+
+    let mut config = Config::default();
+    config.backup.destination = dest.clone();
+    config
+        .targets
+        .push(Target::new(source, Priority::High, "docs".to_string()));
+
+    // 1スレッドで処理し、最初のファイルの完了直後にキャンセルする
+    let token = CancellationToken::new();
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(1).build()?;
+    let result = pool.install(|| {
+        BackupRunner::new(config, false)
+            .with_event_sink(Arc::new(CancelAfterFirstFile(token.clone())))
+            .with_cancellation(token.clone())
+            .run(None, None)
+    })?;
+
+    assert!(result.cancelled);
+    assert_eq!(result.total_files, 5);
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 0);
+    let report = result.report.as_ref().expect("report is written");
+    assert_eq!(report.summary.totals.cancelled, 4);
+
+    // 部分スナップショットは確定済みで、処理済みのファイルだけを復元できる
+    let snapshot = dest.join(&result.backup_name);
+    assert!(snapshot.is_dir());
+    let mut engine = RestoreEngine::new(false);
+    let restored = engine.restore(&snapshot, &temp.path().join("restored"), None)?;
+    assert!(!restored.cancelled);
+    assert_eq!((restored.total_files, restored.restored), (1, 1));
+
+    // マニフェストに部分的なスナップショットとして記録され、書き込まずに検証できる
+    assert!(BackupMetadata::load(&snapshot)?.partial);
+    assert!(BackupMetadata::is_partial(&snapshot));
+    let verified = RestoreEngine::new(false).verify(&snapshot, None)?;
+    assert_eq!(
+        (verified.verified_files, verified.verification_failures),
+        (1, 0)
+    );
+
+    // 改ざんされたファイルは検証で検出される（圧縮ヘッダーが壊れるため展開に失敗する）
+    let stored = walkdir::WalkDir::new(&snapshot)
+        .into_iter()
+        .filter_map(Result::ok)
+        .find(|e| e.file_type().is_file() && e.file_name().to_string_lossy().starts_with("file"))
+        .expect("processed file is stored");
+    fs::write(stored.path(), "tampered")?;
+    let verified = RestoreEngine::new(false).verify(&snapshot, None)?;
+    assert_eq!((verified.failed, verified.verified_files), (1, 0));
+
+    // キャンセル済みのトークンでは復元を開始しない
+    let cancelled = CancellationToken::new();
+    cancelled.cancel();
+    let mut engine = RestoreEngine::new(false).with_cancellation(cancelled);
+    let restored = engine.restore(&snapshot, &temp.path().join("restored2"), None)?;
+    assert!(restored.cancelled);
+    assert_eq!(restored.restored, 0);
+
+    Ok(())
+}
+
+/// Test 51: SQLite fallback keeps the WAL
+///
+/// Tests that when the online backup of a database fails, the database is
+/// copied as a normal file together with its -wal file instead of dropping
+/// the sidecar, and that sidecars of databases that were snapshotted are
+/// still left out.
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_sqlite_fallback_keeps_sidecars() -> Result<()> {
+    use rusqlite::Connection;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("app");
+    let backup_dest = temp.path().join("backup");
+    fs::create_dir_all(&source)?;
+
+    // マジックヘッダーのみ正しく、ページサイズが不正なためオンラインバックアップに失敗する
+    let mut broken = b"SQLite format 3\0".to_vec();
+    broken.extend_from_slice(&[0u8; 112]);
+    fs::write(source.join("broken.db"), &broken)?;
+    fs::write(source.join("broken.db-wal"), b"uncheckpointed frames")?;
+
+    let conn = Connection::open(source.join("state.db"))?;
+    conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))?;
+    conn.execute_batch(
+        "PRAGMA wal_autocheckpoint = 0;
+         CREATE TABLE t (v TEXT);
+         INSERT INTO t VALUES ('live');",
+    )?;
+
+    let mut config = Config::default();
+    config.backup.destination = backup_dest.clone();
+    config.targets.push(Target::new(
+        source.clone(),
+        Priority::High,
+        "apps".to_string(),
+    ));
+    let result = BackupRunner::new(config, false)
+        .with_progress(false)
+        .with_compression(CompressionType::None, 0)
+        .run(None, None)?;
+    drop(conn);
+
+    // broken.db と broken.db-wal は通常コピー、state.db は複製のみ
+    assert_eq!(result.total_files, 3);
+    assert_eq!(result.successful, 3);
+
+    let backup_dir = backup_dest.join(&result.backup_name).join("apps/app");
+    assert_eq!(fs::read(backup_dir.join("broken.db"))?, broken);
+    assert_eq!(
+        fs::read(backup_dir.join("broken.db-wal"))?,
+        b"uncheckpointed frames"
+    );
+    assert!(backup_dir.join("state.db").exists());
+    assert!(!backup_dir.join("state.db-wal").exists());
+    assert!(!backup_dir.join("state.db-shm").exists());
+
+    Ok(())
+}
+
+/// Test 52: Uncompressed files that look compressed
+///
+/// Tests that files stored without compression are restored verbatim even when
+/// their content starts with a codec header or a zstd frame, because the
+/// manifest records which files were compressed instead of restore trial
+/// decoding them.
+#[test]
+fn test_raw_files_with_codec_magic_restore_verbatim() -> Result<()> {
+    use backup_suite::compression::header;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("data");
+    let backup_dest = temp.path().join("backup");
+    let restore_dir = temp.path().join("restore");
+    fs::create_dir_all(&source)?;
+
+    let zstd_frame = zstd::encode_all(&b"inner payload"[..], 3)?;
+    let framed = header::encode(CompressionType::Zstd, &zstd_frame);
+    fs::write(source.join("archive.zst"), &zstd_frame)?;
+    fs::write(source.join("framed.bin"), &framed)?;
+
+    let mut config = Config::default();
+    config.backup.destination = backup_dest.clone();
+    config.targets.push(Target::new(
+        source.clone(),
+        Priority::High,
+        "raw".to_string(),
+    ));
+    let result = BackupRunner::new(config, false)
+        .with_progress(false)
+        .with_verification(false)
+        .with_compression(CompressionType::None, 0)
+        .run(None, None)?;
+    assert_eq!(result.successful, 2);
+
+    let backup_dir = backup_dest.join(&result.backup_name);
+    let metadata = backup_suite::core::BackupMetadata::load(&backup_dir)?;
+    assert!(metadata.records_codecs());
+    assert!(metadata.file_codecs.is_empty());
+
+    let mut engine = RestoreEngine::new(false).with_progress(false);
+    let restored = engine.restore(&backup_dir, &restore_dir, None)?;
+    assert_eq!(restored.failed, 0);
+    assert_eq!(
+        fs::read(restore_dir.join("raw/data/archive.zst"))?,
+        zstd_frame
+    );
+    assert_eq!(fs::read(restore_dir.join("raw/data/framed.bin"))?, framed);
+
+    Ok(())
+}
+
+/// Test 53: Snapshot change detection by size and mtime
+///
+/// Tests that snapshot mode links files whose size and mtime match the
+/// previous snapshot without reading them, refuses to link a previous copy
+/// that was modified after it was written, and compares contents when
+/// checksum mode is enabled.
+#[cfg(unix)]
+#[test]
+fn test_snapshot_links_by_stamp_and_checks_link_source() -> Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("source");
+    let backup_dest = temp.path().join("backup");
+    fs::create_dir_all(&source)?;
+    fs::write(source.join("stable.txt"), "stable content")?;
+    fs::write(source.join("tampered.txt"), "original data")?;
+    fs::write(source.join("same_stamp.txt"), "before")?;
+
+    let run_snapshot = |checksum: bool| -> Result<BackupResult> {
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        let mut config = Config::default();
+        config.backup.destination = backup_dest.clone();
+        config.targets.push(Target::new(
+            source.clone(),
+            Priority::High,
+            "stamps".to_string(),
+        ));
+        BackupRunner::new(config, false)
+            .with_progress(false)
+            .with_compression(CompressionType::None, 0)
+            .with_snapshot(true)
+            .with_checksum(checksum)
+            .run(None, None)
+    };
+    let snapshot_dir =
+        |result: &BackupResult| backup_dest.join(&result.backup_name).join("stamps/source");
+
+    let first = run_snapshot(false)?;
+    assert_eq!(first.successful, 3);
+    let metadata = backup_suite::core::BackupMetadata::load(&backup_dest.join(&first.backup_name))?;
+    assert_eq!(metadata.file_stamps.len(), 3);
+
+    // スナップショット内のコピーを改変（サイズは同じ）: リンク元として使わない
+    fs::write(snapshot_dir(&first).join("tampered.txt"), "CORRUPT DATA!")?;
+
+    // 内容は変わったがサイズと更新日時は同じ（既定では読み込まずに未変更と判定）
+    let mtime = fs::metadata(source.join("same_stamp.txt"))?.modified()?;
+    fs::write(source.join("same_stamp.txt"), "after!")?;
+    fs::File::options()
+        .write(true)
+        .open(source.join("same_stamp.txt"))?
+        .set_modified(mtime)?;
+
+    let second = run_snapshot(false)?;
+    assert_eq!(second.successful, 3);
+    assert_eq!(second.linked_files, 2);
+    let ino = |result: &BackupResult, name: &str| -> Result<u64> {
+        Ok(fs::metadata(snapshot_dir(result).join(name))?.ino())
+    };
+    assert_eq!(ino(&first, "stable.txt")?, ino(&second, "stable.txt")?);
+    assert_ne!(ino(&first, "tampered.txt")?, ino(&second, "tampered.txt")?);
+    assert_eq!(
+        fs::read_to_string(snapshot_dir(&second).join("tampered.txt"))?,
+        "original data"
+    );
+    assert_eq!(
+        ino(&first, "same_stamp.txt")?,
+        ino(&second, "same_stamp.txt")?
+    );
+
+    // チェックサムモードでは内容を比較するため、同じサイズ・更新日時の変更も検出
+    let third = run_snapshot(true)?;
+    assert_eq!(third.successful, 3);
+    assert_eq!(third.linked_files, 2);
+    assert_eq!(
+        fs::read_to_string(snapshot_dir(&third).join("same_stamp.txt"))?,
+        "after!"
+    );
+    assert_eq!(ino(&second, "stable.txt")?, ino(&third, "stable.txt")?);
+
+    Ok(())
+}
+
+/// Test 54: Verify holds the shared repository lock
+///
+/// Tests that verify refuses to start while cleanup holds the exclusive lock,
+/// and that an exclusive lock cannot be taken while verify is reading.
+#[test]
+fn test_verify_holds_shared_lock() -> Result<()> {
+    use backup_suite::core::events::{Event, EventSink};
+    use backup_suite::core::lock::{LockKind, RepositoryLock, LOCK_DIR};
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    /// ファイルの検証中に排他ロックの取得を試みる
+    struct TryExclusive(PathBuf, Mutex<Vec<String>>);
+
+    impl EventSink for TryExclusive {
+        fn on_event(&self, event: &Event) {
+            if matches!(event, Event::FileStarted { .. }) {
+                let outcome = match RepositoryLock::acquire(&self.0, LockKind::Exclusive, "cleanup")
+                {
+                    Ok(_) => "acquired".to_string(),
+                    Err(e) => e.to_string(),
+                };
+                self.1.lock().unwrap().push(outcome);
+            }
+        }
+    }
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("source");
+    let backup_dest = temp.path().join("backup");
+    fs::create_dir_all(&source)?;
+    fs::write(source.join("file.txt"), "content")?;
+
+    let mut config = Config::default();
+    config.backup.destination = backup_dest.clone();
+    config
+        .targets
+        .push(Target::new(source, Priority::High, "lock-test".to_string()));
+    let result = BackupRunner::new(config, false)
+        .with_progress(false)
+        .run(None, None)?;
+    let snapshot = backup_dest.join(&result.backup_name);
+
+    // クリーンアップ中は検証を開始しない
+    let held = RepositoryLock::acquire(&backup_dest, LockKind::Exclusive, "cleanup")?;
+    let err = RestoreEngine::new(false)
+        .verify(&snapshot, None)
+        .unwrap_err();
+    assert!(err.to_string().contains("ロック"), "{err}");
+    drop(held);
+
+    // 検証中は排他ロックを取得できない
+    let sink = Arc::new(TryExclusive(backup_dest.clone(), Mutex::new(Vec::new())));
+    let verified = RestoreEngine::new(false)
+        .with_event_sink(sink.clone())
+        .verify(&snapshot, None)?;
+    assert_eq!(verified.verified_files, 1);
+    let attempts = sink.1.lock().unwrap();
+    assert_eq!(attempts.len(), 1);
+    assert!(attempts[0].contains("verify (shared)"), "{}", attempts[0]);
+    assert!(!backup_dest.join(LOCK_DIR).exists());
+
+    Ok(())
+}
+
+/// Test 55: Compressed and encrypted sparse files keep only their data
+///
+/// Tests that a large sparse file going through the compression/encryption
+/// pipeline is read by its data extents with its holes recorded in the
+/// manifest, and that restore and verify rebuild the full logical file.
+#[cfg(target_os = "linux")]
+#[test]
+fn test_pipeline_sparse_file_records_holes() -> Result<()> {
+    use backup_suite::core::BackupMetadata;
+    use std::io::{Seek, SeekFrom};
+    use std::os::unix::fs::MetadataExt;
+    use std::path::Path;
+
+    const LEN: u64 = 64 * 1024 * 1024;
+
+    let temp = TempDir::new()?;
+    let source = temp.path().join("source");
+    let backup_dest = temp.path().join("backup");
+    fs::create_dir_all(&source)?;
+
+    // 64MBの論理サイズに対し、データは先頭と中央付近のみ
+    let image = source.join("disk.img");
+    let mut file = fs::File::create(&image)?;
+    file.write_all(b"boot sector")?;
+    file.seek(SeekFrom::Start(LEN / 2))?;
+    file.write_all(b"filesystem data")?;
+    file.set_len(LEN)?;
+    drop(file);
+    let meta = fs::metadata(&image)?;
+    if meta.blocks() * 512 >= meta.len() {
+        return Ok(()); // スパースファイルを作成できないファイルシステム
+    }
+
+    let mut config = Config::default();
+    config.backup.destination = backup_dest.clone();
+    config.targets.push(Target::new(
+        source.clone(),
+        Priority::High,
+        "vm".to_string(),
+    ));
+    let result = BackupRunner::new(config, false)
+        .with_progress(false)
+        .with_encryption("sparse-password".to_string())
+        .with_compression(CompressionType::Zstd, 3)
+        .run(None, None)?;
+    assert_eq!((result.successful, result.failed), (1, 0));
+    // 読み込んだのはデータ領域のみ
+    let report = result.report.as_ref().expect("report is written");
+    assert!(report.summary.totals.bytes_read <= meta.blocks() * 512);
+
+    let backup_dir = backup_dest.join(&result.backup_name);
+    let metadata = BackupMetadata::load(&backup_dir)?;
+    let (relative, map) = metadata
+        .sparse_files
+        .iter()
+        .next()
+        .expect("sparse layout is recorded");
+    assert!(relative.ends_with("disk.img"));
+    assert_eq!(map.len, LEN);
+    assert!(map.data_len() <= meta.blocks() * 512);
+
+    // 検証はホールをゼロとして元ファイルのハッシュと照合する
+    let verified = RestoreEngine::new(false).verify(&backup_dir, Some("sparse-password"))?;
+    assert_eq!(
+        (verified.verified_files, verified.verification_failures),
+        (1, 0)
+    );
+
+    // 復元すると元の論理サイズとホールが戻る
+    let restore_dir = temp.path().join("restored");
+    let restored =
+        RestoreEngine::new(false).restore(&backup_dir, &restore_dir, Some("sparse-password"))?;
+    assert_eq!((restored.restored, restored.verification_failures), (1, 0));
+    assert_eq!(restored.total_bytes, LEN);
+    let restored_image = restore_dir.join(relative);
+    let restored_meta = fs::metadata(&restored_image)?;
+    assert_eq!(restored_meta.len(), LEN);
+    assert!(restored_meta.blocks() * 512 < restored_meta.len());
+    let read_at = |path: &Path, offset: u64, len: usize| -> Result<Vec<u8>> {
+        use std::os::unix::fs::FileExt;
+        let mut buffer = vec![0u8; len];
+        fs::File::open(path)?.read_exact_at(&mut buffer, offset)?;
+        Ok(buffer)
+    };
+    assert_eq!(read_at(&restored_image, 0, 11)?, b"boot sector");
+    assert_eq!(read_at(&restored_image, LEN / 2, 15)?, b"filesystem data");
+
+    Ok(())
+}
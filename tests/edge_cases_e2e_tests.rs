@@ -80,20 +80,19 @@ fn test_e2e_empty_directory() -> Result<()> {
     );
 
     // ステップ3: 復元されたディレクトリ構造を確認
-    // 注: backup-suite の実装では、空のディレクトリはバックアップされません。
-    // ファイルを含むディレクトリのみが復元されます。
+    // 注: 空のディレクトリはマニフェストの特殊エントリとして記録され、復元時に再作成されます。
     // 注: ディレクトリバックアップではディレクトリ名も保持されるため、test/source/ 配下に復元される
     let restored_root = restore.join("test/source");
     assert_dir_exists(&restored_root, "Restored root should exist");
 
-    // 空ディレクトリは復元されない（実装の制限）
-    assert!(
-        !restored_root.join("empty_dir1").exists(),
-        "empty_dir1 should NOT be restored (empty directory limitation)"
+    // 空ディレクトリも復元される
+    assert_dir_exists(
+        &restored_root.join("empty_dir1"),
+        "empty_dir1 should be restored",
     );
-    assert!(
-        !restored_root.join("empty_dir2").exists(),
-        "empty_dir2 should NOT be restored (empty directory limitation)"
+    assert_dir_exists(
+        &restored_root.join("empty_dir2/nested_empty"),
+        "empty_dir2/nested_empty should be restored",
     );
 
     // ファイルを含むディレクトリは復元される
@@ -109,7 +108,7 @@ fn test_e2e_empty_directory() -> Result<()> {
     println!("✅ 空ディレクトリテスト成功:");
     println!("  バックアップファイル数: {}", result.total_files);
     println!("  復元ファイル数: {}", restore_result.total_files);
-    println!("  注: 空ディレクトリはバックアップされません（実装の制限）");
+    println!("  特殊エントリ復元数: {}", restore_result.special_entries);
 
     Ok(())
 }
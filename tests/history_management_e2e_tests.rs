@@ -800,7 +800,7 @@ fn test_e2e_list_backup_dirs() -> Result<()> {
         })
         .collect();
 
-    dir_times.sort_by(|a, b| b.1.cmp(&a.1)); // 新しい順
+    dir_times.sort_by_key(|b| std::cmp::Reverse(b.1)); // 新しい順
 
     for i in 0..dir_times.len().saturating_sub(1) {
         assert!(
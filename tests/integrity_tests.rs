@@ -61,7 +61,7 @@ fn test_backup_and_restore_with_integrity_verification() {
     // メタデータを読み込み
     let metadata = BackupMetadata::load(&backup_dir).unwrap();
     assert_eq!(metadata.file_hashes.len(), 2);
-    assert_eq!(metadata.version, "1.0");
+    assert_eq!(metadata.version, "1.3");
 
     // 復元実行（整合性検証有効）
     let mut restore_engine = RestoreEngine::new(false)
@@ -155,10 +155,11 @@ fn test_backup_without_integrity_verification() {
 
     assert_eq!(backup_result.successful, 1);
 
-    // .integrity ファイルが存在しないことを確認
+    // ハッシュ情報が記録されていないことを確認
+    // （.integrity にはディレクトリ等の特殊エントリのみが記録される）
     let backup_dir = backup_base.join(&backup_result.backup_name);
-    let integrity_file = backup_dir.join(".integrity");
-    assert!(!integrity_file.exists());
+    let metadata = BackupMetadata::load(&backup_dir).unwrap();
+    assert!(metadata.file_hashes.is_empty());
 }
 
 #[test]
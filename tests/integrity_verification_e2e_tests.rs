@@ -80,7 +80,7 @@ fn test_e2e_integrity_verification_success() -> Result<()> {
 
     // 検証2: メタデータを読み込み、正しい情報が含まれることを確認
     let metadata = BackupMetadata::load(&backup_dir)?;
-    assert_eq!(metadata.version, "1.0", "Metadata version should be 1.0");
+    assert_eq!(metadata.version, "1.3", "Metadata version should be 1.3");
     assert!(
         !metadata.file_hashes.is_empty(),
         "File hashes should not be empty"
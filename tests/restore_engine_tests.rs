@@ -479,3 +479,34 @@ fn test_restore_error_list_populated() {
     assert!(!result.errors.is_empty());
     assert!(result.errors[0].contains("パスワードが未指定"));
 }
+
+// =============================================================================
+// Test 15: 展開に失敗したファイルがあっても復元を継続
+// =============================================================================
+
+#[test]
+fn test_restore_continues_after_decode_failure() {
+    let temp = TempDir::new().unwrap();
+
+    // 不明な圧縮形式の識別子を持つコーデックヘッダー
+    let mut corrupt = backup_suite::compression::header::CODEC_MAGIC.to_vec();
+    corrupt.push(0xFF);
+    corrupt.extend_from_slice(b"garbage");
+    let backup_dir = create_backup_dir(
+        &temp,
+        vec![("a_corrupt.bin", &corrupt), ("b_ok.txt", b"still restored")],
+    );
+    let restore_dir = temp.path().join("restore");
+
+    let mut engine = RestoreEngine::new(false).with_progress(false);
+    let result = engine.restore(&backup_dir, &restore_dir, None).unwrap();
+
+    assert_eq!(result.total_files, 2);
+    assert_eq!(result.restored, 1);
+    assert_eq!(result.failed, 1);
+    assert!(result.errors[0].contains("a_corrupt.bin"));
+    assert_eq!(
+        fs::read(restore_dir.join("b_ok.txt")).unwrap(),
+        b"still restored"
+    );
+}
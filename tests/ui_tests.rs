@@ -490,6 +490,10 @@ fn test_display_history_with_data() {
             compressed: true,
             encrypted: true,
             error_message: None,
+            inconsistent_files: Vec::new(),
+            targets: Vec::new(),
+            summary: None,
+            job: None,
         },
         BackupHistory {
             timestamp: Utc::now() - Duration::hours(1),
@@ -504,6 +508,10 @@ fn test_display_history_with_data() {
             compressed: false,
             encrypted: false,
             error_message: None,
+            inconsistent_files: Vec::new(),
+            targets: Vec::new(),
+            summary: None,
+            job: None,
         },
     ];
 